@@ -0,0 +1,41 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use indicatif::{MultiProgress, ProgressDrawTarget};
+use std::{hint::black_box, path::Path};
+
+use efd_contribuicoes::{SpedFile, SpedReader, read_and_parse_file};
+
+/*
+### --- ###
+
+Comparar a leitura sequencial (SpedReader, BufRead: uma String por linha)
+com a leitura de read_and_parse_file (mmap: linhas emprestadas e parse paralelo).
+
+Run: cargo bench --bench mmap_reader
+
+### --- ###
+*/
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let path = Path::new("examples/efd_data_random");
+    let multiprogressbar = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
+
+    let mut group = c.benchmark_group("Read and Parse File");
+
+    group.bench_function("bufread", |b| {
+        b.iter(|| {
+            let mut sped_file = SpedFile::new();
+            for record in SpedReader::open(path).unwrap() {
+                sped_file.add_record(record.unwrap());
+            }
+            black_box(sped_file)
+        })
+    });
+    group.bench_function("mmap", |b| {
+        b.iter(|| black_box(read_and_parse_file(path, &multiprogressbar, 0, 1).unwrap()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
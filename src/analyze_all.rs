@@ -8,10 +8,15 @@ use std::{
 };
 
 use crate::{
-    AppConfig, BUFFER_CAPACITY, DELIMITER_CHAR, DocsFiscais, EFDError, EFDResult, Informacoes,
-    OUTPUT_DIRECTORY, ResultExt, TipoDeOperacao, analyze_one_file,
+    AppConfig, AssinaturaDigital, BUFFER_CAPACITY, ContribuicaoPrevidenciaria, ControleDeCreditos,
+    DELIMITER_CHAR, DocsFiscais, EFDError, EFDResult, ErroDeLeitura, Escrituracao, Informacoes,
+    OUTPUT_DIRECTORY, PeriodoEscriturado, ResultExt, RetencoesNaFonte, TipoDeOperacao,
+    analyze_one_file, relatorio_continuidade, relatorio_controle_de_creditos,
+    relatorio_documentos_duplicados, relatorio_retencoes_na_fonte, relatorio_retificadoras,
+    selecionar_escrituracoes,
     structures::{analise_dos_creditos, consolidacao_cst},
-    write_xlsx,
+    verificar_continuidade, verificar_controle_de_creditos, verificar_documentos_duplicados,
+    verificar_retencoes_na_fonte, write_erros_csv, write_xlsx,
 };
 
 // ============================================================================
@@ -24,11 +29,20 @@ pub fn executar_programa(config: &AppConfig, write: &mut dyn Write) -> EFDResult
         .map_loc(|e| EFDError::DirectoryCreationFailed(OUTPUT_DIRECTORY.to_string(), e))?;
 
     // 2. Análise dos arquivos
-    let (pa_total, all_lines) = analyze_all_files(config, write)?;
+    let ArquivosAnalisados {
+        pa_total,
+        all_lines,
+        erros_de_leitura,
+        cprb,
+    } = analyze_all_files(config, write)?;
 
     // 3. Relatório do Período Total
     imprimir_resumo_periodo(&pa_total, write)?;
 
+    // Documentos escriturados em mais de um arquivo ou estabelecimento
+    let duplicados = verificar_documentos_duplicados(&all_lines);
+    write.write_all(relatorio_documentos_duplicados(&duplicados).as_bytes())?;
+
     // 4. Consolidação Global
     // Define se imprimirá as tabelas detalhadas baseado na quantidade de arquivos
     let print_table = pa_total.len() > 1;
@@ -67,6 +81,8 @@ pub fn executar_programa(config: &AppConfig, write: &mut dyn Write) -> EFDResult
                     &filtered_lines,
                     &consolidacao_cst,
                     &consolidacao_nat,
+                    &duplicados,
+                    &cprb,
                     config.memory_mode,
                 )
             }
@@ -84,6 +100,11 @@ pub fn executar_programa(config: &AppConfig, write: &mut dyn Write) -> EFDResult
     res_xlsx?;
     res_csv?;
 
+    // 9. Modo leniente: relação das linhas descartadas, ao lado dos demais resultados
+    if config.leniente {
+        imprimir_erros_de_leitura(config, &erros_de_leitura, write)?;
+    }
+
     Ok(filtered_lines)
 }
 
@@ -114,6 +135,28 @@ fn should_keep_record(doc: &DocsFiscais, config: &AppConfig) -> bool {
     true
 }
 
+/// Grava o CSV com os erros de leitura do modo leniente e informa o total encontrado.
+fn imprimir_erros_de_leitura(
+    config: &AppConfig,
+    erros: &[ErroDeLeitura],
+    write: &mut dyn Write,
+) -> EFDResult<()> {
+    if erros.is_empty() {
+        writeln!(write, "Modo leniente: nenhum erro de leitura encontrado.\n")?;
+        return Ok(());
+    }
+
+    let path_erros = config.path_erros_csv();
+    writeln!(
+        write,
+        "Modo leniente: {} erro(s) de leitura.\nWrite csv file: {:?}\n",
+        erros.len(),
+        path_erros.display()
+    )?;
+
+    write_erros_csv(&path_erros, erros)
+}
+
 /// Imprime o resumo do período de apuração total.
 /// Otimização: Usa min/max (O(N)) em vez de sort (O(N log N)).
 fn imprimir_resumo_periodo(dates: &[NaiveDate], write: &mut dyn Write) -> EFDResult<()> {
@@ -145,12 +188,28 @@ fn imprimir_resumo_periodo(dates: &[NaiveDate], write: &mut dyn Write) -> EFDRes
 // Analyze all Sped EFD files
 // ============================================================================
 
+/// Resultado da análise de todos os arquivos selecionados.
+struct ArquivosAnalisados {
+    /// Períodos de apuração dos arquivos analisados.
+    pa_total: Vec<NaiveDate>,
+    all_lines: Vec<DocsFiscais>,
+    /// Linhas descartadas no modo leniente.
+    erros_de_leitura: Vec<ErroDeLeitura>,
+    /// Contribuição Previdenciária sobre a Receita Bruta (P100) de todos os arquivos.
+    cprb: Vec<ContribuicaoPrevidenciaria>,
+}
+
 fn analyze_all_files(
     config: &AppConfig,
     mut write: &mut dyn Write,
-) -> EFDResult<(Vec<NaiveDate>, Vec<DocsFiscais>)> {
-    let arquivos_efd: &[PathBuf] = &config.all_files;
-    print_arquivos_selecionados(arquivos_efd, &mut write)?;
+) -> EFDResult<ArquivosAnalisados> {
+    print_arquivos_selecionados(&config.all_files, &mut write)?;
+
+    // Escriturações substituídas por retificadoras do mesmo CNPJ e período
+    let arquivos_efd: &[PathBuf] = &selecionar_retificadoras(config, &mut write)?;
+
+    // Meses ausentes, em duplicidade ou com período irregular, por CNPJ base
+    imprimir_continuidade(arquivos_efd, &mut write)?;
 
     // indicatif ProgressBar + rayon
     let total_files_count: usize = arquivos_efd.len();
@@ -162,27 +221,25 @@ fn analyze_all_files(
         .enumerate()
         // "Não dê mais de 1 arquivo para a mesma thread processar enquanto houver threads ociosas".
         .with_max_len(1)
-        .filter_map(|(index, arquivo)| {
-            // Executamos a análise
-            match analyze_one_file(&multiprogressbar, arquivo, index, total_files_count) {
-                // Condição de Sucesso:
-                // Usa .then_some para converter o booleano diretamente em Option<Result>
-                // "Se não estiver vazio, então retorna Some(Ok(info)), senão None"
-                Ok(info) => (!info.all_docs.is_empty()).then_some(Ok(info)),
-
-                // Condição de Erro:
-                // Mapeamos para o erro customizado e mantemos (Some)
-                Err(error) => Some(
-                    Err(EFDError::AnalyzeFileError {
-                        source: Box::new(error),
-                        arquivo: arquivo.clone(),
-                    })
-                    .loc(),
-                ),
-            }
+        // Arquivos sem documentos fiscais são mantidos até a coleta dos erros de leitura
+        // e dos controles apurados mês a mês (ex: créditos informados nos registros 1100/1500).
+        .map(|(index, arquivo)| {
+            // Executamos a análise e mapeamos o erro para o erro customizado
+            analyze_one_file(&multiprogressbar, arquivo, index, total_files_count, config).map_loc(
+                |error| EFDError::AnalyzeFileError {
+                    source: Box::new(error),
+                    arquivo: arquivo.clone(),
+                },
+            )
         })
         .collect::<EFDResult<Vec<Informacoes>>>()?;
 
+    // Erros de leitura (modo leniente) de todos os arquivos, na ordem dos arquivos selecionados.
+    let erros_de_leitura: Vec<ErroDeLeitura> = all_info
+        .iter_mut()
+        .flat_map(|info| std::mem::take(&mut info.erros_de_leitura))
+        .collect();
+
     // 2. Ordenação (Necessária para garantir a ordem dos relatórios EFD 01, EFD 02...)
     all_info.par_sort_by_key(|info| {
         (
@@ -192,6 +249,32 @@ fn analyze_all_files(
         )
     });
 
+    // Controle dos créditos (1100/1500) de todos os arquivos, verificado mês a mês.
+    let mut controle_de_creditos = ControleDeCreditos::default();
+    for info in &mut all_info {
+        controle_de_creditos.append(&mut info.controle_de_creditos);
+    }
+
+    let escrituracoes: Vec<(u32, NaiveDate)> = all_info
+        .iter()
+        .map(|info| (info.cnpj_base, info.periodo_de_apuracao))
+        .collect();
+
+    // Retenções na fonte (F600, M200/M600 e 1300/1700) de todos os arquivos, verificadas mês a mês.
+    let mut retencoes_na_fonte = RetencoesNaFonte::default();
+    for info in &mut all_info {
+        retencoes_na_fonte.append(&mut info.retencoes_na_fonte);
+    }
+
+    // CPRB (P100) de todos os arquivos, por CNPJ base e período, para a planilha própria.
+    let cprb: Vec<ContribuicaoPrevidenciaria> = all_info
+        .iter_mut()
+        .flat_map(|info| std::mem::take(&mut info.cprb))
+        .collect();
+
+    // Relatórios por arquivo: apenas os arquivos com documentos fiscais.
+    all_info.retain(|info| !info.all_docs.is_empty());
+
     // 3. Otimização de RAM: Pré-calculamos a capacidade total necessária.
     // Isso evita que o vetor 'all_data' tenha que redimensionar (e copiar dados) várias vezes.
     let total_docs_count: usize = all_info.iter().map(|info| info.all_docs.len()).sum();
@@ -235,10 +318,32 @@ fn analyze_all_files(
         },
     )?;
 
+    // Razão dos créditos (se houver mais de um arquivo) e divergências do controle
+    let divergencias =
+        verificar_controle_de_creditos(&controle_de_creditos, &escrituracoes, config.tolerancia);
+    write.write_all(
+        relatorio_controle_de_creditos(
+            &controle_de_creditos,
+            &divergencias,
+            escrituracoes.len() > 1,
+        )
+        .as_bytes(),
+    )?;
+
+    // Retenções na fonte: da retenção (F600) à dedução (M200/M600) e aos saldos (1300/1700)
+    let divergencias =
+        verificar_retencoes_na_fonte(&retencoes_na_fonte, &escrituracoes, config.tolerancia);
+    write.write_all(relatorio_retencoes_na_fonte(&retencoes_na_fonte, &divergencias).as_bytes())?;
+
     // 4. PARALELO: Atualização final (O(N) rápido)
     update_line_counter(&mut all_data);
 
-    Ok((pa_total, all_data))
+    Ok(ArquivosAnalisados {
+        pa_total,
+        all_lines: all_data,
+        erros_de_leitura,
+        cprb,
+    })
 }
 
 // ============================================================================
@@ -255,6 +360,52 @@ fn update_line_counter(all_lines: &mut [DocsFiscais]) {
         });
 }
 
+/// Verifica a continuidade dos períodos de apuração dos arquivos selecionados.
+///
+/// Arquivos cujo Bloco 0 não pode ser lido são ignorados: o erro será
+/// informado na análise do arquivo.
+fn imprimir_continuidade(arquivos_efd: &[PathBuf], write: &mut dyn Write) -> EFDResult<()> {
+    let periodos: Vec<PeriodoEscriturado> = arquivos_efd
+        .par_iter()
+        .filter_map(|arquivo| PeriodoEscriturado::ler(arquivo).ok().flatten())
+        .collect();
+
+    write.write_all(relatorio_continuidade(&verificar_continuidade(&periodos)).as_bytes())?;
+
+    Ok(())
+}
+
+/// Seleciona os arquivos a analisar, excluindo as escriturações substituídas por
+/// retificadoras (exceto com a opção --manter-retificadas).
+///
+/// Arquivos cujo Registro 0000 não pode ser lido são mantidos: o erro será
+/// informado na análise do arquivo.
+fn selecionar_retificadoras(config: &AppConfig, write: &mut dyn Write) -> EFDResult<Vec<PathBuf>> {
+    let escrituracoes: Vec<Escrituracao> = config
+        .all_files
+        .par_iter()
+        .filter_map(|arquivo| Escrituracao::ler(arquivo).ok().flatten())
+        .collect();
+
+    let selecao = selecionar_escrituracoes(&escrituracoes, |arquivo| {
+        AssinaturaDigital::ler(arquivo)
+            .ok()
+            .flatten()
+            .and_then(|assinatura| assinatura.data_assinatura)
+    });
+
+    write.write_all(
+        relatorio_retificadoras(&selecao, &escrituracoes, config.manter_retificadas).as_bytes(),
+    )?;
+
+    Ok(config
+        .all_files
+        .iter()
+        .filter(|arquivo| config.manter_retificadas || !selecao.substituido(arquivo))
+        .cloned()
+        .collect())
+}
+
 fn print_arquivos_selecionados(arquivos: &[PathBuf], write: &mut dyn Write) -> EFDResult<()> {
     let number_of_files = arquivos.len();
 
@@ -493,11 +644,11 @@ mod tests_analyze_all {
             arq_file_hash
         );
         assert_eq!(
-            "6e5486b07e74e8416f2df08f6c9d747098dfb80c26cf0e0ea9a1ea428cc46ecd",
+            "1e37e0c475afa97feb71c83c5b27d23aa2fd3e0ffbe61343f36a971c88ce4b1b",
             out_file_hash
         );
         assert_eq!(
-            "8f575fbb3a2b710749d2e875b221a1d233aed840359be8dad20bc821a7703641",
+            "e2c53d01c1e23d301551638b1f0852ad61abedfbc9bee2f12a544c026c86bf4a",
             csv_file_hash
         );
 
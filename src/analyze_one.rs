@@ -1,20 +1,24 @@
 use crate::{
-    BUFFER_CAPACITY, Bloco0, DocsFiscais, EFDError, EFDResult, Informacoes, NEWLINE_BYTE,
-    Registro0000, ResultExt, SpedContext, SpedFile, SpedRecord, extractor::process_block_lines,
-    parser::parse_sped_fields,
+    AppConfig, Bloco0, ControleDeCreditos, DocsFiscais, EFDError, EFDResult, ErroDeLeitura,
+    Informacoes, Leiaute, NEWLINE_BYTE, Registro0000, ResultExt, RetencoesNaFonte, SpedContext,
+    SpedFile, SpedMmap, SpedRecord, apurar_bloco_m, apurar_cprb, conciliar_receitas_nao_tributadas,
+    conciliar_totais, conferir_rateio_dos_creditos, descartar_descendentes, erros_da_linha,
+    extractor::process_block_lines, open_efd_reader, parser::parse_sped_fields,
+    relatorio_apuracao_bloco_m, relatorio_chaves_invalidas, relatorio_conciliacao_de_totais,
+    relatorio_cprb, relatorio_cst_cfop, relatorio_divergencias_de_calculo,
+    relatorio_erros_de_leitura, relatorio_rateio_dos_creditos, relatorio_receitas_nao_tributadas,
+    split_archive_path, verificar_cst_cfop,
 };
 
 use chrono::Datelike;
-use claudiofsr_lib::{FileExtension, digit_count, get_style, open_file};
+use claudiofsr_lib::{digit_count, get_style};
 use encoding_rs::WINDOWS_1252;
 use indicatif::{MultiProgress, ProgressBar};
 use rayon::prelude::*;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
-    sync::Arc,
-};
+use std::{borrow::Cow, io::BufRead, path::Path, sync::Arc};
+
+/// Frequência de atualização da barra de progresso quando o total de linhas é desconhecido.
+const LINHAS_POR_ATUALIZACAO: u64 = 10_000;
 
 /// Analisa um único arquivo EFD, extraindo informações e gerando
 /// documentos fiscais, DocsFiscais.
@@ -24,25 +28,43 @@ use std::{
 /// 2. Ordenação dos registros para garantir integridade hierárquica.
 /// 3. Criação do Contexto (Lookup Tables) baseado no Bloco 0.
 /// 4. Processamento Paralelo dos Blocos de Movimento (A, C, D, F, I, M, P, 1, 9).
+///
+/// No modo `leniente`, as linhas com erro são descartadas e retornadas em `Informacoes::erros_de_leitura`.
+/// A `tolerancia` da configuração é aplicada no recálculo de PIS/COFINS dos documentos.
 pub fn analyze_one_file(
     multiprogressbar: &MultiProgress,
     arquivo: &Path,
     index: usize,
     total: usize,
+    config: &AppConfig,
 ) -> EFDResult<Informacoes> {
     // 1. Leitura e Parsing do Arquivo (IO + CPU Paralelo)
-    let mut sped_file = read_and_parse_file(arquivo, multiprogressbar, index, total)?;
+    let (mut sped_file, erros_de_leitura) =
+        parse_file(arquivo, multiprogressbar, index, total, config.leniente)?;
 
     // 2. Ordenação (Essencial para garantir hierarquia Pai -> Filho)
     // Crucial: Garante que C100 venha antes de C170, mesmo após parse paralelo.
     // O processamento paralelo de leitura pode embaralhar as linhas dentro dos blocos.
     sped_file.sort_records_by_line_number();
 
+    // Registros não suportados pelo parser (preservados como RegistroGenerico).
+    let relatorio_registros_genericos = sped_file.relatorio_registros_genericos();
+
+    // Totais dos documentos (A100, C100 e D100) divergentes da soma dos seus itens.
+    let relatorio_conciliacao_de_totais =
+        relatorio_conciliacao_de_totais(&conciliar_totais(&sped_file, arquivo, config.tolerancia));
+
+    // CNPJ/CPF com dígitos verificadores inválidos (Bloco 0 e estabelecimentos X010).
+    // Calculado antes de o Bloco 0 ser consumido pelo contexto.
+    let relatorio_identificadores_invalidos = sped_file.relatorio_identificadores_invalidos();
+
     // 3. Construção do Contexto (tabelas globais) passando o Bloco0.
     // Necessário processar sequencialmente o Bloco0 antes dos demais.
     // Ao final desta função, Bloco0 sairá de escopo e será LIBERADO da memória.
     // Passa bloco0 POR VALOR (move ownership).
-    let context = Arc::new(SpedContext::new(sped_file.take_bloco_0(), arquivo)?);
+    let mut context = SpedContext::new(sped_file.take_bloco_0(), arquivo)?;
+    context.tolerancia = config.tolerancia;
+    let context = Arc::new(context);
     // println!("sped_context: {sped_context:?}");
 
     // Aqui, Bloco0 já foi destruído automaticamente pelo Rust!
@@ -82,12 +104,47 @@ pub fn analyze_one_file(
         .flat_map(|v| v.iter().map(|s| s.len()))
         .sum();
     let mut all_messages = String::with_capacity(total_msg_len);
+    all_messages.push_str(&relatorio_registros_genericos);
+    all_messages.push_str(&relatorio_identificadores_invalidos);
+    all_messages.push_str(&relatorio_erros_de_leitura(&erros_de_leitura));
     for msgs in msgs_vecs {
         for msg in msgs {
             all_messages.push_str(&msg);
         }
     }
 
+    // Chaves de acesso (NF-e, CT-e, ...) divergentes dos dados dos documentos.
+    all_messages.push_str(&relatorio_chaves_invalidas(&all_docs));
+
+    all_messages.push_str(&relatorio_conciliacao_de_totais);
+
+    // Valores de PIS/COFINS divergentes de base de cálculo × alíquota.
+    all_messages.push_str(&relatorio_divergencias_de_calculo(
+        &all_docs,
+        context.tolerancia,
+    ));
+
+    // Combinações incoerentes de CST, CFOP e tipo de operação.
+    all_messages.push_str(&relatorio_cst_cfop(&verificar_cst_cfop(&all_docs)));
+
+    // Apuração do Bloco M (M200/M600) divergente da apuração recalculada.
+    all_messages.push_str(&relatorio_apuracao_bloco_m(&apurar_bloco_m(
+        &sped_file_arc,
+        &all_docs,
+        context.periodo_de_apuracao,
+        context.tolerancia,
+    )));
+
+    // Receitas sem incidência (M400/M410 e M800/M810) x itens de saída com CST 04 a 09.
+    all_messages.push_str(&relatorio_receitas_nao_tributadas(
+        &conciliar_receitas_nao_tributadas(
+            &sped_file_arc,
+            &all_docs,
+            context.periodo_de_apuracao,
+            context.tolerancia,
+        ),
+    ));
+
     // Enumerar todas as linhas
     // all_docs.par_iter_mut().enumerate().for_each(|(index, docs)| {docs.linhas = index + 2;});
 
@@ -98,17 +155,42 @@ pub fn analyze_one_file(
         all_messages.push('\n');
     }
 
+    // Receita bruta do Registro 0111 x receita bruta dos documentos e impacto nos créditos comuns.
+    if let Some(conferencia) =
+        conferir_rateio_dos_creditos(&context, &all_docs, config.excluir_cst_49)?
+    {
+        all_messages.push_str(&relatorio_rateio_dos_creditos(&conferencia));
+    }
+
+    // Contribuição Previdenciária sobre a Receita Bruta (Bloco P e Registro 0145).
+    let cprb = apurar_cprb(&sped_file_arc, &context);
+    all_messages.push_str(&relatorio_cprb(&cprb));
+
     let cnpj_base = context
         .estabelecimento_cnpj_base
         .parse::<u32>()
         .map_loc(|e| EFDError::ParseIntError(e, context.estabelecimento_cnpj_base.to_string()))?;
 
+    let periodo_de_apuracao = context.periodo_de_apuracao.unwrap_or_default();
+
+    // Controle dos créditos (1100/1500), verificado entre os meses em analyze_all_files.
+    let controle_de_creditos =
+        ControleDeCreditos::extrair(&sped_file_arc, cnpj_base, periodo_de_apuracao);
+
+    // Retenções na fonte (F600, M200/M600 e 1300/1700), verificadas entre os meses em analyze_all_files.
+    let retencoes_na_fonte =
+        RetencoesNaFonte::extrair(&sped_file_arc, cnpj_base, periodo_de_apuracao);
+
     // Return the aggregated results.
     Ok(Informacoes {
         cnpj_base,
-        periodo_de_apuracao: context.periodo_de_apuracao.unwrap_or_default(),
+        periodo_de_apuracao,
         messages: all_messages, // Mensagens acumuladas
         all_docs,
+        erros_de_leitura,
+        controle_de_creditos,
+        retencoes_na_fonte,
+        cprb: cprb.contribuicoes,
     })
 }
 
@@ -116,92 +198,194 @@ pub fn analyze_one_file(
 ///
 /// Padrão "Hybrid Iterator" (Sequencial para o Header -> Paralelo para o Body),
 /// que é a forma mais eficiente de processar arquivos lineares onde o início dita o contexto.
+///
+/// O primeiro erro de leitura (ex: valor decimal inválido) interrompe a leitura do arquivo.
 pub fn read_and_parse_file(
     path: &Path,
     multiprogressbar: &MultiProgress,
     index: usize,
     total: usize,
 ) -> EFDResult<SpedFile> {
-    let file = open_file(path).map_loc(|e| EFDError::InOut {
-        source: e,
-        path: path.to_path_buf(),
-    })?;
+    let (sped_file, _erros) = parse_file(path, multiprogressbar, index, total, false)?;
+    Ok(sped_file)
+}
+
+/// Modo leniente de `read_and_parse_file`: as linhas com erro de decodificação ou de
+/// conversão de campos são descartadas e registradas, e a leitura prossegue até o fim do arquivo.
+///
+/// Retorna o `SpedFile` com os registros válidos e os erros na ordem das linhas.
+/// Erros de I/O continuam interrompendo a leitura.
+pub fn read_and_parse_file_leniente(
+    path: &Path,
+    multiprogressbar: &MultiProgress,
+    index: usize,
+    total: usize,
+) -> EFDResult<(SpedFile, Vec<ErroDeLeitura>)> {
+    parse_file(path, multiprogressbar, index, total, true)
+}
 
+fn parse_file(
+    path: &Path,
+    multiprogressbar: &MultiProgress,
+    index: usize,
+    total: usize,
+    leniente: bool,
+) -> EFDResult<(SpedFile, Vec<ErroDeLeitura>)> {
     let file_number = index + 1;
-    let (progressbar, number_of_lines) = initialize_progressbar(multiprogressbar, index, path)?;
 
-    // Define a frequência de atualização da barra de progresso (ex: a cada 1%)
+    // Entrada de arquivo compactado (ex: `efd.zip!/PISCOFINS_...txt`): leitura em fluxo (BufRead).
+    // Entradas compactadas não podem ser mapeadas e o total de linhas é conhecido apenas
+    // ao final da leitura.
+    if split_archive_path(path).is_some() {
+        let reader = open_efd_reader(path)?;
+        let progressbar = create_progressbar(multiprogressbar, index, 0)?;
+
+        // Número da última linha lida (contagem feita na própria leitura).
+        let mut linhas_lidas: u64 = 0;
+
+        let linhas = reader
+            .split(NEWLINE_BYTE)
+            .enumerate()
+            // Sem o total de linhas, a barra de progresso cresce à medida que o arquivo é lido.
+            .inspect(|(idx, _line_result)| {
+                linhas_lidas = *idx as u64 + 1;
+                if linhas_lidas.is_multiple_of(LINHAS_POR_ATUALIZACAO) {
+                    progressbar.inc_length(LINHAS_POR_ATUALIZACAO);
+                }
+            })
+            .map(|(idx, line_result)| {
+                // .map_loc() é "lazy" [a closure só será executada se houver um erro de leitura]
+                line_result
+                    .map(|bytes| (idx + 1, bytes))
+                    .map_loc(|e| EFDError::InOutDetalhado {
+                        source: e,
+                        path: path.to_path_buf(),
+                        line_number: idx + 1,
+                    })
+            });
+
+        let resultado = parse_lines(
+            linhas,
+            path,
+            &progressbar,
+            file_number,
+            total,
+            LINHAS_POR_ATUALIZACAO,
+            leniente,
+        )?;
+
+        progressbar.set_length(linhas_lidas);
+        progressbar.finish();
+        return Ok(resultado);
+    }
+
+    // Arquivo texto comum: mapeado em memória, as linhas são fatias emprestadas do
+    // mapeamento (sem cópia para buffers intermediários).
+    let sped_mmap = SpedMmap::open(path)?;
+    let number_of_lines = sped_mmap.number_of_lines();
+    let progressbar = create_progressbar(multiprogressbar, index, number_of_lines)?;
+
+    // Define a frequência de atualização da barra de progresso (ex: a cada 1%).
     let delta: u64 = (number_of_lines / 100).max(1);
 
+    let linhas = sped_mmap
+        .linhas()
+        .map(|linha| Ok((linha.line_number, linha.bytes)));
+
+    let resultado = parse_lines(
+        linhas,
+        path,
+        &progressbar,
+        file_number,
+        total,
+        delta,
+        leniente,
+    )?;
+
+    progressbar.finish();
+    Ok(resultado)
+}
+
+/// Converte as linhas numeradas (a partir de 1) de um arquivo SPED EFD em `SpedFile`.
+///
+/// Padrão "Hybrid Iterator": cabeçalho sequencial até o Registro 0000 (que define
+/// a versão do leiaute) e, em seguida, parse paralelo das demais linhas até o Registro 9999.
+///
+/// As linhas podem ser fatias emprestadas (mmap) ou buffers próprios (BufRead).
+/// No modo `leniente`, as linhas com erro são descartadas e registradas.
+fn parse_lines<I, L>(
+    linhas: I,
+    path: &Path,
+    progressbar: &ProgressBar,
+    file_number: usize,
+    total: usize,
+    delta: u64,
+    leniente: bool,
+) -> EFDResult<(SpedFile, Vec<ErroDeLeitura>)>
+where
+    I: Iterator<Item = EFDResult<(usize, L)>> + Send,
+    L: AsRef<[u8]> + Send,
+{
     // Converte u64 para usize de forma segura uma única vez
     let delta_usize: usize = delta.try_into()?;
 
-    // 1. ITERADOR SEQUENCIAL INICIAL
-    let mut lines_iter = BufReader::with_capacity(BUFFER_CAPACITY, file)
-        .split(NEWLINE_BYTE)
-        .enumerate();
+    // OTIMIZAÇÃO: Interrompe o iterador sequencial ANTES do paralelismo.
+    // Após o Registro 9999, consta na EFD a assinatura digital binária.
+    let mut lines_iter = linhas.scan(false, |parar_proxima_iteracao, line_result| {
+        if *parar_proxima_iteracao {
+            return None; // Para o iterador imediatamente
+        }
 
-    // 2. CHAMADA DA FUNÇÃO EXTERNA (HEADER SEQUENCIAL)
-    // Passamos &mut lines_iter para manter o estado do cursor.
-    let mut sped_file =
-        parse_header_sequentially(&mut lines_iter, path, &progressbar, file_number, total)?;
+        // Checagem ultra-rápida sem alocação (6 bytes na Stack)
+        if let Ok((_, ref bytes)) = line_result
+            && bytes.as_ref().trim_ascii_start().starts_with(b"|9999|")
+        {
+            *parar_proxima_iteracao = true;
+        }
 
-    // 3. PROCESSAMENTO PARALELO DO RESTANTE
-    // O lines_iter agora começa exatamente da linha APÓS o 0000.
-    let parallel_results = lines_iter
-        // OTIMIZAÇÃO: Interrompe o iterador sequencial ANTES do paralelismo
-        // scan(estado_inicial, closure with two arguments)
-        .scan(false, |parar_proxima_iteracao, (idx, line_result)| {
-            // Se a flag foi ativada na iteração anterior, mata o iterador AQUI.
-            // Isso evita que qualquer byte da assinatura digital passe adiante.
-            if *parar_proxima_iteracao {
-                return None; // Para o iterador imediatamente
-            }
+        Some(line_result)
+    });
 
-            // .map_loc() é "lazy" [a closure só será executada se houver um erro de leitura]
-            // path.to_path_buf() só será chamado se line_result for Err.
-            let line_result = line_result.map_loc(|e| EFDError::InOutDetalhado {
-                source: e,
-                path: path.to_path_buf(),
-                line_number: idx + 1,
-            });
+    // 1. HEADER SEQUENCIAL
+    // Passamos &mut lines_iter para manter o estado do cursor.
+    let (mut sped_file, mut erros) = parse_header_sequentially(
+        &mut lines_iter,
+        path,
+        progressbar,
+        file_number,
+        total,
+        leniente,
+    )?;
 
-            // Verificamos o fim do arquivo aproveitando que o Result já está na mão
-            if let Ok(ref bytes) = line_result {
-                // Checagem ultra-rápida sem alocação (6 bytes na Stack)
-                // Após Registro9999, consta na EFD a assinatura digital binária.
-                if bytes.starts_with(b"|9999|") {
-                    *parar_proxima_iteracao = true;
-                }
-            }
+    // A versão do leiaute (COD_VER e DT_INI do Registro 0000) define o parser de cada registro.
+    let leiaute = sped_file
+        .obter_registro::<Registro0000>("0000")
+        .map_or_else(|_| Leiaute::mais_recente(), Leiaute::do_registro_0000);
 
-            Some((idx, line_result))
-        })
-        .par_bridge() // Transforma o iterador serial em paralelo. O Rayon agora só recebe linhas válidas até o |9999|
+    // 2. PROCESSAMENTO PARALELO DO RESTANTE
+    // O lines_iter agora começa exatamente da linha APÓS o 0000.
+    let parallel_results = lines_iter
+        .par_bridge() // Transforma o iterador serial em paralelo.
         .try_fold(
-            // O "estado inicial" de cada thread é uma tupla: (O arquivo, O buffer de linha e do registro)
-            || (SpedFile::new(), String::with_capacity(1024), [0u8; 4]),
-            |state, (idx, line_result)| -> EFDResult<(SpedFile, String, [u8; 4])> {
-                let (mut acc, mut line_buf, mut reg_buf) = state;
-                let line_number = idx + 1;
-
-                // O erro já foi envelopado no scan, aqui apenas propagamos com ?
-                let line_bytes = line_result?;
-
-                // Trim ASCII whitespace from both ends of the byte slice.
-                let trimmed = line_bytes.trim_ascii();
-
-                if !trimmed.is_empty() {
-                    // Decode bytes to string, handling potential encoding issues.
-                    // Reutiliza a String (Isso economiza MUITA memória)
-                    get_string_utf8(trimmed, &mut line_buf, line_number, path)?;
-
-                    // Faz o parse (CPU intensivo)
-                    if let Some(record) =
-                        parse_sped_fields(path, line_number, &line_buf, &mut reg_buf)?
-                    {
-                        acc.add_record(record);
-                    }
+            // O "estado inicial" de cada thread é uma tupla:
+            // (O arquivo, Os erros do modo leniente, O buffer do registro)
+            || (SpedFile::new(), Vec::new(), [0u8; 4]),
+            |state, line_result| -> EFDResult<(SpedFile, Vec<ErroDeLeitura>, [u8; 4])> {
+                let (mut acc, mut acc_erros, mut reg_buf) = state;
+
+                // O erro de leitura já foi envelopado, aqui apenas propagamos com ?
+                let (line_number, bytes) = line_result?;
+
+                let erros_leniente = leniente.then_some(&mut acc_erros);
+                if let Some(record) = parse_line(
+                    path,
+                    line_number,
+                    bytes.as_ref(),
+                    &mut reg_buf,
+                    leiaute,
+                    erros_leniente,
+                )? {
+                    acc.add_record(record);
                 }
 
                 // Incrementar ProgressoBar
@@ -210,120 +394,148 @@ pub fn read_and_parse_file(
                 }
 
                 // Retorna a tupla para a próxima iteração da mesma thread
-                Ok((acc, line_buf, reg_buf))
+                Ok((acc, acc_erros, reg_buf))
             },
         )
         // REDUCER: Funde os resultados das threads
         .try_reduce(
-            || (SpedFile::new(), String::new(), [0u8; 4]), // Identidade para a redução
-            |mut main_tuple, thread_tuple| {
-                // Unimos apenas os SpedFiles, o buffer da thread pode ser descartado
+            || (SpedFile::new(), Vec::new(), [0u8; 4]), // Identidade para a redução
+            |mut main_tuple, mut thread_tuple| {
+                // Unimos apenas os SpedFiles e os erros, o buffer da thread pode ser descartado
                 main_tuple.0.merge(thread_tuple.0);
+                main_tuple.1.append(&mut thread_tuple.1);
                 Ok(main_tuple)
             },
         )?;
 
-    // 4. MERGE FINAL: Unir o Header (sequencial) com o Body (paralelo)
-    sped_file.merge(parallel_results.0);
+    // 3. MERGE FINAL: Unir o Header (sequencial) com o Body (paralelo)
+    let (body, mut erros_body, _reg_buf) = parallel_results;
+    sped_file.merge(body);
 
-    progressbar.finish();
-    Ok(sped_file)
+    erros.append(&mut erros_body);
+    descartar_descendentes(path, &mut sped_file, &mut erros);
+
+    Ok((sped_file, erros))
 }
 
 /// Processa o início do arquivo sequencialmente até encontrar o Registro 0000.
 ///
-/// Retorna um `SpedFile` contendo os registros iniciais encontrados.
+/// Retorna um `SpedFile` contendo os registros iniciais encontrados
+/// e os erros de leitura (apenas no modo leniente).
 /// O iterador `lines_iter` é passado por referência mutável para que o cursor
 /// permaneça na posição correta após o `break`.
-fn parse_header_sequentially<I>(
+fn parse_header_sequentially<I, L>(
     lines_iter: &mut I,
     path: &Path,
     progressbar: &ProgressBar,
     file_number: usize,
     total: usize,
-) -> EFDResult<SpedFile>
+    leniente: bool,
+) -> EFDResult<(SpedFile, Vec<ErroDeLeitura>)>
 where
-    I: Iterator<Item = (usize, std::io::Result<Vec<u8>>)>,
+    I: Iterator<Item = EFDResult<(usize, L)>>,
+    L: AsRef<[u8]>,
 {
     let mut sped_file = SpedFile::new();
+    let mut erros = Vec::new();
+
+    // Buffer fixo para normalização de registros (ex: c100 -> C100)
+    let mut reg_buf = [0u8; 4];
+
+    // Antes do Registro 0000, a versão do leiaute ainda é desconhecida.
+    let leiaute = Leiaute::mais_recente();
+
+    for line_result in lines_iter.by_ref() {
+        let (line_number, bytes) = line_result?;
+
+        let erros_leniente = leniente.then_some(&mut erros);
+        let Some(record) = parse_line(
+            path,
+            line_number,
+            bytes.as_ref(),
+            &mut reg_buf,
+            leiaute,
+            erros_leniente,
+        )?
+        else {
+            continue;
+        };
 
-    // ========================================================================
-    // ALOCAÇÕES ÚNICAS (SCRATCHPADS)
-    // Inicializados fora do loop para evitar alocações repetitivas na Heap/Stack.
-    // ========================================================================
-    let mut line_buf = String::with_capacity(1024);
-    let mut reg_buf = [0u8; 4]; // Buffer fixo para normalização de registros (ex: c100 -> C100)
-
-    for (idx, line_result) in lines_iter.by_ref() {
-        let line_number = idx + 1;
+        // Lógica específica para identificar o Registro 0000 (Início da EFD)
+        if let SpedRecord::Bloco0(boxed_bloco) = &record {
+            // Desreferencia o Box para acessar o Enum Bloco0
+            if let Bloco0::R0000(reg_0000) = boxed_bloco.as_ref() {
+                // Atualiza a descrição da barra de progresso com os dados do arquivo
+                update_progressbar_header(progressbar, reg_0000, file_number, total);
 
-        // .map_loc() é lazy e só processa o erro se ele ocorrer
-        let bytes = line_result.map_loc(|e| EFDError::InOut {
-            source: e,
-            path: path.to_path_buf(),
-        })?;
+                sped_file.add_record(record);
+                break; // Interrompe o processamento sequencial após encontrar o 0000
+            }
+        }
 
-        // Trim ASCII whitespace (rápido, sem alocação)
-        let trimmed = bytes.trim_ascii();
+        // Adiciona registros que porventura venham antes do 0000
+        sped_file.add_record(record);
+    }
 
-        if trimmed.is_empty() {
-            continue;
-        }
+    Ok((sped_file, erros))
+}
 
-        // 1. Decodifica os bytes para a String (Reutiliza a memória de line_buf)
-        get_string_utf8(trimmed, &mut line_buf, line_number, path)?;
-
-        // 2. Faz o parse usando os buffers reutilizáveis
-        // O vetor de campos (fields) será criado localmente dentro desta função,
-        // o que é seguro e performático.
-        if let Some(record) = parse_sped_fields(path, line_number, &line_buf, &mut reg_buf)? {
-            // Lógica específica para identificar o Registro 0000 (Início da EFD)
-            if let SpedRecord::Bloco0(boxed_bloco) = &record {
-                // Desreferencia o Box para acessar o Enum Bloco0
-                if let Bloco0::R0000(reg_0000) = boxed_bloco.as_ref() {
-                    // Atualiza a descrição da barra de progresso com os dados do arquivo
-                    update_progressbar_header(progressbar, reg_0000, file_number, total);
-
-                    sped_file.add_record(record);
-                    break; // Interrompe o processamento sequencial após encontrar o 0000
-                }
-            }
+/// Converte uma linha do arquivo em `SpedRecord` (linhas vazias retornam `None`).
+///
+/// O texto é emprestado da própria linha se UTF-8 (ver `decode_line`).
+/// No modo leniente (`erros` presente), a linha com erro é descartada (`None`)
+/// e os erros de todos os seus campos são acrescentados a `erros`.
+fn parse_line(
+    path: &Path,
+    line_number: usize,
+    bytes: &[u8],
+    reg_buf: &mut [u8; 4],
+    leiaute: &Leiaute,
+    erros: Option<&mut Vec<ErroDeLeitura>>,
+) -> EFDResult<Option<SpedRecord>> {
+    // Trim ASCII whitespace (rápido, sem alocação)
+    let trimmed = bytes.trim_ascii();
+
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
 
-            // Adiciona registros que porventura venham antes do 0000
-            sped_file.add_record(record);
+    let parsed = decode_line(trimmed, line_number, path)
+        .and_then(|texto| parse_sped_fields(path, line_number, &texto, reg_buf, leiaute));
+
+    match (parsed, erros) {
+        (Err(error), Some(erros)) => {
+            let texto = decode_line(trimmed, line_number, path).unwrap_or_default();
+            erros.extend(erros_da_linha(
+                path,
+                line_number,
+                trimmed,
+                &texto,
+                leiaute,
+                error,
+            ));
+            Ok(None)
         }
+        (parsed, _) => parsed,
     }
-
-    Ok(sped_file)
 }
 
-/// Initializes and configures an `indicatif::ProgressBar` for a given file.
-///
-/// # Arguments
-/// * `multiprogressbar` - The `MultiProgress` instance to add the progress bar to.
-/// * `index` - The index of the file in the batch (used for insertion order).
-/// * `arquivo` - The path to the file, used to get the total number of lines.
-///
-/// # Returns
-/// An `EFDResult<ProgressBar>` on success, or `EFDError` if style cannot be retrieved or file cannot be opened.
-fn initialize_progressbar(
+/// Creates the styled `indicatif::ProgressBar` for a file with a known number of lines.
+pub(crate) fn create_progressbar(
     multiprogressbar: &MultiProgress,
     index: usize,
-    arquivo: &Path,
-) -> EFDResult<(ProgressBar, u64)> {
-    let file: File = open_file(arquivo)?;
-    let number_of_lines: u64 = file.count_lines()?;
-
+    number_of_lines: u64,
+) -> EFDResult<ProgressBar> {
     let progressbar: ProgressBar =
         multiprogressbar.insert(index, ProgressBar::new(number_of_lines));
     let style = get_style(0, 0, 33).map_loc(|_| EFDError::InvalidStyle)?;
     progressbar.set_style(style);
 
-    Ok((progressbar, number_of_lines))
+    Ok(progressbar)
 }
 
 /// Configurar o título de progressbar
-fn update_progressbar_header(
+pub(crate) fn update_progressbar_header(
     progressbar: &ProgressBar,
     reg_0000: &Registro0000,
     file_number: usize,
@@ -356,15 +568,27 @@ pub fn get_string_utf8(
     // 1. Limpa o conteúdo anterior sem liberar a capacidade alocada (reuso de memória).
     buffer.clear();
 
-    // 2. FAST PATH: Tenta decodificar como UTF-8 (Padrão mais comum).
+    // 2. Copia a linha decodificada para o nosso scratchpad.
+    buffer.push_str(&decode_line(slice_bytes, line_number, path)?);
+
+    Ok(())
+}
+
+/// Decodifica bytes sem cópia sempre que possível (zero-copy).
+///
+/// Retorna `Cow::Borrowed` para linhas UTF-8 válidas (caso mais comum) e
+/// `Cow::Owned` apenas quando é necessária a conversão de WINDOWS-1252.
+pub fn decode_line<'a>(
+    slice_bytes: &'a [u8],
+    line_number: usize,
+    path: &Path,
+) -> EFDResult<Cow<'a, str>> {
+    // 1. FAST PATH: Tenta decodificar como UTF-8 (Padrão mais comum).
     // Usamos std::str::from_utf8 que é extremamente otimizado (muitas vezes via SIMD).
     match str::from_utf8(slice_bytes) {
-        Ok(valid_str) => {
-            // Se for UTF-8 válido, apenas copiamos para o buffer existente.
-            buffer.push_str(valid_str);
-        }
+        Ok(valid_str) => Ok(Cow::Borrowed(valid_str)),
         Err(utf8_error) => {
-            // 3. SLOW PATH: Fallback para WINDOWS-1252 (Padrão de arquivos SPED antigos).
+            // 2. SLOW PATH: Fallback para WINDOWS-1252 (Padrão de arquivos SPED antigos).
             // Em vez de DecodeReaderBytesBuilder (que é para Streams), usamos
             // encoding_rs diretamente para processar o slice de bytes em memória.
 
@@ -388,12 +612,9 @@ pub fn get_string_utf8(
                 .loc();
             }
 
-            // Copia o resultado decodificado para o nosso scratchpad.
-            buffer.push_str(&decoded_res);
+            Ok(decoded_res)
         }
     }
-
-    Ok(())
 }
 
 //----------------------------------------------------------------------------//
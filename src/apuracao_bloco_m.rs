@@ -0,0 +1,639 @@
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, fmt, fmt::Write};
+
+use crate::{
+    Bloco1, BlocoM, CalculoDaContribuicao, Contribuicao, DECIMAL_ALIQ, DECIMAL_VALOR, DecimalExt,
+    DocsFiscais, SpedFile, TipoDeOperacao, decimal_textual, soma,
+};
+
+/// Códigos de contribuição (COD_CONT) totalizados em VL_TOT_CONT_NC_PER (M200/M600).
+const COD_CONT_NAO_CUMULATIVA: [&str; 6] = ["01", "02", "03", "04", "32", "71"];
+
+/// Códigos de contribuição (COD_CONT) totalizados em VL_TOT_CONT_CUM_PER (M200/M600).
+const COD_CONT_CUMULATIVA: [&str; 6] = ["31", "51", "52", "53", "54", "72"];
+
+/// CSTs de saída com incidência da contribuição: 01, 02, 03 e 05.
+const CST_TRIBUTADOS: [u16; 4] = [1, 2, 3, 5];
+
+// ============================================================================
+// Recálculo da Apuração das Contribuições (Bloco M)
+// ============================================================================
+
+/*
+Apuração recalculada para cada contribuição (PIS/PASEP: M100, M200 e M210; COFINS: M500, M600 e M610):
+
+1. Crédito por código (M100/M500):
+   - VL_CRED      = VL_BC × ALIQ / 100 (ou QUANT_BC × ALIQ_QUANT);
+   - VL_BC        = Σ VL_BC_PIS/VL_BC_COFINS dos registros M105/M505;
+   - VL_CRED_DISP = VL_CRED + VL_AJUS_ACRES - VL_AJUS_REDUC - VL_CRED_DIF;
+   - SLD_CRED     = VL_CRED_DISP - VL_CRED_DESC.
+
+2. Contribuição por código (M210/M610):
+   - VL_BC_CONT_AJUS = VL_BC_CONT + VL_AJUS_ACRES_BC - VL_AJUS_REDUC_BC (leiaute a partir de 2019);
+   - VL_CONT_APUR    = VL_BC_CONT_AJUS × ALIQ / 100 (ou QUANT_BC × ALIQ_QUANT);
+   - VL_CONT_PER     = VL_CONT_APUR + VL_AJUS_ACRES - VL_AJUS_REDUC - VL_CONT_DIFER + VL_CONT_DIFER_ANT;
+   - Σ VL_CONT_APUR por alíquota = Σ contribuição dos itens dos documentos fiscais (Blocos A, C, D e F)
+     com CST 01, 02, 03 ou 05, por alíquota.
+
+3. Totais do período (M200/M600):
+   - VL_TOT_CONT_NC_PER   = Σ VL_CONT_PER (COD_CONT 01, 02, 03, 04, 32 e 71);
+   - VL_TOT_CRED_DESC     = Σ VL_CRED_DESC dos registros M100/M500;
+   - VL_TOT_CRED_DESC_ANT = Σ VL_CRED_DESC_EFD dos registros 1100/1500;
+   - VL_TOT_CONT_NC_DEV   = VL_TOT_CONT_NC_PER - VL_TOT_CRED_DESC - VL_TOT_CRED_DESC_ANT;
+   - VL_CONT_NC_REC       = VL_TOT_CONT_NC_DEV - VL_RET_NC - VL_OUT_DED_NC;
+   - VL_TOT_CONT_CUM_PER  = Σ VL_CONT_PER (COD_CONT 31, 51, 52, 53, 54 e 72);
+   - VL_CONT_CUM_REC      = VL_TOT_CONT_CUM_PER - VL_RET_CUM - VL_OUT_DED_CUM;
+   - VL_TOT_CONT_REC      = VL_CONT_NC_REC + VL_CONT_CUM_REC.
+
+Campos não informados em registros existentes valem zero.
+*/
+
+/// Campo da apuração declarada (Bloco M) divergente do valor recalculado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergenciaDeApuracao {
+    /// Período de apuração do arquivo SPED EFD.
+    pub periodo: Option<NaiveDate>,
+    pub contribuicao: Contribuicao,
+    /// Código do registro (ex: "M100", "M210", "M600").
+    pub registro: &'static str,
+    /// Número da linha do registro declarado (`None` se o registro M210/M610 não foi declarado).
+    pub linha: Option<usize>,
+    /// COD_CRED (M100/M500), COD_CONT (M210/M610) ou vazio para os totais (M200/M600).
+    pub codigo: CompactString,
+    /// Nome do campo divergente (ex: "VL_CONT_APUR").
+    pub campo: String,
+    pub declarado: Decimal,
+    pub apurado: Decimal,
+}
+
+impl DivergenciaDeApuracao {
+    /// Identificação do código da apuração (ex: "COD_CONT 01", "COD_CRED 101", "Totais (M200)").
+    pub fn rotulo(&self) -> String {
+        match self.registro {
+            "M100" | "M500" => format!("COD_CRED {}", self.codigo),
+            "M210" | "M610" if self.linha.is_none() => format!("Sem registro {}", self.registro),
+            "M210" | "M610" => format!("COD_CONT {}", self.codigo),
+            registro => format!("Totais ({registro})"),
+        }
+    }
+}
+
+impl fmt::Display for DivergenciaDeApuracao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let apurado = self.apurado.to_formatted_string(DECIMAL_VALOR);
+        match self.linha {
+            Some(linha) => write!(
+                f,
+                "{} [linha nº {linha}] {}: declarado {}, apurado {apurado}",
+                self.registro,
+                self.campo,
+                self.declarado.to_formatted_string(DECIMAL_VALOR),
+            ),
+            None => write!(
+                f,
+                "sem registro {} {}: apurado {apurado}",
+                self.registro, self.campo,
+            ),
+        }
+    }
+}
+
+/// Crédito apurado por código (M100 ou M500).
+struct CreditoDeclarado {
+    registro: &'static str,
+    linha: usize,
+    cod_cred: CompactString,
+    calculo: CalculoDaContribuicao,
+    vl_cred: Option<Decimal>,
+    vl_ajus_acres: Option<Decimal>,
+    vl_ajus_reduc: Option<Decimal>,
+    vl_cred_dif: Option<Decimal>,
+    vl_cred_disp: Option<Decimal>,
+    vl_cred_desc: Option<Decimal>,
+    sld_cred: Option<Decimal>,
+    /// Σ VL_BC dos registros filhos M105/M505 (`None` se não houver detalhamento).
+    vl_bc_detalhado: Option<Decimal>,
+}
+
+/// Contribuição apurada por código (M210 ou M610), em ambos os leiautes.
+struct ContribuicaoDeclarada {
+    registro: &'static str,
+    linha: usize,
+    cod_cont: CompactString,
+    vl_bc_cont: Option<Decimal>,
+    /// VL_AJUS_ACRES_BC, VL_AJUS_REDUC_BC e VL_BC_CONT_AJUS (apenas no leiaute a partir de 2019).
+    ajustes_da_bc: Option<[Option<Decimal>; 3]>,
+    aliquota: Option<Decimal>,
+    quant_bc: Option<Decimal>,
+    aliquota_quant: Option<Decimal>,
+    vl_cont_apur: Option<Decimal>,
+    vl_ajus_acres: Option<Decimal>,
+    vl_ajus_reduc: Option<Decimal>,
+    vl_cont_difer: Option<Decimal>,
+    vl_cont_difer_ant: Option<Decimal>,
+    vl_cont_per: Option<Decimal>,
+}
+
+/// Totais do período (M200 ou M600).
+struct TotaisDeclarados {
+    registro: &'static str,
+    linha: usize,
+    vl_tot_cont_nc_per: Option<Decimal>,
+    vl_tot_cred_desc: Option<Decimal>,
+    vl_tot_cred_desc_ant: Option<Decimal>,
+    vl_tot_cont_nc_dev: Option<Decimal>,
+    vl_ret_nc: Option<Decimal>,
+    vl_out_ded_nc: Option<Decimal>,
+    vl_cont_nc_rec: Option<Decimal>,
+    vl_tot_cont_cum_per: Option<Decimal>,
+    vl_ret_cum: Option<Decimal>,
+    vl_out_ded_cum: Option<Decimal>,
+    vl_cont_cum_rec: Option<Decimal>,
+    vl_tot_cont_rec: Option<Decimal>,
+}
+
+/// Apuração declarada de uma contribuição no Bloco M (e créditos de períodos anteriores no Bloco 1).
+#[derive(Default)]
+struct ApuracaoDeclarada {
+    creditos: Vec<CreditoDeclarado>,
+    contribuicoes: Vec<ContribuicaoDeclarada>,
+    totais: Option<TotaisDeclarados>,
+    /// Σ VL_CRED_DESC_EFD dos registros 1100 (PIS/PASEP) ou 1500 (COFINS).
+    cred_desc_ant: Decimal,
+}
+
+/// Agrupa os registros do Bloco M (e 1100/1500) por contribuição: (PIS/PASEP, COFINS).
+fn coletar_apuracao(sped_file: &SpedFile) -> (ApuracaoDeclarada, ApuracaoDeclarada) {
+    let mut pis = ApuracaoDeclarada::default();
+    let mut cofins = ApuracaoDeclarada::default();
+
+    // Os registros M105/M505 são associados ao M100/M500 imediatamente anterior.
+    let mut registros: Vec<&BlocoM> = sped_file.bloco_m.iter().collect();
+    registros.sort_by_key(|r| r.line_number());
+
+    for registro in registros {
+        match registro {
+            BlocoM::RM100(r) => pis.creditos.push(CreditoDeclarado {
+                registro: "M100",
+                linha: r.line_number,
+                cod_cred: r.cod_cred.map(|c| c.to_string().into()).unwrap_or_default(),
+                calculo: CalculoDaContribuicao {
+                    valor_bc: r.vl_bc_pis,
+                    aliquota: r.aliq_pis,
+                    quant_bc: decimal_textual(r.quant_bc_pis.as_deref()),
+                    aliquota_quant: r.aliq_pis_quant,
+                    valor_declarado: r.vl_cred,
+                },
+                vl_cred: r.vl_cred,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cred_dif: r.vl_cred_dif,
+                vl_cred_disp: r.vl_cred_disp,
+                vl_cred_desc: r.vl_cred_desc,
+                sld_cred: r.sld_cred,
+                vl_bc_detalhado: None,
+            }),
+            BlocoM::RM105(r) => {
+                if let Some(credito) = pis.creditos.last_mut() {
+                    let total = credito.vl_bc_detalhado.get_or_insert_default();
+                    *total += r.vl_bc_pis.unwrap_or_default();
+                }
+            }
+            BlocoM::RM500(r) => cofins.creditos.push(CreditoDeclarado {
+                registro: "M500",
+                linha: r.line_number,
+                cod_cred: r.cod_cred.map(|c| c.to_string().into()).unwrap_or_default(),
+                calculo: CalculoDaContribuicao {
+                    valor_bc: r.vl_bc_cofins,
+                    aliquota: r.aliq_cofins,
+                    quant_bc: decimal_textual(r.quant_bc_cofins.as_deref()),
+                    aliquota_quant: r.aliq_cofins_quant,
+                    valor_declarado: r.vl_cred,
+                },
+                vl_cred: r.vl_cred,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cred_dif: r.vl_cred_difer,
+                vl_cred_disp: r.vl_cred_disp,
+                vl_cred_desc: r.vl_cred_desc,
+                sld_cred: r.sld_cred,
+                vl_bc_detalhado: None,
+            }),
+            BlocoM::RM505(r) => {
+                if let Some(credito) = cofins.creditos.last_mut() {
+                    let total = credito.vl_bc_detalhado.get_or_insert_default();
+                    *total += r.vl_bc_cofins.unwrap_or_default();
+                }
+            }
+            BlocoM::RM200(r) => {
+                pis.totais = Some(TotaisDeclarados {
+                    registro: "M200",
+                    linha: r.line_number,
+                    vl_tot_cont_nc_per: r.vl_tot_cont_nc_per,
+                    vl_tot_cred_desc: r.vl_tot_cred_desc,
+                    vl_tot_cred_desc_ant: r.vl_tot_cred_desc_ant,
+                    vl_tot_cont_nc_dev: r.vl_tot_cont_nc_dev,
+                    vl_ret_nc: r.vl_ret_nc,
+                    vl_out_ded_nc: r.vl_out_ded_nc,
+                    vl_cont_nc_rec: r.vl_cont_nc_rec,
+                    vl_tot_cont_cum_per: r.vl_tot_cont_cum_per,
+                    vl_ret_cum: r.vl_ret_cum,
+                    vl_out_ded_cum: r.vl_out_ded_cum,
+                    vl_cont_cum_rec: r.vl_cont_cum_rec,
+                    vl_tot_cont_rec: r.vl_tot_cont_rec,
+                })
+            }
+            BlocoM::RM600(r) => {
+                cofins.totais = Some(TotaisDeclarados {
+                    registro: "M600",
+                    linha: r.line_number,
+                    vl_tot_cont_nc_per: r.vl_tot_cont_nc_per,
+                    vl_tot_cred_desc: r.vl_tot_cred_desc,
+                    vl_tot_cred_desc_ant: r.vl_tot_cred_desc_ant,
+                    vl_tot_cont_nc_dev: r.vl_tot_cont_nc_dev,
+                    vl_ret_nc: r.vl_ret_nc,
+                    vl_out_ded_nc: r.vl_out_ded_nc,
+                    vl_cont_nc_rec: r.vl_cont_nc_rec,
+                    vl_tot_cont_cum_per: r.vl_tot_cont_cum_per,
+                    vl_ret_cum: r.vl_ret_cum,
+                    vl_out_ded_cum: r.vl_out_ded_cum,
+                    vl_cont_cum_rec: r.vl_cont_cum_rec,
+                    vl_tot_cont_rec: r.vl_tot_cont_rec,
+                })
+            }
+            BlocoM::RM210(r) => pis.contribuicoes.push(ContribuicaoDeclarada {
+                registro: "M210",
+                linha: r.line_number,
+                cod_cont: r.cod_cont.clone().unwrap_or_default(),
+                vl_bc_cont: r.vl_bc_cont,
+                ajustes_da_bc: Some([
+                    r.vl_ajus_acres_bc_pis,
+                    r.vl_ajus_reduc_bc_pis,
+                    r.vl_bc_cont_ajus,
+                ]),
+                aliquota: r.aliq_pis,
+                quant_bc: decimal_textual(r.quant_bc_pis.as_deref()),
+                aliquota_quant: r.aliq_pis_quant,
+                vl_cont_apur: r.vl_cont_apur,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cont_difer: r.vl_cont_difer,
+                vl_cont_difer_ant: r.vl_cont_difer_ant,
+                vl_cont_per: r.vl_cont_per,
+            }),
+            BlocoM::RM210Antigo(r) => pis.contribuicoes.push(ContribuicaoDeclarada {
+                registro: "M210",
+                linha: r.line_number,
+                cod_cont: r.cod_cont.clone().unwrap_or_default(),
+                vl_bc_cont: r.vl_bc_cont,
+                ajustes_da_bc: None,
+                aliquota: r.aliq_pis,
+                quant_bc: decimal_textual(r.quant_bc_pis.as_deref()),
+                aliquota_quant: r.aliq_pis_quant,
+                vl_cont_apur: r.vl_cont_apur,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cont_difer: r.vl_cont_difer,
+                vl_cont_difer_ant: r.vl_cont_difer_ant,
+                vl_cont_per: r.vl_cont_per,
+            }),
+            BlocoM::RM610(r) => cofins.contribuicoes.push(ContribuicaoDeclarada {
+                registro: "M610",
+                linha: r.line_number,
+                cod_cont: r.cod_cont.clone().unwrap_or_default(),
+                vl_bc_cont: r.vl_bc_cont,
+                ajustes_da_bc: Some([
+                    r.vl_ajus_acres_bc_cofins,
+                    r.vl_ajus_reduc_bc_cofins,
+                    r.vl_bc_cont_ajus,
+                ]),
+                aliquota: r.aliq_cofins,
+                quant_bc: decimal_textual(r.quant_bc_cofins.as_deref()),
+                aliquota_quant: r.aliq_cofins_quant,
+                vl_cont_apur: r.vl_cont_apur,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cont_difer: r.vl_cont_difer,
+                vl_cont_difer_ant: r.vl_cont_difer_ant,
+                vl_cont_per: r.vl_cont_per,
+            }),
+            BlocoM::RM610Antigo(r) => cofins.contribuicoes.push(ContribuicaoDeclarada {
+                registro: "M610",
+                linha: r.line_number,
+                cod_cont: r.cod_cont.clone().unwrap_or_default(),
+                vl_bc_cont: r.vl_bc_cont,
+                ajustes_da_bc: None,
+                aliquota: r.aliq_cofins,
+                quant_bc: decimal_textual(r.quant_bc_cofins.as_deref()),
+                aliquota_quant: r.aliq_cofins_quant,
+                vl_cont_apur: r.vl_cont_apur,
+                vl_ajus_acres: r.vl_ajus_acres,
+                vl_ajus_reduc: r.vl_ajus_reduc,
+                vl_cont_difer: r.vl_cont_difer,
+                vl_cont_difer_ant: r.vl_cont_difer_ant,
+                vl_cont_per: r.vl_cont_per,
+            }),
+            _ => {}
+        }
+    }
+
+    for registro in &sped_file.bloco_1 {
+        match registro {
+            Bloco1::R1100(r) => pis.cred_desc_ant += r.vl_cred_desc_efd.unwrap_or_default(),
+            Bloco1::R1500(r) => cofins.cred_desc_ant += r.vl_cred_desc_efd.unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    (pis, cofins)
+}
+
+/// Acumula as divergências entre os valores declarados e os recalculados de uma contribuição.
+struct Verificador<'a> {
+    periodo: Option<NaiveDate>,
+    contribuicao: Contribuicao,
+    tolerancia: Decimal,
+    divergencias: &'a mut Vec<DivergenciaDeApuracao>,
+}
+
+impl Verificador<'_> {
+    /// Registra a divergência se a diferença for maior que a tolerância.
+    /// Valores não recalculados (`apurado = None`) não são verificados.
+    fn conferir(
+        &mut self,
+        (registro, linha, codigo): (&'static str, Option<usize>, &str),
+        campo: impl Into<String>,
+        declarado: Option<Decimal>,
+        apurado: Option<Decimal>,
+    ) {
+        let Some(apurado) = apurado else {
+            return;
+        };
+        let declarado = declarado.unwrap_or_default();
+
+        if (declarado - apurado).abs() > self.tolerancia {
+            self.divergencias.push(DivergenciaDeApuracao {
+                periodo: self.periodo,
+                contribuicao: self.contribuicao,
+                registro,
+                linha,
+                codigo: codigo.into(),
+                campo: campo.into(),
+                declarado,
+                apurado,
+            });
+        }
+    }
+
+    fn conferir_creditos(&mut self, creditos: &[CreditoDeclarado]) {
+        for c in creditos {
+            let origem = (c.registro, Some(c.linha), c.cod_cred.as_str());
+
+            self.conferir(origem, "VL_CRED", c.vl_cred, c.calculo.valor_calculado());
+            let vl_bc = match self.contribuicao {
+                Contribuicao::Pis => "VL_BC_PIS",
+                Contribuicao::Cofins => "VL_BC_COFINS",
+            };
+            self.conferir(origem, vl_bc, c.calculo.valor_bc, c.vl_bc_detalhado);
+
+            let vl_cred_disp = c.vl_cred.unwrap_or_default() + soma([c.vl_ajus_acres])
+                - soma([c.vl_ajus_reduc, c.vl_cred_dif]);
+            self.conferir(origem, "VL_CRED_DISP", c.vl_cred_disp, Some(vl_cred_disp));
+
+            let sld_cred = c.vl_cred_disp.unwrap_or_default() - soma([c.vl_cred_desc]);
+            self.conferir(origem, "SLD_CRED", c.sld_cred, Some(sld_cred));
+        }
+    }
+
+    fn conferir_contribuicoes(&mut self, contribuicoes: &[ContribuicaoDeclarada]) {
+        for c in contribuicoes {
+            let origem = (c.registro, Some(c.linha), c.cod_cont.as_str());
+
+            // Base de cálculo após os ajustes (leiaute a partir de 2019)
+            let vl_bc = match c.ajustes_da_bc {
+                Some([acres, reduc, declarado]) => {
+                    let ajustada = c.vl_bc_cont.unwrap_or_default() + soma([acres]) - soma([reduc]);
+                    self.conferir(origem, "VL_BC_CONT_AJUS", declarado, Some(ajustada));
+                    declarado
+                }
+                None => c.vl_bc_cont,
+            };
+
+            let calculo = CalculoDaContribuicao {
+                valor_bc: vl_bc,
+                aliquota: c.aliquota,
+                quant_bc: c.quant_bc,
+                aliquota_quant: c.aliquota_quant,
+                valor_declarado: c.vl_cont_apur,
+            };
+            self.conferir(
+                origem,
+                "VL_CONT_APUR",
+                c.vl_cont_apur,
+                calculo.valor_calculado(),
+            );
+
+            let vl_cont_per = c.vl_cont_apur.unwrap_or_default()
+                + soma([c.vl_ajus_acres, c.vl_cont_difer_ant])
+                - soma([c.vl_ajus_reduc, c.vl_cont_difer]);
+            self.conferir(origem, "VL_CONT_PER", c.vl_cont_per, Some(vl_cont_per));
+        }
+    }
+
+    /// Confronta a contribuição declarada por alíquota (M210/M610) com a dos itens
+    /// dos documentos fiscais com CST 01, 02, 03 ou 05.
+    fn conferir_documentos(
+        &mut self,
+        contribuicoes: &[ContribuicaoDeclarada],
+        docs: &[DocsFiscais],
+    ) {
+        // alíquota -> Σ contribuição dos itens
+        let mut apurado: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+        // CST da contribuição verificada: `cst_pis` (PIS/PASEP) ou `cst` (COFINS).
+        let cst_da_contribuicao = |doc: &DocsFiscais| match self.contribuicao {
+            Contribuicao::Pis => doc.cst_pis,
+            Contribuicao::Cofins => doc.cst,
+        };
+        for doc in docs.iter().filter(|doc| {
+            doc.tipo_de_operacao == Some(TipoDeOperacao::Saida)
+                && cst_da_contribuicao(doc).is_some_and(|cst| CST_TRIBUTADOS.contains(&cst.code()))
+        }) {
+            let (aliquota, valor) = match self.contribuicao {
+                Contribuicao::Pis => (doc.aliq_pis, doc.valor_pis),
+                Contribuicao::Cofins => (doc.aliq_cofins, doc.valor_cofins),
+            };
+            if let (Some(aliquota), Some(valor)) = (aliquota, valor) {
+                *apurado.entry(aliquota.normalize()).or_default() += valor;
+            }
+        }
+
+        // alíquota -> (primeira linha, códigos, Σ VL_CONT_APUR declarado)
+        let mut declarado: BTreeMap<Decimal, (usize, Vec<&str>, Decimal)> = BTreeMap::new();
+        for c in contribuicoes {
+            if let Some(aliquota) = c.aliquota.filter(|a| !a.is_zero()) {
+                let (_, codigos, total) = declarado.entry(aliquota.normalize()).or_insert((
+                    c.linha,
+                    Vec::new(),
+                    Decimal::ZERO,
+                ));
+                codigos.push(c.cod_cont.as_str());
+                *total += c.vl_cont_apur.unwrap_or_default();
+            }
+        }
+
+        let registro = match self.contribuicao {
+            Contribuicao::Pis => "M210",
+            Contribuicao::Cofins => "M610",
+        };
+
+        for (aliquota, valor) in apurado {
+            let campo = format!(
+                "VL_CONT_APUR (itens dos documentos com alíquota de {}%)",
+                aliquota.to_formatted_string(DECIMAL_ALIQ)
+            );
+            match declarado.get(&aliquota) {
+                Some((linha, codigos, total)) => self.conferir(
+                    (registro, Some(*linha), &codigos.join(", ")),
+                    campo,
+                    Some(*total),
+                    Some(valor),
+                ),
+                // Alíquota sem registro M210/M610 correspondente
+                None => self.conferir((registro, None, ""), campo, None, Some(valor)),
+            }
+        }
+    }
+
+    fn conferir_totais(&mut self, apuracao: &ApuracaoDeclarada) {
+        let Some(t) = &apuracao.totais else {
+            return;
+        };
+        let origem = (t.registro, Some(t.linha), "");
+
+        let por_codigo = |codigos: &[&str]| {
+            soma(
+                apuracao
+                    .contribuicoes
+                    .iter()
+                    .filter(|c| codigos.contains(&c.cod_cont.as_str()))
+                    .map(|c| c.vl_cont_per),
+            )
+        };
+
+        let nc_per = por_codigo(&COD_CONT_NAO_CUMULATIVA);
+        let cred_desc = soma(apuracao.creditos.iter().map(|c| c.vl_cred_desc));
+        let nc_dev = nc_per - cred_desc - apuracao.cred_desc_ant;
+        let nc_rec = nc_dev - soma([t.vl_ret_nc, t.vl_out_ded_nc]);
+        let cum_per = por_codigo(&COD_CONT_CUMULATIVA);
+        let cum_rec = cum_per - soma([t.vl_ret_cum, t.vl_out_ded_cum]);
+
+        #[rustfmt::skip]
+        let campos = [
+            ("VL_TOT_CONT_NC_PER", t.vl_tot_cont_nc_per, nc_per),
+            ("VL_TOT_CRED_DESC", t.vl_tot_cred_desc, cred_desc),
+            ("VL_TOT_CRED_DESC_ANT", t.vl_tot_cred_desc_ant, apuracao.cred_desc_ant),
+            ("VL_TOT_CONT_NC_DEV", t.vl_tot_cont_nc_dev, nc_dev),
+            ("VL_CONT_NC_REC", t.vl_cont_nc_rec, nc_rec),
+            ("VL_TOT_CONT_CUM_PER", t.vl_tot_cont_cum_per, cum_per),
+            ("VL_CONT_CUM_REC", t.vl_cont_cum_rec, cum_rec),
+            ("VL_TOT_CONT_REC", t.vl_tot_cont_rec, nc_rec + cum_rec),
+        ];
+
+        for (campo, declarado, apurado) in campos {
+            self.conferir(origem, campo, declarado, Some(apurado));
+        }
+    }
+}
+
+/// Recalcula a apuração de PIS/PASEP (M100, M210 e M200) e de COFINS (M500, M610 e M600)
+/// a partir dos registros de detalhamento, dos créditos do Bloco M, dos créditos de períodos
+/// anteriores (1100/1500) e dos itens dos documentos fiscais, e confronta, campo a campo,
+/// com os valores declarados.
+///
+/// Diferenças maiores que a `tolerancia` são retornadas por contribuição e na ordem das linhas,
+/// seguidas das alíquotas dos documentos sem registro M210/M610 correspondente.
+pub fn apurar_bloco_m(
+    sped_file: &SpedFile,
+    docs: &[DocsFiscais],
+    periodo: Option<NaiveDate>,
+    tolerancia: Decimal,
+) -> Vec<DivergenciaDeApuracao> {
+    let (pis, cofins) = coletar_apuracao(sped_file);
+    let mut divergencias = Vec::new();
+
+    for (contribuicao, apuracao) in [(Contribuicao::Pis, pis), (Contribuicao::Cofins, cofins)] {
+        // Sem apuração declarada (ex: Bloco M sem movimento), nada a confrontar.
+        if apuracao.totais.is_none() && apuracao.contribuicoes.is_empty() {
+            continue;
+        }
+
+        let mut verificador = Verificador {
+            periodo,
+            contribuicao,
+            tolerancia,
+            divergencias: &mut divergencias,
+        };
+
+        verificador.conferir_creditos(&apuracao.creditos);
+        verificador.conferir_contribuicoes(&apuracao.contribuicoes);
+        verificador.conferir_documentos(&apuracao.contribuicoes, docs);
+        verificador.conferir_totais(&apuracao);
+    }
+
+    // Alíquotas sem registro M210/M610 após as divergências dos registros declarados.
+    divergencias.sort_by_key(|d| (d.contribuicao, d.linha.is_none(), d.linha));
+    divergencias
+}
+
+/// Resumo das divergências da apuração, agrupadas por período, contribuição e código
+/// (COD_CRED, COD_CONT ou totais), ou String vazia se não houver divergências.
+pub fn relatorio_apuracao_bloco_m(divergencias: &[DivergenciaDeApuracao]) -> String {
+    if divergencias.is_empty() {
+        return String::new();
+    }
+
+    let mut grupos: BTreeMap<
+        (Option<NaiveDate>, Contribuicao, String),
+        Vec<&DivergenciaDeApuracao>,
+    > = BTreeMap::new();
+    for divergencia in divergencias {
+        grupos
+            .entry((
+                divergencia.periodo,
+                divergencia.contribuicao,
+                divergencia.rotulo(),
+            ))
+            .or_default()
+            .push(divergencia);
+    }
+
+    let mut relatorio = format!(
+        "Apuração do Bloco M divergente da apuração recalculada: {} divergência(s).\n",
+        divergencias.len()
+    );
+
+    for ((periodo, contribuicao, rotulo), divergencias) in grupos {
+        let periodo = periodo.map_or("N/I".to_string(), |p| p.format("%m/%Y").to_string());
+        let _ = writeln!(
+            relatorio,
+            "   Período {periodo}, {}, {rotulo}:",
+            contribuicao.descricao()
+        );
+        for divergencia in divergencias {
+            let _ = writeln!(relatorio, "      {divergencia}");
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/apuracao_bloco_m_tests.rs"]
+mod apuracao_bloco_m_tests;
@@ -0,0 +1,301 @@
+use flate2::{
+    CrcReader,
+    read::{DeflateDecoder, MultiGzDecoder},
+};
+use glob::{MatchOptions, Pattern, glob_with};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+use zip::{CompressionMethod, ZipArchive, result::ZipError};
+
+use crate::{BUFFER_CAPACITY, EFDError, EFDResult, NEWLINE_BYTE, ResultExt};
+
+// ============================================================================
+// Arquivos EFD compactados (.zip e .gz)
+// ============================================================================
+
+/// Separador entre o arquivo compactado e o arquivo EFD interno (caminho virtual).
+///
+/// Ex: `EFD_2024.zip!/PISCOFINS_20240101_20240131.txt`.
+pub const ARCHIVE_ENTRY_SEPARATOR: &str = "!/";
+
+/// Formatos de arquivo compactado suportados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Gzip,
+}
+
+impl ArchiveFormat {
+    /// Identifica o formato pela extensão do arquivo (.zip ou .gz).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+
+        if extension.eq_ignore_ascii_case("zip") {
+            Some(Self::Zip)
+        } else if extension.eq_ignore_ascii_case("gz") {
+            Some(Self::Gzip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Separa o caminho virtual `arquivo.zip!/entrada.txt` em (arquivo compactado, entrada).
+///
+/// Retorna `None` para caminhos de arquivos comuns.
+pub fn split_archive_path(path: &Path) -> Option<(&Path, &str)> {
+    let path_str = path.to_str()?;
+
+    path_str
+        .match_indices(ARCHIVE_ENTRY_SEPARATOR)
+        .find_map(|(idx, separator)| {
+            let archive = Path::new(&path_str[..idx]);
+            ArchiveFormat::from_path(archive)?;
+            Some((archive, &path_str[idx + separator.len()..]))
+        })
+}
+
+/// Caminho virtual de uma entrada do arquivo compactado.
+pub fn archive_entry_path(archive: &Path, entry: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{ARCHIVE_ENTRY_SEPARATOR}{entry}",
+        archive.display()
+    ))
+}
+
+/// Localiza os arquivos EFD (cujo nome segue `file_pattern`) contidos
+/// nos arquivos .zip e .gz do diretório atual e subdiretórios.
+///
+/// Arquivos compactados ilegíveis são ignorados com um aviso.
+pub fn search_archive_entries(
+    file_pattern: &str,
+    options: MatchOptions,
+) -> EFDResult<Vec<PathBuf>> {
+    let pattern = Pattern::new(file_pattern)?;
+    let mut entries = Vec::new();
+
+    let archives = glob_with("**/*.zip", options)?.chain(glob_with("**/*.gz", options)?);
+
+    for archive in archives.flatten() {
+        match list_archive_entries(&archive, &pattern, options) {
+            Ok(found) => entries.extend(found),
+            Err(error) => log::warn!("Arquivo compactado ignorado {:?}: {error}", archive),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Caminhos virtuais dos arquivos EFD contidos em um arquivo compactado.
+pub fn list_archive_entries(
+    archive: &Path,
+    pattern: &Pattern,
+    options: MatchOptions,
+) -> EFDResult<Vec<PathBuf>> {
+    // O padrão é aplicado apenas ao nome do arquivo (sem os diretórios internos).
+    let matches = |entry: &str| {
+        Path::new(entry)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| pattern.matches_with(name, options))
+    };
+
+    let mut entries: Vec<PathBuf> = match ArchiveFormat::from_path(archive) {
+        Some(ArchiveFormat::Zip) => open_zip(archive)?
+            .file_names()
+            .filter(|entry| !entry.ends_with('/') && matches(entry))
+            .map(|entry| archive_entry_path(archive, entry))
+            .collect(),
+        Some(ArchiveFormat::Gzip) => {
+            let entry = gzip_entry_name(archive)?;
+            matches(&entry)
+                .then(|| archive_entry_path(archive, &entry))
+                .into_iter()
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    entries.sort();
+
+    Ok(entries)
+}
+
+/// Abre um arquivo EFD para leitura sequencial.
+///
+/// Aceita arquivos texto comuns e caminhos virtuais (`arquivo.zip!/entrada.txt`).
+/// Entradas .zip e arquivos .gz são descompactados em fluxo, sem cópia em memória.
+/// Os arquivos .gz com vários membros (concatenados) são lidos por inteiro.
+pub fn open_efd_reader(path: &Path) -> EFDResult<Box<dyn BufRead + Send>> {
+    let Some((archive, entry)) = split_archive_path(path) else {
+        let file = open(path)?;
+        return Ok(Box::new(BufReader::with_capacity(BUFFER_CAPACITY, file)));
+    };
+
+    let decoder: Box<dyn Read + Send> = match ArchiveFormat::from_path(archive) {
+        Some(ArchiveFormat::Zip) => open_zip_entry(archive, entry, path)?,
+        Some(ArchiveFormat::Gzip) | None => {
+            Box::new(MultiGzDecoder::new(BufReader::new(open(archive)?)))
+        }
+    };
+
+    Ok(Box::new(BufReader::with_capacity(BUFFER_CAPACITY, decoder)))
+}
+
+/// Entrada .zip lida em fluxo.
+///
+/// O diretório central informa a posição e o método de compressão da entrada: os dados
+/// compactados são lidos diretamente do arquivo e descompactados sob demanda, com a
+/// conferência do CRC-32 ao final da leitura.
+fn open_zip_entry(archive: &Path, entry: &str, path: &Path) -> EFDResult<Box<dyn Read + Send>> {
+    let zip_error = |source: ZipError| EFDError::Zip {
+        source,
+        path: path.to_path_buf(),
+    };
+
+    let mut zip = open_zip(archive)?;
+    let index = zip
+        .index_for_name(entry)
+        .ok_or(ZipError::FileNotFound)
+        .map_loc(zip_error)?;
+
+    // Apenas os metadados da entrada (sem descompactar).
+    let zip_file = zip.by_index_raw(index).map_loc(zip_error)?;
+    if zip_file.encrypted() {
+        return Err(zip_error(ZipError::UnsupportedArchive(
+            ZipError::PASSWORD_REQUIRED,
+        )))
+        .loc();
+    }
+    let data_start = zip_file
+        .data_start()
+        .ok_or_else(|| ZipError::InvalidArchive("início dos dados não encontrado".into()))
+        .map_loc(zip_error)?;
+    let compressed_size = zip_file.compressed_size();
+    let compression = zip_file.compression();
+    let crc32 = zip_file.crc32();
+
+    let mut file = open(archive)?;
+    file.seek(SeekFrom::Start(data_start))
+        .map_loc(|e| EFDError::InOut {
+            source: e,
+            path: path.to_path_buf(),
+        })?;
+    let compressed = BufReader::new(file).take(compressed_size);
+
+    let decoder: Box<dyn Read + Send> = match compression {
+        CompressionMethod::Stored => Box::new(compressed),
+        CompressionMethod::Deflated => Box::new(DeflateDecoder::new(compressed)),
+        _ => {
+            return Err(zip_error(ZipError::UnsupportedArchive(
+                "método de compressão não suportado",
+            )))
+            .loc();
+        }
+    };
+
+    Ok(Box::new(ZipEntryReader {
+        reader: CrcReader::new(decoder),
+        crc32,
+    }))
+}
+
+/// Confere o CRC-32 da entrada .zip (diretório central) ao final da leitura.
+struct ZipEntryReader {
+    reader: CrcReader<Box<dyn Read + Send>>,
+    crc32: u32,
+}
+
+impl Read for ZipEntryReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.reader.read(buf)?;
+
+        if count == 0 && !buf.is_empty() && self.reader.crc().sum() != self.crc32 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                ZipError::InvalidArchive("CRC-32 da entrada não confere".into()),
+            ));
+        }
+
+        Ok(count)
+    }
+}
+
+/// Conta as linhas de um arquivo EFD (comum ou compactado).
+pub fn count_efd_lines(path: &Path) -> EFDResult<u64> {
+    let mut reader = open_efd_reader(path)?;
+    let mut number_of_lines: u64 = 0;
+    let mut last_byte = NEWLINE_BYTE;
+
+    loop {
+        let buffer = reader.fill_buf().map_loc(|e| EFDError::InOut {
+            source: e,
+            path: path.to_path_buf(),
+        })?;
+
+        let Some(&last) = buffer.last() else {
+            break;
+        };
+
+        number_of_lines += buffer.iter().filter(|&&b| b == NEWLINE_BYTE).count() as u64;
+        last_byte = last;
+
+        let length = buffer.len();
+        reader.consume(length);
+    }
+
+    // Última linha sem o terminador \n
+    if last_byte != NEWLINE_BYTE {
+        number_of_lines += 1;
+    }
+
+    Ok(number_of_lines)
+}
+
+fn open(path: &Path) -> EFDResult<File> {
+    File::open(path).map_loc(|e| EFDError::InOut {
+        source: e,
+        path: path.to_path_buf(),
+    })
+}
+
+fn open_zip(archive: &Path) -> EFDResult<ZipArchive<BufReader<File>>> {
+    ZipArchive::new(BufReader::new(open(archive)?)).map_loc(|e| EFDError::Zip {
+        source: e,
+        path: archive.to_path_buf(),
+    })
+}
+
+/// Nome do arquivo contido no .gz: o nome original gravado no cabeçalho gzip (campo FNAME)
+/// ou, na sua ausência, o nome do próprio arquivo sem a extensão .gz.
+fn gzip_entry_name(archive: &Path) -> EFDResult<String> {
+    let decoder = MultiGzDecoder::new(BufReader::new(open(archive)?));
+
+    let original_name = decoder
+        .header()
+        .and_then(|header| header.filename())
+        .and_then(|name| std::str::from_utf8(name).ok())
+        .and_then(|name| Path::new(name).file_name())
+        .and_then(|name| name.to_str());
+
+    let entry = match original_name {
+        Some(name) => name.to_string(),
+        None => archive
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+
+    Ok(entry)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/archive_tests.rs"]
+mod archive_tests;
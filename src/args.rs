@@ -5,13 +5,14 @@ use clap::{
 use clap_complete::{Generator, Shell, generate};
 use claudiofsr_lib::clear_terminal_screen;
 use colored::Colorize;
+use rust_decimal::Decimal;
 use std::{
     io,
     process, // process::exit(1)
     str,
 };
 
-use crate::{EFDResult, ExcelMemoryMode};
+use crate::{EFDResult, ExcelMemoryMode, TOLERANCIA_DO_CALCULO};
 
 /// Define os estilos de cores para o terminal (Interface Moderna).
 ///
@@ -134,6 +135,44 @@ pub struct Arguments {
     #[arg(short('g'), long("generate"), value_enum)]
     pub generator: Option<Shell>,
 
+    /// Modo leniente: registra os erros de leitura e prossegue com as demais linhas.
+    ///
+    /// Lenient mode: collect every parsing error instead of aborting the file.
+    ///
+    /// As linhas com erro (ex: valor decimal inválido em um C170) são descartadas
+    /// e relacionadas com o arquivo, a linha, o registro e cada campo com erro no
+    /// arquivo CSV de erros, gravado ao lado dos demais resultados.
+    /// Os registros descendentes de uma linha descartada (ex: C170 de um C100)
+    /// também são descartados, pois não podem ser vinculados a outro documento.
+    /// Os registros descartados não compõem os totais e as conciliações.
+    ///
+    /// Exemplo: '-l -r 1 5'.
+    #[arg(
+        short('l'),
+        long("leniente"),
+        verbatim_doc_comment,
+        default_value_t = false,
+        requires = "range"
+    )]
+    pub leniente: bool,
+
+    /// Manter na análise as escriturações substituídas por retificadoras.
+    ///
+    /// Keep files superseded by a rectifying bookkeeping (retificadora) in the analysis.
+    ///
+    /// Por padrão, quando vários arquivos se referem ao mesmo CNPJ e período de
+    /// apuração, apenas a última retificadora é analisada (Registro 0000: TIPO_ESCRIT
+    /// e NUM_REC_ANTERIOR). Os arquivos ignorados são informados com o motivo.
+    ///
+    /// Exemplo: '--manter-retificadas -r 1 5'.
+    #[arg(
+        long("manter-retificadas"),
+        verbatim_doc_comment,
+        default_value_t = false,
+        requires = "range"
+    )]
+    pub manter_retificadas: bool,
+
     /// Seleciona o modo de consumo de memória para a geração da planilha Excel.
     ///
     /// Select the memory consumption mode for generating the Excel spreadsheet.
@@ -205,6 +244,86 @@ pub struct Arguments {
     /// Exemplo: '-r 1' (apenas o primeiro), '-r 1 5' (do primeiro ao quinto).
     #[arg(short, long, value_parser, verbatim_doc_comment, required = false, num_args = 1..=2)]
     pub range: Option<Vec<usize>>,
+
+    /// Tolerância no recálculo de PIS/COFINS (base de cálculo × alíquota), em reais.
+    ///
+    /// Tolerance when recomputing PIS/COFINS (tax base × rate), in reais.
+    ///
+    /// Itens com diferença maior que a tolerância entre o valor calculado e o
+    /// valor declarado são assinalados na coluna 'Verificação do Cálculo' e
+    /// relacionados na aba 'Divergências de Cálculo' da planilha Excel.
+    ///
+    /// A mesma tolerância é aplicada na conciliação dos totais dos documentos
+    /// (A100, C100 e D100) com a soma dos seus itens.
+    ///
+    /// Exemplo: '--tolerancia 0.05 -r 1 5'.
+    #[arg(
+        long("tolerancia"),
+        verbatim_doc_comment,
+        default_value_t = TOLERANCIA_DO_CALCULO,
+        value_parser = parse_tolerancia,
+    )]
+    pub tolerancia: Decimal,
+
+    /// Validar a estrutura dos arquivos SPED EFD selecionados, sem gerar planilhas.
+    ///
+    /// Validate the structure of the selected SPED EFD files (no spreadsheets are generated).
+    ///
+    /// Confronta os registros de encerramento (X990, 9900 e 9999) com os registros lidos,
+    /// verifica a hierarquia dos registros (registros órfãos, pais inesperados e repetições),
+    /// concilia os totais dos documentos com a soma dos seus itens (C100 x C170, A100 x A170,
+    /// D100 x D101/D105) e informa cada divergência com o arquivo e o nº da linha.
+    ///
+    /// Exemplo: '-v -r 1 5'.
+    #[arg(
+        short('v'),
+        long("validar"),
+        verbatim_doc_comment,
+        default_value_t = false,
+        requires = "range",
+        conflicts_with_all = ["assinatura", "diff"]
+    )]
+    pub validar: bool,
+
+    /// Verificar a assinatura digital (PKCS#7) dos arquivos SPED EFD selecionados.
+    ///
+    /// Verify the digital signature (PKCS#7) of the selected SPED EFD files.
+    ///
+    /// Informa o titular do certificado (nome e CNPJ/CPF), a validade e o emissor,
+    /// se o signatário é o contribuinte do Registro 0000 (ou outro estabelecimento
+    /// da mesma empresa) e se o arquivo foi alterado após a assinatura.
+    /// A cadeia de certificação até a AC Raiz da ICP-Brasil não é validada.
+    ///
+    /// Exemplo: '-a -r 1 5'.
+    #[arg(
+        short('a'),
+        long("assinatura"),
+        verbatim_doc_comment,
+        default_value_t = false,
+        requires = "range",
+        conflicts_with = "diff"
+    )]
+    pub assinatura: bool,
+
+    /// Comparar dois arquivos SPED EFD (ex: a escrituração original e a retificadora).
+    ///
+    /// Compare two SPED EFD files (e.g. the original and the rectifying bookkeeping).
+    ///
+    /// Os registros são pareados pela identidade de negócio, e não pelo nº da linha:
+    /// C100 pela chave (ou participante, modelo, série e número), C170 pelo documento
+    /// e NUM_ITEM, 0150 pelo COD_PART e Bloco M pelo CST ou código do crédito/contribuição.
+    /// Registros sem identidade de negócio são pareados pela ordem de ocorrência.
+    /// Informa, por registro, os incluídos, os excluídos e os campos alterados.
+    /// O intervalo deve selecionar exatamente dois arquivos: o anterior e o posterior.
+    ///
+    /// Exemplo: '--diff -r 1 2'.
+    #[arg(
+        long("diff"),
+        verbatim_doc_comment,
+        default_value_t = false,
+        requires = "range"
+    )]
+    pub diff: bool,
 }
 
 impl Arguments {
@@ -250,6 +369,21 @@ impl Arguments {
     }
 }
 
+/// Converte a tolerância informada (ex: "0.05" ou "0,05") em `Decimal` não negativo.
+fn parse_tolerancia(valor: &str) -> Result<Decimal, String> {
+    let tolerancia: Decimal = valor
+        .trim()
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| format!("tolerância inválida: '{valor}'"))?;
+
+    if tolerancia.is_sign_negative() {
+        return Err(format!("a tolerância não pode ser negativa: '{valor}'"));
+    }
+
+    Ok(tolerancia)
+}
+
 #[cfg(test)]
 mod tests_args {
     use super::*;
@@ -260,4 +394,34 @@ mod tests_args {
         use clap::CommandFactory;
         Arguments::command().debug_assert()
     }
+
+    #[test]
+    fn tolerancia_do_calculo() {
+        let args = Arguments::parse_from(["efd_contribuicoes", "-r", "1"]);
+        assert_eq!(args.tolerancia, TOLERANCIA_DO_CALCULO);
+
+        let args = Arguments::parse_from(["efd_contribuicoes", "--tolerancia", "0,05", "-r", "1"]);
+        assert_eq!(args.tolerancia, Decimal::new(5, 2));
+
+        assert!(parse_tolerancia("-0.01").is_err());
+        assert!(parse_tolerancia("abc").is_err());
+    }
+
+    /// As opções --validar, --assinatura e --diff selecionam modos de execução distintos.
+    #[test]
+    fn modos_mutuamente_exclusivos() {
+        for modos in [["-v", "-a"], ["-v", "--diff"], ["-a", "--diff"]] {
+            let error = Arguments::try_parse_from(
+                ["efd_contribuicoes"]
+                    .into_iter()
+                    .chain(modos)
+                    .chain(["-r", "1", "2"]),
+            )
+            .unwrap_err();
+            assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+
+        let args = Arguments::try_parse_from(["efd_contribuicoes", "--diff", "-r", "1", "2"]);
+        assert!(args.is_ok_and(|args| args.diff && !args.validar && !args.assinatura));
+    }
 }
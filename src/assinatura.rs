@@ -0,0 +1,665 @@
+use chrono::{DateTime, NaiveDateTime};
+use cms::{
+    cert::{
+        CertificateChoices,
+        x509::{
+            Certificate,
+            der::{
+                Decode, Encode, SliceReader,
+                asn1::{ObjectIdentifier, OctetString},
+            },
+            ext::pkix::{SubjectAltName, SubjectKeyIdentifier, name::GeneralName},
+            time::Time,
+        },
+    },
+    content_info::ContentInfo,
+    signed_data::{SignedData, SignerIdentifier, SignerInfo},
+};
+use rayon::prelude::*;
+use rsa::{Pkcs1v15Sign, RsaPublicKey, pkcs8::DecodePublicKey};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::{
+    fmt,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    AppConfig, DATE_FORMAT, EFDError, EFDResult, Registro0000, ResultExt, SpedMmap, SpedReader,
+    open_efd_reader, split_archive_path,
+};
+
+// ============================================================================
+// Assinatura digital (PKCS#7 / CMS) após o Registro 9999
+// ============================================================================
+
+/// ContentInfo do tipo SignedData (RFC 5652).
+const OID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+/// Codificação DER do OID SignedData (usada para localizar a estrutura no bloco binário).
+const DER_OID_SIGNED_DATA: &[u8] = &[
+    0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02,
+];
+
+/// Atributos assinados (RFC 5652, seção 11).
+const OID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const OID_SIGNING_TIME: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.5");
+
+/// Algoritmos de assinatura RSA PKCS#1 v1.5 (rsaEncryption e sha*WithRSAEncryption).
+const OIDS_RSA_PKCS1: [ObjectIdentifier; 5] = [
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1"),
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.5"),
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11"),
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12"),
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13"),
+];
+
+/// ICP-Brasil (DOC-ICP-04): campos otherName do SubjectAltName.
+const OID_ICP_PESSOA_FISICA: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.76.1.3.1");
+const OID_ICP_CNPJ: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.76.1.3.3");
+
+/// Algoritmo de resumo (hash) utilizado na assinatura.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgoritmoHash {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl AlgoritmoHash {
+    fn from_oid(oid: &ObjectIdentifier) -> Option<Self> {
+        match oid.to_string().as_str() {
+            "1.3.14.3.2.26" => Some(Self::Sha1),
+            "2.16.840.1.101.3.4.2.1" => Some(Self::Sha256),
+            "2.16.840.1.101.3.4.2.2" => Some(Self::Sha384),
+            "2.16.840.1.101.3.4.2.3" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    fn resumo(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha1 => Sha1::digest(bytes).to_vec(),
+            Self::Sha256 => Sha256::digest(bytes).to_vec(),
+            Self::Sha384 => Sha384::digest(bytes).to_vec(),
+            Self::Sha512 => Sha512::digest(bytes).to_vec(),
+        }
+    }
+
+    fn esquema_rsa(self) -> Pkcs1v15Sign {
+        match self {
+            Self::Sha1 => Pkcs1v15Sign::new::<Sha1>(),
+            Self::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+            Self::Sha384 => Pkcs1v15Sign::new::<Sha384>(),
+            Self::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+        }
+    }
+}
+
+impl fmt::Display for AlgoritmoHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nome = match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        };
+        write!(f, "{nome}")
+    }
+}
+
+/// Dados da assinatura digital de um arquivo SPED EFD.
+///
+/// A cadeia de certificação não é validada: um certificado autoassinado com o
+/// CNPJ do contribuinte é aceito como titular.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssinaturaDigital {
+    /// Titular do certificado (Subject).
+    pub titular: String,
+
+    /// CNPJ ou CPF do titular (ICP-Brasil: SubjectAltName ou sufixo do CN).
+    pub cnpj_cpf: Option<String>,
+
+    /// Autoridade certificadora emissora (Issuer).
+    pub emissor: String,
+
+    /// Início da validade do certificado (UTC).
+    pub validade_inicio: NaiveDateTime,
+
+    /// Fim da validade do certificado (UTC).
+    pub validade_fim: NaiveDateTime,
+
+    /// Data da assinatura (atributo signingTime, UTC), se informada.
+    pub data_assinatura: Option<NaiveDateTime>,
+
+    /// Algoritmo de resumo utilizado.
+    pub algoritmo: AlgoritmoHash,
+
+    /// O resumo do conteúdo confere com o atributo messageDigest (None: atributo ausente).
+    pub resumo_confere: Option<bool>,
+
+    /// A assinatura confere com a chave pública do certificado (None: chave não RSA).
+    pub assinatura_valida: Option<bool>,
+}
+
+impl AssinaturaDigital {
+    /// Lê o arquivo (comum ou entrada de arquivo compactado) e extrai a assinatura.
+    pub fn ler(path: &Path) -> EFDResult<Option<Self>> {
+        if split_archive_path(path).is_some() {
+            let mut bytes = Vec::new();
+            open_efd_reader(path)?
+                .read_to_end(&mut bytes)
+                .map_loc(|e| EFDError::InOut {
+                    source: e,
+                    path: path.to_path_buf(),
+                })?;
+            return Self::extrair(&bytes);
+        }
+
+        // Arquivo mapeado em memória: o conteúdo assinado não é copiado.
+        Self::extrair(SpedMmap::open(path)?.as_bytes())
+    }
+
+    /// Extrai a assinatura (PKCS#7 destacada) dos bytes do arquivo SPED EFD.
+    ///
+    /// O conteúdo assinado corresponde aos bytes do início do arquivo até a linha
+    /// do Registro 9999, inclusive. O bloco binário posterior pode conter um cabeçalho
+    /// antes da estrutura PKCS#7, localizada pelo OID SignedData.
+    ///
+    /// Retorna `Ok(None)` para arquivos sem assinatura.
+    pub fn extrair(bytes: &[u8]) -> EFDResult<Option<Self>> {
+        let Some((conteudo, bloco)) = separar_assinatura(bytes) else {
+            return Ok(None);
+        };
+
+        if bloco.trim_ascii().is_empty() {
+            return Ok(None);
+        }
+
+        let signed_data = localizar_signed_data(bloco)
+            .ok_or_else(|| {
+                EFDError::InvalidSignature(
+                    "estrutura PKCS#7 (SignedData) não encontrada".to_string(),
+                )
+            })
+            .loc()?;
+
+        let signer_info = signed_data
+            .signer_infos
+            .0
+            .get(0)
+            .ok_or_else(|| EFDError::InvalidSignature("assinatura sem SignerInfo".to_string()))
+            .loc()?;
+
+        let certificado = certificado_do_signatario(&signed_data, signer_info)
+            .ok_or_else(|| {
+                EFDError::InvalidSignature(
+                    "signatário não identificado: nenhum certificado corresponde ao \
+                     identificador do SignerInfo"
+                        .to_string(),
+                )
+            })
+            .loc()?;
+
+        let algoritmo = AlgoritmoHash::from_oid(&signer_info.digest_alg.oid)
+            .ok_or_else(|| {
+                EFDError::InvalidSignature(format!(
+                    "algoritmo de resumo não suportado: {}",
+                    signer_info.digest_alg.oid
+                ))
+            })
+            .loc()?;
+
+        let tbs = &certificado.tbs_certificate;
+        let (resumo_confere, assinatura_valida) =
+            verificar_integridade(conteudo, signer_info, certificado, algoritmo);
+
+        Ok(Some(Self {
+            titular: tbs.subject.to_string(),
+            cnpj_cpf: documento_do_titular(certificado),
+            emissor: tbs.issuer.to_string(),
+            validade_inicio: data_hora(&tbs.validity.not_before),
+            validade_fim: data_hora(&tbs.validity.not_after),
+            data_assinatura: data_da_assinatura(signer_info),
+            algoritmo,
+            resumo_confere,
+            assinatura_valida,
+        }))
+    }
+
+    /// Integridade do conteúdo assinado.
+    ///
+    /// O messageDigest, isoladamente, não autentica o conteúdo: o arquivo só é
+    /// considerado inalterado se a assinatura foi conferida com a chave pública
+    /// do certificado.
+    pub fn integridade(&self) -> Integridade {
+        let verificacoes = [self.resumo_confere, self.assinatura_valida];
+
+        if verificacoes.contains(&Some(false)) {
+            Integridade::Alterado
+        } else if self.assinatura_valida == Some(true) {
+            Integridade::Inalterado
+        } else {
+            Integridade::NaoVerificavel
+        }
+    }
+
+    /// O arquivo não foi alterado após a assinatura.
+    pub fn arquivo_integro(&self) -> bool {
+        self.integridade() == Integridade::Inalterado
+    }
+}
+
+/// Resultado da verificação de integridade do conteúdo assinado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integridade {
+    /// Assinatura conferida: o arquivo não foi alterado após a assinatura.
+    Inalterado,
+    /// Resumo ou assinatura divergente.
+    Alterado,
+    /// Assinatura não conferida (ex: chave pública não RSA).
+    NaoVerificavel,
+}
+
+/// Separa o conteúdo assinado (até a linha do Registro 9999, inclusive) do bloco posterior.
+///
+/// Retorna `None` se o arquivo não contém o Registro 9999.
+pub fn separar_assinatura(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let mut inicio = 0;
+
+    for linha in bytes.split(|&b| b == b'\n') {
+        let fim = (inicio + linha.len() + 1).min(bytes.len());
+
+        if linha.trim_ascii_start().starts_with(b"|9999|") {
+            return Some(bytes.split_at(fim));
+        }
+
+        inicio = fim;
+    }
+
+    None
+}
+
+/// Localiza e decodifica a estrutura ContentInfo (SignedData) no bloco binário.
+fn localizar_signed_data(bloco: &[u8]) -> Option<SignedData> {
+    bloco
+        .windows(DER_OID_SIGNED_DATA.len())
+        .enumerate()
+        .filter(|(_, janela)| *janela == DER_OID_SIGNED_DATA)
+        // O OID é precedido pelo cabeçalho da SEQUENCE ContentInfo (tag 0x30 e 1 a 5 bytes de tamanho).
+        .flat_map(|(pos, _)| (2..=6).filter_map(move |recuo| pos.checked_sub(recuo)))
+        .filter(|&inicio| bloco[inicio] == 0x30)
+        .find_map(|inicio| {
+            let mut reader = SliceReader::new(&bloco[inicio..]).ok()?;
+            let content_info = ContentInfo::decode(&mut reader).ok()?;
+            (content_info.content_type == OID_SIGNED_DATA)
+                .then(|| content_info.content.decode_as::<SignedData>().ok())?
+        })
+}
+
+/// Certificado identificado pelo SignerInfo (emissor e número de série ou identificador
+/// da chave), ou `None`: os demais certificados da estrutura não assinaram o conteúdo.
+fn certificado_do_signatario<'a>(
+    signed_data: &'a SignedData,
+    signer_info: &SignerInfo,
+) -> Option<&'a Certificate> {
+    let certificados: Vec<&Certificate> = signed_data
+        .certificates
+        .iter()
+        .flat_map(|set| set.0.iter())
+        .filter_map(|choice| match choice {
+            CertificateChoices::Certificate(cert) => Some(cert),
+            CertificateChoices::Other(_) => None,
+        })
+        .collect();
+
+    certificados.into_iter().find(|cert| {
+        let tbs = &cert.tbs_certificate;
+        match &signer_info.sid {
+            SignerIdentifier::IssuerAndSerialNumber(id) => {
+                tbs.issuer == id.issuer && tbs.serial_number == id.serial_number
+            }
+            SignerIdentifier::SubjectKeyIdentifier(ski) => {
+                matches!(tbs.get::<SubjectKeyIdentifier>(), Ok(Some((_, ref k))) if k == ski)
+            }
+        }
+    })
+}
+
+/// Confere o resumo (messageDigest) e a assinatura RSA do conteúdo.
+///
+/// O conteúdo é conferido com e sem o terminador de linha final do Registro 9999.
+fn verificar_integridade(
+    conteudo: &[u8],
+    signer_info: &SignerInfo,
+    certificado: &Certificate,
+    algoritmo: AlgoritmoHash,
+) -> (Option<bool>, Option<bool>) {
+    let candidatos = [conteudo, conteudo.trim_ascii_end()];
+
+    let chave = chave_rsa(certificado, signer_info);
+    let assinatura = signer_info.signature.as_bytes();
+    let verificar = |mensagem: &[u8]| -> Option<bool> {
+        let chave = chave.as_ref()?;
+        let resumo = algoritmo.resumo(mensagem);
+        Some(
+            chave
+                .verify(algoritmo.esquema_rsa(), &resumo, assinatura)
+                .is_ok(),
+        )
+    };
+
+    match &signer_info.signed_attrs {
+        // Com atributos assinados: o messageDigest é o resumo do conteúdo e a
+        // assinatura recai sobre a codificação DER dos atributos (SET OF).
+        Some(atributos) => {
+            let message_digest = atributos
+                .iter()
+                .find(|attr| attr.oid == OID_MESSAGE_DIGEST)
+                .and_then(|attr| attr.values.get(0))
+                .and_then(|value| value.decode_as::<OctetString>().ok());
+
+            let resumo_confere = message_digest.map(|md| {
+                candidatos
+                    .iter()
+                    .any(|c| algoritmo.resumo(c) == md.as_bytes())
+            });
+
+            let assinatura_valida = atributos.to_der().ok().and_then(|der| verificar(&der));
+
+            (resumo_confere, assinatura_valida)
+        }
+        // Sem atributos assinados: a assinatura recai diretamente sobre o conteúdo.
+        None => {
+            let assinatura_valida = candidatos
+                .iter()
+                .map(|c| verificar(c))
+                .reduce(|a, b| match (a, b) {
+                    (Some(x), Some(y)) => Some(x || y),
+                    _ => None,
+                })
+                .flatten();
+
+            (None, assinatura_valida)
+        }
+    }
+}
+
+fn chave_rsa(certificado: &Certificate, signer_info: &SignerInfo) -> Option<RsaPublicKey> {
+    if !OIDS_RSA_PKCS1.contains(&signer_info.signature_algorithm.oid) {
+        return None;
+    }
+
+    let spki = certificado
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+        .ok()?;
+
+    RsaPublicKey::from_public_key_der(&spki).ok()
+}
+
+/// CNPJ ou CPF do titular do certificado ICP-Brasil.
+///
+/// Prioriza o SubjectAltName (otherName 2.16.76.1.3.3: CNPJ; 2.16.76.1.3.1: data de
+/// nascimento seguida do CPF) e, na sua ausência, o sufixo numérico do CN (`NOME:CNPJ`).
+fn documento_do_titular(certificado: &Certificate) -> Option<String> {
+    let tbs = &certificado.tbs_certificate;
+    let apenas_digitos = |bytes: &[u8]| -> String {
+        bytes
+            .iter()
+            .filter(|b| b.is_ascii_digit())
+            .map(|&b| b as char)
+            .collect()
+    };
+
+    if let Ok(Some((_, san))) = tbs.get::<SubjectAltName>() {
+        for name in &san.0 {
+            let GeneralName::OtherName(other) = name else {
+                continue;
+            };
+
+            let valor = other.value.value();
+
+            if other.type_id == OID_ICP_CNPJ {
+                let cnpj = apenas_digitos(valor);
+                if cnpj.len() == 14 {
+                    return Some(cnpj);
+                }
+            } else if other.type_id == OID_ICP_PESSOA_FISICA {
+                let cpf = apenas_digitos(valor.get(8..19).unwrap_or_default());
+                if cpf.len() == 11 {
+                    return Some(cpf);
+                }
+            }
+        }
+    }
+
+    tbs.subject
+        .0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .filter(|atv| atv.oid.to_string() == "2.5.4.3") // CN
+        .filter_map(|atv| std::str::from_utf8(atv.value.value()).ok())
+        .filter_map(|cn| cn.rsplit_once(':'))
+        .map(|(_nome, documento)| documento.trim())
+        .find(|doc| matches!(doc.len(), 11 | 14) && doc.bytes().all(|b| b.is_ascii_digit()))
+        .map(String::from)
+}
+
+fn data_da_assinatura(signer_info: &SignerInfo) -> Option<NaiveDateTime> {
+    signer_info
+        .signed_attrs
+        .as_ref()?
+        .iter()
+        .find(|attr| attr.oid == OID_SIGNING_TIME)
+        .and_then(|attr| attr.values.get(0))
+        .and_then(|value| value.to_der().ok())
+        .and_then(|der| Time::from_der(&der).ok())
+        .map(|time| data_hora(&time))
+}
+
+fn data_hora(time: &Time) -> NaiveDateTime {
+    let segundos = time
+        .to_unix_duration()
+        .as_secs()
+        .try_into()
+        .unwrap_or(i64::MAX);
+    DateTime::from_timestamp(segundos, 0)
+        .unwrap_or_default()
+        .naive_utc()
+}
+
+// ============================================================================
+// Relatório (opção --assinatura)
+// ============================================================================
+
+/// Relação entre o titular do certificado e o contribuinte do Registro 0000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signatario {
+    /// Titular com o CNPJ do Registro 0000.
+    Contribuinte,
+    /// Titular com a mesma raiz (8 primeiros dígitos) do CNPJ do Registro 0000:
+    /// outro estabelecimento da mesma empresa.
+    OutroEstabelecimento,
+    /// Titular de outra empresa, pessoa física ou não identificado.
+    Divergente,
+}
+
+/// Resultado da verificação da assinatura digital de um arquivo.
+#[derive(Debug)]
+pub struct VerificacaoAssinatura {
+    pub arquivo: PathBuf,
+
+    /// CNPJ informado no Registro 0000.
+    pub cnpj_0000: Option<String>,
+
+    /// Assinatura extraída (None: arquivo sem assinatura).
+    pub assinatura: Option<AssinaturaDigital>,
+}
+
+impl VerificacaoAssinatura {
+    /// Extrai a assinatura do arquivo e o CNPJ do Registro 0000.
+    pub fn new(arquivo: &Path) -> EFDResult<Self> {
+        let cnpj_0000 = match SpedReader::open(arquivo)?.with_registros(&["0000"]).next() {
+            Some(record) => record?
+                .downcast_ref::<Registro0000>()?
+                .cnpj
+                .as_deref()
+                .map(String::from),
+            None => None,
+        };
+
+        Ok(Self {
+            arquivo: arquivo.to_path_buf(),
+            cnpj_0000,
+            assinatura: AssinaturaDigital::ler(arquivo)?,
+        })
+    }
+
+    /// Compara o CNPJ/CPF do titular do certificado com o CNPJ do Registro 0000.
+    pub fn signatario(&self) -> Signatario {
+        let titular = self.assinatura.as_ref().and_then(|a| a.cnpj_cpf.as_deref());
+
+        match (titular, self.cnpj_0000.as_deref()) {
+            (Some(titular), Some(cnpj)) if titular == cnpj => Signatario::Contribuinte,
+            (Some(titular), Some(cnpj))
+                if titular.len() == 14 && cnpj.len() == 14 && titular[..8] == cnpj[..8] =>
+            {
+                Signatario::OutroEstabelecimento
+            }
+            _ => Signatario::Divergente,
+        }
+    }
+
+    /// O titular do certificado é o contribuinte do Registro 0000 ou outro
+    /// estabelecimento da mesma empresa (mesmo CNPJ base).
+    pub fn signatario_confere(&self) -> bool {
+        self.signatario() != Signatario::Divergente
+    }
+
+    /// Arquivo assinado pelo contribuinte e inalterado após a assinatura.
+    pub fn confere(&self) -> bool {
+        self.signatario_confere()
+            && self
+                .assinatura
+                .as_ref()
+                .is_some_and(AssinaturaDigital::arquivo_integro)
+    }
+}
+
+impl fmt::Display for VerificacaoAssinatura {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(assinatura) = &self.assinatura else {
+            return writeln!(f, "   Arquivo sem assinatura digital.");
+        };
+
+        let titular = assinatura.cnpj_cpf.as_deref().unwrap_or("não identificado");
+        let cnpj_0000 = self.cnpj_0000.as_deref().unwrap_or("não informado");
+        let data_assinatura = assinatura
+            .data_assinatura
+            .map_or("não informada".to_string(), |dt| {
+                dt.format(DATE_FORMAT).to_string()
+            });
+
+        writeln!(f, "   Titular: {}", assinatura.titular)?;
+        writeln!(f, "   CNPJ/CPF do titular: {titular}")?;
+        writeln!(f, "   Emissor: {}", assinatura.emissor)?;
+        writeln!(
+            f,
+            "   Cadeia de certificação: NÃO VERIFICADA (o certificado não é validado até a \
+             AC Raiz da ICP-Brasil)."
+        )?;
+        writeln!(
+            f,
+            "   Validade: {} a {} (UTC)",
+            assinatura.validade_inicio.format(DATE_FORMAT),
+            assinatura.validade_fim.format(DATE_FORMAT)
+        )?;
+        writeln!(f, "   Data da assinatura: {data_assinatura} (UTC)")?;
+        writeln!(f, "   Algoritmo de resumo: {}", assinatura.algoritmo)?;
+
+        match self.signatario() {
+            Signatario::Contribuinte => {
+                writeln!(f, "   Signatário: confere com o CNPJ do Registro 0000.")?
+            }
+            Signatario::OutroEstabelecimento => writeln!(
+                f,
+                "   Signatário: assinado por outro estabelecimento da mesma empresa \
+                 (titular {titular}; Registro 0000: CNPJ {cnpj_0000})."
+            )?,
+            Signatario::Divergente => writeln!(
+                f,
+                "   Signatário: DIVERGENTE (titular {titular}; Registro 0000: CNPJ {cnpj_0000})."
+            )?,
+        }
+
+        match assinatura.integridade() {
+            Integridade::Inalterado => {
+                writeln!(f, "   Integridade: arquivo inalterado após a assinatura.")
+            }
+            Integridade::Alterado => writeln!(
+                f,
+                "   Integridade: ARQUIVO ALTERADO após a assinatura (ou assinatura inválida)."
+            ),
+            Integridade::NaoVerificavel => writeln!(
+                f,
+                "   Integridade: NÃO VERIFICÁVEL (a assinatura não foi conferida com a chave \
+                 pública do certificado)."
+            ),
+        }
+    }
+}
+
+/// Verifica a assinatura digital dos arquivos selecionados (opção --assinatura).
+///
+/// Retorna a quantidade de arquivos sem assinatura, com signatário divergente,
+/// alterados após a assinatura, com integridade não verificável ou com assinatura
+/// ilegível.
+pub fn verificar_assinaturas(config: &AppConfig, write: &mut dyn Write) -> EFDResult<usize> {
+    let resultados: Vec<EFDResult<VerificacaoAssinatura>> = config
+        .all_files
+        .par_iter()
+        .map(|arquivo| VerificacaoAssinatura::new(arquivo))
+        .collect();
+
+    writeln!(
+        write,
+        "Assinatura digital (PKCS#7) dos arquivos SPED EFD:\n"
+    )?;
+
+    let mut divergentes = 0;
+    for (index, (arquivo, resultado)) in config.all_files.iter().zip(&resultados).enumerate() {
+        writeln!(write, "EFD {:02}: {}", index + 1, arquivo.display())?;
+
+        match resultado {
+            Ok(verificacao) => {
+                writeln!(write, "{verificacao}")?;
+                if !verificacao.confere() {
+                    divergentes += 1;
+                }
+            }
+            Err(error) => {
+                writeln!(write, "   Erro na leitura da assinatura: {error}\n")?;
+                divergentes += 1;
+            }
+        }
+    }
+
+    writeln!(
+        write,
+        "Total: {divergentes} arquivo(s) com divergência em {} arquivo(s) analisado(s).\n",
+        config.all_files.len()
+    )?;
+
+    Ok(divergentes)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/assinatura_tests.rs"]
+mod assinatura_tests;
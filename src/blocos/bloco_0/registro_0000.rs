@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToCNPJ, ToNaiveDate,
-    ToOptionalInteger, impl_reg_methods,
+    ToOptionalInteger, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use std::{path::Path, sync::Arc};
@@ -39,6 +39,10 @@ pub struct Registro0000 {
 }
 
 impl_reg_methods!(Registro0000);
+impl_sped_writer!(Registro0000 {
+    cod_ver => 3, tipo_escrit, ind_sit_esp, num_rec_anterior, dt_ini, dt_fin, nome, cnpj, uf,
+    cod_mun, suframa, ind_nat_pj, ind_ativ,
+});
 
 impl Registro0000 {
     /// Retorna o CNPJ completo (já validado e limpo durante o parse).
@@ -124,13 +128,12 @@ impl SpedParser for Registro0000 {
         let dt_ini = get_required_date(6, "DT_INI")?; // Will error if empty or invalid date
         let dt_fin = get_required_date(7, "DT_FIN")?;
 
-        //let nome = fields.get(8).map(|n| Arc::from(n.to_uppercase()));
-        let nome = fields.get(8).to_upper_arc(); // Normaliza nome para Uppercase
+        let nome = fields.get(8).to_arc();
 
         // Aplicando a trait ToCNPJ no campo de índice 9
         let cnpj = get_full_cnpj(9, "CNPJ")?;
 
-        let uf = fields.get(10).to_upper_arc(); // UF sempre Uppercase
+        let uf = fields.get(10).to_arc();
         let cod_mun = fields.get(11).to_arc();
         let suframa = fields.get(12).to_arc();
         let ind_nat_pj = fields.get(13).to_arc();
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const EXPECTED_FIELDS: usize = 4;
@@ -43,6 +45,7 @@ pub struct Registro0001 {
 }
 
 impl_reg_methods!(Registro0001);
+impl_sped_writer!(Registro0001 { ind_mov });
 
 impl SpedParser for Registro0001 {
     type Output = Registro0001;
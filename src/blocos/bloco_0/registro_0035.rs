@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const EXPECTED_FIELDS: usize = 6;
@@ -24,6 +26,11 @@ pub struct Registro0035 {
 }
 
 impl_reg_methods!(Registro0035);
+impl_sped_writer!(Registro0035 {
+    cod_scp,
+    desc_scp,
+    inf_comp,
+});
 
 impl SpedParser for Registro0035 {
     type Output = Registro0035;
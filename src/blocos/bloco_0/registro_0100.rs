@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const EXPECTED_FIELDS: usize = 16;
@@ -34,6 +36,21 @@ pub struct Registro0100 {
 }
 
 impl_reg_methods!(Registro0100);
+impl_sped_writer!(Registro0100 {
+    nome,
+    cpf,
+    crc,
+    cnpj,
+    cep,
+    end,
+    num,
+    compl,
+    bairro,
+    fone,
+    fax,
+    email,
+    cod_mun,
+});
 
 impl SpedParser for Registro0100 {
     type Output = Registro0100;
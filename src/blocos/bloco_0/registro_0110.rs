@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0110";
@@ -24,6 +26,12 @@ pub struct Registro0110 {
 }
 
 impl_reg_methods!(Registro0110);
+impl_sped_writer!(Registro0110 {
+    cod_inc_trib,
+    ind_apro_cred,
+    cod_tipo_cont,
+    ind_reg_cum,
+});
 
 impl SpedParser for Registro0110 {
     type Output = Registro0110;
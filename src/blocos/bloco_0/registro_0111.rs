@@ -1,6 +1,6 @@
 use crate::{
     DECIMAL_VALOR, DecimalExt, EFDError, EFDResult, ResultExt, SpedParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use rust_decimal::Decimal;
 use std::{fmt::Write, path::Path, sync::Arc};
@@ -73,6 +73,13 @@ impl Registro0111 {
 }
 
 impl_reg_methods!(Registro0111);
+impl_sped_writer!(Registro0111 {
+    rec_bru_ncum_trib_mi,
+    rec_bru_ncum_nt_mi,
+    rec_bru_ncum_exp,
+    rec_bru_cum,
+    rec_bru_total,
+});
 
 impl SpedParser for Registro0111 {
     type Output = Registro0111;
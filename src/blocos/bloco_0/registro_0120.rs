@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0120";
@@ -22,6 +24,10 @@ pub struct Registro0120 {
 }
 
 impl_reg_methods!(Registro0120);
+impl_sped_writer!(Registro0120 {
+    mes_refer,
+    inf_comp,
+});
 
 impl SpedParser for Registro0120 {
     type Output = Registro0120;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0140";
@@ -28,6 +30,16 @@ pub struct Registro0140 {
 }
 
 impl_reg_methods!(Registro0140);
+impl_sped_writer!(Registro0140 {
+    cod_est,
+    nome,
+    cnpj,
+    uf,
+    ie,
+    cod_mun,
+    im,
+    suframa,
+});
 
 impl SpedParser for Registro0140 {
     type Output = Registro0140;
@@ -47,7 +59,7 @@ impl SpedParser for Registro0140 {
         }
 
         let cod_est = fields.get(2).to_arc();
-        let nome = fields.get(3).to_upper_arc(); // Normaliza nome para Uppercase
+        let nome = fields.get(3).to_arc();
         let cnpj = fields.get(4).to_arc();
         let uf = fields.get(5).to_arc();
         let ie = fields.get(6).to_arc();
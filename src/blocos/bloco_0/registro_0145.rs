@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use rust_decimal::Decimal;
 use std::{path::Path, sync::Arc};
@@ -28,6 +29,13 @@ pub struct Registro0145 {
 }
 
 impl_reg_methods!(Registro0145);
+impl_sped_writer!(Registro0145 {
+    cod_inc_trib,
+    vl_rec_tot,
+    vl_rec_ativ,
+    vl_rec_demais_ativ,
+    info_compl,
+});
 
 impl SpedParser for Registro0145 {
     type Output = Registro0145;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const EXPECTED_FIELDS: usize = 15;
@@ -33,6 +35,20 @@ pub struct Registro0150 {
 }
 
 impl_reg_methods!(Registro0150);
+impl_sped_writer!(Registro0150 {
+    cod_part,
+    nome,
+    cod_pais,
+    cnpj,
+    cpf,
+    ie,
+    cod_mun,
+    suframa,
+    end,
+    num,
+    compl,
+    bairro,
+});
 
 impl SpedParser for Registro0150 {
     type Output = Registro0150;
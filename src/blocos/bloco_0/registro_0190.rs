@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0190";
@@ -22,6 +24,7 @@ pub struct Registro0190 {
 }
 
 impl_reg_methods!(Registro0190);
+impl_sped_writer!(Registro0190 { unid, descr });
 
 impl SpedParser for Registro0190 {
     type Output = Registro0190;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, TipoDoItem, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use rust_decimal::Decimal;
 use std::{path::Path, sync::Arc};
@@ -36,6 +36,19 @@ pub struct Registro0200 {
 }
 
 impl_reg_methods!(Registro0200);
+impl_sped_writer!(Registro0200 {
+    cod_item,
+    descr_item,
+    cod_barra,
+    cod_ant_item,
+    unid_inv,
+    tipo_item,
+    cod_ncm,
+    ex_ipi,
+    cod_gen,
+    cod_lst,
+    aliq_icms,
+});
 
 impl SpedParser for Registro0200 {
     type Output = Registro0200;
@@ -61,7 +74,7 @@ impl SpedParser for Registro0200 {
         };
 
         let cod_item = fields.get(2).to_arc();
-        let descr_item = fields.get(3).to_upper_arc();
+        let descr_item = fields.get(3).to_arc();
         let cod_barra = fields.get(4).to_arc();
         let cod_ant_item = fields.get(5).to_arc();
         let unid_inv = fields.get(6).to_arc();
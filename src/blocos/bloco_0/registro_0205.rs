@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use std::{path::Path, sync::Arc};
@@ -27,6 +28,12 @@ pub struct Registro0205 {
 }
 
 impl_reg_methods!(Registro0205);
+impl_sped_writer!(Registro0205 {
+    descr_ant_item,
+    dt_ini,
+    dt_fim,
+    cod_ant_item,
+});
 
 impl SpedParser for Registro0205 {
     type Output = Registro0205;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0206";
@@ -21,6 +23,7 @@ pub struct Registro0206 {
 }
 
 impl_reg_methods!(Registro0206);
+impl_sped_writer!(Registro0206 { cod_comb });
 
 impl SpedParser for Registro0206 {
     type Output = Registro0206;
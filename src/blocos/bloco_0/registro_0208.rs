@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0208";
@@ -23,6 +25,11 @@ pub struct Registro0208 {
 }
 
 impl_reg_methods!(Registro0208);
+impl_sped_writer!(Registro0208 {
+    cod_tab,
+    cod_gru,
+    marca_com,
+});
 
 impl SpedParser for Registro0208 {
     type Output = Registro0208;
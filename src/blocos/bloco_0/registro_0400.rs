@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0400";
@@ -22,6 +24,7 @@ pub struct Registro0400 {
 }
 
 impl_reg_methods!(Registro0400);
+impl_sped_writer!(Registro0400 { cod_nat, descr_nat });
 
 impl SpedParser for Registro0400 {
     type Output = Registro0400;
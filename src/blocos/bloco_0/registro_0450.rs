@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0450";
@@ -23,6 +25,7 @@ pub struct Registro0450 {
 }
 
 impl_reg_methods!(Registro0450);
+impl_sped_writer!(Registro0450 { cod_inf, txt });
 
 impl SpedParser for Registro0450 {
     type Output = Registro0450;
@@ -42,7 +45,7 @@ impl SpedParser for Registro0450 {
         }
 
         let cod_inf = fields.get(2).to_arc();
-        let txt = fields.get(3).to_upper_arc();
+        let txt = fields.get(3).to_arc();
 
         let reg = Registro0450 {
             nivel: 3,
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, GrupoDeContas, ResultExt, SpedParser, StringParser, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use std::{path::Path, sync::Arc};
@@ -33,6 +33,16 @@ pub struct Registro0500 {
 }
 
 impl_reg_methods!(Registro0500);
+impl_sped_writer!(Registro0500 {
+    dt_alt,
+    cod_nat_cc,
+    ind_cta,
+    nivel_conta,
+    cod_cta,
+    nome_cta,
+    cod_cta_ref,
+    cnpj_est,
+});
 
 impl SpedParser for Registro0500 {
     type Output = Registro0500;
@@ -64,7 +74,7 @@ impl SpedParser for Registro0500 {
         let ind_cta = fields.get(4).to_arc();
         let nivel_conta = fields.get(5).to_arc();
         let cod_cta = fields.get(6).to_arc();
-        let nome_cta = fields.get(7).to_upper_arc();
+        let nome_cta = fields.get(7).to_arc();
         let cod_cta_ref = fields.get(8).to_arc();
         let cnpj_est = fields.get(9).to_arc();
 
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use std::{path::Path, sync::Arc};
@@ -26,6 +27,11 @@ pub struct Registro0600 {
 }
 
 impl_reg_methods!(Registro0600);
+impl_sped_writer!(Registro0600 {
+    dt_alt,
+    cod_ccus,
+    ccus,
+});
 
 impl SpedParser for Registro0600 {
     type Output = Registro0600;
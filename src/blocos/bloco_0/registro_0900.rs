@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods, impl_sped_writer,
+};
 use rust_decimal::Decimal;
 use std::{path::Path, sync::Arc};
 
@@ -35,6 +37,22 @@ pub struct Registro0900 {
 }
 
 impl_reg_methods!(Registro0900);
+impl_sped_writer!(Registro0900 {
+    rec_total_bloco_a,
+    rec_nrb_bloco_a,
+    rec_total_bloco_c,
+    rec_nrb_bloco_c,
+    rec_total_bloco_d,
+    rec_nrb_bloco_d,
+    rec_total_bloco_f,
+    rec_nrb_bloco_f,
+    rec_total_bloco_i,
+    rec_nrb_bloco_i,
+    rec_total_bloco_1,
+    rec_nrb_bloco_1,
+    rec_total_periodo,
+    rec_total_nrb_periodo,
+});
 
 impl SpedParser for Registro0900 {
     type Output = Registro0900;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use std::{path::Path, sync::Arc};
 
 const REGISTRO: &str = "0990";
@@ -21,6 +24,7 @@ pub struct Registro0990 {
 }
 
 impl_reg_methods!(Registro0990);
+impl_sped_writer!(Registro0990 { qtd_lin_0 });
 
 impl SpedParser for Registro0990 {
     type Output = Registro0990;
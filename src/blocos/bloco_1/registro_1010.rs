@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -30,6 +31,14 @@ pub struct Registro1010 {
 }
 
 impl_reg_methods!(Registro1010);
+impl_sped_writer!(Registro1010 {
+    num_proc,
+    id_sec_jud,
+    id_vara,
+    ind_nat_acao,
+    desc_dec_jud,
+    dt_sent_jud,
+});
 
 impl SpedParser for Registro1010 {
     type Output = Registro1010;
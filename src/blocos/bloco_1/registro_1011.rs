@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -51,6 +51,12 @@ pub struct Registro1011 {
 }
 
 impl_reg_methods!(Registro1011);
+impl_sped_writer!(Registro1011 {
+    reg_ref, chave_doc, cod_part, cod_item, dt_oper, vl_oper, cst_pis, vl_bc_pis, aliq_pis, vl_pis,
+    cst_cofins, vl_bc_cofins, aliq_cofins, vl_cofins, cst_pis_susp => 2, vl_bc_pis_susp,
+    aliq_pis_susp, vl_pis_susp, cst_cofins_susp => 2, vl_bc_cofins_susp, aliq_cofins_susp,
+    vl_cofins_susp, cod_cta, cod_ccus, desc_doc_oper,
+});
 
 impl SpedParser for Registro1011 {
     type Output = Registro1011;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -27,6 +28,11 @@ pub struct Registro1020 {
 }
 
 impl_reg_methods!(Registro1020);
+impl_sped_writer!(Registro1020 {
+    num_proc,
+    ind_nat_acao,
+    dt_dec_adm,
+});
 
 impl SpedParser for Registro1020 {
     type Output = Registro1020;
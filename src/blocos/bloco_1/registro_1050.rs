@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -44,6 +44,26 @@ pub struct Registro1050 {
 }
 
 impl_reg_methods!(Registro1050);
+impl_sped_writer!(Registro1050 {
+    dt_ref,
+    ind_aj_bc,
+    cnpj,
+    vl_aj_tot,
+    vl_aj_cst01,
+    vl_aj_cst02,
+    vl_aj_cst03,
+    vl_aj_cst04,
+    vl_aj_cst05,
+    vl_aj_cst06,
+    vl_aj_cst07,
+    vl_aj_cst08,
+    vl_aj_cst09,
+    vl_aj_cst49,
+    vl_aj_cst99,
+    ind_aprop,
+    num_rec,
+    info_compl,
+});
 
 impl SpedParser for Registro1050 {
     type Output = Registro1050;
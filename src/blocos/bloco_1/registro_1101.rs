@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -28,7 +28,7 @@ pub struct Registro1101 {
     pub cod_mod: Option<CompactString>,            // 4
     pub ser: Option<CompactString>,                // 5
     pub sub_ser: Option<CompactString>,            // 6
-    pub num_doc: Option<usize>,                    // 7
+    pub num_doc: Option<CompactString>,            // 7
     pub dt_oper: Option<NaiveDate>,                // 8
     pub chv_nfe: Option<CompactString>,            // 9
     pub vl_oper: Option<Decimal>,                  // 10
@@ -47,6 +47,11 @@ pub struct Registro1101 {
 }
 
 impl_reg_methods!(Registro1101);
+impl_sped_writer!(Registro1101 {
+    cod_part, cod_item, cod_mod, ser, sub_ser, num_doc, dt_oper, chv_nfe, vl_oper, cfop,
+    nat_bc_cred => 2, ind_orig_cred, cst_pis, vl_bc_pis, aliq_pis, vl_pis, cod_cta, cod_ccus,
+    desc_compl, per_escrit, cnpj,
+});
 
 impl SpedParser for Registro1101 {
     type Output = Registro1101;
@@ -84,7 +89,7 @@ impl SpedParser for Registro1101 {
         let cod_mod = fields.get(4).to_compact_string();
         let ser = fields.get(5).to_compact_string();
         let sub_ser = fields.get(6).to_compact_string();
-        let num_doc = fields.get(7).parse_opt();
+        let num_doc = fields.get(7).to_compact_string();
         let dt_oper = get_date(8, "DT_OPER")?;
         let chv_nfe = fields.get(9).to_compact_string();
         let vl_oper = get_decimal(10, "VL_OPER")?;
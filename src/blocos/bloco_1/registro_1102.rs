@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use rust_decimal::Decimal;
 use std::path::Path;
@@ -26,6 +28,11 @@ pub struct Registro1102 {
 }
 
 impl_reg_methods!(Registro1102);
+impl_sped_writer!(Registro1102 {
+    vl_cred_pis_trib_mi,
+    vl_cred_pis_nt_mi,
+    vl_cred_pis_exp,
+});
 
 impl SpedParser for Registro1102 {
     type Output = Registro1102;
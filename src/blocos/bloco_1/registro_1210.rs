@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -36,6 +36,18 @@ pub struct Registro1210 {
 }
 
 impl_reg_methods!(Registro1210);
+impl_sped_writer!(Registro1210 {
+    cnpj,
+    cst_pis,
+    cod_part,
+    dt_oper,
+    vl_oper,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cod_cta,
+    desc_compl,
+});
 
 impl SpedParser for Registro1210 {
     type Output = Registro1210;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -31,6 +32,15 @@ pub struct Registro1300 {
 }
 
 impl_reg_methods!(Registro1300);
+impl_sped_writer!(Registro1300 {
+    ind_nat_ret,
+    pr_rec_ret,
+    vl_ret_apu,
+    vl_ret_ded,
+    vl_ret_per,
+    vl_ret_dcomp,
+    sld_ret,
+});
 
 impl SpedParser for Registro1300 {
     type Output = Registro1300;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -43,6 +43,12 @@ pub struct Registro1500 {
 }
 
 impl_reg_methods!(Registro1500);
+impl_sped_writer!(Registro1500 {
+    per_apu_cred => mmyyyy, orig_cred, cnpj_suc, cod_cred, vl_cred_apu, vl_cred_ext_apu,
+    vl_tot_cred_apu, vl_cred_desc_pa_ant, vl_cred_per_pa_ant, vl_cred_dcomp_pa_ant,
+    sd_cred_disp_efd, vl_cred_desc_efd, vl_cred_per_efd, vl_cred_dcomp_efd, vl_cred_trans,
+    vl_cred_out, sld_cred_fim,
+});
 
 impl SpedParser for Registro1500 {
     type Output = Registro1500;
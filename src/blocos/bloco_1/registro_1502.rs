@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use rust_decimal::Decimal;
 use std::path::Path;
@@ -25,6 +27,11 @@ pub struct Registro1502 {
 }
 
 impl_reg_methods!(Registro1502);
+impl_sped_writer!(Registro1502 {
+    vl_cred_cofins_trib_mi,
+    vl_cred_cofins_nt_mi,
+    vl_cred_cofins_exp,
+});
 
 impl SpedParser for Registro1502 {
     type Output = Registro1502;
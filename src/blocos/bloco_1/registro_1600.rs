@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -36,6 +36,18 @@ pub struct Registro1600 {
 }
 
 impl_reg_methods!(Registro1600);
+impl_sped_writer!(Registro1600 {
+    per_apur_ant,
+    nat_cont_rec,
+    vl_cont_apur,
+    vl_cred_cofins_desc,
+    vl_cont_dev,
+    vl_out_ded,
+    vl_cont_ext,
+    vl_mul,
+    vl_jur,
+    dt_recol,
+});
 
 impl SpedParser for Registro1600 {
     type Output = Registro1600;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -36,6 +36,18 @@ pub struct Registro1610 {
 }
 
 impl_reg_methods!(Registro1610);
+impl_sped_writer!(Registro1610 {
+    cnpj,
+    cst_cofins,
+    cod_part,
+    dt_oper,
+    vl_oper,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+    desc_compl,
+});
 
 impl SpedParser for Registro1610 {
     type Output = Registro1610;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -29,6 +29,12 @@ pub struct Registro1620 {
 }
 
 impl_reg_methods!(Registro1620);
+impl_sped_writer!(Registro1620 {
+    per_apu_cred,
+    orig_cred,
+    cod_cred,
+    vl_cred,
+});
 
 impl SpedParser for Registro1620 {
     type Output = Registro1620;
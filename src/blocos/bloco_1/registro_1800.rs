@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -34,6 +34,16 @@ pub struct Registro1800 {
 }
 
 impl_reg_methods!(Registro1800);
+impl_sped_writer!(Registro1800 {
+    inc_imob,
+    rec_receb_ret,
+    rec_fin_ret,
+    bc_ret,
+    aliq_ret,
+    vl_rec_uni,
+    dt_rec_uni,
+    cod_rec,
+});
 
 impl SpedParser for Registro1800 {
     type Output = Registro1800;
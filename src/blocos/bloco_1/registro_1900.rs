@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -37,6 +37,20 @@ pub struct Registro1900 {
 }
 
 impl_reg_methods!(Registro1900);
+impl_sped_writer!(Registro1900 {
+    cnpj,
+    cod_mod,
+    ser,
+    sub_ser,
+    cod_sit,
+    vl_tot_rec,
+    quant_doc,
+    cst_pis,
+    cst_cofins,
+    cfop,
+    inf_compl,
+    cod_cta,
+});
 
 impl SpedParser for Registro1900 {
     type Output = Registro1900;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct Registro1990 {
 }
 
 impl_reg_methods!(Registro1990);
+impl_sped_writer!(Registro1990 { qtd_lin_1 });
 
 impl SpedParser for Registro1990 {
     type Output = Registro1990;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,10 @@ pub struct Registro9900 {
 }
 
 impl_reg_methods!(Registro9900);
+impl_sped_writer!(Registro9900 {
+    reg_blc,
+    qtd_reg_blc,
+});
 
 impl SpedParser for Registro9900 {
     type Output = Registro9900;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct Registro9999 {
 }
 
 impl_reg_methods!(Registro9999);
+impl_sped_writer!(Registro9999 { qtd_lin });
 
 impl SpedParser for Registro9999 {
     type Output = Registro9999;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +24,7 @@ pub struct RegistroA010 {
 }
 
 impl_reg_methods!(RegistroA010);
+impl_sped_writer!(RegistroA010 { cnpj });
 
 impl SpedParser for RegistroA010 {
     type Output = RegistroA010;
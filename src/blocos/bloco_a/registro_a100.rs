@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -30,7 +30,7 @@ pub struct RegistroA100 {
     pub cod_sit: Option<CompactString>,  // 5
     pub ser: Option<CompactString>,      // 6
     pub sub: Option<CompactString>,      // 7
-    pub num_doc: Option<usize>,          // 8
+    pub num_doc: Option<CompactString>,  // 8
     pub chv_nfse: Option<CompactString>, // 9
     pub dt_doc: Option<NaiveDate>,       // 10
     pub dt_exe_serv: Option<NaiveDate>,  // 11
@@ -47,6 +47,28 @@ pub struct RegistroA100 {
 }
 
 impl_reg_methods!(RegistroA100);
+impl_sped_writer!(RegistroA100 {
+    ind_oper,
+    ind_emit,
+    cod_part,
+    cod_sit,
+    ser,
+    sub,
+    num_doc,
+    chv_nfse,
+    dt_doc,
+    dt_exe_serv,
+    vl_doc,
+    ind_pgto,
+    vl_desc,
+    vl_bc_pis,
+    vl_pis,
+    vl_bc_cofins,
+    vl_cofins,
+    vl_pis_ret,
+    vl_cofins_ret,
+    vl_iss,
+});
 
 impl SpedParser for RegistroA100 {
     type Output = RegistroA100;
@@ -88,7 +110,7 @@ impl SpedParser for RegistroA100 {
         let cod_sit = fields.get(5).to_compact_string();
         let ser = fields.get(6).to_compact_string();
         let sub = fields.get(7).to_compact_string();
-        let num_doc = fields.get(8).parse_opt();
+        let num_doc = fields.get(8).to_compact_string();
         let chv_nfse = fields.get(9).to_compact_string();
 
         let dt_doc = get_date(10, "DT_DOC")?;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroA110 {
 }
 
 impl_reg_methods!(RegistroA110);
+impl_sped_writer!(RegistroA110 { cod_inf, txt_compl });
 
 impl SpedParser for RegistroA110 {
     type Output = RegistroA110;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroA111 {
 }
 
 impl_reg_methods!(RegistroA111);
+impl_sped_writer!(RegistroA111 { num_proc, ind_proc });
 
 impl SpedParser for RegistroA111 {
     type Output = RegistroA111;
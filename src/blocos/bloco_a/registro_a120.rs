@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -34,6 +34,16 @@ pub struct RegistroA120 {
 }
 
 impl_reg_methods!(RegistroA120);
+impl_sped_writer!(RegistroA120 {
+    vl_tot_serv,
+    vl_bc_pis,
+    vl_pis_imp,
+    dt_pag_pis,
+    vl_bc_cofins,
+    vl_cofins_imp,
+    dt_pag_cofins,
+    loc_exe_serv,
+});
 
 impl SpedParser for RegistroA120 {
     type Output = RegistroA120;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -42,6 +42,11 @@ pub struct RegistroA170 {
 }
 
 impl_reg_methods!(RegistroA170);
+impl_sped_writer!(RegistroA170 {
+    num_item, cod_item, descr_compl, vl_item, vl_desc, nat_bc_cred => 2, ind_orig_cred, cst_pis,
+    vl_bc_pis, aliq_pis, vl_pis, cst_cofins, vl_bc_cofins, aliq_cofins, vl_cofins, cod_cta,
+    cod_ccus,
+});
 
 impl SpedParser for RegistroA170 {
     type Output = RegistroA170;
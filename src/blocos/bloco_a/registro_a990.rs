@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroA990 {
 }
 
 impl_reg_methods!(RegistroA990);
+impl_sped_writer!(RegistroA990 { qtd_lin_a });
 
 impl SpedParser for RegistroA990 {
     type Output = RegistroA990;
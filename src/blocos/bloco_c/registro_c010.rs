@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroC010 {
 }
 
 impl_reg_methods!(RegistroC010);
+impl_sped_writer!(RegistroC010 { cnpj, ind_escri });
 
 impl SpedParser for RegistroC010 {
     type Output = RegistroC010;
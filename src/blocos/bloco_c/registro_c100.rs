@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -31,7 +31,7 @@ pub struct RegistroC100 {
     pub cod_mod: Option<CompactString>,  // 5
     pub cod_sit: Option<CompactString>,  // 6
     pub serie: Option<CompactString>,    // 7
-    pub num_doc: Option<usize>,          // 8
+    pub num_doc: Option<CompactString>,  // 8
     pub chv_nfe: Option<CompactString>,  // 9
     pub dt_doc: Option<NaiveDate>,       // 10
     pub dt_e_s: Option<NaiveDate>,       // 11
@@ -56,6 +56,36 @@ pub struct RegistroC100 {
 }
 
 impl_reg_methods!(RegistroC100);
+impl_sped_writer!(RegistroC100 {
+    ind_oper,
+    ind_emit,
+    cod_part,
+    cod_mod,
+    cod_sit,
+    serie,
+    num_doc,
+    chv_nfe,
+    dt_doc,
+    dt_e_s,
+    vl_doc,
+    ind_pgto,
+    vl_desc,
+    vl_abat_nt,
+    vl_merc,
+    ind_frt,
+    vl_frt,
+    vl_seg,
+    vl_out_da,
+    vl_bc_icms,
+    vl_icms,
+    vl_bc_icms_st,
+    vl_icms_st,
+    vl_ipi,
+    vl_pis,
+    vl_cofins,
+    vl_pis_st,
+    vl_cofins_st,
+});
 
 impl SpedParser for RegistroC100 {
     type Output = RegistroC100;
@@ -96,7 +126,7 @@ impl SpedParser for RegistroC100 {
         let cod_mod = fields.get(5).to_compact_string();
         let cod_sit = fields.get(6).to_compact_string();
         let serie = fields.get(7).to_compact_string();
-        let num_doc = fields.get(8).parse_opt();
+        let num_doc = fields.get(8).to_compact_string();
         let chv_nfe = fields.get(9).to_compact_string();
 
         // Usando ToNaiveDate para campos de data
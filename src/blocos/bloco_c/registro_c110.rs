@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -15,6 +17,7 @@ pub struct RegistroC110 {
 }
 
 impl_reg_methods!(RegistroC110);
+impl_sped_writer!(RegistroC110 { cod_inf, txt_compl });
 
 impl SpedParser for RegistroC110 {
     type Output = RegistroC110;
@@ -34,7 +37,7 @@ impl SpedParser for RegistroC110 {
         }
 
         let cod_inf = fields.get(2).to_compact_string();
-        let txt_compl = fields.get(3).map(|&s| s.to_ascii_uppercase().into());
+        let txt_compl = fields.get(3).map(|&s| s.into());
 
         let reg = RegistroC110 {
             nivel: 4,
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -15,6 +17,7 @@ pub struct RegistroC111 {
 }
 
 impl_reg_methods!(RegistroC111);
+impl_sped_writer!(RegistroC111 { num_proc, ind_proc });
 
 impl SpedParser for RegistroC111 {
     type Output = RegistroC111;
@@ -1,6 +1,7 @@
 // registro_c120.rs
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -22,6 +23,13 @@ pub struct RegistroC120 {
 }
 
 impl_reg_methods!(RegistroC120);
+impl_sped_writer!(RegistroC120 {
+    cod_doc_imp,
+    num_doc_imp,
+    vl_pis_imp,
+    vl_cofins_imp,
+    num_acdraw,
+});
 
 impl SpedParser for RegistroC120 {
     type Output = RegistroC120;
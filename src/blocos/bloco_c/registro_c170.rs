@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -63,6 +63,13 @@ pub struct RegistroC170 {
 }
 
 impl_reg_methods!(RegistroC170);
+impl_sped_writer!(RegistroC170 {
+    num_item, cod_item, descr_compl, qtd, unid, vl_item, vl_desc, ind_mov, cst_icms => 3, cfop,
+    cod_nat, vl_bc_icms, aliq_icms, vl_icms, vl_bc_icms_st, aliq_st, vl_icms_st, ind_apur,
+    cst_ipi => 2, cod_enq, vl_bc_ipi, aliq_ipi, vl_ipi, cst_pis, vl_bc_pis, aliq_pis, quant_bc_pis,
+    aliq_pis_quant, vl_pis, cst_cofins, vl_bc_cofins, aliq_cofins, quant_bc_cofins,
+    aliq_cofins_quant, vl_cofins, cod_cta,
+});
 
 impl SpedParser for RegistroC170 {
     type Output = RegistroC170;
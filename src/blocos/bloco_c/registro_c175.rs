@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -42,6 +42,25 @@ pub struct RegistroC175 {
 }
 
 impl_reg_methods!(RegistroC175);
+impl_sped_writer!(RegistroC175 {
+    cfop,
+    vl_opr,
+    vl_desc,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroC175 {
     type Output = RegistroC175;
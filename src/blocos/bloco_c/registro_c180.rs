@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,15 @@ pub struct RegistroC180 {
 }
 
 impl_reg_methods!(RegistroC180);
+impl_sped_writer!(RegistroC180 {
+    cod_mod,
+    dt_doc_ini,
+    dt_doc_fin,
+    cod_item,
+    cod_ncm,
+    ex_ipi,
+    vl_tot_item,
+});
 
 impl SpedParser for RegistroC180 {
     type Output = RegistroC180;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -35,6 +35,18 @@ pub struct RegistroC181 {
 }
 
 impl_reg_methods!(RegistroC181);
+impl_sped_writer!(RegistroC181 {
+    cst_pis,
+    cfop,
+    vl_item,
+    vl_desc,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC181 {
     type Output = RegistroC181;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,15 @@ pub struct RegistroC190 {
 }
 
 impl_reg_methods!(RegistroC190);
+impl_sped_writer!(RegistroC190 {
+    cod_mod,
+    dt_ref_ini,
+    dt_ref_fin,
+    cod_item,
+    cod_ncm,
+    ex_ipi,
+    vl_tot_item,
+});
 
 impl SpedParser for RegistroC190 {
     type Output = RegistroC190;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -36,6 +36,19 @@ pub struct RegistroC191 {
 }
 
 impl_reg_methods!(RegistroC191);
+impl_sped_writer!(RegistroC191 {
+    cnpj_cpf_part,
+    cst_pis,
+    cfop,
+    vl_item,
+    vl_desc,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC191 {
     type Output = RegistroC191;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -36,6 +36,19 @@ pub struct RegistroC195 {
 }
 
 impl_reg_methods!(RegistroC195);
+impl_sped_writer!(RegistroC195 {
+    cnpj_cpf_part,
+    cst_cofins,
+    cfop,
+    vl_item,
+    vl_desc,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC195 {
     type Output = RegistroC195;
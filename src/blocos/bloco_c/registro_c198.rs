@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroC198 {
 }
 
 impl_reg_methods!(RegistroC198);
+impl_sped_writer!(RegistroC198 { num_proc, ind_proc });
 
 impl SpedParser for RegistroC198 {
     type Output = RegistroC198;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -29,6 +30,13 @@ pub struct RegistroC199 {
 }
 
 impl_reg_methods!(RegistroC199);
+impl_sped_writer!(RegistroC199 {
+    cod_doc_imp,
+    num_doc_imp,
+    vl_pis_imp,
+    vl_cofins_imp,
+    num_acdraw,
+});
 
 impl SpedParser for RegistroC199 {
     type Output = RegistroC199;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,15 @@ pub struct RegistroC380 {
 }
 
 impl_reg_methods!(RegistroC380);
+impl_sped_writer!(RegistroC380 {
+    cod_mod,
+    dt_doc_ini,
+    dt_doc_fin,
+    num_doc_ini,
+    num_doc_fin,
+    vl_doc,
+    vl_doc_canc,
+});
 
 impl SpedParser for RegistroC380 {
     type Output = RegistroC380;
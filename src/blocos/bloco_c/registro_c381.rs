@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -34,6 +34,17 @@ pub struct RegistroC381 {
 }
 
 impl_reg_methods!(RegistroC381);
+impl_sped_writer!(RegistroC381 {
+    cst_pis,
+    cod_item,
+    vl_item,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC381 {
     type Output = RegistroC381;
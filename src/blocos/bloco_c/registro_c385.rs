@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -34,6 +34,17 @@ pub struct RegistroC385 {
 }
 
 impl_reg_methods!(RegistroC385);
+impl_sped_writer!(RegistroC385 {
+    cst_cofins,
+    cod_item,
+    vl_item,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC385 {
     type Output = RegistroC385;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -27,12 +27,21 @@ pub struct RegistroC395 {
     pub cod_part: Option<CompactString>, // 3
     pub ser: Option<CompactString>,      // 4
     pub sub_ser: Option<CompactString>,  // 5
-    pub num_doc: Option<usize>,          // 6
+    pub num_doc: Option<CompactString>,  // 6
     pub dt_doc: Option<NaiveDate>,       // 7
     pub vl_doc: Option<Decimal>,         // 8
 }
 
 impl_reg_methods!(RegistroC395);
+impl_sped_writer!(RegistroC395 {
+    cod_mod,
+    cod_part,
+    ser,
+    sub_ser,
+    num_doc,
+    dt_doc,
+    vl_doc,
+});
 
 impl SpedParser for RegistroC395 {
     type Output = RegistroC395;
@@ -68,7 +77,7 @@ impl SpedParser for RegistroC395 {
         let cod_part = fields.get(3).to_compact_string();
         let ser = fields.get(4).to_compact_string();
         let sub_ser = fields.get(5).to_compact_string();
-        let num_doc = fields.get(6).parse_opt();
+        let num_doc = fields.get(6).to_compact_string();
         let dt_doc = get_date(7, "DT_DOC")?;
         let vl_doc = get_decimal(8, "VL_DOC")?;
 
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -38,6 +38,10 @@ pub struct RegistroC396 {
 }
 
 impl_reg_methods!(RegistroC396);
+impl_sped_writer!(RegistroC396 {
+    cod_item, vl_item, vl_desc, nat_bc_cred => 2, cst_pis, vl_bc_pis, aliq_pis, vl_pis, cst_cofins,
+    vl_bc_cofins, aliq_cofins, vl_cofins, cod_cta,
+});
 
 impl SpedParser for RegistroC396 {
     type Output = RegistroC396;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -25,6 +27,12 @@ pub struct RegistroC400 {
 }
 
 impl_reg_methods!(RegistroC400);
+impl_sped_writer!(RegistroC400 {
+    cod_mod,
+    ecf_mod,
+    ecf_fab,
+    ecf_cx,
+});
 
 impl SpedParser for RegistroC400 {
     type Output = RegistroC400;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -24,6 +24,14 @@ pub struct RegistroC405 {
 }
 
 impl_reg_methods!(RegistroC405);
+impl_sped_writer!(RegistroC405 {
+    dt_doc,
+    cro,
+    crz,
+    num_coo_fin,
+    gt_fin,
+    vl_brt,
+});
 
 impl SpedParser for RegistroC405 {
     type Output = RegistroC405;
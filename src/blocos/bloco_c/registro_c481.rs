@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -26,6 +26,17 @@ pub struct RegistroC481 {
 }
 
 impl_reg_methods!(RegistroC481);
+impl_sped_writer!(RegistroC481 {
+    cst_pis,
+    vl_item,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cod_item,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC481 {
     type Output = RegistroC481;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -26,6 +26,17 @@ pub struct RegistroC485 {
 }
 
 impl_reg_methods!(RegistroC485);
+impl_sped_writer!(RegistroC485 {
+    cst_cofins,
+    vl_item,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_item,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC485 {
     type Output = RegistroC485;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -19,6 +20,11 @@ pub struct RegistroC490 {
 }
 
 impl_reg_methods!(RegistroC490);
+impl_sped_writer!(RegistroC490 {
+    dt_doc_ini,
+    dt_doc_fin,
+    cod_mod,
+});
 
 impl SpedParser for RegistroC490 {
     type Output = RegistroC490;
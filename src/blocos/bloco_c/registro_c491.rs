@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -27,6 +27,18 @@ pub struct RegistroC491 {
 }
 
 impl_reg_methods!(RegistroC491);
+impl_sped_writer!(RegistroC491 {
+    cod_item,
+    cst_pis,
+    cfop,
+    vl_item,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC491 {
     type Output = RegistroC491;
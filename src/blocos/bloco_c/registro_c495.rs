@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -27,6 +27,18 @@ pub struct RegistroC495 {
 }
 
 impl_reg_methods!(RegistroC495);
+impl_sped_writer!(RegistroC495 {
+    cod_item,
+    cst_cofins,
+    cfop,
+    vl_item,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC495 {
     type Output = RegistroC495;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -15,6 +17,7 @@ pub struct RegistroC499 {
 }
 
 impl_reg_methods!(RegistroC499);
+impl_sped_writer!(RegistroC499 { num_proc, ind_proc });
 
 impl SpedParser for RegistroC499 {
     type Output = RegistroC499;
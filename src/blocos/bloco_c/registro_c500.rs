@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -22,7 +22,7 @@ pub struct RegistroC500 {
     pub cod_sit: Option<CompactString>,  // 4
     pub ser: Option<CompactString>,      // 5
     pub sub: Option<CompactString>,      // 6
-    pub num_doc: Option<usize>,          // 7
+    pub num_doc: Option<CompactString>,  // 7
     pub dt_doc: Option<NaiveDate>,       // 8
     pub dt_ent: Option<NaiveDate>,       // 9
     pub vl_doc: Option<Decimal>,         // 10
@@ -38,6 +38,22 @@ pub struct RegistroC500 {
 // A partir de 01/01/2020, o campo é obrigatório quando COD_MOD for igual a “66” ou “55”.
 
 impl_reg_methods!(RegistroC500);
+impl_sped_writer!(RegistroC500 {
+    cod_part,
+    cod_mod,
+    cod_sit,
+    ser,
+    sub,
+    num_doc,
+    dt_doc,
+    dt_ent,
+    vl_doc,
+    vl_icms,
+    cod_inf,
+    vl_pis,
+    vl_cofins,
+    chv_doce,
+});
 
 impl SpedParser for RegistroC500 {
     type Output = RegistroC500;
@@ -73,7 +89,7 @@ impl SpedParser for RegistroC500 {
         let cod_sit = fields.get(4).to_compact_string();
         let ser = fields.get(5).to_compact_string();
         let sub = fields.get(6).to_compact_string();
-        let num_doc = fields.get(7).parse_opt();
+        let num_doc = fields.get(7).to_compact_string();
         let dt_doc = get_date(8, "DT_DOC")?;
         let dt_ent = get_date(9, "DT_ENT")?;
         let vl_doc = get_decimal(10, "VL_DOC")?;
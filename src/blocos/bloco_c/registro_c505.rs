@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -32,6 +32,9 @@ pub struct RegistroC505 {
 }
 
 impl_reg_methods!(RegistroC505);
+impl_sped_writer!(RegistroC505 {
+    cst_cofins, vl_item, nat_bc_cred => 2, vl_bc_cofins, aliq_cofins, vl_cofins, cod_cta,
+});
 
 impl SpedParser for RegistroC505 {
     type Output = RegistroC505;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -47,6 +47,29 @@ pub struct RegistroC600 {
 }
 
 impl_reg_methods!(RegistroC600);
+impl_sped_writer!(RegistroC600 {
+    cod_mod,
+    cod_mun,
+    ser,
+    sub,
+    cod_cons,
+    qtd_cons,
+    qtd_canc,
+    dt_doc,
+    vl_doc,
+    vl_desc,
+    cons,
+    vl_forn,
+    vl_serv_nt,
+    vl_terc,
+    vl_da,
+    vl_bc_icms,
+    vl_icms,
+    vl_bc_icms_st,
+    vl_icms_st,
+    vl_pis,
+    vl_cofins,
+});
 
 impl SpedParser for RegistroC600 {
     type Output = RegistroC600;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -42,6 +42,24 @@ pub struct RegistroC800 {
 }
 
 impl_reg_methods!(RegistroC800);
+impl_sped_writer!(RegistroC800 {
+    cod_mod,
+    cod_sit,
+    num_cfe,
+    dt_doc,
+    vl_cfe,
+    vl_pis,
+    vl_cofins,
+    cnpj_cpf,
+    nr_sat,
+    chv_cfe,
+    vl_desc,
+    vl_merc,
+    vl_out_da,
+    vl_icms,
+    vl_pis_st,
+    vl_cofins_st,
+});
 
 impl SpedParser for RegistroC800 {
     type Output = RegistroC800;
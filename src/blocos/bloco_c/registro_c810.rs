@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -37,6 +37,20 @@ pub struct RegistroC810 {
 }
 
 impl_reg_methods!(RegistroC810);
+impl_sped_writer!(RegistroC810 {
+    cfop,
+    vl_item,
+    cod_item,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC810 {
     type Output = RegistroC810;
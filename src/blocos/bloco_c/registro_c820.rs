@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -37,6 +37,20 @@ pub struct RegistroC820 {
 }
 
 impl_reg_methods!(RegistroC820);
+impl_sped_writer!(RegistroC820 {
+    cfop,
+    vl_item,
+    cod_item,
+    cst_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cst_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC820 {
     type Output = RegistroC820;
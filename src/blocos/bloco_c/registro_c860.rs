@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToNaiveDate, impl_reg_methods,
+    impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -29,6 +30,13 @@ pub struct RegistroC860 {
 }
 
 impl_reg_methods!(RegistroC860);
+impl_sped_writer!(RegistroC860 {
+    cod_mod,
+    nr_sat,
+    dt_doc,
+    doc_ini,
+    doc_fim,
+});
 
 impl SpedParser for RegistroC860 {
     type Output = RegistroC860;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -38,6 +38,21 @@ pub struct RegistroC870 {
 }
 
 impl_reg_methods!(RegistroC870);
+impl_sped_writer!(RegistroC870 {
+    cod_item,
+    cfop,
+    vl_item,
+    vl_desc,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC870 {
     type Output = RegistroC870;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -38,6 +38,21 @@ pub struct RegistroC880 {
 }
 
 impl_reg_methods!(RegistroC880);
+impl_sped_writer!(RegistroC880 {
+    cod_item,
+    cfop,
+    vl_item,
+    vl_desc,
+    cst_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cst_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroC880 {
     type Output = RegistroC880;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroC990 {
 }
 
 impl_reg_methods!(RegistroC990);
+impl_sped_writer!(RegistroC990 { qtd_lin_c });
 
 impl SpedParser for RegistroC990 {
     type Output = RegistroC990;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +24,7 @@ pub struct RegistroD001 {
 }
 
 impl_reg_methods!(RegistroD001);
+impl_sped_writer!(RegistroD001 { ind_mov });
 
 impl SpedParser for RegistroD001 {
     type Output = RegistroD001;
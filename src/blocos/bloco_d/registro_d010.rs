@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -14,6 +16,7 @@ pub struct RegistroD010 {
 }
 
 impl_reg_methods!(RegistroD010);
+impl_sped_writer!(RegistroD010 { cnpj });
 
 impl SpedParser for RegistroD010 {
     type Output = RegistroD010;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -22,7 +22,7 @@ pub struct RegistroD100 {
     pub cod_sit: Option<CompactString>,     // 6
     pub ser: Option<CompactString>,         // 7
     pub sub: Option<CompactString>,         // 8
-    pub num_doc: Option<usize>,             // 9
+    pub num_doc: Option<CompactString>,     // 9
     pub chv_cte: Option<CompactString>,     // 10
     pub dt_doc: Option<NaiveDate>,          // 11
     pub dt_a_p: Option<NaiveDate>,          // 12
@@ -40,6 +40,30 @@ pub struct RegistroD100 {
 }
 
 impl_reg_methods!(RegistroD100);
+impl_sped_writer!(RegistroD100 {
+    ind_oper,
+    ind_emit,
+    cod_part,
+    cod_mod,
+    cod_sit,
+    ser,
+    sub,
+    num_doc,
+    chv_cte,
+    dt_doc,
+    dt_a_p,
+    tp_cte,
+    chv_cte_ref,
+    vl_doc,
+    vl_desc,
+    ind_frt,
+    vl_serv,
+    vl_bc_icms,
+    vl_icms,
+    vl_nt,
+    cod_inf,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD100 {
     type Output = RegistroD100;
@@ -77,7 +101,7 @@ impl SpedParser for RegistroD100 {
         let cod_sit = fields.get(6).to_compact_string();
         let ser = fields.get(7).to_compact_string();
         let sub = fields.get(8).to_compact_string();
-        let num_doc = fields.get(9).parse_opt();
+        let num_doc = fields.get(9).to_compact_string();
         let chv_cte = fields.get(10).to_compact_string();
         let dt_doc = get_date(11, "DT_DOC")?;
         let dt_a_p = get_date(12, "DT_A_P")?;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -33,6 +33,9 @@ pub struct RegistroD101 {
 }
 
 impl_reg_methods!(RegistroD101);
+impl_sped_writer!(RegistroD101 {
+    ind_nat_frt, vl_item, cst_pis, nat_bc_cred => 2, vl_bc_pis, aliq_pis, vl_pis, cod_cta,
+});
 
 impl SpedParser for RegistroD101 {
     type Output = RegistroD101;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -33,6 +33,10 @@ pub struct RegistroD105 {
 }
 
 impl_reg_methods!(RegistroD105);
+impl_sped_writer!(RegistroD105 {
+    ind_nat_frt, vl_item, cst_cofins, nat_bc_cred => 2, vl_bc_cofins, aliq_cofins, vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD105 {
     type Output = RegistroD105;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -23,19 +23,31 @@ pub struct RegistroD200 {
     /// Número da linha do arquivo Sped EFD Contribuições
     pub line_number: usize,
 
-    pub cod_mod: Option<CompactString>, // 2
-    pub cod_sit: Option<CompactString>, // 3
-    pub ser: Option<CompactString>,     // 4
-    pub sub: Option<CompactString>,     // 5
-    pub num_doc_ini: Option<usize>,     // 6
-    pub num_doc_fin: Option<usize>,     // 7
-    pub cfop: Option<u16>,              // 8
-    pub dt_ref: Option<NaiveDate>,      // 9
-    pub vl_doc: Option<Decimal>,        // 10
-    pub vl_desc: Option<Decimal>,       // 11
+    pub cod_mod: Option<CompactString>,     // 2
+    pub cod_sit: Option<CompactString>,     // 3
+    pub ser: Option<CompactString>,         // 4
+    pub sub: Option<CompactString>,         // 5
+    pub num_doc_ini: Option<CompactString>, // 6
+    pub num_doc_fin: Option<CompactString>, // 7
+    pub cfop: Option<u16>,                  // 8
+    pub dt_ref: Option<NaiveDate>,          // 9
+    pub vl_doc: Option<Decimal>,            // 10
+    pub vl_desc: Option<Decimal>,           // 11
 }
 
 impl_reg_methods!(RegistroD200);
+impl_sped_writer!(RegistroD200 {
+    cod_mod,
+    cod_sit,
+    ser,
+    sub,
+    num_doc_ini,
+    num_doc_fin,
+    cfop,
+    dt_ref,
+    vl_doc,
+    vl_desc,
+});
 
 impl SpedParser for RegistroD200 {
     type Output = RegistroD200;
@@ -73,8 +85,8 @@ impl SpedParser for RegistroD200 {
         let cod_sit = fields.get(3).to_compact_string();
         let ser = fields.get(4).to_compact_string();
         let sub = fields.get(5).to_compact_string();
-        let num_doc_ini = fields.get(6).parse_opt();
-        let num_doc_fin = fields.get(7).parse_opt();
+        let num_doc_ini = fields.get(6).to_compact_string();
+        let num_doc_fin = fields.get(7).to_compact_string();
         let cfop = fields.get(8).parse_opt();
         let dt_ref = get_date(9, "DT_REF")?;
         let vl_doc = get_decimal(10, "VL_DOC")?;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -31,6 +31,14 @@ pub struct RegistroD201 {
 }
 
 impl_reg_methods!(RegistroD201);
+impl_sped_writer!(RegistroD201 {
+    cst_pis,
+    vl_item,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD201 {
     type Output = RegistroD201;
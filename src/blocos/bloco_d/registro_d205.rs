@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -31,6 +31,14 @@ pub struct RegistroD205 {
 }
 
 impl_reg_methods!(RegistroD205);
+impl_sped_writer!(RegistroD205 {
+    cst_cofins,
+    vl_item,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD205 {
     type Output = RegistroD205;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroD209 {
 }
 
 impl_reg_methods!(RegistroD209);
+impl_sped_writer!(RegistroD209 { num_proc, ind_proc });
 
 impl SpedParser for RegistroD209 {
     type Output = RegistroD209;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -44,6 +44,26 @@ pub struct RegistroD300 {
 }
 
 impl_reg_methods!(RegistroD300);
+impl_sped_writer!(RegistroD300 {
+    cod_mod,
+    ser,
+    sub,
+    num_doc_ini,
+    num_doc_fin,
+    cfop,
+    dt_ref,
+    vl_doc,
+    vl_desc,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD300 {
     type Output = RegistroD300;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroD309 {
 }
 
 impl_reg_methods!(RegistroD309);
+impl_sped_writer!(RegistroD309 { num_proc, ind_proc });
 
 impl SpedParser for RegistroD309 {
     type Output = RegistroD309;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -48,6 +48,30 @@ pub struct RegistroD350 {
 }
 
 impl_reg_methods!(RegistroD350);
+impl_sped_writer!(RegistroD350 {
+    cod_mod,
+    ecf_mod,
+    ecf_fab,
+    dt_doc,
+    cro,
+    crz,
+    num_coo_fin,
+    gt_fin,
+    vl_brt,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD350 {
     type Output = RegistroD350;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -30,7 +30,7 @@ pub struct RegistroD500 {
     pub cod_sit: Option<CompactString>,  // 6
     pub ser: Option<CompactString>,      // 7
     pub sub: Option<CompactString>,      // 8
-    pub num_doc: Option<usize>,          // 9
+    pub num_doc: Option<CompactString>,  // 9
     pub dt_doc: Option<NaiveDate>,       // 10
     pub dt_a_p: Option<NaiveDate>,       // 11
     pub vl_doc: Option<Decimal>,         // 12
@@ -47,6 +47,29 @@ pub struct RegistroD500 {
 }
 
 impl_reg_methods!(RegistroD500);
+impl_sped_writer!(RegistroD500 {
+    ind_oper,
+    ind_emit,
+    cod_part,
+    cod_mod,
+    cod_sit,
+    ser,
+    sub,
+    num_doc,
+    dt_doc,
+    dt_a_p,
+    vl_doc,
+    vl_desc,
+    vl_serv,
+    vl_serv_nt,
+    vl_terc,
+    vl_da,
+    vl_bc_icms,
+    vl_icms,
+    cod_inf,
+    vl_pis,
+    vl_cofins,
+});
 
 impl SpedParser for RegistroD500 {
     type Output = RegistroD500;
@@ -87,7 +110,7 @@ impl SpedParser for RegistroD500 {
         let cod_sit = fields.get(6).to_compact_string();
         let ser = fields.get(7).to_compact_string();
         let sub = fields.get(8).to_compact_string();
-        let num_doc = fields.get(9).parse_opt();
+        let num_doc = fields.get(9).to_compact_string();
         let dt_doc = get_date(10, "DT_DOC")?;
         let dt_a_p = get_date(11, "DT_A_P")?;
         let vl_doc = get_decimal(12, "VL_DOC")?;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -32,6 +32,9 @@ pub struct RegistroD501 {
 }
 
 impl_reg_methods!(RegistroD501);
+impl_sped_writer!(RegistroD501 {
+    cst_pis, vl_item, nat_bc_cred => 2, vl_bc_pis, aliq_pis, vl_pis, cod_cta,
+});
 
 impl SpedParser for RegistroD501 {
     type Output = RegistroD501;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroD509 {
 }
 
 impl_reg_methods!(RegistroD509);
+impl_sped_writer!(RegistroD509 { num_proc, ind_proc });
 
 impl SpedParser for RegistroD509 {
     type Output = RegistroD509;
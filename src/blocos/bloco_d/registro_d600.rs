@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -44,6 +44,26 @@ pub struct RegistroD600 {
 }
 
 impl_reg_methods!(RegistroD600);
+impl_sped_writer!(RegistroD600 {
+    cod_mod,
+    cod_mun,
+    ser,
+    sub,
+    ind_rec,
+    qtd_cons,
+    dt_doc_ini,
+    dt_doc_fin,
+    vl_doc,
+    vl_desc,
+    vl_serv,
+    vl_serv_nt,
+    vl_terc,
+    vl_da,
+    vl_bc_icms,
+    vl_icms,
+    vl_pis,
+    vl_cofins,
+});
 
 impl SpedParser for RegistroD600 {
     type Output = RegistroD600;
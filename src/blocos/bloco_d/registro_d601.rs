@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -33,6 +33,16 @@ pub struct RegistroD601 {
 }
 
 impl_reg_methods!(RegistroD601);
+impl_sped_writer!(RegistroD601 {
+    cod_class,
+    vl_item,
+    vl_desc,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD601 {
     type Output = RegistroD601;
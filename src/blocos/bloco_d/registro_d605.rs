@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -33,6 +33,16 @@ pub struct RegistroD605 {
 }
 
 impl_reg_methods!(RegistroD605);
+impl_sped_writer!(RegistroD605 {
+    cod_class,
+    vl_item,
+    vl_desc,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_cta,
+});
 
 impl SpedParser for RegistroD605 {
     type Output = RegistroD605;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroD990 {
 }
 
 impl_reg_methods!(RegistroD990);
+impl_sped_writer!(RegistroD990 { qtd_lin_d });
 
 impl SpedParser for RegistroD990 {
     type Output = RegistroD990;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +24,7 @@ pub struct RegistroF010 {
 }
 
 impl_reg_methods!(RegistroF010);
+impl_sped_writer!(RegistroF010 { cnpj });
 
 impl SpedParser for RegistroF010 {
     type Output = RegistroF010;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -44,6 +44,11 @@ pub struct RegistroF100 {
 }
 
 impl_reg_methods!(RegistroF100);
+impl_sped_writer!(RegistroF100 {
+    ind_oper, cod_part, cod_item, dt_oper, vl_oper, cst_pis, vl_bc_pis, aliq_pis, vl_pis,
+    cst_cofins, vl_bc_cofins, aliq_cofins, vl_cofins, nat_bc_cred => 2, ind_orig_cred, cod_cta,
+    cod_ccus, desc_doc_oper,
+});
 
 impl SpedParser for RegistroF100 {
     type Output = RegistroF100;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -42,6 +42,11 @@ pub struct RegistroF120 {
 }
 
 impl_reg_methods!(RegistroF120);
+impl_sped_writer!(RegistroF120 {
+    nat_bc_cred => 2, ident_bem_imob, ind_orig_cred, ind_util_bem_imob, vl_oper_dep,
+    parc_oper_nao_bc_cred, cst_pis, vl_bc_pis, aliq_pis, vl_pis, cst_cofins, vl_bc_cofins,
+    aliq_cofins, vl_cofins, cod_cta, cod_ccus, desc_bem_imob,
+});
 
 impl SpedParser for RegistroF120 {
     type Output = RegistroF120;
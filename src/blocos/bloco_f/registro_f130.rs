@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -49,6 +49,11 @@ pub struct RegistroF130 {
 }
 
 impl_reg_methods!(RegistroF130);
+impl_sped_writer!(RegistroF130 {
+    nat_bc_cred => 2, ident_bem_imob, ind_orig_cred, ind_util_bem_imob, mes_oper_aquis => mmyyyy,
+    vl_oper_aquis, parc_oper_nao_bc_cred, vl_bc_cred, ind_nr_parc, cst_pis, vl_bc_pis, aliq_pis,
+    vl_pis, cst_cofins, vl_bc_cofins, aliq_cofins, vl_cofins, cod_cta, cod_ccus, desc_bem_imob,
+});
 
 impl SpedParser for RegistroF130 {
     type Output = RegistroF130;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -38,6 +38,10 @@ pub struct RegistroF150 {
 }
 
 impl_reg_methods!(RegistroF150);
+impl_sped_writer!(RegistroF150 {
+    nat_bc_cred => 2, vl_tot_est, est_imp, vl_bc_est, vl_bc_men_est, cst_pis, aliq_pis, vl_cred_pis,
+    cst_cofins, aliq_cofins, vl_cred_cofins, desc_est, cod_cta,
+});
 
 impl SpedParser for RegistroF150 {
     type Output = RegistroF150;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -47,6 +47,29 @@ pub struct RegistroF200 {
 }
 
 impl_reg_methods!(RegistroF200);
+impl_sped_writer!(RegistroF200 {
+    ind_oper,
+    unid_imob,
+    ident_emp,
+    desc_unid_imob,
+    num_cont,
+    cpf_cnpj_adqu,
+    dt_oper,
+    vl_tot_vend,
+    vl_rec_acum,
+    vl_tot_rec,
+    cst_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cst_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    perc_rec_receb,
+    ind_nat_emp,
+    inf_comp,
+});
 
 impl SpedParser for RegistroF200 {
     type Output = RegistroF200;
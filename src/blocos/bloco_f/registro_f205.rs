@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -42,6 +42,25 @@ pub struct RegistroF205 {
 }
 
 impl_reg_methods!(RegistroF205);
+impl_sped_writer!(RegistroF205 {
+    vl_cus_inc_acum_ant,
+    vl_cus_inc_per_esc,
+    vl_cus_inc_acum,
+    vl_exc_bc_cus_inc_acum,
+    vl_bc_cus_inc,
+    cst_pis,
+    aliq_pis,
+    vl_cred_pis_acum,
+    vl_cred_pis_desc_ant,
+    vl_cred_pis_desc,
+    vl_cred_pis_desc_fut,
+    cst_cofins,
+    aliq_cofins,
+    vl_cred_cofins_acum,
+    vl_cred_cofins_desc_ant,
+    vl_cred_cofins_desc,
+    vl_cred_cofins_desc_fut,
+});
 
 impl SpedParser for RegistroF205 {
     type Output = RegistroF205;
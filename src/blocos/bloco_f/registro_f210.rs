@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -35,6 +35,18 @@ pub struct RegistroF210 {
 }
 
 impl_reg_methods!(RegistroF210);
+impl_sped_writer!(RegistroF210 {
+    vl_cus_orc,
+    vl_exc,
+    vl_cus_orc_aju,
+    vl_bc_cred,
+    cst_pis,
+    aliq_pis,
+    vl_cred_pis_util,
+    cst_cofins,
+    aliq_cofins,
+    vl_cred_cofins_util,
+});
 
 impl SpedParser for RegistroF210 {
     type Output = RegistroF210;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -23,6 +25,7 @@ pub struct RegistroF211 {
 }
 
 impl_reg_methods!(RegistroF211);
+impl_sped_writer!(RegistroF211 { num_proc, ind_proc });
 
 impl SpedParser for RegistroF211 {
     type Output = RegistroF211;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -40,6 +40,23 @@ pub struct RegistroF500 {
 }
 
 impl_reg_methods!(RegistroF500);
+impl_sped_writer!(RegistroF500 {
+    vl_rec_caixa,
+    cst_pis,
+    vl_desc_pis,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    cst_cofins,
+    vl_desc_cofins,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    cod_mod,
+    cfop,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroF500 {
     type Output = RegistroF500;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -25,7 +25,7 @@ pub struct RegistroF525 {
     pub vl_rec: Option<Decimal>,                      // 2
     pub ind_rec: Option<CompactString>,               // 3
     pub cnpj_cpf: Option<CompactString>,              // 4
-    pub num_doc: Option<usize>,                       // 5
+    pub num_doc: Option<CompactString>,               // 5
     pub cod_item: Option<CompactString>,              // 6
     pub vl_rec_det: Option<Decimal>,                  // 7
     pub cst_pis: Option<CodigoSituacaoTributaria>,    // 8
@@ -35,6 +35,18 @@ pub struct RegistroF525 {
 }
 
 impl_reg_methods!(RegistroF525);
+impl_sped_writer!(RegistroF525 {
+    vl_rec,
+    ind_rec,
+    cnpj_cpf,
+    num_doc,
+    cod_item,
+    vl_rec_det,
+    cst_pis,
+    cst_cofins,
+    info_compl,
+    cod_cta,
+});
 
 impl SpedParser for RegistroF525 {
     type Output = RegistroF525;
@@ -63,7 +75,7 @@ impl SpedParser for RegistroF525 {
         let vl_rec = get_decimal(2, "VL_REC")?;
         let ind_rec = fields.get(3).to_compact_string();
         let cnpj_cpf = fields.get(4).to_compact_string();
-        let num_doc = fields.get(5).parse_opt();
+        let num_doc = fields.get(5).to_compact_string();
         let cod_item = fields.get(6).to_compact_string();
         let vl_rec_det = get_decimal(7, "VL_REC_DET")?;
         let cst_pis = fields
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -40,6 +40,23 @@ pub struct RegistroF560 {
 }
 
 impl_reg_methods!(RegistroF560);
+impl_sped_writer!(RegistroF560 {
+    vl_rec_comp,
+    cst_pis,
+    vl_desc_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_pis,
+    cst_cofins,
+    vl_desc_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cofins,
+    cod_mod,
+    cfop,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroF560 {
     type Output = RegistroF560;
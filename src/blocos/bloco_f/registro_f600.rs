@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -36,6 +36,18 @@ pub struct RegistroF600 {
 }
 
 impl_reg_methods!(RegistroF600);
+impl_sped_writer!(RegistroF600 {
+    ind_nat_ret,
+    dt_ret,
+    vl_bc_ret,
+    vl_ret,
+    cod_rec,
+    ind_nat_rec,
+    cnpj,
+    vl_ret_pis,
+    vl_ret_cofins,
+    ind_dec,
+});
 
 impl SpedParser for RegistroF600 {
     type Output = RegistroF600;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -31,6 +32,15 @@ pub struct RegistroF700 {
 }
 
 impl_reg_methods!(RegistroF700);
+impl_sped_writer!(RegistroF700 {
+    ind_ori_ded,
+    ind_nat_ded,
+    vl_ded_pis,
+    vl_ded_cofins,
+    vl_bc_oper,
+    cnpj,
+    inf_comp,
+});
 
 impl SpedParser for RegistroF700 {
     type Output = RegistroF700;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -34,6 +34,16 @@ pub struct RegistroF800 {
 }
 
 impl_reg_methods!(RegistroF800);
+impl_sped_writer!(RegistroF800 {
+    ind_nat_even,
+    dt_even,
+    cnpj_suced,
+    pa_cont_cred,
+    cod_cred,
+    vl_cred_pis,
+    vl_cred_cofins,
+    per_cred_cis,
+});
 
 impl SpedParser for RegistroF800 {
     type Output = RegistroF800;
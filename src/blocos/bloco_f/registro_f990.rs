@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroF990 {
 }
 
 impl_reg_methods!(RegistroF990);
+impl_sped_writer!(RegistroF990 { qtd_lin_f });
 
 impl SpedParser for RegistroF990 {
     type Output = RegistroF990;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -24,6 +26,11 @@ pub struct RegistroI010 {
 }
 
 impl_reg_methods!(RegistroI010);
+impl_sped_writer!(RegistroI010 {
+    cnpj,
+    ind_ativ,
+    info_compl,
+});
 
 impl SpedParser for RegistroI010 {
     type Output = RegistroI010;
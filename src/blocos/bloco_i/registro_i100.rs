@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -36,6 +36,19 @@ pub struct RegistroI100 {
 }
 
 impl_reg_methods!(RegistroI100);
+impl_sped_writer!(RegistroI100 {
+    vl_rec,
+    cst_pis_cofins,
+    vl_tot_ded_ger,
+    vl_tot_ded_esp,
+    vl_bc_pis,
+    aliq_pis,
+    vl_pis,
+    vl_bc_cofins,
+    aliq_cofins,
+    vl_cofins,
+    info_compl,
+});
 
 impl SpedParser for RegistroI100 {
     type Output = RegistroI100;
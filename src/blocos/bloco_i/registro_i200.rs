@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -26,6 +28,13 @@ pub struct RegistroI200 {
 }
 
 impl_reg_methods!(RegistroI200);
+impl_sped_writer!(RegistroI200 {
+    num_campo,
+    cod_det,
+    det_valor,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroI200 {
     type Output = RegistroI200;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -25,6 +27,12 @@ pub struct RegistroI300 {
 }
 
 impl_reg_methods!(RegistroI300);
+impl_sped_writer!(RegistroI300 {
+    cod_comp,
+    det_valor,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroI300 {
     type Output = RegistroI300;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroI990 {
 }
 
 impl_reg_methods!(RegistroI990);
+impl_sped_writer!(RegistroI990 { qtd_lin_i });
 
 impl SpedParser for RegistroI990 {
     type Output = RegistroI990;
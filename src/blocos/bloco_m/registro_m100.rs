@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -40,6 +40,22 @@ pub struct RegistroM100 {
 }
 
 impl_reg_methods!(RegistroM100);
+impl_sped_writer!(RegistroM100 {
+    cod_cred,
+    ind_cred_ori,
+    vl_bc_pis,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_cred,
+    vl_ajus_acres,
+    vl_ajus_reduc,
+    vl_cred_dif,
+    vl_cred_disp,
+    ind_desc_cred,
+    vl_cred_desc,
+    sld_cred,
+});
 
 impl SpedParser for RegistroM100 {
     type Output = RegistroM100;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -34,6 +34,10 @@ pub struct RegistroM105 {
 }
 
 impl_reg_methods!(RegistroM105);
+impl_sped_writer!(RegistroM105 {
+    nat_bc_cred => 2, cst_pis, vl_bc_pis_tot, vl_bc_pis_cum, vl_bc_pis_nc, vl_bc_pis,
+    quant_bc_pis_tot, quant_bc_pis, desc_cred,
+});
 
 impl SpedParser for RegistroM105 {
     type Output = RegistroM105;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,16 @@ pub struct RegistroM115 {
 }
 
 impl_reg_methods!(RegistroM115);
+impl_sped_writer!(RegistroM115 {
+    det_valor_aj,
+    cst_pis,
+    det_bc_cred,
+    det_aliq,
+    dt_oper_aj,
+    desc_aj,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroM115 {
     type Output = RegistroM115;
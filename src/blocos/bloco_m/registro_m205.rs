@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -27,6 +28,11 @@ pub struct RegistroM205 {
 }
 
 impl_reg_methods!(RegistroM205);
+impl_sped_writer!(RegistroM205 {
+    num_campo,
+    cod_rec,
+    vl_debito,
+});
 
 impl SpedParser for RegistroM205 {
     type Output = RegistroM205;
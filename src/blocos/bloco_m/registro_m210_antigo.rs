@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -36,6 +37,20 @@ pub struct RegistroM210Antigo {
 }
 
 impl_reg_methods!(RegistroM210Antigo);
+impl_sped_writer!(RegistroM210Antigo {
+    cod_cont,
+    vl_rec_brt,
+    vl_bc_cont,
+    aliq_pis,
+    quant_bc_pis,
+    aliq_pis_quant,
+    vl_cont_apur,
+    vl_ajus_acres,
+    vl_ajus_reduc,
+    vl_cont_difer,
+    vl_cont_difer_ant,
+    vl_cont_per,
+});
 
 impl SpedParser for RegistroM210Antigo {
     type Output = RegistroM210Antigo;
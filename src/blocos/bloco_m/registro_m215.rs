@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -26,7 +26,7 @@ pub struct RegistroM215 {
     pub ind_aj_bc: Option<CompactString>,   // 2
     pub vl_aj_bc: Option<Decimal>,          // 3
     pub cod_aj_bc: Option<CompactString>,   // 4
-    pub num_doc: Option<usize>,             // 5
+    pub num_doc: Option<CompactString>,     // 5
     pub descr_aj_bc: Option<CompactString>, // 6
     pub dt_ref: Option<NaiveDate>,          // 7
     pub cod_cta: Option<CompactString>,     // 8
@@ -35,6 +35,17 @@ pub struct RegistroM215 {
 }
 
 impl_reg_methods!(RegistroM215);
+impl_sped_writer!(RegistroM215 {
+    ind_aj_bc,
+    vl_aj_bc,
+    cod_aj_bc,
+    num_doc,
+    descr_aj_bc,
+    dt_ref,
+    cod_cta,
+    cnpj,
+    info_compl,
+});
 
 impl SpedParser for RegistroM215 {
     type Output = RegistroM215;
@@ -71,7 +82,7 @@ impl SpedParser for RegistroM215 {
         let ind_aj_bc = fields.get(2).to_compact_string();
         let vl_aj_bc = get_decimal(3, "VL_AJ_BC")?;
         let cod_aj_bc = fields.get(4).to_compact_string();
-        let num_doc = fields.get(5).parse_opt();
+        let num_doc = fields.get(5).to_compact_string();
         let descr_aj_bc = fields.get(6).to_compact_string();
         let dt_ref = get_date(7, "DT_REF")?;
         let cod_cta = fields.get(8).to_compact_string();
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,16 @@ pub struct RegistroM225 {
 }
 
 impl_reg_methods!(RegistroM225);
+impl_sped_writer!(RegistroM225 {
+    det_valor_aj,
+    cst_pis,
+    det_bc_cred,
+    det_aliq,
+    dt_oper_aj,
+    desc_aj,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroM225 {
     type Output = RegistroM225;
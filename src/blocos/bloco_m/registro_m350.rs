@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use rust_decimal::Decimal;
 use std::path::Path;
@@ -27,6 +29,13 @@ pub struct RegistroM350 {
 }
 
 impl_reg_methods!(RegistroM350);
+impl_sped_writer!(RegistroM350 {
+    vl_tot_fol,
+    vl_exc_bc,
+    vl_tot_bc,
+    aliq_pis_fol,
+    vl_tot_cont_fol,
+});
 
 impl SpedParser for RegistroM350 {
     type Output = RegistroM350;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -29,6 +29,12 @@ pub struct RegistroM400 {
 }
 
 impl_reg_methods!(RegistroM400);
+impl_sped_writer!(RegistroM400 {
+    cst_pis,
+    vl_tot_rec,
+    cod_cta,
+    desc_compl,
+});
 
 impl SpedParser for RegistroM400 {
     type Output = RegistroM400;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -28,6 +29,12 @@ pub struct RegistroM410 {
 }
 
 impl_reg_methods!(RegistroM410);
+impl_sped_writer!(RegistroM410 {
+    nat_rec,
+    vl_rec,
+    cod_cta,
+    desc_compl,
+});
 
 impl SpedParser for RegistroM410 {
     type Output = RegistroM410;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -39,6 +39,22 @@ pub struct RegistroM500 {
 }
 
 impl_reg_methods!(RegistroM500);
+impl_sped_writer!(RegistroM500 {
+    cod_cred,
+    ind_cred_ori,
+    vl_bc_cofins,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cred,
+    vl_ajus_acres,
+    vl_ajus_reduc,
+    vl_cred_difer,
+    vl_cred_disp,
+    ind_desc_cred,
+    vl_cred_desc,
+    sld_cred,
+});
 
 impl SpedParser for RegistroM500 {
     type Output = RegistroM500;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -34,6 +34,10 @@ pub struct RegistroM505 {
 }
 
 impl_reg_methods!(RegistroM505);
+impl_sped_writer!(RegistroM505 {
+    nat_bc_cred => 2, cst_cofins, vl_bc_cofins_tot, vl_bc_cofins_cum, vl_bc_cofins_nc, vl_bc_cofins,
+    quant_bc_cofins_tot, quant_bc_cofins, desc_cred,
+});
 
 impl SpedParser for RegistroM505 {
     type Output = RegistroM505;
@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToDecimal, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use rust_decimal::Decimal;
 use std::path::Path;
@@ -34,6 +36,20 @@ pub struct RegistroM600 {
 }
 
 impl_reg_methods!(RegistroM600);
+impl_sped_writer!(RegistroM600 {
+    vl_tot_cont_nc_per,
+    vl_tot_cred_desc,
+    vl_tot_cred_desc_ant,
+    vl_tot_cont_nc_dev,
+    vl_ret_nc,
+    vl_out_ded_nc,
+    vl_cont_nc_rec,
+    vl_tot_cont_cum_per,
+    vl_ret_cum,
+    vl_out_ded_cum,
+    vl_cont_cum_rec,
+    vl_tot_cont_rec,
+});
 
 impl SpedParser for RegistroM600 {
     type Output = RegistroM600;
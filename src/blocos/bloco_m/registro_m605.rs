@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -27,6 +28,11 @@ pub struct RegistroM605 {
 }
 
 impl_reg_methods!(RegistroM605);
+impl_sped_writer!(RegistroM605 {
+    num_campo,
+    cod_rec,
+    vl_debito,
+});
 
 impl SpedParser for RegistroM605 {
     type Output = RegistroM605;
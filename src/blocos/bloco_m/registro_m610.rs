@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -39,6 +40,23 @@ pub struct RegistroM610 {
 }
 
 impl_reg_methods!(RegistroM610);
+impl_sped_writer!(RegistroM610 {
+    cod_cont,
+    vl_rec_brt,
+    vl_bc_cont,
+    vl_ajus_acres_bc_cofins,
+    vl_ajus_reduc_bc_cofins,
+    vl_bc_cont_ajus,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cont_apur,
+    vl_ajus_acres,
+    vl_ajus_reduc,
+    vl_cont_difer,
+    vl_cont_difer_ant,
+    vl_cont_per,
+});
 
 impl SpedParser for RegistroM610 {
     type Output = RegistroM610;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -36,6 +37,20 @@ pub struct RegistroM610Antigo {
 }
 
 impl_reg_methods!(RegistroM610Antigo);
+impl_sped_writer!(RegistroM610Antigo {
+    cod_cont,
+    vl_rec_brt,
+    vl_bc_cont,
+    aliq_cofins,
+    quant_bc_cofins,
+    aliq_cofins_quant,
+    vl_cont_apur,
+    vl_ajus_acres,
+    vl_ajus_reduc,
+    vl_cont_difer,
+    vl_cont_difer_ant,
+    vl_cont_per,
+});
 
 impl SpedParser for RegistroM610Antigo {
     type Output = RegistroM610Antigo;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -29,6 +30,13 @@ pub struct RegistroM611 {
 }
 
 impl_reg_methods!(RegistroM611);
+impl_sped_writer!(RegistroM611 {
+    ind_tip_coop,
+    vl_bc_cont_ant_exc_coop,
+    vl_exc_coop_ger,
+    vl_exc_esp_coop,
+    vl_bc_cont,
+});
 
 impl SpedParser for RegistroM611 {
     type Output = RegistroM611;
@@ -1,6 +1,6 @@
 use crate::{
     CodigoSituacaoTributaria, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    ToNaiveDate, impl_reg_methods,
+    ToNaiveDate, impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -34,6 +34,16 @@ pub struct RegistroM625 {
 }
 
 impl_reg_methods!(RegistroM625);
+impl_sped_writer!(RegistroM625 {
+    det_valor_aj,
+    cst_cofins,
+    det_bc_cred,
+    det_aliq,
+    dt_oper_aj,
+    desc_aj,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroM625 {
     type Output = RegistroM625;
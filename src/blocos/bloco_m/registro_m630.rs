@@ -1,6 +1,6 @@
 use crate::{
     CodigoDoCredito, EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -31,6 +31,14 @@ pub struct RegistroM630 {
 }
 
 impl_reg_methods!(RegistroM630);
+impl_sped_writer!(RegistroM630 {
+    cnpj,
+    vl_vend,
+    vl_nao_receb,
+    vl_cont_dif,
+    vl_cred_dif,
+    cod_cred,
+});
 
 impl SpedParser for RegistroM630 {
     type Output = RegistroM630;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -33,6 +33,15 @@ pub struct RegistroM700 {
 }
 
 impl_reg_methods!(RegistroM700);
+impl_sped_writer!(RegistroM700 {
+    cod_cont,
+    vl_cont_apur_difer,
+    nat_cred_desc,
+    vl_cred_desc_difer,
+    vl_cont_difer_ant,
+    per_apur,
+    dt_receb,
+});
 
 impl SpedParser for RegistroM700 {
     type Output = RegistroM700;
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroM990 {
 }
 
 impl_reg_methods!(RegistroM990);
+impl_sped_writer!(RegistroM990 { qtd_lin_m });
 
 impl SpedParser for RegistroM990 {
     type Output = RegistroM990;
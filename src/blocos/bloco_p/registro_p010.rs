@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +24,7 @@ pub struct RegistroP010 {
 }
 
 impl_reg_methods!(RegistroP010);
+impl_sped_writer!(RegistroP010 { cnpj });
 
 impl SpedParser for RegistroP010 {
     type Output = RegistroP010;
@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -37,6 +37,19 @@ pub struct RegistroP100 {
 }
 
 impl_reg_methods!(RegistroP100);
+impl_sped_writer!(RegistroP100 {
+    dt_ini,
+    dt_fin,
+    vl_rec_tot_est,
+    cod_ativ_econ,
+    vl_rec_ativ_estab,
+    vl_exc,
+    vl_bc_cont,
+    aliq_cont,
+    vl_cont_apu,
+    cod_cta,
+    info_compl,
+});
 
 impl SpedParser for RegistroP100 {
     type Output = RegistroP100;
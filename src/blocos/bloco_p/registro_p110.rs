@@ -1,4 +1,6 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, StringParser, impl_reg_methods, impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -25,6 +27,12 @@ pub struct RegistroP110 {
 }
 
 impl_reg_methods!(RegistroP110);
+impl_sped_writer!(RegistroP110 {
+    num_campo,
+    cod_det,
+    det_valor,
+    inf_compl,
+});
 
 impl SpedParser for RegistroP110 {
     type Output = RegistroP110;
@@ -1,5 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, impl_reg_methods,
+    impl_sped_writer,
 };
 use compact_str::CompactString;
 use rust_decimal::Decimal;
@@ -30,6 +31,14 @@ pub struct RegistroP200 {
 }
 
 impl_reg_methods!(RegistroP200);
+impl_sped_writer!(RegistroP200 {
+    per_ref,
+    vl_tot_cont_apu,
+    vl_tot_aj_reduc,
+    vl_tot_aj_acres,
+    vl_tot_cont_dev,
+    cod_rec,
+});
 
 impl SpedParser for RegistroP200 {
     type Output = RegistroP200;
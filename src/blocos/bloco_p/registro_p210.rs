@@ -1,6 +1,6 @@
 use crate::{
     EFDError, EFDResult, ResultExt, SpedParser, StringParser, ToDecimal, ToNaiveDate,
-    impl_reg_methods,
+    impl_reg_methods, impl_sped_writer,
 };
 use chrono::NaiveDate;
 use compact_str::CompactString;
@@ -26,12 +26,20 @@ pub struct RegistroP210 {
     pub ind_aj: Option<CompactString>,   // 2
     pub vl_aj: Option<Decimal>,          // 3
     pub cod_aj: Option<CompactString>,   // 4
-    pub num_doc: Option<usize>,          // 5
+    pub num_doc: Option<CompactString>,  // 5
     pub descr_aj: Option<CompactString>, // 6
     pub dt_ref: Option<NaiveDate>,       // 7
 }
 
 impl_reg_methods!(RegistroP210);
+impl_sped_writer!(RegistroP210 {
+    ind_aj,
+    vl_aj,
+    cod_aj,
+    num_doc,
+    descr_aj,
+    dt_ref,
+});
 
 impl SpedParser for RegistroP210 {
     type Output = RegistroP210;
@@ -68,7 +76,7 @@ impl SpedParser for RegistroP210 {
         let ind_aj = fields.get(2).to_compact_string();
         let vl_aj = get_decimal(3, "VL_AJ")?;
         let cod_aj = fields.get(4).to_compact_string();
-        let num_doc = fields.get(5).parse_opt();
+        let num_doc = fields.get(5).to_compact_string();
         let descr_aj = fields.get(6).to_compact_string();
         let dt_ref = get_date(7, "DT_REF")?;
 
@@ -1,4 +1,7 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods};
+use crate::{
+    EFDError, EFDResult, ResultExt, SpedParser, ToOptionalInteger, impl_reg_methods,
+    impl_sped_writer,
+};
 use compact_str::CompactString;
 use std::path::Path;
 
@@ -22,6 +25,7 @@ pub struct RegistroP990 {
 }
 
 impl_reg_methods!(RegistroP990);
+impl_sped_writer!(RegistroP990 { qtd_lin_p });
 
 impl SpedParser for RegistroP990 {
     type Output = RegistroP990;
@@ -10,6 +10,8 @@ pub mod bloco_i;
 pub mod bloco_m;
 pub mod bloco_p;
 
+pub mod registro_generico;
+
 pub use bloco_0::*;
 pub use bloco_1::*;
 pub use bloco_9::*;
@@ -21,3 +23,5 @@ pub use bloco_f::*;
 pub use bloco_i::*;
 pub use bloco_m::*;
 pub use bloco_p::*;
+
+pub use registro_generico::*;
@@ -0,0 +1,76 @@
+use crate::{DELIMITER_CHAR, SpedWriter, impl_reg_methods};
+use compact_str::CompactString;
+
+/// Registro não suportado pelo parser (ex: registro criado em leiaute posterior).
+///
+/// Preserva o código do registro, o número da linha e os campos originais,
+/// de modo que o arquivo possa ser reescrito (`SpedFile::write_to`) sem perda de linhas.
+#[derive(Debug, Clone)]
+pub struct RegistroGenerico {
+    /// Nível hierárquico estimado a partir do código do registro (ver `estimar_nivel`).
+    pub nivel: u16,
+    pub bloco: char,
+    pub registro: CompactString,
+    pub line_number: usize,
+    /// Campos 02 em diante, tal como lidos do arquivo.
+    pub campos: Vec<CompactString>,
+}
+
+impl_reg_methods!(RegistroGenerico);
+
+impl SpedWriter for RegistroGenerico {
+    fn write_fields(&self, buffer: &mut String) {
+        for campo in &self.campos {
+            buffer.push(DELIMITER_CHAR);
+            buffer.push_str(campo);
+        }
+    }
+}
+
+impl RegistroGenerico {
+    /// Cria o registro a partir dos campos da linha, divididos por '|'
+    /// (`fields[1]` é o código do registro e o último campo é o vazio após o '|' final).
+    pub fn new(registro: &str, line_number: usize, fields: &[&str]) -> Self {
+        let campos = fields
+            .get(2..fields.len().saturating_sub(1))
+            .unwrap_or_default()
+            .iter()
+            .map(|&campo| CompactString::new(campo))
+            .collect();
+
+        Self {
+            nivel: estimar_nivel(registro),
+            bloco: registro.chars().next().unwrap_or('?'),
+            registro: registro.into(),
+            line_number,
+            campos,
+        }
+    }
+}
+
+/// Estima o nível hierárquico de um registro não suportado a partir do seu código.
+///
+/// - 0000 e 9999: nível 0;
+/// - X001 e X990 (abertura e encerramento de bloco): nível 1;
+/// - Blocos com registro de estabelecimento X010 (A, C, D, F, I e P):
+///   X010 nível 2, registros terminados em "00" nível 3 e os demais nível 4;
+/// - Blocos 0, M, 1 e 9: registros terminados em "00" nível 2 e os demais nível 3.
+pub fn estimar_nivel(registro: &str) -> u16 {
+    let bytes = registro.as_bytes();
+
+    if bytes.len() != 4 || !registro.is_ascii() {
+        return 0;
+    }
+
+    let codigo = &registro[1..];
+
+    match (bytes[0], codigo) {
+        (b'0', "000") | (b'9', "999") => 0,
+        (_, "001" | "990") => 1,
+        (b'A' | b'C' | b'D' | b'F' | b'I' | b'P', "010") => 2,
+        (b'A' | b'C' | b'D' | b'F' | b'I' | b'P', _) if codigo.ends_with("00") => 3,
+        (b'A' | b'C' | b'D' | b'F' | b'I' | b'P', _) => 4,
+        _ if codigo.ends_with("00") => 2,
+        _ => 3,
+    }
+}
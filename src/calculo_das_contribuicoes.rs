@@ -0,0 +1,194 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use std::{collections::BTreeMap, fmt, fmt::Write};
+
+use crate::{DECIMAL_VALOR, DecimalExt, DocsFiscais};
+
+/// Valor da coluna "Verificação do Cálculo" para valores sem divergências.
+pub const CALCULO_CONFORME: &str = "Conforme";
+
+/// Prefixo da coluna "Verificação do Cálculo" para valores divergentes.
+pub const CALCULO_DIVERGENTE: &str = "Divergente";
+
+// ============================================================================
+// Recálculo das Contribuições (PIS/PASEP e COFINS)
+// ============================================================================
+
+/*
+Valor da contribuição:
+- Alíquota em percentual: VL_BC × ALIQ / 100;
+- Alíquota em reais (por unidade de medida): QUANT_BC × ALIQ_QUANT.
+
+O valor calculado é arredondado com 2 casas decimais e confrontado com o valor
+declarado. Diferenças maiores que a tolerância (--tolerancia) são divergências.
+*/
+
+/// Contribuição (PIS/PASEP ou COFINS) recalculada a partir da base e da alíquota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Contribuicao {
+    Pis,
+    Cofins,
+}
+
+impl Contribuicao {
+    pub const fn descricao(&self) -> &'static str {
+        match self {
+            Self::Pis => "PIS/PASEP",
+            Self::Cofins => "COFINS",
+        }
+    }
+}
+
+/// Base de cálculo, alíquotas e valor declarado de uma contribuição.
+///
+/// Campos com valor `None` não foram informados no registro.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CalculoDaContribuicao {
+    /// Valor da base de cálculo (VL_BC_PIS ou VL_BC_COFINS).
+    pub valor_bc: Option<Decimal>,
+    /// Alíquota em percentual (ALIQ_PIS ou ALIQ_COFINS).
+    pub aliquota: Option<Decimal>,
+    /// Base de cálculo em quantidade (QUANT_BC_PIS ou QUANT_BC_COFINS).
+    pub quant_bc: Option<Decimal>,
+    /// Alíquota em reais (ALIQ_PIS_QUANT ou ALIQ_COFINS_QUANT).
+    pub aliquota_quant: Option<Decimal>,
+    /// Valor declarado (VL_PIS ou VL_COFINS).
+    pub valor_declarado: Option<Decimal>,
+}
+
+impl CalculoDaContribuicao {
+    /// Valor da contribuição calculado com a alíquota em percentual ou,
+    /// na falta desta, com a alíquota em reais (por quantidade).
+    pub fn valor_calculado(&self) -> Option<Decimal> {
+        let valor = match (self.valor_bc, self.aliquota) {
+            (Some(bc), Some(aliq)) => bc * aliq / dec!(100),
+            _ => self.quant_bc? * self.aliquota_quant?,
+        };
+
+        Some(
+            valor.round_dp_with_strategy(
+                DECIMAL_VALOR as u32,
+                RoundingStrategy::MidpointAwayFromZero,
+            ),
+        )
+    }
+}
+
+/// Diferença entre o valor calculado e o valor declarado de uma contribuição.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivergenciaDeCalculo {
+    pub contribuicao: Contribuicao,
+    pub calculado: Decimal,
+    pub declarado: Decimal,
+}
+
+impl fmt::Display for DivergenciaDeCalculo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} calculado {}, declarado {}",
+            self.contribuicao.descricao(),
+            self.calculado.to_formatted_string(DECIMAL_VALOR),
+            self.declarado.to_formatted_string(DECIMAL_VALOR),
+        )
+    }
+}
+
+/// Resultado do recálculo das contribuições de um item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificacaoCalculo {
+    Conforme,
+    Divergente(Vec<DivergenciaDeCalculo>),
+}
+
+impl VerificacaoCalculo {
+    pub fn is_conforme(&self) -> bool {
+        matches!(self, Self::Conforme)
+    }
+}
+
+impl fmt::Display for VerificacaoCalculo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conforme => write!(f, "{CALCULO_CONFORME}"),
+            Self::Divergente(divergencias) => {
+                let divergencias: Vec<String> =
+                    divergencias.iter().map(ToString::to_string).collect();
+                write!(f, "{CALCULO_DIVERGENTE}: {}", divergencias.join("; "))
+            }
+        }
+    }
+}
+
+/// Recalcula PIS/PASEP e COFINS e confronta com os valores declarados.
+///
+/// Retorna `None` se nenhuma das contribuições puder ser recalculada
+/// (valor declarado, base de cálculo ou alíquota não informados).
+pub fn verificar_calculo(
+    pis: &CalculoDaContribuicao,
+    cofins: &CalculoDaContribuicao,
+    tolerancia: Decimal,
+) -> Option<VerificacaoCalculo> {
+    let mut verificado = false;
+    let mut divergencias = Vec::new();
+
+    for (contribuicao, calculo) in [(Contribuicao::Pis, pis), (Contribuicao::Cofins, cofins)] {
+        let (Some(declarado), Some(calculado)) =
+            (calculo.valor_declarado, calculo.valor_calculado())
+        else {
+            continue;
+        };
+
+        verificado = true;
+
+        if (calculado - declarado).abs() > tolerancia {
+            divergencias.push(DivergenciaDeCalculo {
+                contribuicao,
+                calculado,
+                declarado,
+            });
+        }
+    }
+
+    match (verificado, divergencias.is_empty()) {
+        (false, _) => None,
+        (true, true) => Some(VerificacaoCalculo::Conforme),
+        (true, false) => Some(VerificacaoCalculo::Divergente(divergencias)),
+    }
+}
+
+/// Resumo dos itens com divergências no cálculo de PIS/PASEP e COFINS
+/// (quantidade de itens por registro), ou String vazia se não houver divergências.
+pub fn relatorio_divergencias_de_calculo(docs: &[DocsFiscais], tolerancia: Decimal) -> String {
+    let mut por_registro: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for doc in docs.iter().filter(|doc| doc.calculo_divergente()) {
+        *por_registro.entry(&doc.registro).or_default() += 1;
+    }
+
+    if por_registro.is_empty() {
+        return String::new();
+    }
+
+    let total: usize = por_registro.values().sum();
+
+    let mut relatorio = format!(
+        "Divergências no cálculo de PIS/PASEP e COFINS (tolerância de R$ {}): {total} item(ns).\n",
+        tolerancia.to_formatted_string(DECIMAL_VALOR)
+    );
+
+    for (registro, quantidade) in por_registro {
+        let _ = writeln!(relatorio, "   Registro {registro}: {quantidade} item(ns)");
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/calculo_das_contribuicoes_tests.rs"]
+mod calculo_das_contribuicoes_tests;
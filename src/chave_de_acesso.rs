@@ -0,0 +1,230 @@
+use chrono::{Datelike, NaiveDate};
+use std::{collections::HashSet, fmt, fmt::Write};
+
+use crate::{DocsFiscais, digito_modulo_11};
+
+/// Número de caracteres da chave de acesso dos documentos fiscais eletrônicos.
+pub const TAMANHO_DA_CHAVE: usize = 44;
+
+/// Valor da coluna "Verificação da Chave" para chaves sem divergências.
+pub const CHAVE_VALIDA: &str = "Válida";
+
+// ============================================================================
+// Chave de Acesso (NF-e, NFC-e, CT-e, CT-e OS, BP-e e NF3e)
+// ============================================================================
+
+/*
+Composição da chave de acesso (44 caracteres):
+
+| cUF | AAMM | CNPJ/CPF do Emitente | mod | serie | nNF | tpEmis | cNF | cDV |
+|  2  |  4   |         14           |  2  |   3   |  9  |   1    |  8  |  1  |
+
+O dígito verificador (cDV) é calculado pelo módulo 11 sobre os 43 primeiros caracteres,
+com pesos de 2 a 9 aplicados da direita para a esquerda.
+*/
+
+/// Campos da chave de acesso confrontados com os dados do documento fiscal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CampoDaChave {
+    DigitoVerificador,
+    Uf,
+    AnoMes,
+    Cnpj,
+    Modelo,
+    Serie,
+    Numero,
+}
+
+impl CampoDaChave {
+    pub const fn descricao(&self) -> &'static str {
+        match self {
+            Self::DigitoVerificador => "dígito verificador",
+            Self::Uf => "UF",
+            Self::AnoMes => "AAMM",
+            Self::Cnpj => "CNPJ do emitente",
+            Self::Modelo => "modelo",
+            Self::Serie => "série",
+            Self::Numero => "número",
+        }
+    }
+}
+
+/// Resultado da verificação da chave de acesso.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificacaoChave {
+    Valida,
+    /// Chave com tamanho diferente de 44 ou com caracteres inválidos.
+    TamanhoIncorreto,
+    /// Campos da chave divergentes dos dados do documento (ou DV incorreto).
+    Divergente(Vec<CampoDaChave>),
+}
+
+impl VerificacaoChave {
+    pub fn is_valida(&self) -> bool {
+        matches!(self, Self::Valida)
+    }
+}
+
+impl fmt::Display for VerificacaoChave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Valida => write!(f, "{CHAVE_VALIDA}"),
+            Self::TamanhoIncorreto => write!(f, "Inválida/Tamanho Incorreto"),
+            Self::Divergente(campos) => {
+                let campos: Vec<&str> = campos.iter().map(CampoDaChave::descricao).collect();
+                write!(f, "Inválida: {}", campos.join(", "))
+            }
+        }
+    }
+}
+
+/// Dados do documento fiscal (C100, D100 ou C500) esperados na chave de acesso.
+///
+/// Campos com valor `None` não são verificados.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DadosDoDocumento<'a> {
+    /// Código do modelo do documento fiscal (COD_MOD).
+    pub cod_mod: Option<&'a str>,
+    /// Série do documento fiscal (SER).
+    pub serie: Option<&'a str>,
+    /// Número do documento fiscal (NUM_DOC).
+    pub num_doc: Option<usize>,
+    /// Data da emissão do documento fiscal (DT_DOC).
+    pub dt_doc: Option<NaiveDate>,
+    /// CNPJ (ou CPF) do emitente: estabelecimento ou participante.
+    pub cnpj_emitente: Option<&'a str>,
+    /// Código do município do emitente (os 2 primeiros dígitos identificam a UF).
+    pub cod_mun_emitente: Option<&'a str>,
+}
+
+/// Calcula o dígito verificador (módulo 11) dos 43 primeiros caracteres da chave de acesso.
+///
+/// Caracteres alfanuméricos (CNPJ alfanumérico) valem o código ASCII menos 48.
+pub fn digito_verificador_da_chave(chave_sem_dv: &str) -> Option<u8> {
+    let bytes = chave_sem_dv.as_bytes();
+
+    if bytes.len() != TAMANHO_DA_CHAVE - 1 || !bytes.iter().all(eh_caractere_da_chave) {
+        return None;
+    }
+
+    Some(digito_modulo_11(bytes, 9))
+}
+
+/// Verifica o dígito verificador da chave de acesso e confronta os seus campos
+/// (UF, AAMM, CNPJ do emitente, modelo, série e número) com os dados do documento.
+pub fn verificar_chave(chave: &str, dados: &DadosDoDocumento) -> VerificacaoChave {
+    let bytes = chave.as_bytes();
+
+    if bytes.len() != TAMANHO_DA_CHAVE || !bytes.iter().all(eh_caractere_da_chave) {
+        return VerificacaoChave::TamanhoIncorreto;
+    }
+
+    let mut divergencias = Vec::new();
+
+    let dv_informado = bytes[TAMANHO_DA_CHAVE - 1].checked_sub(b'0');
+    if digito_verificador_da_chave(&chave[..TAMANHO_DA_CHAVE - 1]) != dv_informado {
+        divergencias.push(CampoDaChave::DigitoVerificador);
+    }
+
+    if let Some(uf) = dados.cod_mun_emitente.and_then(|cod_mun| cod_mun.get(..2))
+        && uf != &chave[0..2]
+    {
+        divergencias.push(CampoDaChave::Uf);
+    }
+
+    if let Some(dt_doc) = dados.dt_doc
+        && format!("{:02}{:02}", dt_doc.year() % 100, dt_doc.month()) != chave[2..6]
+    {
+        divergencias.push(CampoDaChave::AnoMes);
+    }
+
+    if let Some(emitente) = dados.cnpj_emitente
+        && !emitente_confere(emitente, &chave[6..20])
+    {
+        divergencias.push(CampoDaChave::Cnpj);
+    }
+
+    if let Some(cod_mod) = dados.cod_mod
+        && cod_mod.trim() != &chave[20..22]
+    {
+        divergencias.push(CampoDaChave::Modelo);
+    }
+
+    // Séries não numéricas (ex: "U", única) não são comparadas.
+    if let Some(serie) = dados.serie.and_then(|s| s.trim().parse::<u32>().ok())
+        && chave[22..25].parse::<u32>().ok() != Some(serie)
+    {
+        divergencias.push(CampoDaChave::Serie);
+    }
+
+    if let Some(num_doc) = dados.num_doc
+        && chave[25..34].parse::<usize>().ok() != Some(num_doc)
+    {
+        divergencias.push(CampoDaChave::Numero);
+    }
+
+    if divergencias.is_empty() {
+        VerificacaoChave::Valida
+    } else {
+        VerificacaoChave::Divergente(divergencias)
+    }
+}
+
+/// Dígitos (ou letras maiúsculas do CNPJ alfanumérico) permitidos na chave de acesso.
+fn eh_caractere_da_chave(byte: &u8) -> bool {
+    byte.is_ascii_digit() || byte.is_ascii_uppercase()
+}
+
+/// Emitente pessoa física: o CPF ocupa os 11 últimos caracteres do campo CNPJ da chave.
+fn emitente_confere(emitente: &str, cnpj_da_chave: &str) -> bool {
+    match emitente.len() {
+        11 => cnpj_da_chave.starts_with("000") && &cnpj_da_chave[3..] == emitente,
+        _ => cnpj_da_chave == emitente,
+    }
+}
+
+/// Resumo das chaves de acesso inválidas (uma linha por chave),
+/// ou String vazia se todas as chaves verificadas forem válidas.
+pub fn relatorio_chaves_invalidas(docs: &[DocsFiscais]) -> String {
+    let mut chaves_vistas = HashSet::new();
+
+    let invalidas: Vec<&DocsFiscais> = docs
+        .iter()
+        .filter(|doc| {
+            !doc.verificacao_chave.is_empty() && doc.verificacao_chave.as_ref() != CHAVE_VALIDA
+        })
+        .filter(|doc| chaves_vistas.insert(doc.chave_doc.clone()))
+        .collect();
+
+    if invalidas.is_empty() {
+        return String::new();
+    }
+
+    let mut relatorio = format!(
+        "Chaves de acesso inválidas: {} documento(s) fiscal(is).\n",
+        invalidas.len()
+    );
+
+    for doc in invalidas {
+        let _ = writeln!(
+            relatorio,
+            "   Registro {} [linha nº {}], Nº do Documento {}: {} ({})",
+            doc.registro,
+            doc.num_linha_efd.unwrap_or_default(),
+            doc.num_doc.unwrap_or_default(),
+            doc.chave_doc,
+            doc.verificacao_chave,
+        );
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/chave_de_acesso_tests.rs"]
+mod chave_de_acesso_tests;
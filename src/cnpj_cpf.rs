@@ -0,0 +1,195 @@
+use compact_str::CompactString;
+use std::{fmt, fmt::Write};
+
+use crate::{Bloco0, BlocoA, BlocoC, BlocoD, BlocoF, SpedFile};
+
+// ============================================================================
+// Dígitos verificadores de CNPJ e CPF
+// ============================================================================
+
+/*
+CNPJ (numérico ou alfanumérico, a partir de 2026):
+- 12 primeiros caracteres: letras maiúsculas (A-Z) ou dígitos, valendo o código ASCII menos 48;
+- 2 últimos caracteres: dígitos verificadores numéricos, calculados pelo módulo 11
+  com pesos de 2 a 9 aplicados da direita para a esquerda.
+
+CPF: 9 dígitos seguidos de 2 dígitos verificadores (módulo 11, pesos de 2 a 11).
+*/
+
+/// Calcula o dígito verificador (módulo 11) com pesos crescentes a partir da direita.
+pub(crate) fn digito_modulo_11(base: &[u8], peso_maximo: u32) -> u8 {
+    let soma: u32 = base
+        .iter()
+        .rev()
+        .zip((2..=peso_maximo).cycle())
+        .map(|(&byte, peso)| u32::from(byte - b'0') * peso)
+        .sum();
+
+    match soma % 11 {
+        0 | 1 => 0,
+        resto => (11 - resto) as u8,
+    }
+}
+
+/// Verifica os 2 dígitos verificadores dos identificadores com `base` caracteres.
+fn digitos_conferem(bytes: &[u8], base: usize, peso_maximo: u32) -> bool {
+    let dv1 = digito_modulo_11(&bytes[..base], peso_maximo);
+    let dv2 = digito_modulo_11(&bytes[..=base], peso_maximo);
+
+    bytes[base] == b'0' + dv1 && bytes[base + 1] == b'0' + dv2
+}
+
+/// Sequências de caracteres repetidos (ex: "00000000000000") satisfazem o módulo 11,
+/// mas não são identificadores válidos.
+fn caracteres_repetidos(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&byte| byte == bytes[0])
+}
+
+/// Valida o CNPJ (14 caracteres, sem formatação), inclusive o CNPJ alfanumérico.
+///
+/// Ex: "12345678000195" e "12ABC34501DE35" são válidos.
+pub fn cnpj_valido(cnpj: &str) -> bool {
+    let bytes = cnpj.as_bytes();
+
+    bytes.len() == 14
+        && bytes[..12]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        && bytes[12..].iter().all(u8::is_ascii_digit)
+        && !caracteres_repetidos(bytes)
+        && digitos_conferem(bytes, 12, 9)
+}
+
+/// Valida o CPF (11 dígitos, sem formatação).
+pub fn cpf_valido(cpf: &str) -> bool {
+    let bytes = cpf.as_bytes();
+
+    bytes.len() == 11
+        && bytes.iter().all(u8::is_ascii_digit)
+        && !caracteres_repetidos(bytes)
+        && digitos_conferem(bytes, 9, 11)
+}
+
+// ============================================================================
+// Identificadores inválidos
+// ============================================================================
+
+/// CNPJ ou CPF com dígitos verificadores inválidos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentificadorInvalido {
+    /// Número da linha do registro.
+    pub linha: usize,
+
+    /// Código do registro (ex: "0150").
+    pub registro: CompactString,
+
+    /// Nome do campo: "CNPJ" ou "CPF".
+    pub campo: &'static str,
+
+    /// Valor informado no arquivo.
+    pub valor: String,
+}
+
+impl fmt::Display for IdentificadorInvalido {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Registro {} [linha nº {}], campo {}: {}",
+            self.registro, self.linha, self.campo, self.valor
+        )
+    }
+}
+
+impl SpedFile {
+    /// CNPJ e CPF inválidos dos registros 0000, 0140, 0150 e dos
+    /// estabelecimentos A010, C010, D010 e F010, em ordem de linha.
+    pub fn identificadores_invalidos(&self) -> Vec<IdentificadorInvalido> {
+        let mut invalidos = Vec::new();
+
+        let mut verificar = |linha: usize, registro: &str, campo, valor: Option<&str>| {
+            let Some(valor) = valor.filter(|v| !v.is_empty()) else {
+                return;
+            };
+
+            let valido = match campo {
+                "CPF" => cpf_valido(valor),
+                _ => cnpj_valido(valor),
+            };
+
+            if !valido {
+                invalidos.push(IdentificadorInvalido {
+                    linha,
+                    registro: registro.into(),
+                    campo,
+                    valor: valor.to_string(),
+                });
+            }
+        };
+
+        for registro in &self.bloco_0 {
+            match registro {
+                Bloco0::R0000(r) => verificar(r.line_number, "0000", "CNPJ", r.cnpj.as_deref()),
+                Bloco0::R0140(r) => verificar(r.line_number, "0140", "CNPJ", r.cnpj.as_deref()),
+                Bloco0::R0150(r) => {
+                    verificar(r.line_number, "0150", "CNPJ", r.cnpj.as_deref());
+                    verificar(r.line_number, "0150", "CPF", r.cpf.as_deref());
+                }
+                _ => {}
+            }
+        }
+
+        let estabelecimentos = self
+            .bloco_a
+            .iter()
+            .filter_map(|r| match r {
+                BlocoA::RA010(r) => Some((r.line_number, "A010", r.cnpj.as_deref())),
+                _ => None,
+            })
+            .chain(self.bloco_c.iter().filter_map(|r| match r {
+                BlocoC::RC010(r) => Some((r.line_number, "C010", r.cnpj.as_deref())),
+                _ => None,
+            }))
+            .chain(self.bloco_d.iter().filter_map(|r| match r {
+                BlocoD::RD010(r) => Some((r.line_number, "D010", r.cnpj.as_deref())),
+                _ => None,
+            }))
+            .chain(self.bloco_f.iter().filter_map(|r| match r {
+                BlocoF::RF010(r) => Some((r.line_number, "F010", r.cnpj.as_deref())),
+                _ => None,
+            }));
+
+        for (linha, registro, cnpj) in estabelecimentos {
+            verificar(linha, registro, "CNPJ", cnpj);
+        }
+
+        invalidos.sort_by_key(|i| i.linha);
+        invalidos
+    }
+
+    /// Relatório dos CNPJ e CPF com dígitos verificadores inválidos.
+    ///
+    /// Retorna uma String vazia se todos os identificadores forem válidos.
+    pub fn relatorio_identificadores_invalidos(&self) -> String {
+        let invalidos = self.identificadores_invalidos();
+
+        if invalidos.is_empty() {
+            return String::new();
+        }
+
+        let mut relatorio = String::from("CNPJ/CPF com dígitos verificadores inválidos:\n");
+        for invalido in invalidos {
+            let _ = writeln!(relatorio, "   {invalido}");
+        }
+        relatorio.push('\n');
+
+        relatorio
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/cnpj_cpf_tests.rs"]
+mod cnpj_cpf_tests;
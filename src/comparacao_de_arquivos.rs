@@ -0,0 +1,959 @@
+use chrono::NaiveDate;
+use indicatif::MultiProgress;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::{self, Write as _},
+    io::Write,
+    path::PathBuf,
+};
+
+use crate::{
+    EFDError, EFDResult, EfdRaise, Registro0150, Registro1100, Registro1300, Registro1500,
+    Registro1700, RegistroA100, RegistroA170, RegistroC100, RegistroC170, RegistroC180,
+    RegistroC181, RegistroC185, RegistroC190, RegistroC191, RegistroC195, RegistroC500,
+    RegistroC501, RegistroC505, RegistroD100, RegistroD101, RegistroD105, RegistroF100,
+    RegistroF120, RegistroF130, RegistroF150, RegistroGenerico, RegistroM100, RegistroM105,
+    RegistroM200, RegistroM210, RegistroM210Antigo, RegistroM400, RegistroM410, RegistroM500,
+    RegistroM505, RegistroM600, RegistroM610, RegistroM610Antigo, RegistroM800, RegistroM810,
+    RegistroP010, RegistroP100, RegistroP110, RegistroP199, RegistroP200, RegistroP210, ResultExt,
+    SpedFile, SpedWriter, ToSpedField, config::AppConfig, read_and_parse_file,
+    write_sped_com_zeros, write_sped_periodo,
+};
+
+// ============================================================================
+// Comparação de Arquivos (modo --diff)
+// ============================================================================
+
+/*
+Compara duas escriturações (ex: a original e a sua retificadora) pela identidade
+de negócio dos registros, e não pelo número da linha:
+- 0150: COD_PART;
+- A100, C100, C500 e D100: chave do documento ou, na sua ausência,
+  indicador da operação, participante, modelo, série, subsérie e número;
+- A170 e C170: documento (registro pai) e NUM_ITEM;
+- C180 e C190: modelo, período e COD_ITEM;
+- C181/C185 e C191/C195: consolidação (registro pai), participante (C191/C195),
+  CST, CFOP, alíquota e COD_CTA;
+- C501/C505 e D101/D105: documento (registro pai), natureza do frete (D101/D105),
+  CST, NAT_BC_CRED, alíquota e COD_CTA;
+- F100: indicador da operação, participante, COD_ITEM, data e NAT_BC_CRED;
+- F120 e F130: NAT_BC_CRED, bem, origem, utilização, mês de aquisição (F130) e COD_CTA;
+- F150: NAT_BC_CRED, CST e COD_CTA;
+- M100 e M500: COD_CRED, IND_CRED_ORI e alíquota;
+- M105 e M505: crédito (registro pai), NAT_BC_CRED e CST;
+- M200 e M600: registro único no período;
+- M210 e M610: COD_CONT e alíquota;
+- M400 e M800: CST e COD_CTA;
+- M410 e M810: receita (registro pai), NAT_REC e COD_CTA;
+- P010: CNPJ do estabelecimento;
+- P100: estabelecimento (registro pai), período, COD_ATIV_ECON e COD_CTA;
+- P110 e P199: apuração (registro pai) e campo detalhado ou processo;
+- P200: PER_REF e COD_REC;
+- P210: consolidação (registro pai), ajuste, documento e data de referência;
+- 1100 e 1500: PER_APU_CRED, ORIG_CRED, CNPJ_SUC e COD_CRED;
+- 1300 e 1700: IND_NAT_RET e PR_REC_RET.
+
+Os demais registros (e os não suportados pelo parser) são comparados pela posição:
+a n-ésima ocorrência do registro em um arquivo com a n-ésima do outro. Os registros
+de encerramento (X990 e Bloco 9) são recalculados e não são comparados.
+
+Identidades repetidas no mesmo arquivo recebem o sufixo " #n", na ordem das linhas.
+*/
+
+/// Sequência dos blocos no leiaute.
+const ORDEM_DOS_BLOCOS: &str = "0ACDFIMP19";
+
+/// Identidade dos registros únicos no período (M200 e M600).
+const REGISTRO_UNICO: &str = "registro único";
+
+/// Registro identificado pela sua identidade de negócio.
+#[derive(Debug, Clone)]
+struct RegistroIdentificado {
+    identidade: String,
+    linha: usize,
+    nomes_dos_campos: &'static [&'static str],
+    /// Linha SPED do registro: `|REG|CAMPO_02|...|CAMPO_N|`.
+    conteudo: String,
+}
+
+impl RegistroIdentificado {
+    /// Valores dos campos 02 em diante.
+    fn campos(&self) -> Vec<&str> {
+        let campos: Vec<&str> = self.conteudo.split('|').collect();
+        campos
+            .get(2..campos.len().saturating_sub(1))
+            .unwrap_or_default()
+            .to_vec()
+    }
+
+    /// Nome oficial do campo (ex: "VL_DOC"), a partir do índice em `campos()`.
+    fn nome_do_campo(&self, indice: usize) -> String {
+        match self.nomes_dos_campos.get(indice) {
+            Some(nome) => nome.to_uppercase(),
+            None => format!("CAMPO_{:02}", indice + 2),
+        }
+    }
+}
+
+/// Tipo da diferença entre as duas escriturações.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoDeDiferenca {
+    /// Registro presente apenas no arquivo posterior.
+    Incluido,
+    /// Registro presente apenas no arquivo anterior.
+    Excluido,
+    /// Registro presente nos dois arquivos com campos distintos.
+    Alterado,
+}
+
+impl fmt::Display for TipoDeDiferenca {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let descricao = match self {
+            Self::Incluido => "Incluído",
+            Self::Excluido => "Excluído",
+            Self::Alterado => "Alterado",
+        };
+        write!(f, "{descricao}")
+    }
+}
+
+/// Campo com valores distintos nas duas escriturações.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampoAlterado {
+    pub campo: String,
+    pub anterior: String,
+    pub posterior: String,
+}
+
+/// Diferença de um registro entre as duas escriturações.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiferencaDeRegistro {
+    pub registro: String,
+    pub identidade: String,
+    pub tipo: TipoDeDiferenca,
+    pub linha_anterior: Option<usize>,
+    pub linha_posterior: Option<usize>,
+    /// Campos alterados (apenas em `TipoDeDiferenca::Alterado`).
+    pub campos: Vec<CampoAlterado>,
+    /// Linha SPED do registro incluído ou excluído.
+    pub conteudo: String,
+}
+
+/// Valor do campo formatado segundo o leiaute da EFD.
+fn campo<T: ToSpedField>(valor: &T) -> String {
+    let mut buffer = String::new();
+    valor.write_sped_field(&mut buffer);
+    buffer
+}
+
+/// Valor numérico com zeros à esquerda (ex: NAT_BC_CRED "01").
+fn campo_com_zeros(valor: &Option<u16>, largura: usize) -> String {
+    let mut buffer = String::new();
+    write_sped_com_zeros(valor, largura, &mut buffer);
+    buffer
+}
+
+/// Período no formato `MMAAAA` (ex: PER_APU_CRED).
+fn campo_periodo(valor: &Option<NaiveDate>) -> String {
+    let mut buffer = String::new();
+    write_sped_periodo(valor, &mut buffer);
+    buffer
+}
+
+/// Identifica os registros do tipo `T`, na ordem das linhas.
+fn identificar<T, F>(
+    sped_file: &SpedFile,
+    registro: &str,
+    identidade: F,
+) -> Vec<RegistroIdentificado>
+where
+    T: SpedWriter + 'static + Sync,
+    F: Fn(&T) -> String,
+{
+    let mut registros: Vec<RegistroIdentificado> = sped_file
+        .obter_lista_registros::<T>(registro)
+        .into_iter()
+        .map(|r| RegistroIdentificado {
+            identidade: identidade(r),
+            linha: r.line_number(),
+            nomes_dos_campos: r.nomes_dos_campos(),
+            conteudo: r.to_sped_line(),
+        })
+        .collect();
+
+    // A leitura paralela não preserva a ordem das linhas, da qual dependem
+    // o registro pai dos filhos e a numeração das repetições.
+    registros.sort_by_key(|r| r.linha);
+
+    numerar_repeticoes(registros)
+}
+
+/// Identifica os registros filhos do tipo `T` precedidos pela identidade do registro pai
+/// (o último registro pai anterior à linha do filho).
+fn identificar_filhos<T, F>(
+    sped_file: &SpedFile,
+    registro: &str,
+    pais: &[RegistroIdentificado],
+    identidade: F,
+) -> Vec<RegistroIdentificado>
+where
+    T: SpedWriter + 'static + Sync,
+    F: Fn(&T) -> String,
+{
+    identificar(sped_file, registro, |r: &T| {
+        let indice = pais.partition_point(|pai| pai.linha < r.line_number());
+        let pai = match indice {
+            0 => "sem registro pai",
+            _ => &pais[indice - 1].identidade,
+        };
+        format!("{pai} > {}", identidade(r))
+    })
+}
+
+/// Acrescenta o sufixo " #n" às identidades repetidas no mesmo arquivo.
+fn numerar_repeticoes(mut registros: Vec<RegistroIdentificado>) -> Vec<RegistroIdentificado> {
+    let mut ocorrencias: HashMap<String, usize> = HashMap::new();
+
+    for registro in &mut registros {
+        let n = ocorrencias.entry(registro.identidade.clone()).or_default();
+        *n += 1;
+        if *n > 1 {
+            registro.identidade = format!("{} #{n}", registro.identidade);
+        }
+    }
+
+    registros
+}
+
+/// Identidade do documento: chave, se informada, ou indicador da operação,
+/// participante, modelo, série, subsérie e número.
+fn identidade_do_documento(chave: (&str, String), documento: &[(&str, String)]) -> String {
+    let (nome_da_chave, chave) = chave;
+
+    if !chave.is_empty() {
+        return format!("{nome_da_chave} {chave}");
+    }
+
+    documento
+        .iter()
+        .map(|(nome, valor)| format!("{nome} {valor}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Registros comparados pela identidade de negócio, na ordem do leiaute.
+fn identificar_registros(sped_file: &SpedFile) -> Vec<(&'static str, Vec<RegistroIdentificado>)> {
+    let r0150 = identificar(sped_file, "0150", |r: &Registro0150| {
+        format!("COD_PART {}", campo(&r.cod_part))
+    });
+
+    let a100 = identificar(sped_file, "A100", |r: &RegistroA100| {
+        identidade_do_documento(
+            ("CHV_NFSE", campo(&r.chv_nfse)),
+            &[
+                ("IND_OPER", campo(&r.ind_oper)),
+                ("COD_PART", campo(&r.cod_part)),
+                ("SER", campo(&r.ser)),
+                ("SUB", campo(&r.sub)),
+                ("NUM_DOC", campo(&r.num_doc)),
+            ],
+        )
+    });
+    let a170 = identificar_filhos(sped_file, "A170", &a100, |r: &RegistroA170| {
+        format!("NUM_ITEM {}", campo(&r.num_item))
+    });
+
+    let c100 = identificar(sped_file, "C100", |r: &RegistroC100| {
+        identidade_do_documento(
+            ("CHV_NFE", campo(&r.chv_nfe)),
+            &[
+                ("IND_OPER", campo(&r.ind_oper)),
+                ("COD_PART", campo(&r.cod_part)),
+                ("COD_MOD", campo(&r.cod_mod)),
+                ("SER", campo(&r.serie)),
+                ("NUM_DOC", campo(&r.num_doc)),
+            ],
+        )
+    });
+    let c170 = identificar_filhos(sped_file, "C170", &c100, |r: &RegistroC170| {
+        format!("NUM_ITEM {}", campo(&r.num_item))
+    });
+
+    let c180 = identificar(sped_file, "C180", |r: &RegistroC180| {
+        format!(
+            "COD_MOD {}, DT_DOC_INI {}, DT_DOC_FIN {}, COD_ITEM {}",
+            campo(&r.cod_mod),
+            campo(&r.dt_doc_ini),
+            campo(&r.dt_doc_fin),
+            campo(&r.cod_item)
+        )
+    });
+    let c181 = identificar_filhos(sped_file, "C181", &c180, |r: &RegistroC181| {
+        format!(
+            "CST_PIS {}, CFOP {}, ALIQ_PIS {}, COD_CTA {}",
+            campo(&r.cst_pis),
+            campo(&r.cfop),
+            campo(&r.aliq_pis),
+            campo(&r.cod_cta)
+        )
+    });
+    let c185 = identificar_filhos(sped_file, "C185", &c180, |r: &RegistroC185| {
+        format!(
+            "CST_COFINS {}, CFOP {}, ALIQ_COFINS {}, COD_CTA {}",
+            campo(&r.cst_cofins),
+            campo(&r.cfop),
+            campo(&r.aliq_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let c190 = identificar(sped_file, "C190", |r: &RegistroC190| {
+        format!(
+            "COD_MOD {}, DT_REF_INI {}, DT_REF_FIN {}, COD_ITEM {}",
+            campo(&r.cod_mod),
+            campo(&r.dt_ref_ini),
+            campo(&r.dt_ref_fin),
+            campo(&r.cod_item)
+        )
+    });
+    let c191 = identificar_filhos(sped_file, "C191", &c190, |r: &RegistroC191| {
+        format!(
+            "CNPJ_CPF_PART {}, CST_PIS {}, CFOP {}, ALIQ_PIS {}, COD_CTA {}",
+            campo(&r.cnpj_cpf_part),
+            campo(&r.cst_pis),
+            campo(&r.cfop),
+            campo(&r.aliq_pis),
+            campo(&r.cod_cta)
+        )
+    });
+    let c195 = identificar_filhos(sped_file, "C195", &c190, |r: &RegistroC195| {
+        format!(
+            "CNPJ_CPF_PART {}, CST_COFINS {}, CFOP {}, ALIQ_COFINS {}, COD_CTA {}",
+            campo(&r.cnpj_cpf_part),
+            campo(&r.cst_cofins),
+            campo(&r.cfop),
+            campo(&r.aliq_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let c500 = identificar(sped_file, "C500", |r: &RegistroC500| {
+        identidade_do_documento(
+            ("CHV_DOCE", campo(&r.chv_doce)),
+            &[
+                ("COD_PART", campo(&r.cod_part)),
+                ("COD_MOD", campo(&r.cod_mod)),
+                ("SER", campo(&r.ser)),
+                ("SUB", campo(&r.sub)),
+                ("NUM_DOC", campo(&r.num_doc)),
+            ],
+        )
+    });
+    let c501 = identificar_filhos(sped_file, "C501", &c500, |r: &RegistroC501| {
+        format!(
+            "CST_PIS {}, NAT_BC_CRED {}, ALIQ_PIS {}, COD_CTA {}",
+            campo(&r.cst_pis),
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.aliq_pis),
+            campo(&r.cod_cta)
+        )
+    });
+    let c505 = identificar_filhos(sped_file, "C505", &c500, |r: &RegistroC505| {
+        format!(
+            "CST_COFINS {}, NAT_BC_CRED {}, ALIQ_COFINS {}, COD_CTA {}",
+            campo(&r.cst_cofins),
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.aliq_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let d100 = identificar(sped_file, "D100", |r: &RegistroD100| {
+        identidade_do_documento(
+            ("CHV_CTE", campo(&r.chv_cte)),
+            &[
+                ("IND_OPER", campo(&r.ind_oper)),
+                ("COD_PART", campo(&r.cod_part)),
+                ("COD_MOD", campo(&r.cod_mod)),
+                ("SER", campo(&r.ser)),
+                ("SUB", campo(&r.sub)),
+                ("NUM_DOC", campo(&r.num_doc)),
+            ],
+        )
+    });
+
+    let d101 = identificar_filhos(sped_file, "D101", &d100, |r: &RegistroD101| {
+        format!(
+            "IND_NAT_FRT {}, CST_PIS {}, NAT_BC_CRED {}, ALIQ_PIS {}, COD_CTA {}",
+            campo(&r.ind_nat_frt),
+            campo(&r.cst_pis),
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.aliq_pis),
+            campo(&r.cod_cta)
+        )
+    });
+    let d105 = identificar_filhos(sped_file, "D105", &d100, |r: &RegistroD105| {
+        format!(
+            "IND_NAT_FRT {}, CST_COFINS {}, NAT_BC_CRED {}, ALIQ_COFINS {}, COD_CTA {}",
+            campo(&r.ind_nat_frt),
+            campo(&r.cst_cofins),
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.aliq_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let f100 = identificar(sped_file, "F100", |r: &RegistroF100| {
+        format!(
+            "IND_OPER {}, COD_PART {}, COD_ITEM {}, DT_OPER {}, NAT_BC_CRED {}",
+            campo(&r.ind_oper),
+            campo(&r.cod_part),
+            campo(&r.cod_item),
+            campo(&r.dt_oper),
+            campo_com_zeros(&r.nat_bc_cred, 2)
+        )
+    });
+    let f120 = identificar(sped_file, "F120", |r: &RegistroF120| {
+        format!(
+            "NAT_BC_CRED {}, IDENT_BEM_IMOB {}, IND_ORIG_CRED {}, IND_UTIL_BEM_IMOB {}, COD_CTA {}",
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.ident_bem_imob),
+            campo(&r.ind_orig_cred),
+            campo(&r.ind_util_bem_imob),
+            campo(&r.cod_cta)
+        )
+    });
+    let f130 = identificar(sped_file, "F130", |r: &RegistroF130| {
+        format!(
+            "NAT_BC_CRED {}, IDENT_BEM_IMOB {}, IND_ORIG_CRED {}, IND_UTIL_BEM_IMOB {}, \
+             MES_OPER_AQUIS {}, COD_CTA {}",
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.ident_bem_imob),
+            campo(&r.ind_orig_cred),
+            campo(&r.ind_util_bem_imob),
+            campo_periodo(&r.mes_oper_aquis),
+            campo(&r.cod_cta)
+        )
+    });
+    let f150 = identificar(sped_file, "F150", |r: &RegistroF150| {
+        format!(
+            "NAT_BC_CRED {}, CST_PIS {}, CST_COFINS {}, COD_CTA {}",
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.cst_pis),
+            campo(&r.cst_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let m100 = identificar(sped_file, "M100", |r: &RegistroM100| {
+        format!(
+            "COD_CRED {}, IND_CRED_ORI {}, ALIQ_PIS {}",
+            campo(&r.cod_cred),
+            campo(&r.ind_cred_ori),
+            campo(&r.aliq_pis)
+        )
+    });
+    let m105 = identificar_filhos(sped_file, "M105", &m100, |r: &RegistroM105| {
+        format!(
+            "NAT_BC_CRED {}, CST_PIS {}",
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.cst_pis)
+        )
+    });
+    let m200 = identificar(sped_file, "M200", |_: &RegistroM200| {
+        REGISTRO_UNICO.to_string()
+    });
+    let mut m210 = identificar(sped_file, "M210", |r: &RegistroM210| {
+        format!(
+            "COD_CONT {}, ALIQ_PIS {}",
+            campo(&r.cod_cont),
+            campo(&r.aliq_pis)
+        )
+    });
+    // Leiaute até 2018 (RegistroM210Antigo).
+    m210.extend(identificar(sped_file, "M210", |r: &RegistroM210Antigo| {
+        format!(
+            "COD_CONT {}, ALIQ_PIS {}",
+            campo(&r.cod_cont),
+            campo(&r.aliq_pis)
+        )
+    }));
+    let m400 = identificar(sped_file, "M400", |r: &RegistroM400| {
+        format!(
+            "CST_PIS {}, COD_CTA {}",
+            campo(&r.cst_pis),
+            campo(&r.cod_cta)
+        )
+    });
+    let m410 = identificar_filhos(sped_file, "M410", &m400, |r: &RegistroM410| {
+        format!(
+            "NAT_REC {}, COD_CTA {}",
+            campo(&r.nat_rec),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let m500 = identificar(sped_file, "M500", |r: &RegistroM500| {
+        format!(
+            "COD_CRED {}, IND_CRED_ORI {}, ALIQ_COFINS {}",
+            campo(&r.cod_cred),
+            campo(&r.ind_cred_ori),
+            campo(&r.aliq_cofins)
+        )
+    });
+    let m505 = identificar_filhos(sped_file, "M505", &m500, |r: &RegistroM505| {
+        format!(
+            "NAT_BC_CRED {}, CST_COFINS {}",
+            campo_com_zeros(&r.nat_bc_cred, 2),
+            campo(&r.cst_cofins)
+        )
+    });
+    let m600 = identificar(sped_file, "M600", |_: &RegistroM600| {
+        REGISTRO_UNICO.to_string()
+    });
+    let mut m610 = identificar(sped_file, "M610", |r: &RegistroM610| {
+        format!(
+            "COD_CONT {}, ALIQ_COFINS {}",
+            campo(&r.cod_cont),
+            campo(&r.aliq_cofins)
+        )
+    });
+    // Leiaute até 2018 (RegistroM610Antigo).
+    m610.extend(identificar(sped_file, "M610", |r: &RegistroM610Antigo| {
+        format!(
+            "COD_CONT {}, ALIQ_COFINS {}",
+            campo(&r.cod_cont),
+            campo(&r.aliq_cofins)
+        )
+    }));
+    let m800 = identificar(sped_file, "M800", |r: &RegistroM800| {
+        format!(
+            "CST_COFINS {}, COD_CTA {}",
+            campo(&r.cst_cofins),
+            campo(&r.cod_cta)
+        )
+    });
+    let m810 = identificar_filhos(sped_file, "M810", &m800, |r: &RegistroM810| {
+        format!(
+            "NAT_REC {}, COD_CTA {}",
+            campo(&r.nat_rec),
+            campo(&r.cod_cta)
+        )
+    });
+
+    let p010 = identificar(sped_file, "P010", |r: &RegistroP010| {
+        format!("CNPJ {}", campo(&r.cnpj))
+    });
+    let p100 = identificar_filhos(sped_file, "P100", &p010, |r: &RegistroP100| {
+        format!(
+            "DT_INI {}, DT_FIN {}, COD_ATIV_ECON {}, COD_CTA {}",
+            campo(&r.dt_ini),
+            campo(&r.dt_fin),
+            campo(&r.cod_ativ_econ),
+            campo(&r.cod_cta)
+        )
+    });
+    let p110 = identificar_filhos(sped_file, "P110", &p100, |r: &RegistroP110| {
+        format!(
+            "NUM_CAMPO {}, COD_DET {}",
+            campo(&r.num_campo),
+            campo(&r.cod_det)
+        )
+    });
+    let p199 = identificar_filhos(sped_file, "P199", &p100, |r: &RegistroP199| {
+        format!(
+            "NUM_PROC {}, IND_PROC {}",
+            campo(&r.num_proc),
+            campo(&r.ind_proc)
+        )
+    });
+    let p200 = identificar(sped_file, "P200", |r: &RegistroP200| {
+        format!(
+            "PER_REF {}, COD_REC {}",
+            campo(&r.per_ref),
+            campo(&r.cod_rec)
+        )
+    });
+    let p210 = identificar_filhos(sped_file, "P210", &p200, |r: &RegistroP210| {
+        format!(
+            "IND_AJ {}, COD_AJ {}, NUM_DOC {}, DT_REF {}",
+            campo(&r.ind_aj),
+            campo(&r.cod_aj),
+            campo(&r.num_doc),
+            campo(&r.dt_ref)
+        )
+    });
+
+    let r1100 = identificar(sped_file, "1100", |r: &Registro1100| {
+        format!(
+            "PER_APU_CRED {}, ORIG_CRED {}, CNPJ_SUC {}, COD_CRED {}",
+            campo_periodo(&r.per_apu_cred),
+            campo(&r.orig_cred),
+            campo(&r.cnpj_suc),
+            campo(&r.cod_cred)
+        )
+    });
+    let r1500 = identificar(sped_file, "1500", |r: &Registro1500| {
+        format!(
+            "PER_APU_CRED {}, ORIG_CRED {}, CNPJ_SUC {}, COD_CRED {}",
+            campo_periodo(&r.per_apu_cred),
+            campo(&r.orig_cred),
+            campo(&r.cnpj_suc),
+            campo(&r.cod_cred)
+        )
+    });
+
+    let r1300 = identificar(sped_file, "1300", |r: &Registro1300| {
+        format!(
+            "IND_NAT_RET {}, PR_REC_RET {}",
+            campo(&r.ind_nat_ret),
+            campo(&r.pr_rec_ret)
+        )
+    });
+    let r1700 = identificar(sped_file, "1700", |r: &Registro1700| {
+        format!(
+            "IND_NAT_RET {}, PR_REC_RET {}",
+            campo(&r.ind_nat_ret),
+            campo(&r.pr_rec_ret)
+        )
+    });
+
+    vec![
+        ("0150", r0150),
+        ("A100", a100),
+        ("A170", a170),
+        ("C100", c100),
+        ("C170", c170),
+        ("C180", c180),
+        ("C181", c181),
+        ("C185", c185),
+        ("C190", c190),
+        ("C191", c191),
+        ("C195", c195),
+        ("C500", c500),
+        ("C501", c501),
+        ("C505", c505),
+        ("D100", d100),
+        ("D101", d101),
+        ("D105", d105),
+        ("F100", f100),
+        ("F120", f120),
+        ("F130", f130),
+        ("F150", f150),
+        ("M100", m100),
+        ("M105", m105),
+        ("M200", m200),
+        ("M210", m210),
+        ("M400", m400),
+        ("M410", m410),
+        ("M500", m500),
+        ("M505", m505),
+        ("M600", m600),
+        ("M610", m610),
+        ("M800", m800),
+        ("M810", m810),
+        ("P010", p010),
+        ("P100", p100),
+        ("P110", p110),
+        ("P199", p199),
+        ("P200", p200),
+        ("P210", p210),
+        ("1100", r1100),
+        ("1300", r1300),
+        ("1500", r1500),
+        ("1700", r1700),
+    ]
+}
+
+/// Acrescenta aos `grupos` os registros do bloco sem identidade de negócio
+/// (ou não suportados pelo parser), exceto o X990.
+fn agrupar_por_registro<'a, R: SpedWriter>(
+    registros: &'a [R],
+    com_identidade: &HashSet<&str>,
+    grupos: &mut BTreeMap<&'a str, Vec<RegistroIdentificado>>,
+) {
+    for r in registros.iter().filter(|r| {
+        let nome = r.registro_name();
+        !nome.ends_with("990")
+            && (!com_identidade.contains(nome) || r.as_any().is::<RegistroGenerico>())
+    }) {
+        grupos
+            .entry(r.registro_name())
+            .or_default()
+            .push(RegistroIdentificado {
+                identidade: String::new(),
+                linha: r.line_number(),
+                nomes_dos_campos: r.nomes_dos_campos(),
+                conteudo: r.to_sped_line(),
+            });
+    }
+}
+
+/// Registros sem identidade de negócio, identificados pela ocorrência (1ª, 2ª, ...)
+/// na ordem das linhas. O Bloco 9 não é comparado.
+fn identificar_por_posicao<'a>(
+    sped_file: &'a SpedFile,
+    com_identidade: &HashSet<&str>,
+) -> BTreeMap<&'a str, Vec<RegistroIdentificado>> {
+    let mut grupos = BTreeMap::new();
+
+    agrupar_por_registro(&sped_file.bloco_0, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_a, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_c, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_d, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_f, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_i, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_m, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_p, com_identidade, &mut grupos);
+    agrupar_por_registro(&sped_file.bloco_1, com_identidade, &mut grupos);
+
+    for registros in grupos.values_mut() {
+        registros.sort_by_key(|r| r.linha);
+        for (n, registro) in registros.iter_mut().enumerate() {
+            registro.identidade = format!("ocorrência nº {}", n + 1);
+        }
+    }
+
+    grupos
+}
+
+/// Posição do registro no leiaute: sequência do bloco e código do registro.
+fn ordem_do_leiaute(registro: &str) -> (usize, String) {
+    let bloco = registro.chars().next().unwrap_or_default();
+    let posicao = ORDEM_DOS_BLOCOS
+        .find(bloco)
+        .unwrap_or(ORDEM_DOS_BLOCOS.len());
+    (posicao, registro.to_string())
+}
+
+/// Todos os registros comparados (pela identidade de negócio ou pela posição),
+/// na ordem do leiaute.
+fn registros_comparados(
+    sped_file: &SpedFile,
+) -> BTreeMap<(usize, String), Vec<RegistroIdentificado>> {
+    let mut comparados: BTreeMap<(usize, String), Vec<RegistroIdentificado>> = BTreeMap::new();
+
+    for (registro, registros) in identificar_registros(sped_file) {
+        comparados
+            .entry(ordem_do_leiaute(registro))
+            .or_default()
+            .extend(registros);
+    }
+
+    let com_identidade: HashSet<&str> = comparados.keys().map(|(_, r)| r.as_str()).collect();
+    for (registro, registros) in identificar_por_posicao(sped_file, &com_identidade) {
+        comparados
+            .entry(ordem_do_leiaute(registro))
+            .or_default()
+            .extend(registros);
+    }
+
+    comparados
+}
+
+/// Campos com valores distintos entre os dois registros.
+fn campos_alterados(
+    anterior: &RegistroIdentificado,
+    posterior: &RegistroIdentificado,
+) -> Vec<CampoAlterado> {
+    let campos_anteriores = anterior.campos();
+    let campos_posteriores = posterior.campos();
+    let quantidade = campos_anteriores.len().max(campos_posteriores.len());
+
+    (0..quantidade)
+        .filter_map(|indice| {
+            let valor_anterior = campos_anteriores.get(indice).copied().unwrap_or_default();
+            let valor_posterior = campos_posteriores.get(indice).copied().unwrap_or_default();
+
+            (valor_anterior != valor_posterior).then(|| CampoAlterado {
+                campo: posterior.nome_do_campo(indice),
+                anterior: valor_anterior.to_string(),
+                posterior: valor_posterior.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Compara duas escriturações pela identidade de negócio dos registros.
+///
+/// As diferenças são agrupadas por registro, na ordem do leiaute: os registros
+/// alterados e excluídos na ordem do arquivo anterior, seguidos dos incluídos
+/// na ordem do arquivo posterior.
+pub fn comparar_sped_files(anterior: &SpedFile, posterior: &SpedFile) -> Vec<DiferencaDeRegistro> {
+    let mut diferencas = Vec::new();
+
+    let mut registros_anteriores = registros_comparados(anterior);
+    let mut registros_posteriores = registros_comparados(posterior);
+
+    let chaves: BTreeSet<(usize, String)> = registros_anteriores
+        .keys()
+        .chain(registros_posteriores.keys())
+        .cloned()
+        .collect();
+
+    for chave in chaves {
+        let anteriores = registros_anteriores.remove(&chave).unwrap_or_default();
+        let posteriores = registros_posteriores.remove(&chave).unwrap_or_default();
+        let registro = chave.1;
+
+        let por_identidade: HashMap<&str, &RegistroIdentificado> = posteriores
+            .iter()
+            .map(|r| (r.identidade.as_str(), r))
+            .collect();
+
+        for reg_anterior in &anteriores {
+            match por_identidade.get(reg_anterior.identidade.as_str()) {
+                Some(reg_posterior) => {
+                    let campos = campos_alterados(reg_anterior, reg_posterior);
+                    if !campos.is_empty() {
+                        diferencas.push(DiferencaDeRegistro {
+                            registro: registro.clone(),
+                            identidade: reg_anterior.identidade.clone(),
+                            tipo: TipoDeDiferenca::Alterado,
+                            linha_anterior: Some(reg_anterior.linha),
+                            linha_posterior: Some(reg_posterior.linha),
+                            campos,
+                            conteudo: String::new(),
+                        });
+                    }
+                }
+                None => diferencas.push(DiferencaDeRegistro {
+                    registro: registro.clone(),
+                    identidade: reg_anterior.identidade.clone(),
+                    tipo: TipoDeDiferenca::Excluido,
+                    linha_anterior: Some(reg_anterior.linha),
+                    linha_posterior: None,
+                    campos: Vec::new(),
+                    conteudo: reg_anterior.conteudo.clone(),
+                }),
+            }
+        }
+
+        let identidades_anteriores: HashSet<&str> =
+            anteriores.iter().map(|r| r.identidade.as_str()).collect();
+
+        for reg_posterior in posteriores
+            .iter()
+            .filter(|r| !identidades_anteriores.contains(r.identidade.as_str()))
+        {
+            diferencas.push(DiferencaDeRegistro {
+                registro: registro.clone(),
+                identidade: reg_posterior.identidade.clone(),
+                tipo: TipoDeDiferenca::Incluido,
+                linha_anterior: None,
+                linha_posterior: Some(reg_posterior.linha),
+                campos: Vec::new(),
+                conteudo: reg_posterior.conteudo.clone(),
+            });
+        }
+    }
+
+    diferencas
+}
+
+/// Relatório das diferenças, agrupadas por registro.
+pub fn relatorio_comparacao(diferencas: &[DiferencaDeRegistro]) -> String {
+    if diferencas.is_empty() {
+        return "Nenhuma diferença encontrada nos registros comparados.\n\n".to_string();
+    }
+
+    let mut relatorio = String::new();
+
+    for grupo in diferencas.chunk_by(|a, b| a.registro == b.registro) {
+        let quantidade = |tipo: TipoDeDiferenca| grupo.iter().filter(|d| d.tipo == tipo).count();
+
+        let _ = writeln!(
+            relatorio,
+            "Registro {}: {} incluído(s), {} excluído(s), {} alterado(s).",
+            grupo[0].registro,
+            quantidade(TipoDeDiferenca::Incluido),
+            quantidade(TipoDeDiferenca::Excluido),
+            quantidade(TipoDeDiferenca::Alterado),
+        );
+
+        for diferenca in grupo {
+            let linhas = match (diferenca.linha_anterior, diferenca.linha_posterior) {
+                (Some(anterior), Some(posterior)) => format!("linhas nº {anterior} -> {posterior}"),
+                (Some(linha), None) | (None, Some(linha)) => format!("linha nº {linha}"),
+                (None, None) => String::new(),
+            };
+
+            let _ = writeln!(
+                relatorio,
+                "   {} [{linhas}]: {}",
+                diferenca.tipo, diferenca.identidade
+            );
+
+            match diferenca.tipo {
+                TipoDeDiferenca::Alterado => {
+                    for campo in &diferenca.campos {
+                        let _ = writeln!(
+                            relatorio,
+                            "      {}: '{}' -> '{}'",
+                            campo.campo, campo.anterior, campo.posterior
+                        );
+                    }
+                }
+                _ => {
+                    let _ = writeln!(relatorio, "      {}", diferenca.conteudo);
+                }
+            }
+        }
+
+        relatorio.push('\n');
+    }
+
+    relatorio
+}
+
+// ============================================================================
+// Modo --diff
+// ============================================================================
+
+/// Compara os dois arquivos selecionados (opção --diff) e imprime
+/// o relatório das diferenças.
+///
+/// Retorna o número de registros incluídos, excluídos ou alterados.
+pub fn comparar_arquivos(config: &AppConfig, write: &mut dyn Write) -> EFDResult<usize> {
+    let [arquivo_anterior, arquivo_posterior] = config.all_files.as_slice() else {
+        return EFDError::DiffRequiresTwoFiles.raise();
+    };
+
+    let multiprogressbar = MultiProgress::new();
+    let ler = |index: usize, arquivo: &PathBuf| {
+        read_and_parse_file(arquivo, &multiprogressbar, index, 2).map_loc(|error| {
+            EFDError::AnalyzeFileError {
+                source: Box::new(error),
+                arquivo: arquivo.clone(),
+            }
+        })
+    };
+
+    let anterior = ler(0, arquivo_anterior)?;
+    let posterior = ler(1, arquivo_posterior)?;
+
+    let diferencas = comparar_sped_files(&anterior, &posterior);
+
+    writeln!(
+        write,
+        "Comparação dos arquivos SPED EFD pela identidade dos registros:\n"
+    )?;
+    writeln!(write, "Anterior:  {}", arquivo_anterior.display())?;
+    writeln!(write, "Posterior: {}\n", arquivo_posterior.display())?;
+    write.write_all(relatorio_comparacao(&diferencas).as_bytes())?;
+    writeln!(
+        write,
+        "Total: {} registro(s) incluído(s), excluído(s) ou alterado(s).\n",
+        diferencas.len()
+    )?;
+
+    Ok(diferencas.len())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/comparacao_de_arquivos_tests.rs"]
+mod comparacao_de_arquivos_tests;
@@ -0,0 +1,321 @@
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+use crate::{
+    BlocoA, BlocoC, BlocoD, DECIMAL_VALOR, DecimalExt, Inconsistencia, SpedFile, SpedRecordTrait,
+};
+
+// ============================================================================
+// Conciliação dos totais dos documentos com os seus itens
+// ============================================================================
+
+/*
+Registro pai (documento) e registros filhos (itens) conciliados:
+
+| Documento | Itens       | Campos do documento                 | Campos dos itens                     |
+|-----------|-------------|-------------------------------------|--------------------------------------|
+| A100      | A170        | VL_DOC, VL_DESC, VL_PIS, VL_COFINS  | VL_ITEM, VL_DESC, VL_PIS, VL_COFINS  |
+| C100      | C170        | VL_MERC, VL_DESC, VL_PIS, VL_COFINS | VL_ITEM, VL_DESC, VL_PIS, VL_COFINS  |
+| D100      | D101 e D105 | VL_DOC                              | VL_ITEM                              |
+
+O VL_DOC do C100 inclui frete, seguro, outras despesas, IPI e ICMS-ST, que não constam
+do VL_ITEM do C170: o valor das mercadorias (VL_MERC) é conciliado em seu lugar.
+
+Cada tipo de registro filho é somado separadamente: D101 (PIS/PASEP) e D105 (COFINS)
+detalham o mesmo serviço de transporte. Documentos sem itens não são conciliados.
+*/
+
+/// Campos totalizados do documento (registro pai) ou dos seus itens (registros filhos).
+///
+/// Campos com valor `None` não foram informados ou não existem no registro.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TotaisDoDocumento {
+    /// Valor do documento (VL_DOC ou VL_MERC) ou VL_ITEM dos itens.
+    pub valor: Option<Decimal>,
+    /// VL_DESC.
+    pub desconto: Option<Decimal>,
+    /// VL_PIS.
+    pub pis: Option<Decimal>,
+    /// VL_COFINS.
+    pub cofins: Option<Decimal>,
+}
+
+impl TotaisDoDocumento {
+    /// Acumula os valores de um item. A soma de um campo é `None`
+    /// apenas se o campo não foi informado em nenhum dos itens.
+    fn somar(&mut self, item: &Self) {
+        fn somar_campo(total: &mut Option<Decimal>, valor: Option<Decimal>) {
+            if let Some(valor) = valor {
+                *total = Some(total.unwrap_or_default() + valor);
+            }
+        }
+
+        somar_campo(&mut self.valor, item.valor);
+        somar_campo(&mut self.desconto, item.desconto);
+        somar_campo(&mut self.pis, item.pis);
+        somar_campo(&mut self.cofins, item.cofins);
+    }
+
+    /// Pares (nome do campo no documento, nome do campo nos itens, valor no documento, soma dos itens).
+    ///
+    /// `campo_do_valor` é o nome do campo do documento confrontado com VL_ITEM.
+    fn campos(
+        &self,
+        itens: &Self,
+        campo_do_valor: &'static str,
+    ) -> [(&'static str, &'static str, Option<Decimal>, Option<Decimal>); 4] {
+        [
+            (campo_do_valor, "VL_ITEM", self.valor, itens.valor),
+            ("VL_DESC", "VL_DESC", self.desconto, itens.desconto),
+            ("VL_PIS", "VL_PIS", self.pis, itens.pis),
+            ("VL_COFINS", "VL_COFINS", self.cofins, itens.cofins),
+        ]
+    }
+}
+
+/// Soma dos itens de um mesmo tipo de registro filho sob o documento.
+struct SomaDosItens {
+    totais: TotaisDoDocumento,
+    primeira_linha: usize,
+    ultima_linha: usize,
+}
+
+impl SomaDosItens {
+    fn somar(&mut self, totais: &TotaisDoDocumento, linha: usize) {
+        self.totais.somar(totais);
+        self.ultima_linha = linha;
+    }
+}
+
+/// Documento (registro pai) em conciliação com os seus itens.
+struct Documento<'a> {
+    registro: &'a str,
+    /// Campo do documento confrontado com VL_ITEM (ex: "VL_DOC", "VL_MERC").
+    campo_do_valor: &'static str,
+    nivel: u16,
+    linha: usize,
+    totais: TotaisDoDocumento,
+    /// Registro filho (ex: "D101", "D105") -> soma dos itens.
+    itens: BTreeMap<&'a str, SomaDosItens>,
+}
+
+/// Soma os itens (VL_ITEM, VL_DESC, VL_PIS e VL_COFINS) dos registros A170, C170, D101 e D105
+/// e confronta com os totais (VL_DOC ou VL_MERC, VL_DESC, VL_PIS e VL_COFINS) dos documentos
+/// A100, C100 e D100.
+///
+/// Diferenças maiores que a `tolerancia` são retornadas na ordem das linhas, com a linha do
+/// documento e as linhas dos itens correspondentes.
+pub fn conciliar_totais(
+    sped_file: &SpedFile,
+    arquivo: &Path,
+    tolerancia: Decimal,
+) -> Vec<Inconsistencia> {
+    let mut inconsistencias = Vec::new();
+
+    conciliar_bloco(
+        &sped_file.bloco_a,
+        arquivo,
+        tolerancia,
+        "VL_DOC",
+        |registro| match registro {
+            BlocoA::RA100(r) => Some(TotaisDoDocumento {
+                valor: r.vl_doc,
+                desconto: r.vl_desc,
+                pis: r.vl_pis,
+                cofins: r.vl_cofins,
+            }),
+            _ => None,
+        },
+        |registro| match registro {
+            BlocoA::RA170(r) => Some(TotaisDoDocumento {
+                valor: r.vl_item,
+                desconto: r.vl_desc,
+                pis: r.vl_pis,
+                cofins: r.vl_cofins,
+            }),
+            _ => None,
+        },
+        &mut inconsistencias,
+    );
+
+    conciliar_bloco(
+        &sped_file.bloco_c,
+        arquivo,
+        tolerancia,
+        "VL_MERC",
+        |registro| match registro {
+            BlocoC::RC100(r) => Some(TotaisDoDocumento {
+                valor: r.vl_merc,
+                desconto: r.vl_desc,
+                pis: r.vl_pis,
+                cofins: r.vl_cofins,
+            }),
+            _ => None,
+        },
+        |registro| match registro {
+            BlocoC::RC170(r) => Some(TotaisDoDocumento {
+                valor: r.vl_item,
+                desconto: r.vl_desc,
+                pis: r.vl_pis,
+                cofins: r.vl_cofins,
+            }),
+            _ => None,
+        },
+        &mut inconsistencias,
+    );
+
+    conciliar_bloco(
+        &sped_file.bloco_d,
+        arquivo,
+        tolerancia,
+        "VL_DOC",
+        |registro| match registro {
+            BlocoD::RD100(r) => Some(TotaisDoDocumento {
+                valor: r.vl_doc,
+                ..Default::default()
+            }),
+            _ => None,
+        },
+        |registro| match registro {
+            BlocoD::RD101(r) => Some(TotaisDoDocumento {
+                valor: r.vl_item,
+                ..Default::default()
+            }),
+            BlocoD::RD105(r) => Some(TotaisDoDocumento {
+                valor: r.vl_item,
+                ..Default::default()
+            }),
+            _ => None,
+        },
+        &mut inconsistencias,
+    );
+
+    inconsistencias.sort_by_key(|i| i.linha);
+    inconsistencias
+}
+
+/// Percorre os registros do bloco na ordem das linhas, acumulando os itens sob o documento
+/// corrente até o próximo registro de nível igual ou superior ao do documento.
+///
+/// `documento` retorna os totais apenas para o registro pai e `item` apenas para os registros filhos.
+/// `campo_do_valor` nomeia o campo do documento confrontado com a soma de VL_ITEM.
+fn conciliar_bloco<T: SpedRecordTrait>(
+    registros: &[T],
+    arquivo: &Path,
+    tolerancia: Decimal,
+    campo_do_valor: &'static str,
+    documento: impl Fn(&T) -> Option<TotaisDoDocumento>,
+    item: impl Fn(&T) -> Option<TotaisDoDocumento>,
+    inconsistencias: &mut Vec<Inconsistencia>,
+) {
+    let mut registros: Vec<&T> = registros.iter().collect();
+    registros.sort_by_key(|r| r.line_number());
+
+    let mut corrente: Option<Documento> = None;
+
+    for registro in registros {
+        let nivel = registro.nivel();
+
+        if corrente.as_ref().is_some_and(|doc| nivel <= doc.nivel)
+            && let Some(doc) = corrente.take()
+        {
+            conciliar_documento(doc, arquivo, tolerancia, inconsistencias);
+        }
+
+        if let Some(totais) = documento(registro) {
+            corrente = Some(Documento {
+                registro: registro.registro_name(),
+                campo_do_valor,
+                nivel,
+                linha: registro.line_number(),
+                totais,
+                itens: BTreeMap::new(),
+            });
+            continue;
+        }
+
+        if let (Some(doc), Some(totais)) = (corrente.as_mut(), item(registro)) {
+            let linha = registro.line_number();
+            doc.itens
+                .entry(registro.registro_name())
+                .or_insert(SomaDosItens {
+                    totais: TotaisDoDocumento::default(),
+                    primeira_linha: linha,
+                    ultima_linha: linha,
+                })
+                .somar(&totais, linha);
+        }
+    }
+
+    if let Some(doc) = corrente {
+        conciliar_documento(doc, arquivo, tolerancia, inconsistencias);
+    }
+}
+
+/// Confronta os totais do documento com a soma de cada tipo de registro filho.
+fn conciliar_documento(
+    doc: Documento,
+    arquivo: &Path,
+    tolerancia: Decimal,
+    inconsistencias: &mut Vec<Inconsistencia>,
+) {
+    for (registro_filho, soma) in &doc.itens {
+        let linhas = match soma.primeira_linha == soma.ultima_linha {
+            true => format!("linha nº {}", soma.primeira_linha),
+            false => format!("linhas nº {} a {}", soma.primeira_linha, soma.ultima_linha),
+        };
+
+        for (campo_doc, campo_item, declarado, apurado) in
+            doc.totais.campos(&soma.totais, doc.campo_do_valor)
+        {
+            let (Some(declarado), Some(apurado)) = (declarado, apurado) else {
+                continue;
+            };
+
+            if (declarado - apurado).abs() > tolerancia {
+                inconsistencias.push(Inconsistencia::new(
+                    arquivo,
+                    doc.linha,
+                    doc.registro,
+                    format!(
+                        "{campo_doc} = {}, soma de {campo_item} dos registros {registro_filho} ({linhas}) = {}.",
+                        declarado.to_formatted_string(DECIMAL_VALOR),
+                        apurado.to_formatted_string(DECIMAL_VALOR),
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Resumo dos documentos com totais divergentes da soma dos seus itens
+/// (uma linha por divergência), ou String vazia se não houver divergências.
+pub fn relatorio_conciliacao_de_totais(inconsistencias: &[Inconsistencia]) -> String {
+    if inconsistencias.is_empty() {
+        return String::new();
+    }
+
+    let mut relatorio = format!(
+        "Totais dos documentos divergentes da soma dos itens: {} divergência(s).\n",
+        inconsistencias.len()
+    );
+
+    for inconsistencia in inconsistencias {
+        let _ = writeln!(
+            relatorio,
+            "   Registro {} [linha nº {}]: {}",
+            inconsistencia.registro, inconsistencia.linha, inconsistencia.descricao
+        );
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/conciliacao_de_totais_tests.rs"]
+mod conciliacao_de_totais_tests;
@@ -0,0 +1,290 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fmt::Write,
+};
+
+use crate::{Contribuicao, DECIMAL_VALOR, DecimalExt};
+
+// ============================================================================
+// Divergências e Saldos Controlados Mês a Mês
+// ============================================================================
+
+/*
+Estruturas comuns às conferências de valores declarados:
+- Divergencia: campo declarado divergente do valor esperado;
+- Verificador: acumula as divergências maiores que a tolerância;
+- ControleMensal e SaldoMensal: saldos acompanhados mês a mês entre escriturações
+  do mesmo CNPJ base (ex: Registros 1100/1500 e 1300/1700).
+*/
+
+/// Primeiro dia do mês da data.
+pub fn mes(data: NaiveDate) -> NaiveDate {
+    data.with_day(1).unwrap_or(data)
+}
+
+pub fn mes_anterior(data: NaiveDate) -> Option<NaiveDate> {
+    mes(data).checked_sub_months(Months::new(1))
+}
+
+pub fn mes_seguinte(data: NaiveDate) -> Option<NaiveDate> {
+    mes(data).checked_add_months(Months::new(1))
+}
+
+/// Mês no formato "MM/AAAA" ou "N/I" (não informado).
+pub fn mm_aaaa(data: Option<NaiveDate>) -> String {
+    data.map_or("N/I".to_string(), |d| d.format("%m/%Y").to_string())
+}
+
+/// Campo declarado divergente do valor esperado.
+///
+/// `origem` identifica no relatório onde a divergência foi encontrada
+/// (ex: escrituração, contribuição e crédito controlado).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergencia<O> {
+    pub origem: O,
+    /// Código do registro (ex: "1100", "M400", "P200").
+    pub registro: &'static str,
+    /// Número da linha do registro (`None` se o registro não foi informado).
+    pub linha: Option<usize>,
+    /// Nome do campo divergente (ex: "SLD_CRED_FIM", "VL_REC (NAT_REC 101)").
+    pub campo: String,
+    pub declarado: Decimal,
+    pub esperado: Decimal,
+    /// Origem do valor esperado (ex: "SLD_CRED_FIM de 11/2020").
+    pub referencia: String,
+}
+
+impl<O> fmt::Display for Divergencia<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.linha {
+            Some(linha) => write!(
+                f,
+                "{} [linha nº {linha}] {}: declarado {}, esperado {} ({})",
+                self.registro,
+                self.campo,
+                self.declarado.to_formatted_string(DECIMAL_VALOR),
+                self.esperado.to_formatted_string(DECIMAL_VALOR),
+                self.referencia,
+            ),
+            None => write!(
+                f,
+                "{} não informado: {} esperado {} ({})",
+                self.registro,
+                self.campo,
+                self.esperado.to_formatted_string(DECIMAL_VALOR),
+                self.referencia,
+            ),
+        }
+    }
+}
+
+/// Acumula as divergências maiores que a tolerância.
+#[derive(Debug)]
+pub struct Verificador<O> {
+    tolerancia: Decimal,
+    divergencias: Vec<Divergencia<O>>,
+}
+
+impl<O> Verificador<O> {
+    pub fn new(tolerancia: Decimal) -> Self {
+        Self {
+            tolerancia,
+            divergencias: Vec::new(),
+        }
+    }
+
+    pub fn tolerancia(&self) -> Decimal {
+        self.tolerancia
+    }
+
+    /// Registra a divergência se a diferença entre o declarado e o esperado for maior
+    /// que a tolerância.
+    pub fn conferir(
+        &mut self,
+        origem: O,
+        (registro, linha): (&'static str, Option<usize>),
+        campo: impl Into<String>,
+        (declarado, esperado): (Decimal, Decimal),
+        referencia: impl Into<String>,
+    ) {
+        if (declarado - esperado).abs() > self.tolerancia {
+            self.divergencias.push(Divergencia {
+                origem,
+                registro,
+                linha,
+                campo: campo.into(),
+                declarado,
+                esperado,
+                referencia: referencia.into(),
+            });
+        }
+    }
+
+    /// Valor esperado sem o registro correspondente na escrituração.
+    pub fn nao_informado(
+        &mut self,
+        origem: O,
+        registro: &'static str,
+        campo: impl Into<String>,
+        esperado: Decimal,
+        referencia: impl Into<String>,
+    ) {
+        self.conferir(
+            origem,
+            (registro, None),
+            campo,
+            (Decimal::ZERO, esperado),
+            referencia,
+        );
+    }
+
+    pub fn into_divergencias(self) -> Vec<Divergencia<O>> {
+        self.divergencias
+    }
+}
+
+/// Escrituração e saldo controlado (crédito ou retenção) em que a divergência foi encontrada.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ControleMensal {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração.
+    pub periodo: NaiveDate,
+    pub contribuicao: Contribuicao,
+    /// Identificação do saldo (ex: "COD_CRED 101 de 10/2020", "natureza 03 de 10/2020").
+    pub rotulo: String,
+}
+
+/// Registro de controle de um saldo informado mês a mês (ex: 1100/1500, 1300/1700).
+pub trait SaldoMensal {
+    /// Identificação do saldo ao longo dos meses.
+    type Chave: Ord;
+
+    fn chave(&self) -> Self::Chave;
+
+    /// Escrituração que informa o registro e identificação do saldo.
+    fn controle(&self) -> ControleMensal;
+
+    /// Período de apuração da escrituração que informa o registro.
+    fn periodo(&self) -> NaiveDate;
+
+    /// Código do registro e número da linha.
+    fn registro(&self) -> (&'static str, usize);
+}
+
+/// Saldo -> período da escrituração -> registro.
+pub type Razao<'a, S> = BTreeMap<<S as SaldoMensal>::Chave, BTreeMap<NaiveDate, &'a S>>;
+
+/// Agrupa os registros de controle por saldo e período da escrituração.
+pub fn razao<S: SaldoMensal>(saldos: &[S]) -> Razao<'_, S> {
+    let mut razao: Razao<'_, S> = BTreeMap::new();
+    for saldo in saldos {
+        razao
+            .entry(saldo.chave())
+            .or_default()
+            .insert(saldo.periodo(), saldo);
+    }
+    razao
+}
+
+/// Escriturações analisadas: CNPJ base e mês do período de apuração.
+pub fn escrituracoes_analisadas(escrituracoes: &[(u32, NaiveDate)]) -> BTreeSet<(u32, NaiveDate)> {
+    escrituracoes
+        .iter()
+        .map(|&(cnpj_base, periodo)| (cnpj_base, mes(periodo)))
+        .collect()
+}
+
+impl Verificador<ControleMensal> {
+    /// Confere um campo do registro de controle.
+    pub fn conferir_registro<S: SaldoMensal>(
+        &mut self,
+        saldo: &S,
+        campo: &'static str,
+        valores: (Decimal, Decimal),
+        referencia: impl Into<String>,
+    ) {
+        let (registro, linha) = saldo.registro();
+        self.conferir(
+            saldo.controle(),
+            (registro, Some(linha)),
+            campo,
+            valores,
+            referencia,
+        );
+    }
+
+    /// Registro de um mês x registro do mesmo saldo no mês seguinte.
+    ///
+    /// `campos`: (campo do mês seguinte, valor declarado, campo do mês anterior, valor anterior).
+    pub fn conferir_transporte<S: SaldoMensal>(
+        &mut self,
+        anterior: &S,
+        seguinte: &S,
+        campos: &[(&'static str, Decimal, &'static str, Decimal)],
+    ) {
+        let periodo = mm_aaaa(Some(anterior.periodo()));
+        for &(campo, declarado, campo_anterior, valor_anterior) in campos {
+            self.conferir_registro(
+                seguinte,
+                campo,
+                (declarado, valor_anterior),
+                format!("{campo_anterior} de {periodo}"),
+            );
+        }
+    }
+
+    /// Divergências por CNPJ base, período, contribuição e linha.
+    pub fn divergencias_ordenadas(self) -> Vec<Divergencia<ControleMensal>> {
+        let mut divergencias = self.divergencias;
+        divergencias.sort_by_key(|d| {
+            (
+                d.origem.cnpj_base,
+                d.origem.periodo,
+                d.origem.contribuicao,
+                d.linha,
+            )
+        });
+        divergencias
+    }
+}
+
+/// Divergências agrupadas por escrituração, contribuição e saldo controlado,
+/// ou String vazia se não houver divergências.
+pub fn relatorio_divergencias_mensais(
+    titulo: &str,
+    divergencias: &[Divergencia<ControleMensal>],
+) -> String {
+    if divergencias.is_empty() {
+        return String::new();
+    }
+
+    let mut grupos: BTreeMap<&ControleMensal, Vec<&Divergencia<ControleMensal>>> = BTreeMap::new();
+    for divergencia in divergencias {
+        grupos
+            .entry(&divergencia.origem)
+            .or_default()
+            .push(divergencia);
+    }
+
+    let mut relatorio = format!("{titulo}: {} divergência(s).\n", divergencias.len());
+
+    for (controle, divergencias) in grupos {
+        let _ = writeln!(
+            relatorio,
+            "   CNPJ base {:08}, período {}, {}, {}:",
+            controle.cnpj_base,
+            mm_aaaa(Some(controle.periodo)),
+            controle.contribuicao.descricao(),
+            controle.rotulo
+        );
+        for divergencia in divergencias {
+            let _ = writeln!(relatorio, "      {divergencia}");
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
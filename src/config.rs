@@ -1,8 +1,9 @@
-use crate::{EFDError, EfdRaise, ExcelMemoryMode};
+use crate::{EFDError, EfdRaise, ExcelMemoryMode, TOLERANCIA_DO_CALCULO, search_archive_entries};
 use crate::{EFDResult, args::Arguments};
 use claudiofsr_lib::Colors;
 use colored::*;
 use glob::{MatchOptions, glob_with};
+use rust_decimal::Decimal;
 use std::cmp::Ordering;
 use std::io::Write;
 use std::path::PathBuf;
@@ -10,6 +11,7 @@ use std::path::PathBuf;
 pub const OUTPUT_DIRECTORY: &str = "novo";
 pub const BASE_NAME: &str = "Info do Contribuinte EFD Contribuicoes";
 const EFD_PATTERN: &str = "**/PISCOFINS_[0-9][0-9]*_[0-9][0-9]*.txt";
+const EFD_FILE_NAME: &str = "PISCOFINS_[0-9][0-9]*_[0-9][0-9]*.txt";
 
 /// Configuração central da aplicação.
 ///
@@ -23,12 +25,18 @@ pub struct AppConfig {
     /// Nome do Programa extraído do binário
     pub app_name: String,
 
+    /// Se true, apenas verifica a assinatura digital dos arquivos (modo --assinatura).
+    pub assinatura: bool,
+
     /// Nome base para os arquivos de saída (sem extensão).
     pub base_name: String,
 
     /// Ativa logs detalhados de depuração.
     pub debug: bool,
 
+    /// Se true, apenas compara os dois arquivos selecionados (modo --diff).
+    pub diff: bool,
+
     /// Se true, limita o rateio aos CSTs 01 a 09.
     pub excluir_cst_49: bool,
 
@@ -36,6 +44,12 @@ pub struct AppConfig {
     /// Se true, remove itens de saída do relatório final.
     pub excluir_saidas: bool,
 
+    /// Se true, registra os erros de leitura e prossegue com as demais linhas (modo --leniente).
+    pub leniente: bool,
+
+    /// Se true, mantém na análise as escriturações substituídas por retificadoras.
+    pub manter_retificadas: bool,
+
     /// Modo de consumo de memória selecionado para o Excel.
     pub memory_mode: ExcelMemoryMode,
 
@@ -51,6 +65,12 @@ pub struct AppConfig {
 
     /// Se true, gera o arquivo .csv.
     pub print_csv: bool,
+
+    /// Diferença aceita nos recálculos de PIS/COFINS e na conciliação dos totais dos documentos.
+    pub tolerancia: Decimal,
+
+    /// Se true, apenas valida a estrutura dos arquivos (modo --validar).
+    pub validar: bool,
 }
 
 /// Implementação manual do Default para suportar valores customizados
@@ -59,15 +79,21 @@ impl Default for AppConfig {
         Self {
             all_files: Vec::new(),
             app_name: String::new(),
+            assinatura: false,
             base_name: BASE_NAME.to_string(),
             debug: false,
+            diff: false,
             excluir_cst_49: false,
             excluir_saidas: false,
+            leniente: false,
+            manter_retificadas: false,
             memory_mode: ExcelMemoryMode::default(),
             no_excel: false,
             operacoes_de_creditos: false,
             output_dir: PathBuf::from(OUTPUT_DIRECTORY),
             print_csv: false,
+            tolerancia: TOLERANCIA_DO_CALCULO,
+            validar: false,
         }
     }
 }
@@ -100,18 +126,27 @@ impl AppConfig {
         Ok(Self {
             app_name: args.get_app_name(),
             all_files: found_files,
+            assinatura: args.assinatura,
             debug: args.debug,
+            diff: args.diff,
             excluir_saidas: args.excluir_saidas,
             excluir_cst_49: args.excluir_cst_49,
+            leniente: args.leniente,
+            manter_retificadas: args.manter_retificadas,
             memory_mode: args.memory_mode,
             no_excel: args.no_excel,
             operacoes_de_creditos: args.operacoes_de_creditos,
             print_csv: args.print_csv,
+            tolerancia: args.tolerancia,
+            validar: args.validar,
             ..Self::default() // output_dir e base_name vêm do impl Default acima
         })
     }
 
     /// Executa a busca por arquivos TXT usando o padrão global.
+    ///
+    /// Inclui os arquivos EFD contidos em arquivos compactados (.zip e .gz),
+    /// identificados pelo caminho virtual `arquivo.zip!/PISCOFINS_...txt`.
     fn search_files(pattern: &str) -> EFDResult<Vec<PathBuf>> {
         let options = MatchOptions {
             case_sensitive: false,
@@ -119,7 +154,9 @@ impl AppConfig {
             require_literal_leading_dot: false,
         };
 
-        let paths: Vec<PathBuf> = glob_with(pattern, options)?.flatten().collect();
+        let mut paths: Vec<PathBuf> = glob_with(pattern, options)?.flatten().collect();
+        paths.extend(search_archive_entries(EFD_FILE_NAME, options)?);
+        paths.sort();
 
         Ok(paths)
     }
@@ -248,4 +285,11 @@ impl AppConfig {
     pub fn path_xlsx(&self) -> PathBuf {
         self.output_dir.join(&self.base_name).with_extension("xlsx")
     }
+
+    /// CSV com os erros de leitura do modo leniente (`<base_name> - Erros de Leitura.csv`).
+    pub fn path_erros_csv(&self) -> PathBuf {
+        self.output_dir
+            .join(format!("{} - Erros de Leitura", self.base_name))
+            .with_extension("csv")
+    }
 }
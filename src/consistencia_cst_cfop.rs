@@ -0,0 +1,196 @@
+use claudiofsr_lib::CFOP_DE_EXPORTACAO;
+use std::{fmt, fmt::Write, sync::Arc};
+
+use crate::{CodigoSituacaoTributaria, DocsFiscais, TipoDeCredito, TipoDeOperacao, is_importacao};
+
+// ============================================================================
+// Consistência entre CST, CFOP e Tipo de Operação
+// ============================================================================
+
+/*
+O primeiro dígito do CFOP indica o sentido da operação:
+- 1, 2 e 3: entradas (3 = importação);
+- 5, 6 e 7: saídas (7 = exportação).
+
+O Tipo de Operação (Entrada ou Saída) é obtido do CST. Cada regra identifica uma
+combinação incoerente dos três campos. Apenas os itens de Entrada ou Saída com
+CST e CFOP informados são verificados.
+*/
+
+/// Regra de consistência entre CST, CFOP e Tipo de Operação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RegraCstCfop {
+    /// CST de crédito (50 a 66) com CFOP de saída.
+    CreditoEmCfopDeSaida,
+    /// CST de receita (01 a 49) com CFOP de entrada.
+    ReceitaEmCfopDeEntrada,
+    /// CFOP de importação sem CST de entrada ou com crédito de mercado interno.
+    ImportacaoSemCstDeImportacao,
+    /// Saída com CFOP de exportação sem CST 08 ou 09.
+    ExportacaoSemCstDeExportacao,
+}
+
+impl RegraCstCfop {
+    /// Regras verificadas, na ordem de apresentação.
+    pub const TODAS: [Self; 4] = [
+        Self::CreditoEmCfopDeSaida,
+        Self::ReceitaEmCfopDeEntrada,
+        Self::ImportacaoSemCstDeImportacao,
+        Self::ExportacaoSemCstDeExportacao,
+    ];
+
+    pub const fn codigo(&self) -> &'static str {
+        match self {
+            Self::CreditoEmCfopDeSaida => "R1",
+            Self::ReceitaEmCfopDeEntrada => "R2",
+            Self::ImportacaoSemCstDeImportacao => "R3",
+            Self::ExportacaoSemCstDeExportacao => "R4",
+        }
+    }
+
+    pub const fn descricao(&self) -> &'static str {
+        match self {
+            Self::CreditoEmCfopDeSaida => {
+                "CST de crédito (50 a 66) com CFOP de saída (5xxx, 6xxx ou 7xxx)"
+            }
+            Self::ReceitaEmCfopDeEntrada => {
+                "CST de receita (01 a 49) com CFOP de entrada (1xxx, 2xxx ou 3xxx)"
+            }
+            Self::ImportacaoSemCstDeImportacao => {
+                "CFOP de importação (3xxx) sem CST de entrada (50 a 99) ou com crédito de mercado interno"
+            }
+            Self::ExportacaoSemCstDeExportacao => {
+                "saída com CFOP de exportação sem CST 08 (sem incidência) ou 09 (suspensão)"
+            }
+        }
+    }
+
+    /// Verifica se o item viola a regra.
+    pub fn violada(&self, doc: &DocsFiscais) -> bool {
+        let (Some(cst), Some(cfop)) = (doc.cst, doc.cfop) else {
+            return false;
+        };
+
+        let codigo = cst.code();
+
+        match self {
+            Self::CreditoEmCfopDeSaida => cst.eh_base_de_credito() && cfop_de_saida(cfop),
+            Self::ReceitaEmCfopDeEntrada => (1..=49).contains(&codigo) && cfop_de_entrada(cfop),
+            Self::ImportacaoSemCstDeImportacao => {
+                is_importacao(cfop)
+                    && (codigo < 50
+                        || (cst.eh_base_de_credito()
+                            && doc
+                                .tipo_de_credito
+                                .is_some_and(|t| t != TipoDeCredito::Importacao)))
+            }
+            Self::ExportacaoSemCstDeExportacao => {
+                doc.tipo_de_operacao == Some(TipoDeOperacao::Saida)
+                    && CFOP_DE_EXPORTACAO.binary_search(&cfop).is_ok()
+                    && !matches!(codigo, 8 | 9)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RegraCstCfop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Regra {} ({})", self.codigo(), self.descricao())
+    }
+}
+
+fn cfop_de_entrada(cfop: u16) -> bool {
+    matches!(cfop / 1000, 1..=3)
+}
+
+fn cfop_de_saida(cfop: u16) -> bool {
+    matches!(cfop / 1000, 5..=7)
+}
+
+/// Item com combinação incoerente de CST, CFOP e Tipo de Operação.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcorrenciaCstCfop {
+    pub regra: RegraCstCfop,
+    /// Número da linha do item no arquivo SPED EFD.
+    pub linha: usize,
+    pub registro: Arc<str>,
+    pub cst: CodigoSituacaoTributaria,
+    pub cfop: u16,
+}
+
+impl fmt::Display for OcorrenciaCstCfop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Registro {} [linha nº {}]: CST {:02}, CFOP {:04}",
+            self.registro,
+            self.linha,
+            self.cst.code(),
+            self.cfop
+        )
+    }
+}
+
+/// Aplica as regras de consistência aos itens de Entrada ou Saída.
+///
+/// As ocorrências são retornadas na ordem das linhas (e das regras, na mesma linha).
+pub fn verificar_cst_cfop(docs: &[DocsFiscais]) -> Vec<OcorrenciaCstCfop> {
+    let mut ocorrencias: Vec<OcorrenciaCstCfop> = docs
+        .iter()
+        .filter(|doc| doc.operacoes_de_entrada_ou_saida())
+        .flat_map(|doc| {
+            RegraCstCfop::TODAS
+                .into_iter()
+                .filter(|regra| regra.violada(doc))
+                .filter_map(|regra| {
+                    Some(OcorrenciaCstCfop {
+                        regra,
+                        linha: doc.num_linha_efd.unwrap_or_default(),
+                        registro: doc.registro.clone(),
+                        cst: doc.cst?,
+                        cfop: doc.cfop?,
+                    })
+                })
+        })
+        .collect();
+
+    ocorrencias.sort_by_key(|o| (o.linha, o.regra));
+    ocorrencias
+}
+
+/// Resumo das combinações incoerentes de CST e CFOP agrupadas por regra
+/// (uma linha por item), ou String vazia se não houver ocorrências.
+pub fn relatorio_cst_cfop(ocorrencias: &[OcorrenciaCstCfop]) -> String {
+    if ocorrencias.is_empty() {
+        return String::new();
+    }
+
+    let mut relatorio = format!(
+        "Combinações incoerentes de CST, CFOP e tipo de operação: {} ocorrência(s).\n",
+        ocorrencias.len()
+    );
+
+    for regra in RegraCstCfop::TODAS {
+        let mut da_regra = ocorrencias.iter().filter(|o| o.regra == regra).peekable();
+
+        if da_regra.peek().is_none() {
+            continue;
+        }
+
+        let _ = writeln!(relatorio, "   {regra}:");
+        for ocorrencia in da_regra {
+            let _ = writeln!(relatorio, "      {ocorrencia}");
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/consistencia_cst_cfop_tests.rs"]
+mod consistencia_cst_cfop_tests;
@@ -0,0 +1,340 @@
+use chrono::{Datelike, Months, NaiveDate};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    iter,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{EFDResult, Registro0000, Registro0120, SpedReader};
+
+// ============================================================================
+// Continuidade dos Períodos de Apuração
+// ============================================================================
+
+/*
+Antes de analisar os créditos de vários anos, é preciso saber quais escriturações
+estão ausentes. Para cada CNPJ base (8 primeiros dígitos), os períodos de apuração
+dos arquivos selecionados são verificados do primeiro ao último mês:
+- meses ausentes: sem arquivo e não dispensados da escrituração;
+- meses dispensados: informados no Registro 0120 (MES_REFER) de algum arquivo;
+- meses em duplicidade: mais de um arquivo para o mesmo mês;
+- períodos irregulares: DT_INI e DT_FIN (Registro 0000) que não correspondem a um
+  mês completo (exceto em situação especial, IND_SIT_ESP) ou que divergem do
+  período indicado no nome do arquivo (PISCOFINS_AAAAMMDD_AAAAMMDD_...).
+*/
+
+/// Período de apuração declarado no Registro 0000 e períodos dispensados (Registro 0120).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeriodoEscriturado {
+    pub arquivo: PathBuf,
+    pub cnpj_base: Arc<str>,
+    pub dt_ini: NaiveDate,
+    pub dt_fin: NaiveDate,
+    /// Indicador de situação especial (abertura, cisão, fusão, incorporação ou encerramento).
+    pub situacao_especial: bool,
+    /// Meses dispensados da escrituração (MES_REFER), no primeiro dia do mês.
+    pub periodos_dispensados: Vec<NaiveDate>,
+}
+
+impl PeriodoEscriturado {
+    /// Lê o Registro 0000 e os Registros 0120 do arquivo (apenas o Bloco 0).
+    ///
+    /// Retorna `Ok(None)` se o arquivo não contém o Registro 0000.
+    pub fn ler(arquivo: &Path) -> EFDResult<Option<Self>> {
+        let mut periodo: Option<Self> = None;
+
+        for record in SpedReader::open(arquivo)?.with_registros(&["0000", "0120", "0990"]) {
+            let record = record?;
+
+            if let Ok(registro) = record.downcast_ref::<Registro0000>() {
+                periodo = Some(Self {
+                    arquivo: arquivo.to_path_buf(),
+                    cnpj_base: registro.get_cnpj_base(),
+                    dt_ini: registro.dt_ini,
+                    dt_fin: registro.dt_fin,
+                    situacao_especial: registro.ind_sit_esp.is_some(),
+                    periodos_dispensados: Vec::new(),
+                });
+            } else if let Ok(registro) = record.downcast_ref::<Registro0120>() {
+                let dispensado = registro.mes_refer.as_deref().and_then(mes_de_referencia);
+                if let (Some(periodo), Some(mes)) = (periodo.as_mut(), dispensado) {
+                    periodo.periodos_dispensados.push(mes);
+                }
+            } else {
+                // Registro 0990: encerramento do Bloco 0.
+                break;
+            }
+        }
+
+        Ok(periodo)
+    }
+
+    /// Mês do período de apuração (primeiro dia do mês de DT_INI).
+    pub fn periodo_de_apuracao(&self) -> NaiveDate {
+        primeiro_dia(self.dt_ini)
+    }
+
+    /// Motivos pelos quais o período declarado no Registro 0000 é irregular.
+    fn irregularidades(&self) -> Vec<String> {
+        let mut motivos = Vec::new();
+
+        if self.dt_fin < self.dt_ini {
+            motivos.push(format!(
+                "DT_FIN ({}) anterior a DT_INI ({})",
+                data(self.dt_fin),
+                data(self.dt_ini)
+            ));
+        } else if primeiro_dia(self.dt_fin) != self.periodo_de_apuracao() {
+            motivos.push(format!(
+                "período de {} a {} abrange mais de um mês",
+                data(self.dt_ini),
+                data(self.dt_fin)
+            ));
+        } else if !self.situacao_especial
+            && (self.dt_ini.day() != 1 || self.dt_fin != ultimo_dia(self.dt_fin))
+        {
+            motivos.push(format!(
+                "período parcial de {} a {} sem situação especial (IND_SIT_ESP)",
+                data(self.dt_ini),
+                data(self.dt_fin)
+            ));
+        }
+
+        if let Some((inicio, fim)) = periodo_do_nome(&self.arquivo)
+            && (inicio, fim) != (self.dt_ini, self.dt_fin)
+        {
+            motivos.push(format!(
+                "o nome do arquivo indica o período de {} a {}, o Registro 0000 informa de {} a {}",
+                data(inicio),
+                data(fim),
+                data(self.dt_ini),
+                data(self.dt_fin)
+            ));
+        }
+
+        motivos
+    }
+}
+
+/// MES_REFER no formato `MMAAAA`.
+fn mes_de_referencia(mes_refer: &str) -> Option<NaiveDate> {
+    let mes: u32 = mes_refer.get(0..2)?.parse().ok()?;
+    let ano: i32 = mes_refer.get(2..6)?.parse().ok()?;
+    NaiveDate::from_ymd_opt(ano, mes, 1)
+}
+
+/// Período indicado no nome gerado pelo PVA: `PISCOFINS_AAAAMMDD_AAAAMMDD_...`.
+fn periodo_do_nome(arquivo: &Path) -> Option<(NaiveDate, NaiveDate)> {
+    let nome = arquivo.file_name()?.to_str()?;
+    let mut partes = nome.strip_prefix("PISCOFINS_")?.split(['_', '.']);
+
+    let inicio = NaiveDate::parse_from_str(partes.next()?, "%Y%m%d").ok()?;
+    let fim = NaiveDate::parse_from_str(partes.next()?, "%Y%m%d").ok()?;
+
+    Some((inicio, fim))
+}
+
+fn primeiro_dia(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn ultimo_dia(date: NaiveDate) -> NaiveDate {
+    primeiro_dia(date)
+        .checked_add_months(Months::new(1))
+        .and_then(|proximo| proximo.pred_opt())
+        .unwrap_or(date)
+}
+
+fn data(date: NaiveDate) -> String {
+    date.format("%d/%m/%Y").to_string()
+}
+
+fn mes(date: NaiveDate) -> String {
+    format!("{:02}/{}", date.month(), date.year())
+}
+
+/// Arquivo cujo período declarado no Registro 0000 é irregular.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodoIrregular {
+    pub arquivo: PathBuf,
+    pub motivo: String,
+}
+
+/// Continuidade dos períodos de apuração de um CNPJ base.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContinuidadeDosPeriodos {
+    pub cnpj_base: Arc<str>,
+    /// Primeiro e último mês escriturados.
+    pub inicio: NaiveDate,
+    pub fim: NaiveDate,
+    /// Quantidade de meses com ao menos um arquivo.
+    pub meses_escriturados: usize,
+    /// Meses sem arquivo e sem dispensa (Registro 0120).
+    pub meses_ausentes: Vec<NaiveDate>,
+    /// Meses sem arquivo dispensados da escrituração (Registro 0120).
+    pub meses_dispensados: Vec<NaiveDate>,
+    /// Meses com mais de um arquivo.
+    pub meses_duplicados: Vec<(NaiveDate, Vec<PathBuf>)>,
+    pub periodos_irregulares: Vec<PeriodoIrregular>,
+}
+
+impl ContinuidadeDosPeriodos {
+    /// Não há lacunas, duplicidades ou períodos irregulares.
+    pub fn regular(&self) -> bool {
+        self.meses_ausentes.is_empty()
+            && self.meses_duplicados.is_empty()
+            && self.periodos_irregulares.is_empty()
+    }
+
+    /// Quantidade de meses do primeiro ao último período escriturado.
+    fn total_de_meses(&self) -> usize {
+        let meses = |date: NaiveDate| date.year() as usize * 12 + date.month0() as usize;
+        meses(self.fim) - meses(self.inicio) + 1
+    }
+}
+
+/// Verifica a continuidade dos períodos de apuração de cada CNPJ base.
+///
+/// Os meses dispensados (Registro 0120) são considerados para todos os arquivos
+/// do mesmo CNPJ base, pois a dispensa é informada na escrituração seguinte.
+pub fn verificar_continuidade(periodos: &[PeriodoEscriturado]) -> Vec<ContinuidadeDosPeriodos> {
+    let mut grupos: BTreeMap<&str, Vec<&PeriodoEscriturado>> = BTreeMap::new();
+    for periodo in periodos {
+        grupos.entry(&periodo.cnpj_base).or_default().push(periodo);
+    }
+
+    grupos
+        .into_iter()
+        .filter_map(|(cnpj_base, grupo)| {
+            let mut arquivos_por_mes: BTreeMap<NaiveDate, Vec<PathBuf>> = BTreeMap::new();
+            for periodo in &grupo {
+                arquivos_por_mes
+                    .entry(periodo.periodo_de_apuracao())
+                    .or_default()
+                    .push(periodo.arquivo.clone());
+            }
+
+            let dispensados: BTreeSet<NaiveDate> = grupo
+                .iter()
+                .flat_map(|periodo| periodo.periodos_dispensados.iter().copied())
+                .collect();
+
+            let inicio = *arquivos_por_mes.keys().next()?;
+            let fim = *arquivos_por_mes.keys().next_back()?;
+
+            let (meses_dispensados, meses_ausentes): (Vec<NaiveDate>, Vec<NaiveDate>) =
+                iter::successors(Some(inicio), |date| date.checked_add_months(Months::new(1)))
+                    .take_while(|date| *date <= fim)
+                    .filter(|date| !arquivos_por_mes.contains_key(date))
+                    .partition(|date| dispensados.contains(date));
+
+            let periodos_irregulares = grupo
+                .iter()
+                .flat_map(|periodo| {
+                    periodo
+                        .irregularidades()
+                        .into_iter()
+                        .map(|motivo| PeriodoIrregular {
+                            arquivo: periodo.arquivo.clone(),
+                            motivo,
+                        })
+                })
+                .collect();
+
+            Some(ContinuidadeDosPeriodos {
+                cnpj_base: cnpj_base.into(),
+                inicio,
+                fim,
+                meses_escriturados: arquivos_por_mes.len(),
+                meses_ausentes,
+                meses_dispensados,
+                meses_duplicados: arquivos_por_mes
+                    .into_iter()
+                    .filter(|(_, arquivos)| arquivos.len() > 1)
+                    .collect(),
+                periodos_irregulares,
+            })
+        })
+        .collect()
+}
+
+/// Relatório da continuidade dos períodos de cada CNPJ base.
+///
+/// Um CNPJ base com um único arquivo regular não é informado.
+pub fn relatorio_continuidade(continuidades: &[ContinuidadeDosPeriodos]) -> String {
+    let mut relatorio = String::new();
+
+    for continuidade in continuidades {
+        if continuidade.total_de_meses() == 1 && continuidade.regular() {
+            continue;
+        }
+
+        let _ = writeln!(
+            relatorio,
+            "Continuidade dos períodos de apuração (CNPJ base {}): {} a {}, {} de {} mês(es) escriturado(s).",
+            continuidade.cnpj_base,
+            mes(continuidade.inicio),
+            mes(continuidade.fim),
+            continuidade.meses_escriturados,
+            continuidade.total_de_meses(),
+        );
+
+        let meses =
+            |datas: &[NaiveDate]| datas.iter().map(|d| mes(*d)).collect::<Vec<_>>().join(", ");
+
+        if !continuidade.meses_ausentes.is_empty() {
+            let _ = writeln!(
+                relatorio,
+                "   Meses ausentes: {}",
+                meses(&continuidade.meses_ausentes)
+            );
+        }
+
+        if !continuidade.meses_dispensados.is_empty() {
+            let _ = writeln!(
+                relatorio,
+                "   Meses dispensados da escrituração (Registro 0120): {}",
+                meses(&continuidade.meses_dispensados)
+            );
+        }
+
+        for (periodo, arquivos) in &continuidade.meses_duplicados {
+            let _ = writeln!(
+                relatorio,
+                "   Mês {} em duplicidade: {} arquivos",
+                mes(*periodo),
+                arquivos.len()
+            );
+            for arquivo in arquivos {
+                let _ = writeln!(relatorio, "      {}", arquivo.display());
+            }
+        }
+
+        for irregular in &continuidade.periodos_irregulares {
+            let _ = writeln!(
+                relatorio,
+                "   Período irregular: {}: {}.",
+                irregular.arquivo.display(),
+                irregular.motivo
+            );
+        }
+
+        if continuidade.regular() {
+            let _ = writeln!(relatorio, "   Nenhum mês ausente.");
+        }
+
+        relatorio.push('\n');
+    }
+
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/continuidade_dos_periodos_tests.rs"]
+mod continuidade_dos_periodos_tests;
@@ -0,0 +1,406 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_xlsxwriter::serialize_option_datetime_to_excel;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
+use struct_iterable::Iterable;
+
+use crate::{
+    BlocoP, DECIMAL_ALIQ, DECIMAL_VALOR, DecimalExt, Divergencia, ExcelExtension, SpedContext,
+    SpedFile, Verificador, serialize_decimal, serialize_option_decimal, soma,
+};
+
+// ============================================================================
+// Contribuição Previdenciária sobre a Receita Bruta (Bloco P)
+// ============================================================================
+
+/*
+Apuração da CPRB por estabelecimento (P010) e código de atividade econômica (P100),
+consolidada por período (P200) e confrontada com a receita bruta do Registro 0145:
+
+1. Em cada registro P100:
+   - VL_BC_CONT        = VL_REC_ATIV_ESTAB - VL_EXC;
+   - VL_CONT_APU       = VL_BC_CONT × ALIQ_CONT / 100;
+   - VL_REC_ATIV_ESTAB ≤ VL_REC_TOT_EST.
+
+2. Consolidação (P200) e ajustes (P210):
+   - Σ VL_TOT_CONT_APU = Σ VL_CONT_APU dos registros P100;
+   - VL_TOT_AJ_REDUC   = Σ VL_AJ dos registros P210 com IND_AJ 0 (redução);
+   - VL_TOT_AJ_ACRES   = Σ VL_AJ dos registros P210 com IND_AJ 1 (acréscimo);
+   - VL_TOT_CONT_DEV   = VL_TOT_CONT_APU - VL_TOT_AJ_REDUC + VL_TOT_AJ_ACRES.
+
+3. Receita bruta do Registro 0145 (obrigatório quando houver Bloco P):
+   - VL_REC_ATIV        = Σ VL_REC_ATIV_ESTAB dos registros P100;
+   - VL_REC_DEMAIS_ATIV = VL_REC_TOT - VL_REC_ATIV.
+
+   VL_REC_TOT é a receita bruta total da pessoa jurídica e não é conferida com
+   VL_REC_TOT_EST: estabelecimentos sem atividade sujeita à CPRB não constam do Bloco P.
+
+Campos não informados em registros existentes valem zero.
+*/
+
+/// Linha da planilha da CPRB: um registro P100 (estabelecimento e atividade econômica).
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Iterable)]
+pub struct ContribuicaoPrevidenciaria {
+    #[serde(rename = "Arquivo da EFD Contribuições")]
+    pub arquivo_efd: Arc<str>,
+
+    #[serde(
+        rename = "Nº da Linha da EFD",
+        deserialize_with = "csv::invalid_option"
+    )]
+    pub num_linha_efd: Option<usize>,
+
+    #[serde(
+        rename = "Período de Apuração",
+        serialize_with = "serialize_option_datetime_to_excel"
+    )]
+    pub periodo_de_apuracao: Option<NaiveDate>,
+
+    #[serde(rename = "CNPJ dos Estabelecimentos do Contribuinte")]
+    pub estabelecimento_cnpj: Arc<str>,
+
+    #[serde(rename = "Nome do Estabelecimento")]
+    pub estabelecimento_nome: Arc<str>,
+
+    #[serde(rename = "Código da Atividade Econômica")]
+    pub cod_ativ_econ: Arc<str>,
+
+    #[serde(
+        rename = "Valor da Receita Bruta Total do Estabelecimento",
+        serialize_with = "serialize_decimal"
+    )]
+    pub vl_rec_tot_est: Decimal,
+
+    #[serde(
+        rename = "Valor da Receita Bruta da Atividade",
+        serialize_with = "serialize_decimal"
+    )]
+    pub vl_rec_ativ_estab: Decimal,
+
+    #[serde(rename = "Valor das Exclusões", serialize_with = "serialize_decimal")]
+    pub vl_exc: Decimal,
+
+    #[serde(
+        rename = "Valor da Base de Cálculo da CPRB",
+        serialize_with = "serialize_decimal"
+    )]
+    pub vl_bc_cont: Decimal,
+
+    #[serde(
+        rename = "Alíquota da CPRB (em percentual)",
+        serialize_with = "serialize_option_decimal",
+        deserialize_with = "csv::invalid_option"
+    )]
+    pub aliq_cont: Option<Decimal>,
+
+    #[serde(rename = "Valor da CPRB Apurada", serialize_with = "serialize_decimal")]
+    pub vl_cont_apu: Decimal,
+
+    #[serde(
+        rename = "Valor da CPRB Recalculada",
+        serialize_with = "serialize_decimal"
+    )]
+    pub vl_cont_calculado: Decimal,
+
+    #[serde(rename = "Código da Conta Analítica Contábil")]
+    pub cod_cta: Arc<str>,
+}
+
+impl ExcelExtension for ContribuicaoPrevidenciaria {}
+
+/// Campo da CPRB declarada (P100, P200 ou 0145) divergente do valor esperado.
+///
+/// A CPRB é conferida por escrituração: a divergência não tem outra origem além
+/// do registro e da linha.
+pub type DivergenciaDaCprb = Divergencia<()>;
+
+/// CPRB de uma escrituração: registros P100 e divergências da apuração.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ApuracaoDaCprb {
+    pub contribuicoes: Vec<ContribuicaoPrevidenciaria>,
+    pub divergencias: Vec<DivergenciaDaCprb>,
+}
+
+/// Consolidação declarada (P200) e Σ dos ajustes detalhados nos registros P210.
+struct ConsolidacaoDeclarada {
+    linha: usize,
+    vl_tot_cont_apu: Decimal,
+    vl_tot_aj_reduc: Decimal,
+    vl_tot_aj_acres: Decimal,
+    reducoes: Decimal,
+    acrescimos: Decimal,
+}
+
+/// Extrai a CPRB por estabelecimento e atividade econômica (P010/P100) e confronta:
+/// - a base de cálculo e a contribuição de cada P100;
+/// - os totais e ajustes do P200 (e P210) com os registros P100;
+/// - a receita das atividades do Registro 0145 com a dos registros P100.
+///
+/// Diferenças maiores que a tolerância do contexto são retornadas na ordem das linhas.
+pub fn apurar_cprb(sped_file: &SpedFile, context: &SpedContext) -> ApuracaoDaCprb {
+    let mut verificador = Verificador::new(context.tolerancia);
+    let mut contribuicoes = Vec::new();
+
+    let mut consolidacoes: Vec<ConsolidacaoDeclarada> = Vec::new();
+
+    let mut registros: Vec<&BlocoP> = sped_file.bloco_p.iter().collect();
+    registros.sort_by_key(|r| r.line_number());
+
+    let mut cnpj: Arc<str> = Arc::from("");
+
+    for registro in registros {
+        match registro {
+            BlocoP::RP010(r) => {
+                cnpj = r.cnpj.as_deref().unwrap_or_default().into();
+            }
+            BlocoP::RP100(r) => {
+                let origem = ("P100", Some(r.line_number));
+                let vl_rec_tot_est = soma([r.vl_rec_tot_est]);
+                let vl_rec_ativ_estab = soma([r.vl_rec_ativ_estab]);
+                let vl_exc = soma([r.vl_exc]);
+                let vl_bc_cont = soma([r.vl_bc_cont]);
+                let vl_cont_apu = soma([r.vl_cont_apu]);
+                let vl_cont_calculado = (vl_bc_cont * r.aliq_cont.unwrap_or_default()
+                    / Decimal::ONE_HUNDRED)
+                    .round_dp(DECIMAL_VALOR as u32);
+
+                verificador.conferir(
+                    (),
+                    origem,
+                    "VL_BC_CONT",
+                    (vl_bc_cont, vl_rec_ativ_estab - vl_exc),
+                    "VL_REC_ATIV_ESTAB - VL_EXC",
+                );
+                verificador.conferir(
+                    (),
+                    origem,
+                    "VL_CONT_APU",
+                    (vl_cont_apu, vl_cont_calculado),
+                    "VL_BC_CONT × ALIQ_CONT / 100",
+                );
+                if vl_rec_ativ_estab > vl_rec_tot_est {
+                    verificador.conferir(
+                        (),
+                        origem,
+                        "VL_REC_ATIV_ESTAB",
+                        (vl_rec_ativ_estab, vl_rec_tot_est),
+                        "limitado a VL_REC_TOT_EST",
+                    );
+                }
+
+                contribuicoes.push(ContribuicaoPrevidenciaria {
+                    arquivo_efd: context.arquivo_efd.clone(),
+                    num_linha_efd: Some(r.line_number),
+                    periodo_de_apuracao: r.dt_ini,
+                    estabelecimento_cnpj: cnpj.clone(),
+                    estabelecimento_nome: context
+                        .estabelecimentos
+                        .get(cnpj.as_ref())
+                        .cloned()
+                        .unwrap_or_default(),
+                    cod_ativ_econ: r.cod_ativ_econ.as_deref().unwrap_or_default().into(),
+                    vl_rec_tot_est,
+                    vl_rec_ativ_estab,
+                    vl_exc,
+                    vl_bc_cont,
+                    aliq_cont: r.aliq_cont,
+                    vl_cont_apu,
+                    vl_cont_calculado,
+                    cod_cta: r.cod_cta.as_deref().unwrap_or_default().into(),
+                });
+            }
+            BlocoP::RP200(r) => {
+                let vl_tot_cont_apu = soma([r.vl_tot_cont_apu]);
+                let vl_tot_aj_reduc = soma([r.vl_tot_aj_reduc]);
+                let vl_tot_aj_acres = soma([r.vl_tot_aj_acres]);
+
+                verificador.conferir(
+                    (),
+                    ("P200", Some(r.line_number)),
+                    "VL_TOT_CONT_DEV",
+                    (
+                        soma([r.vl_tot_cont_dev]),
+                        vl_tot_cont_apu - vl_tot_aj_reduc + vl_tot_aj_acres,
+                    ),
+                    "VL_TOT_CONT_APU - VL_TOT_AJ_REDUC + VL_TOT_AJ_ACRES",
+                );
+
+                consolidacoes.push(ConsolidacaoDeclarada {
+                    linha: r.line_number,
+                    vl_tot_cont_apu,
+                    vl_tot_aj_reduc,
+                    vl_tot_aj_acres,
+                    reducoes: Decimal::ZERO,
+                    acrescimos: Decimal::ZERO,
+                });
+            }
+            BlocoP::RP210(r) => {
+                // Os registros P210 são associados ao P200 imediatamente anterior.
+                if let Some(consolidacao) = consolidacoes.last_mut() {
+                    match r.ind_aj.as_deref() {
+                        Some("0") => consolidacao.reducoes += soma([r.vl_aj]),
+                        Some("1") => consolidacao.acrescimos += soma([r.vl_aj]),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for c in &consolidacoes {
+        let origem = ("P200", Some(c.linha));
+        verificador.conferir(
+            (),
+            origem,
+            "VL_TOT_AJ_REDUC",
+            (c.vl_tot_aj_reduc, c.reducoes),
+            "Σ VL_AJ dos registros P210 com IND_AJ 0",
+        );
+        verificador.conferir(
+            (),
+            origem,
+            "VL_TOT_AJ_ACRES",
+            (c.vl_tot_aj_acres, c.acrescimos),
+            "Σ VL_AJ dos registros P210 com IND_AJ 1",
+        );
+    }
+
+    let vl_cont_apu: Decimal = contribuicoes.iter().map(|c| c.vl_cont_apu).sum();
+    if let Some(primeira) = consolidacoes.first() {
+        verificador.conferir(
+            (),
+            ("P200", Some(primeira.linha)),
+            "VL_TOT_CONT_APU",
+            (
+                consolidacoes.iter().map(|c| c.vl_tot_cont_apu).sum(),
+                vl_cont_apu,
+            ),
+            "Σ VL_CONT_APU dos registros P100",
+        );
+    } else if !contribuicoes.is_empty() {
+        verificador.conferir(
+            (),
+            ("P200", None),
+            "VL_TOT_CONT_APU",
+            (Decimal::ZERO, vl_cont_apu),
+            "Σ VL_CONT_APU dos registros P100",
+        );
+    }
+
+    let vl_rec_ativ: Decimal = contribuicoes.iter().map(|c| c.vl_rec_ativ_estab).sum();
+
+    match &context.registro_0145 {
+        Some(r) => {
+            let origem = ("0145", Some(r.line_number));
+            verificador.conferir(
+                (),
+                origem,
+                "VL_REC_ATIV",
+                (soma([r.vl_rec_ativ]), vl_rec_ativ),
+                "Σ VL_REC_ATIV_ESTAB dos registros P100",
+            );
+            verificador.conferir(
+                (),
+                origem,
+                "VL_REC_DEMAIS_ATIV",
+                (
+                    soma([r.vl_rec_demais_ativ]),
+                    soma([r.vl_rec_tot]) - soma([r.vl_rec_ativ]),
+                ),
+                "VL_REC_TOT - VL_REC_ATIV",
+            );
+        }
+        None if !contribuicoes.is_empty() => {
+            verificador.conferir(
+                (),
+                ("0145", None),
+                "VL_REC_ATIV",
+                (Decimal::ZERO, vl_rec_ativ),
+                "Σ VL_REC_ATIV_ESTAB dos registros P100",
+            );
+        }
+        None => {}
+    }
+
+    let mut divergencias = verificador.into_divergencias();
+    divergencias.sort_by_key(|d| d.linha);
+
+    ApuracaoDaCprb {
+        contribuicoes,
+        divergencias,
+    }
+}
+
+/// Resumo da CPRB por estabelecimento e divergências da apuração,
+/// ou String vazia se a escrituração não tiver Bloco P nem Registro 0145.
+pub fn relatorio_cprb(apuracao: &ApuracaoDaCprb) -> String {
+    if apuracao.contribuicoes.is_empty() && apuracao.divergencias.is_empty() {
+        return String::new();
+    }
+
+    // CNPJ -> (receita das atividades, exclusões, base de cálculo, CPRB apurada, alíquotas)
+    let mut estabelecimentos: BTreeMap<&str, (Decimal, Decimal, Decimal, Decimal, Vec<String>)> =
+        BTreeMap::new();
+    for c in &apuracao.contribuicoes {
+        let (receita, exclusoes, base, cprb, aliquotas) = estabelecimentos
+            .entry(c.estabelecimento_cnpj.as_ref())
+            .or_default();
+        *receita += c.vl_rec_ativ_estab;
+        *exclusoes += c.vl_exc;
+        *base += c.vl_bc_cont;
+        *cprb += c.vl_cont_apu;
+        let aliquota = format!(
+            "{}%",
+            c.aliq_cont
+                .unwrap_or_default()
+                .to_formatted_string(DECIMAL_ALIQ)
+        );
+        if !aliquotas.contains(&aliquota) {
+            aliquotas.push(aliquota);
+        }
+    }
+
+    let total: Decimal = apuracao.contribuicoes.iter().map(|c| c.vl_cont_apu).sum();
+    let mut relatorio = format!(
+        "Contribuição Previdenciária sobre a Receita Bruta (Bloco P): {} estabelecimento(s), CPRB apurada {}.\n",
+        estabelecimentos.len(),
+        total.to_formatted_string(DECIMAL_VALOR)
+    );
+
+    for (cnpj, (receita, exclusoes, base, cprb, aliquotas)) in estabelecimentos {
+        let _ = writeln!(
+            relatorio,
+            "   CNPJ {cnpj}: receita das atividades {}, exclusões {}, base de cálculo {}, \
+             alíquota {}, CPRB apurada {}",
+            receita.to_formatted_string(DECIMAL_VALOR),
+            exclusoes.to_formatted_string(DECIMAL_VALOR),
+            base.to_formatted_string(DECIMAL_VALOR),
+            aliquotas.join(", "),
+            cprb.to_formatted_string(DECIMAL_VALOR),
+        );
+    }
+
+    if !apuracao.divergencias.is_empty() {
+        let _ = writeln!(
+            relatorio,
+            "   Divergências da apuração: {}",
+            apuracao.divergencias.len()
+        );
+        for divergencia in &apuracao.divergencias {
+            let _ = writeln!(relatorio, "      {divergencia}");
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/contribuicao_previdenciaria_tests.rs"]
+mod contribuicao_previdenciaria_tests;
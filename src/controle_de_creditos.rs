@@ -0,0 +1,550 @@
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    Bloco1, BlocoM, Contribuicao, ControleMensal, DECIMAL_VALOR, DecimalExt, Divergencia,
+    SaldoMensal, SpedFile, Verificador, decimal_textual, escrituracoes_analisadas, mes,
+    mes_seguinte, mm_aaaa, razao, relatorio_divergencias_mensais, soma,
+};
+
+/// Origem do crédito (ORIG_CRED) para operações próprias.
+const ORIG_CRED_PROPRIO: &str = "01";
+
+// ============================================================================
+// Controle dos Saldos de Créditos (Registros 1100/1500)
+// ============================================================================
+
+/*
+Controle de cada crédito (PER_APU_CRED, ORIG_CRED, CNPJ_SUC, COD_CRED) mês a mês:
+
+1. Em cada registro 1100/1500:
+   - VL_TOT_CRED_APU  = VL_CRED_APU + VL_CRED_EXT_APU;
+   - SD_CRED_DISP_EFD = VL_TOT_CRED_APU - VL_CRED_DESC_PA_ANT - VL_CRED_PER_PA_ANT - VL_CRED_DCOMP_PA_ANT;
+   - SLD_CRED_FIM     = SD_CRED_DISP_EFD - VL_CRED_DESC_EFD - VL_CRED_PER_EFD - VL_CRED_DCOMP_EFD
+                        - VL_CRED_TRANS - VL_CRED_OUT.
+
+2. Entre escriturações de meses consecutivos do mesmo CNPJ base:
+   - SD_CRED_DISP_EFD (mês seguinte) = SLD_CRED_FIM (mês anterior);
+   - VL_TOT_CRED_APU  (mês seguinte) = VL_TOT_CRED_APU (mês anterior);
+   - saldo final positivo sem registro no mês seguinte: crédito não controlado.
+
+3. Crédito apurado no Bloco M (M100/M500), no primeiro mês de controle (PER_APU_CRED = mês do M100):
+   - VL_CRED_APU         = Σ VL_CRED_DISP dos registros M100/M500 do COD_CRED;
+   - VL_CRED_DESC_PA_ANT = Σ VL_CRED_DESC dos registros M100/M500 do COD_CRED;
+   - SLD_CRED positivo sem registro 1100/1500 no mês seguinte: crédito não controlado.
+
+As verificações entre meses exigem a escrituração do mês seguinte entre os arquivos analisados.
+Campos não informados valem zero.
+*/
+
+/// Crédito de um período anterior informado no controle de créditos (Registro 1100 ou 1500).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaldoDeCredito {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração que informa o registro.
+    pub periodo: NaiveDate,
+    pub contribuicao: Contribuicao,
+    /// Código do registro: "1100" ou "1500".
+    pub registro: &'static str,
+    pub linha: usize,
+    /// Período de apuração de origem do crédito.
+    pub per_apu_cred: Option<NaiveDate>,
+    pub orig_cred: CompactString,
+    pub cnpj_suc: CompactString,
+    pub cod_cred: CompactString,
+    pub vl_cred_apu: Decimal,
+    pub vl_cred_ext_apu: Decimal,
+    pub vl_tot_cred_apu: Decimal,
+    pub vl_cred_desc_pa_ant: Decimal,
+    pub vl_cred_per_pa_ant: Decimal,
+    pub vl_cred_dcomp_pa_ant: Decimal,
+    pub sd_cred_disp_efd: Decimal,
+    pub vl_cred_desc_efd: Decimal,
+    pub vl_cred_per_efd: Decimal,
+    pub vl_cred_dcomp_efd: Decimal,
+    pub vl_cred_trans: Decimal,
+    pub vl_cred_out: Decimal,
+    pub sld_cred_fim: Decimal,
+}
+
+/// Identificação de um crédito ao longo dos meses.
+type ChaveDoCredito = (
+    u32,
+    Contribuicao,
+    Option<NaiveDate>,
+    CompactString,
+    CompactString,
+    CompactString,
+);
+
+impl SaldoMensal for SaldoDeCredito {
+    type Chave = ChaveDoCredito;
+
+    fn chave(&self) -> ChaveDoCredito {
+        (
+            self.cnpj_base,
+            self.contribuicao,
+            self.per_apu_cred,
+            self.orig_cred.clone(),
+            self.cnpj_suc.clone(),
+            self.cod_cred.clone(),
+        )
+    }
+
+    fn controle(&self) -> ControleMensal {
+        ControleMensal {
+            cnpj_base: self.cnpj_base,
+            periodo: self.periodo,
+            contribuicao: self.contribuicao,
+            rotulo: self.rotulo(),
+        }
+    }
+
+    fn periodo(&self) -> NaiveDate {
+        self.periodo
+    }
+
+    fn registro(&self) -> (&'static str, usize) {
+        (self.registro, self.linha)
+    }
+}
+
+impl SaldoDeCredito {
+    /// Identificação do crédito (ex: "COD_CRED 101 de 10/2020").
+    pub fn rotulo(&self) -> String {
+        rotulo(
+            &self.cod_cred,
+            self.per_apu_cred,
+            &self.orig_cred,
+            &self.cnpj_suc,
+        )
+    }
+}
+
+/// Crédito apurado no período (M100 ou M500), totalizado por COD_CRED.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreditoDoPeriodo {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração.
+    pub periodo: NaiveDate,
+    pub contribuicao: Contribuicao,
+    /// Código do registro: "M100" ou "M500".
+    pub registro: &'static str,
+    /// Linha do primeiro registro do COD_CRED.
+    pub linha: usize,
+    pub cod_cred: CompactString,
+    /// Σ VL_CRED_DISP.
+    pub vl_cred_disp: Decimal,
+    /// Σ VL_CRED_DESC.
+    pub vl_cred_desc: Decimal,
+    /// Σ SLD_CRED.
+    pub sld_cred: Decimal,
+}
+
+/// Registros de controle de créditos (1100/1500) e créditos do Bloco M (M100/M500)
+/// de uma ou mais escriturações.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ControleDeCreditos {
+    pub saldos: Vec<SaldoDeCredito>,
+    pub creditos_do_periodo: Vec<CreditoDoPeriodo>,
+}
+
+fn rotulo(
+    cod_cred: &str,
+    per_apu_cred: Option<NaiveDate>,
+    orig_cred: &str,
+    cnpj_suc: &str,
+) -> String {
+    let mut rotulo = format!("COD_CRED {cod_cred} de {}", mm_aaaa(per_apu_cred));
+    if !orig_cred.is_empty() && orig_cred != ORIG_CRED_PROPRIO {
+        let _ = write!(rotulo, ", ORIG_CRED {orig_cred}");
+    }
+    if !cnpj_suc.is_empty() {
+        let _ = write!(rotulo, ", CNPJ_SUC {cnpj_suc}");
+    }
+    rotulo
+}
+
+macro_rules! saldo_de_credito {
+    ($r:expr, $registro:literal, $contribuicao:expr, $cnpj_base:expr, $periodo:expr) => {
+        SaldoDeCredito {
+            cnpj_base: $cnpj_base,
+            periodo: $periodo,
+            contribuicao: $contribuicao,
+            registro: $registro,
+            linha: $r.line_number,
+            per_apu_cred: $r.per_apu_cred.map(mes),
+            orig_cred: $r.orig_cred.clone().unwrap_or_default(),
+            cnpj_suc: $r.cnpj_suc.clone().unwrap_or_default(),
+            cod_cred: $r
+                .cod_cred
+                .map(|c| c.to_string().into())
+                .unwrap_or_default(),
+            vl_cred_apu: soma([$r.vl_cred_apu]),
+            vl_cred_ext_apu: soma([$r.vl_cred_ext_apu]),
+            vl_tot_cred_apu: soma([$r.vl_tot_cred_apu]),
+            vl_cred_desc_pa_ant: soma([$r.vl_cred_desc_pa_ant]),
+            vl_cred_per_pa_ant: soma([$r.vl_cred_per_pa_ant]),
+            vl_cred_dcomp_pa_ant: soma([$r.vl_cred_dcomp_pa_ant]),
+            // SD_CRED_DISP_EFD é armazenado como texto: "1234,56".
+            sd_cred_disp_efd: decimal_textual($r.sd_cred_disp_efd.as_deref()).unwrap_or_default(),
+            vl_cred_desc_efd: soma([$r.vl_cred_desc_efd]),
+            vl_cred_per_efd: soma([$r.vl_cred_per_efd]),
+            vl_cred_dcomp_efd: soma([$r.vl_cred_dcomp_efd]),
+            vl_cred_trans: soma([$r.vl_cred_trans]),
+            vl_cred_out: soma([$r.vl_cred_out]),
+            sld_cred_fim: soma([$r.sld_cred_fim]),
+        }
+    };
+}
+
+impl ControleDeCreditos {
+    /// Extrai os registros 1100/1500 e os créditos M100/M500 (por COD_CRED) de uma escrituração.
+    pub fn extrair(sped_file: &SpedFile, cnpj_base: u32, periodo: NaiveDate) -> Self {
+        let periodo = mes(periodo);
+        let mut controle = Self::default();
+
+        for registro in &sped_file.bloco_1 {
+            let saldo = match registro {
+                Bloco1::R1100(r) => {
+                    saldo_de_credito!(r, "1100", Contribuicao::Pis, cnpj_base, periodo)
+                }
+                Bloco1::R1500(r) => {
+                    saldo_de_credito!(r, "1500", Contribuicao::Cofins, cnpj_base, periodo)
+                }
+                _ => continue,
+            };
+            controle.saldos.push(saldo);
+        }
+
+        // (contribuição, COD_CRED) -> crédito do período
+        let mut creditos: BTreeMap<(Contribuicao, CompactString), CreditoDoPeriodo> =
+            BTreeMap::new();
+        for registro in &sped_file.bloco_m {
+            let (contribuicao, nome, linha, cod_cred, disp, desc, sld) = match registro {
+                BlocoM::RM100(r) => (
+                    Contribuicao::Pis,
+                    "M100",
+                    r.line_number,
+                    r.cod_cred,
+                    r.vl_cred_disp,
+                    r.vl_cred_desc,
+                    r.sld_cred,
+                ),
+                BlocoM::RM500(r) => (
+                    Contribuicao::Cofins,
+                    "M500",
+                    r.line_number,
+                    r.cod_cred,
+                    r.vl_cred_disp,
+                    r.vl_cred_desc,
+                    r.sld_cred,
+                ),
+                _ => continue,
+            };
+            let cod_cred: CompactString =
+                cod_cred.map(|c| c.to_string().into()).unwrap_or_default();
+
+            let credito = creditos
+                .entry((contribuicao, cod_cred.clone()))
+                .or_insert_with(|| CreditoDoPeriodo {
+                    cnpj_base,
+                    periodo,
+                    contribuicao,
+                    registro: nome,
+                    linha,
+                    cod_cred,
+                    vl_cred_disp: Decimal::ZERO,
+                    vl_cred_desc: Decimal::ZERO,
+                    sld_cred: Decimal::ZERO,
+                });
+            credito.linha = credito.linha.min(linha);
+            credito.vl_cred_disp += soma([disp]);
+            credito.vl_cred_desc += soma([desc]);
+            credito.sld_cred += soma([sld]);
+        }
+        controle.creditos_do_periodo = creditos.into_values().collect();
+
+        controle
+    }
+
+    /// Move os registros de `other` para `self`, deixando `other` vazio.
+    pub fn append(&mut self, other: &mut Self) {
+        self.saldos.append(&mut other.saldos);
+        self.creditos_do_periodo
+            .append(&mut other.creditos_do_periodo);
+    }
+}
+
+/// Campo do controle de créditos (1100/1500) divergente do valor esperado,
+/// identificado pela escrituração, contribuição e crédito (ex: "COD_CRED 101 de 10/2020").
+pub type DivergenciaDeCredito = Divergencia<ControleMensal>;
+
+/// Registro de controle de créditos correspondente a uma contribuição.
+const fn registro_de_controle(contribuicao: Contribuicao) -> &'static str {
+    match contribuicao {
+        Contribuicao::Pis => "1100",
+        Contribuicao::Cofins => "1500",
+    }
+}
+
+fn conferir_saldo(verificador: &mut Verificador<ControleMensal>, s: &SaldoDeCredito) {
+    verificador.conferir_registro(
+        s,
+        "VL_TOT_CRED_APU",
+        (s.vl_tot_cred_apu, s.vl_cred_apu + s.vl_cred_ext_apu),
+        "VL_CRED_APU + VL_CRED_EXT_APU",
+    );
+    verificador.conferir_registro(
+        s,
+        "SD_CRED_DISP_EFD",
+        (
+            s.sd_cred_disp_efd,
+            s.vl_tot_cred_apu
+                - s.vl_cred_desc_pa_ant
+                - s.vl_cred_per_pa_ant
+                - s.vl_cred_dcomp_pa_ant,
+        ),
+        "VL_TOT_CRED_APU menos os valores utilizados em períodos anteriores",
+    );
+    verificador.conferir_registro(
+        s,
+        "SLD_CRED_FIM",
+        (
+            s.sld_cred_fim,
+            s.sd_cred_disp_efd
+                - s.vl_cred_desc_efd
+                - s.vl_cred_per_efd
+                - s.vl_cred_dcomp_efd
+                - s.vl_cred_trans
+                - s.vl_cred_out,
+        ),
+        "SD_CRED_DISP_EFD menos os valores utilizados no período",
+    );
+}
+
+/// Saldo positivo sem registro de controle na escrituração seguinte.
+fn nao_controlado(
+    verificador: &mut Verificador<ControleMensal>,
+    controle: ControleMensal,
+    saldo: Decimal,
+    referencia: String,
+) {
+    let registro = registro_de_controle(controle.contribuicao);
+    verificador.nao_informado(
+        controle,
+        registro,
+        "SD_CRED_DISP_EFD",
+        saldo.max(Decimal::ZERO),
+        referencia,
+    );
+}
+
+/// Crédito do Bloco M (M100/M500) x primeiro mês de controle (1100/1500).
+fn conferir_credito_do_periodo(
+    verificador: &mut Verificador<ControleMensal>,
+    credito: &CreditoDoPeriodo,
+    saldos: &[&SaldoDeCredito],
+) {
+    let periodo = mm_aaaa(Some(credito.periodo));
+    let vl_cred_apu: Decimal = saldos.iter().map(|s| s.vl_cred_apu).sum();
+    let vl_cred_desc_pa_ant: Decimal = saldos.iter().map(|s| s.vl_cred_desc_pa_ant).sum();
+
+    let Some(primeiro) = saldos.first() else {
+        return;
+    };
+    verificador.conferir_registro(
+        *primeiro,
+        "VL_CRED_APU",
+        (vl_cred_apu, credito.vl_cred_disp),
+        format!("VL_CRED_DISP do {} de {periodo}", credito.registro),
+    );
+    verificador.conferir_registro(
+        *primeiro,
+        "VL_CRED_DESC_PA_ANT",
+        (vl_cred_desc_pa_ant, credito.vl_cred_desc),
+        format!("VL_CRED_DESC do {} de {periodo}", credito.registro),
+    );
+}
+
+/// Acompanha cada crédito dos registros 1100/1500 mês a mês e confronta:
+/// - os saldos de cada registro com os valores utilizados;
+/// - o saldo final de um mês com o saldo inicial do mês seguinte;
+/// - o crédito apurado no Bloco M (M100/M500) com o primeiro mês de controle.
+///
+/// `escrituracoes` contém o CNPJ base e o período de apuração dos arquivos analisados:
+/// as verificações entre meses exigem a escrituração do mês seguinte.
+///
+/// Diferenças maiores que a `tolerancia` são retornadas por CNPJ base, período e contribuição.
+pub fn verificar_controle_de_creditos(
+    controle: &ControleDeCreditos,
+    escrituracoes: &[(u32, NaiveDate)],
+    tolerancia: Decimal,
+) -> Vec<DivergenciaDeCredito> {
+    let escrituracoes = escrituracoes_analisadas(escrituracoes);
+    let analisada = |cnpj_base: u32, periodo: NaiveDate| -> Option<NaiveDate> {
+        mes_seguinte(periodo).filter(|seguinte| escrituracoes.contains(&(cnpj_base, *seguinte)))
+    };
+
+    let mut verificador = Verificador::new(tolerancia);
+
+    controle
+        .saldos
+        .iter()
+        .for_each(|saldo| conferir_saldo(&mut verificador, saldo));
+    let razao = razao(&controle.saldos);
+
+    for (chave, meses) in &razao {
+        for (periodo, anterior) in meses {
+            let Some(seguinte) = analisada(chave.0, *periodo) else {
+                continue;
+            };
+            match meses.get(&seguinte) {
+                Some(saldo) => verificador.conferir_transporte(
+                    *anterior,
+                    *saldo,
+                    &[
+                        (
+                            "SD_CRED_DISP_EFD",
+                            saldo.sd_cred_disp_efd,
+                            "SLD_CRED_FIM",
+                            anterior.sld_cred_fim,
+                        ),
+                        (
+                            "VL_TOT_CRED_APU",
+                            saldo.vl_tot_cred_apu,
+                            "VL_TOT_CRED_APU",
+                            anterior.vl_tot_cred_apu,
+                        ),
+                    ],
+                ),
+                None => nao_controlado(
+                    &mut verificador,
+                    ControleMensal {
+                        periodo: seguinte,
+                        ..anterior.controle()
+                    },
+                    anterior.sld_cred_fim,
+                    format!("SLD_CRED_FIM de {}", mm_aaaa(Some(*periodo))),
+                ),
+            }
+        }
+    }
+
+    for credito in &controle.creditos_do_periodo {
+        let Some(seguinte) = analisada(credito.cnpj_base, credito.periodo) else {
+            continue;
+        };
+        let saldos: Vec<&SaldoDeCredito> = razao
+            .iter()
+            .filter(
+                |((cnpj_base, contribuicao, per_apu_cred, _, cnpj_suc, cod_cred), _)| {
+                    *cnpj_base == credito.cnpj_base
+                        && *contribuicao == credito.contribuicao
+                        && *per_apu_cred == Some(credito.periodo)
+                        && cnpj_suc.is_empty()
+                        && *cod_cred == credito.cod_cred
+                },
+            )
+            .filter_map(|(_, meses)| meses.get(&seguinte).copied())
+            .collect();
+
+        if saldos.is_empty() {
+            nao_controlado(
+                &mut verificador,
+                ControleMensal {
+                    cnpj_base: credito.cnpj_base,
+                    periodo: seguinte,
+                    contribuicao: credito.contribuicao,
+                    rotulo: rotulo(
+                        &credito.cod_cred,
+                        Some(credito.periodo),
+                        ORIG_CRED_PROPRIO,
+                        "",
+                    ),
+                },
+                credito.sld_cred,
+                format!(
+                    "SLD_CRED do {} de {}",
+                    credito.registro,
+                    mm_aaaa(Some(credito.periodo))
+                ),
+            );
+        } else {
+            conferir_credito_do_periodo(&mut verificador, credito, &saldos);
+        }
+    }
+
+    verificador.divergencias_ordenadas()
+}
+
+/// Razão dos créditos de períodos anteriores (mês a mês, se `print_razao`) seguido das
+/// divergências do controle, ou String vazia se não houver o que informar.
+pub fn relatorio_controle_de_creditos(
+    controle: &ControleDeCreditos,
+    divergencias: &[DivergenciaDeCredito],
+    print_razao: bool,
+) -> String {
+    let mut relatorio = String::new();
+
+    let mut razao: BTreeMap<ChaveDoCredito, Vec<&SaldoDeCredito>> = BTreeMap::new();
+    for saldo in controle.saldos.iter().filter(|_| print_razao) {
+        razao.entry(saldo.chave()).or_default().push(saldo);
+    }
+
+    if !razao.is_empty() {
+        let _ = writeln!(
+            relatorio,
+            "Controle dos créditos de períodos anteriores (Registros 1100/1500): {} crédito(s).",
+            razao.len()
+        );
+    }
+
+    for ((cnpj_base, contribuicao, ..), mut saldos) in razao {
+        saldos.sort_by_key(|s| s.periodo);
+        let _ = writeln!(
+            relatorio,
+            "   CNPJ base {cnpj_base:08}, {}, {}:",
+            contribuicao.descricao(),
+            saldos[0].rotulo()
+        );
+        for s in saldos {
+            let valor = |v: Decimal| v.to_formatted_string(DECIMAL_VALOR);
+            let _ = writeln!(
+                relatorio,
+                "      {} [{}, linha nº {}]: saldo inicial {}, desconto {}, ressarcimento {}, \
+                 compensação {}, transferência {}, outras {}, saldo final {}",
+                mm_aaaa(Some(s.periodo)),
+                s.registro,
+                s.linha,
+                valor(s.sd_cred_disp_efd),
+                valor(s.vl_cred_desc_efd),
+                valor(s.vl_cred_per_efd),
+                valor(s.vl_cred_dcomp_efd),
+                valor(s.vl_cred_trans),
+                valor(s.vl_cred_out),
+                valor(s.sld_cred_fim),
+            );
+        }
+    }
+
+    if !relatorio.is_empty() {
+        relatorio.push('\n');
+    }
+
+    relatorio.push_str(&relatorio_divergencias_mensais(
+        "Controle dos créditos divergente dos saldos esperados",
+        divergencias,
+    ));
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/controle_de_creditos_tests.rs"]
+mod controle_de_creditos_tests;
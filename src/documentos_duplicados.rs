@@ -0,0 +1,266 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use rust_xlsxwriter::serialize_option_datetime_to_excel;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    sync::Arc,
+};
+use struct_iterable::Iterable;
+
+use crate::{DocsFiscais, ExcelExtension, TipoDeOperacao, serialize_decimal};
+
+// ============================================================================
+// Documentos Duplicados entre Arquivos
+// ============================================================================
+
+/*
+Quando vários arquivos da EFD Contribuições são analisados em conjunto, o mesmo
+documento fiscal pode ser escriturado em períodos ou estabelecimentos distintos,
+o que geralmente indica crédito aproveitado em duplicidade.
+
+Uma ocorrência é o conjunto de itens do documento escriturados em um mesmo arquivo
+por um mesmo estabelecimento. O documento é identificado:
+- pela chave de acesso (NF-e, CT-e, ...); ou
+- pela identidade do documento: emitente, participante, modelo, série e número.
+
+O emitente é o estabelecimento, na emissão própria, ou o participante, na emissão
+por terceiros: documentos próprios de estabelecimentos distintos com o mesmo número
+não constituem duplicidade. Ocorrências com chaves de acesso distintas também não.
+
+Os tipos de operação são separados: a saída e a entrada de uma transferência entre
+estabelecimentos do contribuinte não constituem duplicidade.
+*/
+
+pub const CRITERIO_CHAVE: &str = "Chave do Documento";
+pub const CRITERIO_IDENTIDADE: &str = "Emitente, Participante, Modelo, Série e Nº";
+
+/// Ocorrência de um documento fiscal escriturado mais de uma vez.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Iterable)]
+pub struct DocumentoDuplicado {
+    #[serde(rename = "Critério de Duplicidade")]
+    pub criterio: Arc<str>,
+
+    #[serde(rename = "Identificação do Documento")]
+    pub identificacao: Arc<str>,
+
+    #[serde(rename = "Nº de Ocorrências")]
+    pub ocorrencias: usize,
+
+    #[serde(rename = "Arquivo da EFD Contribuições")]
+    pub arquivo_efd: Arc<str>,
+
+    #[serde(
+        rename = "Nº da Linha da EFD",
+        deserialize_with = "csv::invalid_option"
+    )]
+    pub num_linha_efd: Option<usize>,
+
+    #[serde(
+        rename = "Período de Apuração",
+        serialize_with = "serialize_option_datetime_to_excel"
+    )]
+    pub periodo_de_apuracao: Option<NaiveDate>,
+
+    #[serde(rename = "CNPJ dos Estabelecimentos do Contribuinte")]
+    pub estabelecimento_cnpj: Arc<str>,
+
+    #[serde(rename = "Tipo de Operação")]
+    pub tipo_de_operacao: Option<TipoDeOperacao>,
+
+    #[serde(rename = "Registro")]
+    pub registro: Arc<str>,
+
+    #[serde(rename = "Valor Total dos Itens", serialize_with = "serialize_decimal")]
+    pub valor_item: Decimal,
+}
+
+impl ExcelExtension for DocumentoDuplicado {}
+
+/// Documento (critério, identificação, tipo de operação) -> ocorrências (arquivo, estabelecimento).
+type Agrupamento<'a> = BTreeMap<
+    (&'static str, String, Option<TipoDeOperacao>),
+    BTreeMap<(Arc<str>, Arc<str>), Vec<&'a DocsFiscais>>,
+>;
+
+/// Identidade do documento: emitente, participante, modelo, série e número.
+///
+/// Retorna None se faltar o emitente, o participante, o modelo ou o número do documento.
+fn identidade_do_documento(doc: &DocsFiscais) -> Option<String> {
+    let emitente = Some(&doc.emitente).filter(|e| !e.is_empty())?;
+    let participante = [&doc.participante_cnpj, &doc.participante_cpf]
+        .into_iter()
+        .find(|p| !p.is_empty())?;
+
+    // "55 - Nota Fiscal Eletrônica" -> "55"
+    let modelo = doc
+        .modelo_doc_fiscal
+        .split(" - ")
+        .next()
+        .unwrap_or_default();
+    let num_doc = doc.num_doc?;
+
+    if modelo.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Emitente {emitente}, Participante {participante}, Modelo {modelo}, Série {}, Nº {num_doc}",
+        doc.serie_doc
+    ))
+}
+
+/// Verifica os documentos de Entrada ou Saída escriturados em mais de um arquivo
+/// ou estabelecimento, pela chave de acesso e pela identidade do documento.
+///
+/// Retorna uma linha por ocorrência, agrupadas por documento.
+pub fn verificar_documentos_duplicados(docs: &[DocsFiscais]) -> Vec<DocumentoDuplicado> {
+    let mut agrupamento: Agrupamento = BTreeMap::new();
+
+    for doc in docs
+        .iter()
+        .filter(|doc| doc.operacoes_de_entrada_ou_saida())
+    {
+        let ocorrencia = (doc.arquivo_efd.clone(), doc.estabelecimento_cnpj.clone());
+
+        if !doc.chave_doc.is_empty() {
+            let chave = (
+                CRITERIO_CHAVE,
+                doc.chave_doc.to_string(),
+                doc.tipo_de_operacao,
+            );
+            agrupamento
+                .entry(chave)
+                .or_default()
+                .entry(ocorrencia.clone())
+                .or_default()
+                .push(doc);
+        }
+
+        if let Some(identidade) = identidade_do_documento(doc) {
+            let chave = (CRITERIO_IDENTIDADE, identidade, doc.tipo_de_operacao);
+            agrupamento
+                .entry(chave)
+                .or_default()
+                .entry(ocorrencia)
+                .or_default()
+                .push(doc);
+        }
+    }
+
+    agrupamento
+        .into_iter()
+        .filter(|(_, ocorrencias)| ocorrencias.len() > 1)
+        // Se todas as ocorrências possuem a mesma chave, a duplicidade já foi
+        // informada pelo critério da chave do documento. Chaves distintas
+        // identificam documentos distintos.
+        .filter(|((criterio, _, _), ocorrencias)| {
+            *criterio == CRITERIO_CHAVE || !chaves_informadas(ocorrencias.values().flatten())
+        })
+        .flat_map(
+            |((criterio, identificacao, tipo_de_operacao), ocorrencias)| {
+                let total = ocorrencias.len();
+                let identificacao: Arc<str> = identificacao.into();
+
+                ocorrencias
+                    .into_iter()
+                    .map(move |((arquivo_efd, estabelecimento_cnpj), itens)| {
+                        let primeiro = itens
+                            .iter()
+                            .min_by_key(|doc| doc.num_linha_efd)
+                            .expect("ocorrência com ao menos um item");
+
+                        DocumentoDuplicado {
+                            criterio: criterio.into(),
+                            identificacao: identificacao.clone(),
+                            ocorrencias: total,
+                            arquivo_efd,
+                            num_linha_efd: primeiro.num_linha_efd,
+                            periodo_de_apuracao: primeiro.periodo_de_apuracao,
+                            estabelecimento_cnpj,
+                            tipo_de_operacao,
+                            registro: primeiro.registro.clone(),
+                            valor_item: itens.iter().filter_map(|doc| doc.valor_item).sum(),
+                        }
+                    })
+            },
+        )
+        .collect()
+}
+
+/// Todas as ocorrências possuem a mesma chave, ou há ocorrências com chaves distintas.
+///
+/// Em ambos os casos, a identidade do documento não indica duplicidade adicional.
+fn chaves_informadas<'a>(itens: impl Iterator<Item = &'a &'a DocsFiscais>) -> bool {
+    let mut sem_chave = false;
+    let chaves: BTreeSet<&str> = itens
+        .filter_map(|doc| {
+            sem_chave |= doc.chave_doc.is_empty();
+            Some(doc.chave_doc.as_ref()).filter(|chave| !chave.is_empty())
+        })
+        .collect();
+
+    chaves.len() > 1 || (chaves.len() == 1 && !sem_chave)
+}
+
+fn mesmo_documento(a: &DocumentoDuplicado, b: &DocumentoDuplicado) -> bool {
+    (&a.criterio, &a.identificacao, a.tipo_de_operacao)
+        == (&b.criterio, &b.identificacao, b.tipo_de_operacao)
+}
+
+/// Resumo dos documentos duplicados (uma linha por ocorrência),
+/// ou String vazia se não houver duplicidades.
+pub fn relatorio_documentos_duplicados(duplicados: &[DocumentoDuplicado]) -> String {
+    if duplicados.is_empty() {
+        return String::new();
+    }
+
+    let documentos = duplicados.chunk_by(mesmo_documento).count();
+
+    let mut relatorio = format!(
+        "Documentos duplicados entre arquivos ou estabelecimentos: {documentos} documento(s), {} ocorrência(s).\n",
+        duplicados.len()
+    );
+
+    for grupo in duplicados.chunk_by(mesmo_documento) {
+        let documento = &grupo[0];
+        let tipo = documento
+            .tipo_de_operacao
+            .map(|t| format!(" ({t})"))
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            relatorio,
+            "   {}: {}{tipo}",
+            documento.criterio, documento.identificacao
+        );
+
+        for ocorrencia in grupo {
+            let periodo = ocorrencia
+                .periodo_de_apuracao
+                .map(|pa| format!("{:02}/{}", pa.month(), pa.year()))
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                relatorio,
+                "      {} [linha nº {}]: período {periodo}, estabelecimento {}, Registro {}",
+                ocorrencia.arquivo_efd,
+                ocorrencia.num_linha_efd.unwrap_or_default(),
+                ocorrencia.estabelecimento_cnpj,
+                ocorrencia.registro,
+            );
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/documentos_duplicados_tests.rs"]
+mod documentos_duplicados_tests;
@@ -10,6 +10,7 @@ use std::{
     str::Utf8Error,
 };
 use thiserror::Error;
+use zip::result::ZipError;
 
 /// Um tipo de resultado especializado para operações que podem retornar um `EFDError`.
 pub type EFDResult<T> = Result<T, EFDError>;
@@ -333,6 +334,14 @@ pub enum EFDError {
     #[error("Erro de padrão (glob): {0}")]
     PatternError(#[from] PatternError), // source PatternError
 
+    /// Envolve `zip::result::ZipError` (leitura de arquivos .zip).
+    #[error("Erro no arquivo compactado {path:?}: {source}")]
+    Zip {
+        #[source]
+        source: ZipError,
+        path: PathBuf,
+    },
+
     /// Erro para registros não definidos no arquivo EFD.
     #[error(
         "Registro '{record}' não definido conforme sped_efd.rs\n\
@@ -347,6 +356,24 @@ pub enum EFDError {
         fields: Vec<String>,
     },
 
+    /// Inconsistências encontradas pela validação estrutural (opção --validar).
+    #[error("Validação estrutural: {0} inconsistência(s) encontrada(s) nos arquivos SPED EFD.")]
+    StructuralValidationFailed(usize),
+
+    /// Bloco de assinatura digital (após o Registro 9999) ilegível.
+    #[error("Assinatura digital inválida: {0}")]
+    InvalidSignature(String),
+
+    /// Arquivos sem assinatura, com signatário divergente ou alterados (opção --assinatura).
+    #[error("Assinatura digital: {0} arquivo(s) com divergência.")]
+    SignatureCheckFailed(usize),
+
+    /// A comparação de arquivos (opção --diff) exige exatamente dois arquivos selecionados.
+    #[error(
+        "Comparação de arquivos (opção --diff): selecione exatamente dois arquivos (ex: '--diff -r 1 2')."
+    )]
+    DiffRequiresTwoFiles,
+
     /// Erro quando um registro específico solicitado não é encontrado no arquivo.
     #[error("Registro '{0}' não encontrado.")]
     RecordNotFound(String),
@@ -0,0 +1,358 @@
+use compact_str::CompactString;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    BUFFER_CAPACITY, DELIMITER_CHAR, EFDError, EFDResult, Leiaute, ResultExt, SpedFile,
+    estimar_nivel, parse_sped_fields, regra_hierarquia,
+};
+
+// ============================================================================
+// Erros de leitura (modo leniente)
+// ============================================================================
+
+/// Erro encontrado na leitura de uma linha do arquivo SPED EFD no modo leniente.
+///
+/// No modo leniente (`--leniente`), a linha com erro é descartada e a leitura prossegue,
+/// de modo que todos os problemas do arquivo são listados de uma só vez.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErroDeLeitura {
+    /// Arquivo SPED EFD analisado.
+    pub arquivo: PathBuf,
+
+    /// Número da linha descartada.
+    pub linha: usize,
+
+    /// Código do registro (ex: "C170"), obtido do campo 01 da linha.
+    pub registro: CompactString,
+
+    /// Nome do campo com erro, quando informado pelo parser (ex: "VL_ITEM").
+    pub campo: Option<String>,
+
+    /// Descrição do erro (mensagem do `EFDError` em uma única linha).
+    pub descricao: String,
+}
+
+impl ErroDeLeitura {
+    /// Registra o erro ocorrido na leitura da linha `bytes` (sem o terminador).
+    pub fn new(arquivo: &Path, linha: usize, bytes: &[u8], error: &EFDError) -> Self {
+        let registro = bytes
+            .split(|&b| b == DELIMITER_CHAR as u8)
+            .nth(1)
+            .map(|reg| String::from_utf8_lossy(reg.trim_ascii()).to_ascii_uppercase())
+            .unwrap_or_default();
+
+        let causa = causa_do_erro(error);
+
+        let descricao = causa
+            .to_string()
+            .lines()
+            .map(str::trim)
+            .filter(|linha| !linha.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self {
+            arquivo: arquivo.to_path_buf(),
+            linha,
+            registro: registro.into(),
+            campo: campo_do_erro(causa).map(String::from),
+            descricao,
+        }
+    }
+}
+
+impl fmt::Display for ErroDeLeitura {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:[linha nº {}] Registro {}",
+            self.arquivo.display(),
+            self.linha,
+            self.registro
+        )?;
+        if let Some(campo) = &self.campo {
+            write!(f, ", campo {campo}")?;
+        }
+        write!(f, ": {}", self.descricao)
+    }
+}
+
+/// Erros de leitura da linha descartada no modo leniente, um para cada campo com erro.
+///
+/// O parser interrompe a conversão da linha no primeiro campo inválido. Para que todos os
+/// campos com erro sejam listados, o campo inválido é esvaziado e a linha `texto` é
+/// convertida novamente, até que não reste erro de conversão de campo.
+pub fn erros_da_linha(
+    arquivo: &Path,
+    linha: usize,
+    bytes: &[u8],
+    texto: &str,
+    leiaute: &Leiaute,
+    error: EFDError,
+) -> Vec<ErroDeLeitura> {
+    let mut erros = vec![ErroDeLeitura::new(arquivo, linha, bytes, &error)];
+
+    let delimitador = DELIMITER_CHAR.to_string();
+    let mut campos: Vec<&str> = texto.split(DELIMITER_CHAR).collect();
+    let mut reg_buf = [0u8; 4];
+    let mut error = error;
+
+    // Cada iteração esvazia um campo preenchido: no máximo um parse por campo.
+    while let Some(valor) = valor_do_erro(causa_do_erro(&error)) {
+        // Campos 0 e 1: antes do primeiro delimitador e código do registro (REG).
+        let Some(indice) = campos
+            .iter()
+            .skip(2)
+            .position(|campo| !campo.trim().is_empty() && campo.trim() == valor)
+            .map(|posicao| posicao + 2)
+        else {
+            break;
+        };
+        campos[indice] = "";
+
+        let linha_corrigida = campos.join(&delimitador);
+        match parse_sped_fields(arquivo, linha, &linha_corrigida, &mut reg_buf, leiaute) {
+            Err(proximo) => {
+                let erro = ErroDeLeitura::new(arquivo, linha, bytes, &proximo);
+                if erros.contains(&erro) {
+                    break;
+                }
+                erros.push(erro);
+                error = proximo;
+            }
+            Ok(_) => break,
+        }
+    }
+
+    erros
+}
+
+/// Registro descartado no modo leniente: os seus registros descendentes (ex: C170 de um
+/// C100 descartado) não podem ser vinculados ao registro pai anterior e também são descartados.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistroDescartado {
+    /// Código do registro descartado (ex: "C100").
+    pub registro: CompactString,
+
+    /// Linha do registro descartado.
+    pub linha: usize,
+}
+
+impl RegistroDescartado {
+    /// Atualiza o registro pai descartado após a linha descartada `erro`.
+    ///
+    /// Uma linha descendente do registro já descartado não o substitui, e registros
+    /// fora da tabela de hierarquia não têm descendentes conhecidos.
+    pub fn atualizar(descartado: &mut Option<Self>, erro: &ErroDeLeitura) {
+        let descende = descartado
+            .as_ref()
+            .is_some_and(|pai| pai.contem(&erro.registro));
+
+        if !descende && regra_hierarquia(&erro.registro).is_some() {
+            *descartado = Some(Self {
+                registro: erro.registro.clone(),
+                linha: erro.linha,
+            });
+        }
+    }
+
+    /// Verifica se `registro` descende do registro descartado, pela tabela de hierarquia
+    /// (ou pelo nível estimado, para os registros fora da tabela).
+    pub fn contem(&self, registro: &str) -> bool {
+        if regra_hierarquia(registro).is_none() {
+            return estimar_nivel(registro) > estimar_nivel(&self.registro);
+        }
+
+        let mut atual = registro;
+        while let Some(pai) = regra_hierarquia(atual).and_then(|regra| regra.pai) {
+            if pai == self.registro {
+                return true;
+            }
+            atual = pai;
+        }
+
+        false
+    }
+
+    /// Erro de leitura do registro descendente descartado na `linha`.
+    pub fn erro_do_descendente(
+        &self,
+        arquivo: &Path,
+        linha: usize,
+        registro: &str,
+    ) -> ErroDeLeitura {
+        ErroDeLeitura {
+            arquivo: arquivo.to_path_buf(),
+            linha,
+            registro: registro.into(),
+            campo: None,
+            descricao: format!(
+                "registro descendente do Registro {} descartado (linha nº {}).",
+                self.registro, self.linha
+            ),
+        }
+    }
+}
+
+/// Descarta do `sped_file` os registros descendentes das linhas descartadas no modo
+/// leniente, até o próximo registro de mesmo nível ou de nível superior, e acrescenta
+/// um erro de leitura para cada um deles. Os erros são ordenados pelas linhas.
+pub fn descartar_descendentes(
+    arquivo: &Path,
+    sped_file: &mut SpedFile,
+    erros: &mut Vec<ErroDeLeitura>,
+) {
+    if erros.is_empty() {
+        return;
+    }
+    erros.sort_by_key(|erro| erro.linha);
+
+    let mut registros: Vec<(usize, &str)> = sped_file
+        .iter_registros()
+        .map(|r| (r.line_number(), r.registro_name()))
+        .collect();
+    registros.sort_unstable_by_key(|&(linha, _)| linha);
+
+    let mut descendentes = Vec::new();
+    let mut descartado: Option<RegistroDescartado> = None;
+    let mut proximo_erro = erros.iter().peekable();
+
+    for (linha, registro) in registros {
+        while let Some(erro) = proximo_erro.next_if(|erro| erro.linha < linha) {
+            RegistroDescartado::atualizar(&mut descartado, erro);
+        }
+
+        match &descartado {
+            Some(pai) if pai.contem(registro) => {
+                descendentes.push(pai.erro_do_descendente(arquivo, linha, registro));
+            }
+            _ => descartado = None,
+        }
+    }
+
+    if descendentes.is_empty() {
+        return;
+    }
+
+    let linhas: BTreeSet<usize> = descendentes.iter().map(|erro| erro.linha).collect();
+    sped_file.remover_linhas(&linhas);
+
+    erros.append(&mut descendentes);
+    erros.sort_by_key(|erro| erro.linha);
+}
+
+/// Remove as camadas de localização (`EFDError::Position`) e retorna o erro original.
+fn causa_do_erro(error: &EFDError) -> &EFDError {
+    match error {
+        EFDError::Position { source, .. } => causa_do_erro(source),
+        other => other,
+    }
+}
+
+/// Nome do campo informado pelos erros de conversão de campo.
+fn campo_do_erro(error: &EFDError) -> Option<&str> {
+    match error {
+        EFDError::InvalidField { campo, .. } => Some(campo),
+        EFDError::InvalidCNPJ { campo_nome, .. }
+        | EFDError::ParseDecimalError { campo_nome, .. }
+        | EFDError::ParseDateError { campo_nome, .. }
+        | EFDError::ParseIntegerError { campo_nome, .. } => Some(campo_nome),
+        _ => None,
+    }
+}
+
+/// Valor do campo informado pelos erros de conversão de campo.
+fn valor_do_erro(error: &EFDError) -> Option<&str> {
+    match error {
+        EFDError::InvalidField { valor, .. } => Some(valor),
+        EFDError::InvalidCNPJ { cnpj, .. } => Some(cnpj),
+        EFDError::ParseDecimalError { valor_str, .. } => Some(valor_str),
+        EFDError::ParseDateError { data_str, .. }
+        | EFDError::ParseIntegerError { data_str, .. } => Some(data_str),
+        _ => None,
+    }
+}
+
+/// Linhas descartadas no modo leniente, por registro, e o efeito do descarte nos demais
+/// relatórios, ou String vazia se não houver erros de leitura.
+pub fn relatorio_erros_de_leitura(erros: &[ErroDeLeitura]) -> String {
+    if erros.is_empty() {
+        return String::new();
+    }
+
+    // Registro -> linhas descartadas (a mesma linha pode ter vários campos com erro)
+    let mut descartadas: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for erro in erros {
+        descartadas
+            .entry(erro.registro.as_str())
+            .or_default()
+            .insert(erro.linha);
+    }
+
+    let total_de_linhas: usize = descartadas.values().map(BTreeSet::len).sum();
+    let registros: Vec<String> = descartadas
+        .iter()
+        .map(|(registro, linhas)| {
+            let linhas: Vec<String> = linhas.iter().map(usize::to_string).collect();
+            format!("{registro} (linha nº {})", linhas.join(", "))
+        })
+        .collect();
+
+    format!(
+        "Modo leniente: {total_de_linhas} linha(s) descartada(s) ({} erro(s) de leitura).\n\
+         \x20  Registros descartados: {}.\n\
+         \x20  Os registros descendentes de um registro descartado (ex: C170 de um C100) \
+         também são descartados.\n\
+         \x20  Os registros descartados não compõem os documentos, os totais e as conciliações \
+         desta escrituração:\n\
+         \x20  divergências dos registros relacionados (ex: C100 x Σ C170, Bloco M x documentos) \
+         podem decorrer do descarte.\n\n",
+        erros.len(),
+        registros.join("; "),
+    )
+}
+
+/// Grava os erros de leitura em arquivo CSV (delimitado por '|'), ao lado dos demais resultados.
+pub fn write_erros_csv(path_csv: &Path, erros: &[ErroDeLeitura]) -> EFDResult<()> {
+    let file = File::create(path_csv).map_loc(|e| EFDError::InOut {
+        source: e,
+        path: path_csv.to_path_buf(),
+    })?;
+
+    let buffer = BufWriter::with_capacity(BUFFER_CAPACITY, file);
+
+    let mut csv_builder = csv::WriterBuilder::new()
+        .delimiter(DELIMITER_CHAR as u8)
+        .has_headers(true)
+        .quote_style(csv::QuoteStyle::NonNumeric)
+        .from_writer(buffer);
+
+    csv_builder.write_record(["Arquivo", "Linha", "Registro", "Campo", "Descrição do Erro"])?;
+
+    for erro in erros {
+        csv_builder.write_record([
+            erro.arquivo.display().to_string().as_str(),
+            erro.linha.to_string().as_str(),
+            erro.registro.as_str(),
+            erro.campo.as_deref().unwrap_or_default(),
+            erro.descricao.as_str(),
+        ])?;
+    }
+
+    csv_builder.flush()?;
+    Ok(())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/erros_de_leitura_tests.rs"]
+mod erros_de_leitura_tests;
@@ -44,6 +44,12 @@ pub enum SheetType {
     ConsolidacaoCST,
     /// Detalhamento da análise de naturezas de crédito.
     AnaliseCreditos,
+    /// Itens com valores de PIS/COFINS divergentes de base de cálculo × alíquota.
+    DivergenciasDeCalculo,
+    /// Documentos fiscais escriturados em mais de um arquivo ou estabelecimento.
+    DocumentosDuplicados,
+    /// Contribuição Previdenciária sobre a Receita Bruta por estabelecimento (Bloco P).
+    ContribuicaoPrevidenciaria,
 }
 
 impl SheetType {
@@ -52,10 +58,13 @@ impl SheetType {
             Self::ItensDocsFiscais => "Itens de Docs Fiscais",
             Self::ConsolidacaoCST => "Consolidação CST",
             Self::AnaliseCreditos => "Análise dos Créditos",
+            Self::DivergenciasDeCalculo => "Divergências de Cálculo",
+            Self::DocumentosDuplicados => "Documentos Duplicados",
+            Self::ContribuicaoPrevidenciaria => "CPRB",
         }
     }
     pub fn is_itens(&self) -> bool {
-        matches!(self, Self::ItensDocsFiscais)
+        matches!(self, Self::ItensDocsFiscais | Self::DivergenciasDeCalculo)
     }
 }
 
@@ -32,8 +32,9 @@ use struct_iterable::Iterable;
 
 use crate::{
     AnaliseDosCreditos, BUFFER_CAPACITY, CodigoDoCredito, CodigoSituacaoTributaria,
-    ConsolidacaoCST, DocsFiscais, EFDError, EFDResult, IndicadorDeOrigem, NaturezaBaseCalculo,
-    ResultExt, TipoDeCredito, TipoDeOperacao, TipoDoItem, display_cst, excel_format::*,
+    ConsolidacaoCST, ContribuicaoPrevidenciaria, DocsFiscais, DocumentoDuplicado, EFDError,
+    EFDResult, IndicadorDeOrigem, NaturezaBaseCalculo, ResultExt, TipoDeCredito, TipoDeOperacao,
+    TipoDoItem, display_cst, excel_format::*,
 };
 
 // --- Macros ---
@@ -79,6 +80,12 @@ pub struct AllData<'a> {
     pub cst: &'a [ConsolidacaoCST],
     /// Credit analysis data categorized by core calculations.
     pub nat: &'a [AnaliseDosCreditos],
+    /// Items whose PIS/COFINS values diverge from the recomputed tax base × rate.
+    pub div: &'a [DocsFiscais],
+    /// Occurrences of documents recorded in more than one file or establishment.
+    pub dup: &'a [DocumentoDuplicado],
+    /// Payroll-tax-relief contribution (CPRB) per establishment and economic activity.
+    pub cprb: &'a [ContribuicaoPrevidenciaria],
 }
 
 impl<'a> AllData<'a> {
@@ -87,8 +94,18 @@ impl<'a> AllData<'a> {
         efd: &'a [DocsFiscais],
         cst: &'a [ConsolidacaoCST],
         nat: &'a [AnaliseDosCreditos],
+        div: &'a [DocsFiscais],
+        dup: &'a [DocumentoDuplicado],
+        cprb: &'a [ContribuicaoPrevidenciaria],
     ) -> Self {
-        Self { efd, cst, nat }
+        Self {
+            efd,
+            cst,
+            nat,
+            div,
+            dup,
+            cprb,
+        }
     }
 
     /// Generates all worksheets concurrently using a structured Rayon scope.
@@ -104,6 +121,9 @@ impl<'a> AllData<'a> {
         let mut res_efd: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
         let mut res_cst: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
         let mut res_nat: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
+        let mut res_div: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
+        let mut res_dup: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
+        let mut res_cprb: EFDResult<Vec<Worksheet>> = Ok(Vec::new());
 
         // We use a Rayon scope to spawn detached logical tasks on separate threads.
         // This ensures the main thread coordinates thread-safety barriers.
@@ -135,12 +155,42 @@ impl<'a> AllData<'a> {
                     2,
                 );
             });
+            s.spawn(|_| {
+                res_div = process_sheet_type(
+                    self.div,
+                    SheetType::DivergenciasDeCalculo,
+                    registry,
+                    multiprogressbar,
+                    3,
+                );
+            });
+            s.spawn(|_| {
+                res_dup = process_sheet_type(
+                    self.dup,
+                    SheetType::DocumentosDuplicados,
+                    registry,
+                    multiprogressbar,
+                    4,
+                );
+            });
+            s.spawn(|_| {
+                res_cprb = process_sheet_type(
+                    self.cprb,
+                    SheetType::ContribuicaoPrevidenciaria,
+                    registry,
+                    multiprogressbar,
+                    5,
+                );
+            });
         });
 
         // Safely propagate first-occurring thread errors and assemble the resulting sequence.
         let mut worksheets = res_efd?;
         worksheets.extend(res_cst?);
         worksheets.extend(res_nat?);
+        worksheets.extend(res_div?);
+        worksheets.extend(res_dup?);
+        worksheets.extend(res_cprb?);
 
         Ok(worksheets)
     }
@@ -184,6 +234,33 @@ impl<'a> AllData<'a> {
             2,
             memory_mode,
         )?;
+        process_sheet_type_sequential(
+            workbook,
+            self.div,
+            SheetType::DivergenciasDeCalculo,
+            registry,
+            multiprogressbar,
+            3,
+            memory_mode,
+        )?;
+        process_sheet_type_sequential(
+            workbook,
+            self.dup,
+            SheetType::DocumentosDuplicados,
+            registry,
+            multiprogressbar,
+            4,
+            memory_mode,
+        )?;
+        process_sheet_type_sequential(
+            workbook,
+            self.cprb,
+            SheetType::ContribuicaoPrevidenciaria,
+            registry,
+            multiprogressbar,
+            5,
+            memory_mode,
+        )?;
         Ok(())
     }
 }
@@ -199,6 +276,8 @@ pub fn write_xlsx(
     data_efd: &[DocsFiscais],
     data_cst: &[ConsolidacaoCST],
     data_nat: &[AnaliseDosCreditos],
+    data_dup: &[DocumentoDuplicado],
+    data_cprb: &[ContribuicaoPrevidenciaria],
     memory_mode: ExcelMemoryMode,
 ) -> EFDResult<()> {
     let file = File::create(path_xlsx).map_loc(|e| EFDError::InOut {
@@ -206,8 +285,15 @@ pub fn write_xlsx(
         path: path_xlsx.to_path_buf(),
     })?;
 
+    // Items flagged by the PIS/COFINS recomputation get a dedicated worksheet
+    let data_div: Vec<DocsFiscais> = data_efd
+        .iter()
+        .filter(|doc| doc.calculo_divergente())
+        .cloned()
+        .collect();
+
     // Instantiate our unified data context
-    let all_data = AllData::new(data_efd, data_cst, data_nat);
+    let all_data = AllData::new(data_efd, data_cst, data_nat, &data_div, data_dup, data_cprb);
 
     let buffer = BufWriter::with_capacity(BUFFER_CAPACITY, file);
     let mut workbook = Workbook::new();
@@ -7,12 +7,13 @@ use std::{collections::HashMap, fmt::Write, str::FromStr, sync::Arc};
 
 use crate::{
     ALIQ_BASICA_COF, ALIQ_BASICA_PIS, Bloco1, BlocoA, BlocoC, BlocoD, BlocoF, BlocoI, BlocoM,
-    CSTOption, CodigoDoCredito, CodigoSituacaoTributaria, DECIMAL_ALIQ, DECIMAL_VALOR, DecimalExt,
-    DocsFiscais, IndicadorDeOrigem, MesesDoAno, ModeloDocFiscal, NaturezaBaseCalculo, SpedContext,
-    SpedFile, SpedRecordTrait, StringParser, TipoDeCredito, TipoDeOperacao, blocos::*,
-    capture_cnpj, cred_presumido, impl_dopai, impl_filho, is_importacao, obter_natureza_da_bc,
+    CSTOption, CalculoDaContribuicao, CodigoDoCredito, CodigoSituacaoTributaria, DECIMAL_ALIQ,
+    DECIMAL_VALOR, DadosDoDocumento, DecimalExt, DocsFiscais, IndicadorDeOrigem, MesesDoAno,
+    ModeloDocFiscal, NaturezaBaseCalculo, SpedContext, SpedFile, SpedRecordTrait, StringParser,
+    TipoDeCredito, TipoDeOperacao, blocos::*, capture_cnpj, cnpj_valido, cpf_valido,
+    cred_presumido, impl_dopai, impl_filho, is_importacao, obter_natureza_da_bc,
     obter_pis_da_tabela_estatica, process_child_and_parent, process_correlations,
-    process_only_child, store_pis,
+    process_only_child, store_pis, verificar_calculo, verificar_chave,
 };
 
 const NUM_DE_ESTABELECIMENTOS: usize = 1;
@@ -88,10 +89,18 @@ pub trait RegistroPai {
     fn get_cod_part(&self) -> Option<&str> {
         None
     }
+    /// Indicador do emitente do documento fiscal: 0 - Emissão própria; 1 - Terceiros
+    fn get_ind_emit(&self) -> Option<&str> {
+        None
+    }
     /// Número do documento fiscal
     fn get_num_doc(&self) -> Option<usize> {
         None
     }
+    /// Série do documento fiscal
+    fn get_serie(&self) -> Option<&str> {
+        None
+    }
 
     // --- Valores de Impostos Retidos/Totais ---
 
@@ -203,6 +212,12 @@ pub trait RegistroFilho: SpedRecordTrait {
     fn get_valor_pis(&self) -> Option<Decimal> {
         None
     }
+    fn get_quant_bc_pis(&self) -> Option<Decimal> {
+        None
+    }
+    fn get_aliq_pis_quant(&self) -> Option<Decimal> {
+        None
+    }
 
     // COFINS
     fn get_valor_bc_cofins(&self) -> Option<Decimal> {
@@ -214,6 +229,12 @@ pub trait RegistroFilho: SpedRecordTrait {
     fn get_valor_cofins(&self) -> Option<Decimal> {
         None
     }
+    fn get_quant_bc_cofins(&self) -> Option<Decimal> {
+        None
+    }
+    fn get_aliq_cofins_quant(&self) -> Option<Decimal> {
+        None
+    }
 
     // Outros Tributos (ICMS, ISS, IPI)
     fn get_valor_iss(&self) -> Option<Decimal> {
@@ -261,6 +282,7 @@ impl_filho!(RegistroA170, {
 impl_dopai!(RegistroC100, {
     get_dt_emissao: dt_doc, get_dt_entrada: dt_e_s, get_chave: chv_nfe,
     get_cod_part: cod_part, get_cod_mod: cod_mod, get_num_doc: num_doc,
+    get_ind_emit: ind_emit, get_serie: serie,
 });
 impl_dopai!(RegistroC110, {
     get_cod_inf: cod_inf
@@ -273,12 +295,16 @@ impl_filho!(RegistroC170, {
     get_valor_bc_pis: vl_bc_pis, get_aliq_pis: aliq_pis, get_valor_pis: vl_pis,
     get_valor_bc_cofins: vl_bc_cofins, get_aliq_cofins: aliq_cofins, get_valor_cofins: vl_cofins,
     get_valor_bc_icms: vl_bc_icms, get_aliq_icms: aliq_icms, get_valor_icms: vl_icms,
-    get_valor_bc_icms_st: vl_bc_icms_st, get_valor_icms_st: vl_icms_st, get_valor_ipi: vl_ipi
+    get_valor_bc_icms_st: vl_bc_icms_st, get_valor_icms_st: vl_icms_st, get_valor_ipi: vl_ipi,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 impl_filho!(RegistroC175, {
     get_valor_item: vl_opr, get_cst_pis: cst_pis, get_cst_cofins: cst_cofins, get_cfop: cfop,
     get_valor_bc_pis: vl_bc_pis, get_aliq_pis: aliq_pis, get_valor_pis: vl_pis,
-    get_valor_bc_cofins: vl_bc_cofins, get_aliq_cofins: aliq_cofins, get_valor_cofins: vl_cofins
+    get_valor_bc_cofins: vl_bc_cofins, get_aliq_cofins: aliq_cofins, get_valor_cofins: vl_cofins,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 // Blocos Especiais (filhos que agem como itens)
@@ -294,7 +320,8 @@ impl_filho!(RegistroC181, {
 impl_filho!(RegistroC185, {
     get_cst_cofins: cst_cofins, get_cfop: cfop, get_valor_item: vl_item,
     get_valor_bc_cofins: vl_bc_cofins, get_aliq_cofins: aliq_cofins,
-    get_valor_cofins: vl_cofins, get_cod_cta: cod_cta
+    get_valor_cofins: vl_cofins, get_cod_cta: cod_cta,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 /*
@@ -320,6 +347,7 @@ impl_filho!(RegistroC195, {
     get_part_override: cnpj_cpf_part, get_cfop: cfop, get_cod_cta: cod_cta,
     get_cst_cofins: cst_cofins, get_valor_item: vl_item, get_valor_bc_cofins: vl_bc_cofins,
     get_aliq_cofins: aliq_cofins, get_valor_cofins: vl_cofins,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 impl_dopai!(RegistroC380, { get_dt_emissao: dt_doc_ini });
@@ -328,7 +356,8 @@ impl_filho!(RegistroC381, {
 });
 impl_filho!(RegistroC385, {
     get_valor_item: vl_item, get_cst_cofins: cst_cofins, get_aliq_cofins: aliq_cofins,
-    get_valor_cofins: vl_cofins, get_valor_bc_cofins: vl_bc_cofins
+    get_valor_cofins: vl_cofins, get_valor_bc_cofins: vl_bc_cofins,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 impl_dopai!(RegistroC395, { get_dt_emissao: dt_doc, get_cod_part: cod_part });
@@ -347,7 +376,8 @@ impl_filho!(RegistroC481, {
 impl_filho!(RegistroC485, {
     get_valor_item: vl_item, get_cst_cofins: cst_cofins, get_aliq_cofins: aliq_cofins,
     get_valor_cofins: vl_cofins, get_valor_bc_cofins: vl_bc_cofins,
-    get_cod_item: cod_item, get_cod_cta: cod_cta
+    get_cod_item: cod_item, get_cod_cta: cod_cta,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 impl_dopai!(RegistroC490, { get_dt_emissao: dt_doc_ini });
@@ -356,14 +386,15 @@ impl_filho!(RegistroC491, {
 });
 impl_filho!(RegistroC495, {
     get_valor_item: vl_item, get_cst_cofins: cst_cofins, get_aliq_cofins: aliq_cofins,
-    get_valor_cofins: vl_cofins, get_valor_bc_cofins: vl_bc_cofins, get_cfop: cfop
+    get_valor_cofins: vl_cofins, get_valor_bc_cofins: vl_bc_cofins, get_cfop: cfop,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 impl_dopai!(RegistroC500, {
     get_dt_emissao: dt_doc, get_dt_entrada: dt_ent,
     get_cod_part: cod_part, get_cod_mod: cod_mod,
     get_num_doc: num_doc, get_valor_icms: vl_icms,
-    get_cod_inf: cod_inf, get_chave: chv_doce,
+    get_cod_inf: cod_inf, get_chave: chv_doce, get_serie: ser,
 });
 impl_filho!(RegistroC501, {
     get_cst_pis: cst_pis, get_valor_item: vl_item, get_nat_bc_cred: nat_bc_cred,
@@ -393,7 +424,9 @@ impl_filho!(RegistroC870, {
 });
 impl_filho!(RegistroC880, {
     get_valor_item: vl_item, get_cst_pis: cst_pis, get_cst_cofins: cst_cofins, get_cfop: cfop,
-    get_valor_pis: vl_pis, get_valor_cofins: vl_cofins
+    get_valor_pis: vl_pis, get_valor_cofins: vl_cofins,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 // Bloco D
@@ -403,6 +436,7 @@ impl_dopai!(RegistroD100, {
     get_dt_emissao: dt_doc, get_dt_entrada: dt_a_p,
     get_valor_bc_icms: vl_bc_icms, get_valor_icms: vl_icms,
     get_cod_inf: cod_inf, get_cod_cta: cod_cta,
+    get_ind_emit: ind_emit, get_serie: ser,
 });
 impl_filho!(RegistroD101, {
     get_nat_bc_cred: nat_bc_cred, get_valor_item: vl_item, get_cst_pis: cst_pis,
@@ -439,7 +473,9 @@ impl_filho!(RegistroD300, {
 impl_filho!(RegistroD350, {
     get_valor_item: vl_brt, get_cst_pis: cst_pis, get_cst_cofins: cst_cofins, get_aliq_pis: aliq_pis,
     get_valor_pis: vl_pis, get_aliq_cofins: aliq_cofins, get_valor_cofins: vl_cofins,
-    get_valor_bc_cofins: vl_bc_cofins
+    get_valor_bc_cofins: vl_bc_cofins,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 impl_dopai!(RegistroD500, {
@@ -540,6 +576,8 @@ impl_filho!(RegistroF510, {
     get_cst_pis: cst_pis, get_cst_cofins: cst_cofins,
     get_valor_pis: vl_pis, get_valor_cofins: vl_cofins,
     get_cod_mod: cod_mod, get_cfop: cfop, get_cod_cta: cod_cta,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 impl_filho!(RegistroF525, {
     get_valor_item: vl_rec, get_info_compl: info_compl,
@@ -561,6 +599,8 @@ impl_filho!(RegistroF560, {
     get_cst_pis: cst_pis, get_cst_cofins: cst_cofins,
     get_valor_pis: vl_pis, get_valor_cofins: vl_cofins,
     get_cod_mod: cod_mod, get_cfop: cfop, get_cod_cta: cod_cta,
+    get_quant_bc_pis: quant_bc_pis, get_aliq_pis_quant: aliq_pis_quant,
+    get_quant_bc_cofins: quant_bc_cofins, get_aliq_cofins_quant: aliq_cofins_quant,
 });
 
 // Bloco I
@@ -821,7 +861,9 @@ struct ParentHeader<'a> {
     cod_mod: Option<&'a str>,
     cod_ncm: Option<&'a str>,
     cod_part: Option<&'a str>,
+    ind_emit: Option<&'a str>,
     num_doc: Option<usize>,
+    serie: Option<&'a str>,
     vl_iss: Option<Decimal>,
     vl_bc_icms: Option<Decimal>,
     vl_icms: Option<Decimal>,
@@ -846,7 +888,9 @@ impl<'a> ParentHeader<'a> {
             cod_mod: p.get_cod_mod(),
             cod_ncm: p.get_cod_ncm(),
             cod_part: p.get_cod_part(),
+            ind_emit: p.get_ind_emit(),
             num_doc: p.get_num_doc(),
+            serie: p.get_serie(),
             vl_iss: p.get_valor_iss(),
             vl_bc_icms: p.get_valor_bc_icms(),
             vl_icms: p.get_valor_icms(),
@@ -868,9 +912,11 @@ impl<'a> ParentHeader<'a> {
 
 #[derive(Clone)]
 struct DocsBuilder<'a> {
-    ctx: &'a SpedContext,     // [STACK] 8 bytes (ponteiro)
-    header: ParentHeader<'a>, // [STACK] ~100 bytes (apenas ponteiros e números)
-    doc: DocsFiscais,         // [STACK] Estrutura contendo Arcs (ponteiros inteligentes)
+    ctx: &'a SpedContext,                  // [STACK] 8 bytes (ponteiro)
+    header: ParentHeader<'a>,              // [STACK] ~100 bytes (apenas ponteiros e números)
+    doc: DocsFiscais, // [STACK] Estrutura contendo Arcs (ponteiros inteligentes)
+    calculo_pis: CalculoDaContribuicao, // Bases para o recálculo de PIS/PASEP
+    calculo_cofins: CalculoDaContribuicao, // Bases para o recálculo de COFINS
 }
 
 impl<'a> DocsBuilder<'a> {
@@ -904,6 +950,8 @@ impl<'a> DocsBuilder<'a> {
             ctx,
             header: ParentHeader::default(), // Inicia com header vazio
             doc,
+            calculo_pis: CalculoDaContribuicao::default(),
+            calculo_cofins: CalculoDaContribuicao::default(),
         }
     }
 
@@ -938,6 +986,7 @@ impl<'a> DocsBuilder<'a> {
         builder
             .with_header(filho) // Mescla dados de cabeçalho (Datas, Chaves)
             .with_itens_and_participant(filho) // Resolve Itens e Participante
+            .with_verificacao_chave() // Valida a chave de acesso (NF-e, CT-e, ...)
             .with_values_and_classification(filho) // Aplica valores e CSTs
     }
 
@@ -966,6 +1015,7 @@ impl<'a> DocsBuilder<'a> {
         self.doc.cfop = filho.get_cfop().or(self.header.cfop);
         self.doc.chave_doc = self.header.chave.unwrap_or_default().into();
         self.doc.num_doc = self.header.num_doc;
+        self.doc.serie_doc = self.header.serie.unwrap_or_default().into();
         self.doc.cod_ncm = self.header.cod_ncm.unwrap_or_default().into();
 
         self.doc.valor_iss = filho.get_valor_iss().or(self.header.vl_iss);
@@ -1000,6 +1050,17 @@ impl<'a> DocsBuilder<'a> {
 
         self.apply_participant_info(cod_part);
 
+        // 3. Emitente: o estabelecimento (IND_EMIT = 0) ou o participante.
+        self.doc.emitente = if self.header.ind_emit == Some("0") {
+            self.doc.estabelecimento_cnpj.clone()
+        } else {
+            [&self.doc.participante_cnpj, &self.doc.participante_cpf]
+                .into_iter()
+                .find(|p| !p.is_empty())
+                .cloned()
+                .unwrap_or_default()
+        };
+
         self
     }
 
@@ -1036,12 +1097,14 @@ impl<'a> DocsBuilder<'a> {
         };
 
         // 1. Tentativa pelo Código do Participante (Lookup rápido no Hash de Participantes)
+        // CNPJ/CPF com dígitos verificadores inválidos não são propagados
+        // (ver SpedFile::relatorio_identificadores_invalidos).
         if let Some(reg_0150) = self.ctx.participantes.get(cod) {
             // Zero-copy clones (Arc)
-            if let Some(cnpj) = &reg_0150.cnpj {
+            if let Some(cnpj) = reg_0150.cnpj.as_ref().filter(|c| cnpj_valido(c)) {
                 self.doc.participante_cnpj = cnpj.clone();
             }
-            if let Some(cpf) = &reg_0150.cpf {
+            if let Some(cpf) = reg_0150.cpf.as_ref().filter(|c| cpf_valido(c)) {
                 self.doc.participante_cpf = cpf.clone();
             }
             if let Some(nome) = &reg_0150.nome {
@@ -1053,7 +1116,7 @@ impl<'a> DocsBuilder<'a> {
         // 2. Fallback: O código não está no cadastro (0150), mas pode ser o próprio CNPJ/CPF direto no campo.
         // Isso acontece frequentemente em registros filhos que citam terceiros não cadastrados.
         match cod.len() {
-            14 => {
+            14 if cnpj_valido(cod) => {
                 // É um CNPJ
                 self.doc.participante_cnpj = cod.into();
                 // Busca inteligente: Exato -> Base Frequente -> Vazio
@@ -1061,7 +1124,7 @@ impl<'a> DocsBuilder<'a> {
                     self.doc.participante_nome = nome;
                 }
             }
-            11 => {
+            11 if cpf_valido(cod) => {
                 // É um CPF
                 self.doc.participante_cpf = cod.into();
                 if let Some(nome) = self.ctx.obter_nome_por_cpf(cod) {
@@ -1069,11 +1132,66 @@ impl<'a> DocsBuilder<'a> {
                 }
             }
             _ => {
-                // Código desconhecido e formato inválido
+                // Código desconhecido, formato ou dígitos verificadores inválidos
             }
         }
     }
 
+    /// Verifica a chave de acesso do documento eletrônico (C100, D100, C500)
+    /// contra os dados do registro pai e do emitente.
+    fn with_verificacao_chave(mut self) -> Self {
+        let Some(chave) = self.header.chave.filter(|c| !c.is_empty()) else {
+            return self;
+        };
+
+        // Apenas modelos com chave de 44 dígitos (ex: chave da NFS-e no A100 não é verificada).
+        let com_chave_de_acesso = self
+            .header
+            .cod_mod
+            .and_then(|m| ModeloDocFiscal::from_str(m).ok())
+            .is_some_and(|modelo| modelo.possui_chave_de_acesso());
+
+        if !com_chave_de_acesso {
+            return self;
+        }
+
+        // Emissão própria (IND_EMIT = 0): o emitente é o estabelecimento.
+        // Emissão por terceiros (ou C500, sem IND_EMIT): o emitente é o participante.
+        let (cnpj_emitente, cod_mun_emitente) = if self.header.ind_emit == Some("0") {
+            let cnpj = &self.doc.estabelecimento_cnpj;
+            (
+                Some(cnpj.as_ref()).filter(|s| !s.is_empty()),
+                self.ctx
+                    .municipios_dos_estabelecimentos
+                    .get(cnpj)
+                    .map(AsRef::as_ref),
+            )
+        } else {
+            let participante = self
+                .header
+                .cod_part
+                .and_then(|cod| self.ctx.participantes.get(cod));
+            (
+                participante.and_then(|p| p.cnpj.as_deref().or(p.cpf.as_deref())),
+                participante.and_then(|p| p.cod_mun.as_deref()),
+            )
+        };
+
+        let dados = DadosDoDocumento {
+            cod_mod: self.header.cod_mod,
+            serie: self.header.serie,
+            num_doc: self.header.num_doc,
+            dt_doc: self.header.dt_emissao,
+            cnpj_emitente: cnpj_emitente.filter(|s| !s.is_empty()),
+            cod_mun_emitente: cod_mun_emitente.filter(|s| !s.is_empty()),
+        };
+
+        let verificacao = verificar_chave(chave, &dados).to_string();
+        self.doc.verificacao_chave = verificacao.into();
+
+        self
+    }
+
     fn with_values_and_classification<F>(mut self, filho: &F) -> Self
     where
         F: RegistroFilho + ?Sized,
@@ -1081,6 +1199,7 @@ impl<'a> DocsBuilder<'a> {
         // 1. Identificadores e Classificação
         self.doc.num_item = filho.get_num_item();
         self.doc.cst = filho.get_cst_cofins();
+        self.doc.cst_pis = filho.get_cst_pis();
 
         self.doc.natureza_bc = filho
             .get_nat_bc_cred()
@@ -1101,6 +1220,23 @@ impl<'a> DocsBuilder<'a> {
 
         self.doc.aliq_icms = filho.get_aliq_icms();
 
+        // 4. Campos do próprio registro para o recálculo das contribuições
+        // (sem os valores de PIS/PASEP obtidos por correlação)
+        self.calculo_pis = CalculoDaContribuicao {
+            valor_bc: filho.get_valor_bc_pis(),
+            aliquota: filho.get_aliq_pis(),
+            quant_bc: filho.get_quant_bc_pis(),
+            aliquota_quant: filho.get_aliq_pis_quant(),
+            valor_declarado: filho.get_valor_pis(),
+        };
+        self.calculo_cofins = CalculoDaContribuicao {
+            valor_bc: filho.get_valor_bc_cofins(),
+            aliquota: filho.get_aliq_cofins(),
+            quant_bc: filho.get_quant_bc_cofins(),
+            aliquota_quant: filho.get_aliq_cofins_quant(),
+            valor_declarado: filho.get_valor_cofins(),
+        };
+
         // Lookup de Natureza
         if let Some(desc) = filho
             .get_cod_nat()
@@ -1173,6 +1309,8 @@ impl<'a> DocsBuilder<'a> {
             corr_ctx,
             filho.get_aliq_cofins(),
         ) {
+            // O registro de PIS/PASEP correlacionado tem o mesmo CST (chave da correlação).
+            self.doc.cst_pis = self.doc.cst_pis.or(self.doc.cst);
             self.doc.aliq_pis = pis_data.aliq_pis;
             self.doc.valor_pis = pis_data.vl_pis;
         } else if let Some(aliq_cof) = self.doc.aliq_cofins {
@@ -1321,12 +1459,29 @@ impl<'a> DocsBuilder<'a> {
         }
     }
 
+    /// Recalcula PIS/PASEP e COFINS (base × alíquota ou quantidade × alíquota em reais)
+    /// e confronta com os valores declarados, apenas com os campos do próprio registro.
+    ///
+    /// A contribuição não declarada no registro (ex: PIS/PASEP nos registros C185 e C195,
+    /// obtido por correlação) não é verificada. Sem base de cálculo, alíquota ou valor
+    /// declarado, a coluna permanece vazia.
+    fn resolve_verificacao_calculo(mut self) -> Self {
+        if let Some(verificacao) =
+            verificar_calculo(&self.calculo_pis, &self.calculo_cofins, self.ctx.tolerancia)
+        {
+            self.doc.verificacao_calculo = verificacao.to_string().into();
+        }
+
+        self
+    }
+
     fn build(self) -> DocsFiscais {
         let mut builder = self
             .resolve_tipo_de_operacao()
             .resolve_natureza_bc()
             .resolve_indicador_de_origem()
-            .resolve_tipo_de_credito();
+            .resolve_tipo_de_credito()
+            .resolve_verificacao_calculo();
 
         // Atenção: O método format() em DocsFiscais deve estar preparado para lidar
         // com campos Arc<str>. Se o método original tentava mutar (push_str/insert)
@@ -1727,6 +1882,7 @@ impl<'a> BlocoCExtractor<'a> {
                     let doc = builder
                         .with_header(filho) // Mescla dados de cabeçalho (Datas, Chaves)
                         .with_itens_and_participant(filho) // Resolve Itens e Participante
+                        .with_verificacao_chave() // Valida a chave de acesso (NF-e)
                         .with_values_and_classification(filho) // Aplica valores e CSTs
                         .resolve_pis_correlation(&mut self.correlacao, filho)
                         .build();
@@ -0,0 +1,369 @@
+use std::{collections::HashMap, path::Path, sync::LazyLock};
+
+use crate::{Inconsistencia, RegistroGenerico, SpedFile, SpedRecordTrait};
+
+// ============================================================================
+// Tabela de hierarquia dos registros
+// ============================================================================
+
+/// Número de ocorrências permitidas de um registro sob o mesmo registro pai.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ocorrencia {
+    /// Ocorrência "1" ou "0:1": no máximo um registro por registro pai.
+    Unica,
+    /// Ocorrência "1:N" ou "0:N": vários registros por registro pai.
+    Multipla,
+}
+
+/// Regra de hierarquia de um registro, conforme o Guia Prático da EFD Contribuições.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegraHierarquia {
+    /// Registro pai imediato (`None` para os registros 0000 e 9999).
+    pub pai: Option<&'static str>,
+    /// Número de ocorrências permitidas sob o registro pai.
+    pub ocorrencia: Ocorrencia,
+}
+
+/// Tabela de hierarquia: registro -> regra (registro pai, ocorrência).
+///
+/// Os registros de abertura e encerramento de bloco (X001 e X990) são filhos do registro 0000.
+static HIERARQUIA: LazyLock<HashMap<&'static str, RegraHierarquia>> = LazyLock::new(|| {
+    use Ocorrencia::{Multipla, Unica};
+
+    #[rustfmt::skip]
+    let tabela: &[(&str, Option<&str>, Ocorrencia)] = &[
+        // Bloco 0
+        ("0000", None, Unica),
+        ("0001", Some("0000"), Unica),
+        ("0035", Some("0001"), Multipla),
+        ("0100", Some("0001"), Unica),
+        ("0110", Some("0001"), Unica),
+        ("0111", Some("0110"), Unica),
+        ("0120", Some("0001"), Multipla),
+        ("0140", Some("0001"), Multipla),
+        ("0145", Some("0140"), Unica),
+        ("0150", Some("0140"), Multipla),
+        ("0190", Some("0140"), Multipla),
+        ("0200", Some("0140"), Multipla),
+        ("0205", Some("0200"), Multipla),
+        ("0206", Some("0200"), Unica),
+        ("0208", Some("0200"), Unica),
+        ("0400", Some("0140"), Multipla),
+        ("0450", Some("0140"), Multipla),
+        ("0500", Some("0001"), Multipla),
+        ("0600", Some("0001"), Multipla),
+        ("0900", Some("0001"), Unica),
+        ("0990", Some("0000"), Unica),
+        // Bloco A
+        ("A001", Some("0000"), Unica),
+        ("A010", Some("A001"), Multipla),
+        ("A100", Some("A010"), Multipla),
+        ("A110", Some("A100"), Multipla),
+        ("A111", Some("A100"), Multipla),
+        ("A120", Some("A100"), Multipla),
+        ("A170", Some("A100"), Multipla),
+        ("A990", Some("0000"), Unica),
+        // Bloco C
+        ("C001", Some("0000"), Unica),
+        ("C010", Some("C001"), Multipla),
+        ("C100", Some("C010"), Multipla),
+        ("C110", Some("C100"), Multipla),
+        ("C111", Some("C100"), Multipla),
+        ("C120", Some("C100"), Multipla),
+        ("C170", Some("C100"), Multipla),
+        ("C175", Some("C100"), Multipla),
+        ("C180", Some("C010"), Multipla),
+        ("C181", Some("C180"), Multipla),
+        ("C185", Some("C180"), Multipla),
+        ("C188", Some("C180"), Multipla),
+        ("C190", Some("C010"), Multipla),
+        ("C191", Some("C190"), Multipla),
+        ("C195", Some("C190"), Multipla),
+        ("C198", Some("C190"), Multipla),
+        ("C199", Some("C190"), Multipla),
+        ("C380", Some("C010"), Multipla),
+        ("C381", Some("C380"), Multipla),
+        ("C385", Some("C380"), Multipla),
+        ("C395", Some("C010"), Multipla),
+        ("C396", Some("C395"), Multipla),
+        ("C400", Some("C010"), Multipla),
+        ("C405", Some("C400"), Multipla),
+        ("C481", Some("C405"), Multipla),
+        ("C485", Some("C405"), Multipla),
+        ("C489", Some("C400"), Multipla),
+        ("C490", Some("C010"), Multipla),
+        ("C491", Some("C490"), Multipla),
+        ("C495", Some("C490"), Multipla),
+        ("C499", Some("C490"), Multipla),
+        ("C500", Some("C010"), Multipla),
+        ("C501", Some("C500"), Multipla),
+        ("C505", Some("C500"), Multipla),
+        ("C509", Some("C500"), Multipla),
+        ("C600", Some("C010"), Multipla),
+        ("C601", Some("C600"), Multipla),
+        ("C605", Some("C600"), Multipla),
+        ("C609", Some("C600"), Multipla),
+        ("C800", Some("C010"), Multipla),
+        ("C810", Some("C800"), Multipla),
+        ("C820", Some("C800"), Multipla),
+        ("C830", Some("C800"), Multipla),
+        ("C860", Some("C010"), Multipla),
+        ("C870", Some("C860"), Multipla),
+        ("C880", Some("C860"), Multipla),
+        ("C890", Some("C860"), Multipla),
+        ("C990", Some("0000"), Unica),
+        // Bloco D
+        ("D001", Some("0000"), Unica),
+        ("D010", Some("D001"), Multipla),
+        ("D100", Some("D010"), Multipla),
+        ("D101", Some("D100"), Multipla),
+        ("D105", Some("D100"), Multipla),
+        ("D111", Some("D100"), Multipla),
+        ("D200", Some("D010"), Multipla),
+        ("D201", Some("D200"), Multipla),
+        ("D205", Some("D200"), Multipla),
+        ("D209", Some("D200"), Multipla),
+        ("D300", Some("D010"), Multipla),
+        ("D309", Some("D300"), Multipla),
+        ("D350", Some("D010"), Multipla),
+        ("D359", Some("D350"), Multipla),
+        ("D500", Some("D010"), Multipla),
+        ("D501", Some("D500"), Multipla),
+        ("D505", Some("D500"), Multipla),
+        ("D509", Some("D500"), Multipla),
+        ("D600", Some("D010"), Multipla),
+        ("D601", Some("D600"), Multipla),
+        ("D605", Some("D600"), Multipla),
+        ("D609", Some("D600"), Multipla),
+        ("D990", Some("0000"), Unica),
+        // Bloco F
+        ("F001", Some("0000"), Unica),
+        ("F010", Some("F001"), Multipla),
+        ("F100", Some("F010"), Multipla),
+        ("F111", Some("F100"), Multipla),
+        ("F120", Some("F010"), Multipla),
+        ("F129", Some("F120"), Multipla),
+        ("F130", Some("F010"), Multipla),
+        ("F139", Some("F130"), Multipla),
+        ("F150", Some("F010"), Multipla),
+        ("F200", Some("F010"), Multipla),
+        ("F205", Some("F200"), Unica),
+        ("F210", Some("F200"), Multipla),
+        ("F211", Some("F200"), Multipla),
+        ("F500", Some("F010"), Multipla),
+        ("F509", Some("F500"), Multipla),
+        ("F510", Some("F010"), Multipla),
+        ("F519", Some("F510"), Multipla),
+        ("F525", Some("F010"), Multipla),
+        ("F550", Some("F010"), Multipla),
+        ("F559", Some("F550"), Multipla),
+        ("F560", Some("F010"), Multipla),
+        ("F569", Some("F560"), Multipla),
+        ("F600", Some("F010"), Multipla),
+        ("F700", Some("F010"), Multipla),
+        ("F800", Some("F010"), Multipla),
+        ("F990", Some("0000"), Unica),
+        // Bloco I
+        ("I001", Some("0000"), Unica),
+        ("I010", Some("I001"), Multipla),
+        ("I100", Some("I010"), Multipla),
+        ("I199", Some("I100"), Multipla),
+        ("I200", Some("I100"), Multipla),
+        ("I299", Some("I200"), Multipla),
+        ("I300", Some("I200"), Multipla),
+        ("I399", Some("I300"), Multipla),
+        ("I990", Some("0000"), Unica),
+        // Bloco M
+        ("M001", Some("0000"), Unica),
+        ("M100", Some("M001"), Multipla),
+        ("M105", Some("M100"), Multipla),
+        ("M110", Some("M100"), Multipla),
+        ("M115", Some("M110"), Multipla),
+        ("M200", Some("M001"), Unica),
+        ("M205", Some("M200"), Multipla),
+        ("M210", Some("M200"), Multipla),
+        ("M211", Some("M210"), Unica),
+        ("M215", Some("M210"), Multipla),
+        ("M220", Some("M210"), Multipla),
+        ("M225", Some("M220"), Multipla),
+        ("M230", Some("M210"), Multipla),
+        ("M300", Some("M001"), Multipla),
+        ("M350", Some("M001"), Unica),
+        ("M400", Some("M001"), Multipla),
+        ("M410", Some("M400"), Multipla),
+        ("M500", Some("M001"), Multipla),
+        ("M505", Some("M500"), Multipla),
+        ("M510", Some("M500"), Multipla),
+        ("M515", Some("M510"), Multipla),
+        ("M600", Some("M001"), Unica),
+        ("M605", Some("M600"), Multipla),
+        ("M610", Some("M600"), Multipla),
+        ("M611", Some("M610"), Unica),
+        ("M615", Some("M610"), Multipla),
+        ("M620", Some("M610"), Multipla),
+        ("M625", Some("M620"), Multipla),
+        ("M630", Some("M610"), Multipla),
+        ("M700", Some("M001"), Multipla),
+        ("M800", Some("M001"), Multipla),
+        ("M810", Some("M800"), Multipla),
+        ("M990", Some("0000"), Unica),
+        // Bloco P
+        ("P001", Some("0000"), Unica),
+        ("P010", Some("P001"), Multipla),
+        ("P100", Some("P010"), Multipla),
+        ("P110", Some("P100"), Multipla),
+        ("P199", Some("P100"), Multipla),
+        ("P200", Some("P001"), Multipla),
+        ("P210", Some("P200"), Multipla),
+        ("P990", Some("0000"), Unica),
+        // Bloco 1
+        ("1001", Some("0000"), Unica),
+        ("1010", Some("1001"), Multipla),
+        ("1011", Some("1010"), Multipla),
+        ("1020", Some("1001"), Multipla),
+        ("1050", Some("1001"), Multipla),
+        ("1100", Some("1001"), Multipla),
+        ("1101", Some("1100"), Multipla),
+        ("1102", Some("1101"), Unica),
+        ("1200", Some("1001"), Multipla),
+        ("1210", Some("1200"), Multipla),
+        ("1220", Some("1200"), Multipla),
+        ("1300", Some("1001"), Multipla),
+        ("1500", Some("1001"), Multipla),
+        ("1501", Some("1500"), Multipla),
+        ("1502", Some("1501"), Unica),
+        ("1600", Some("1001"), Multipla),
+        ("1610", Some("1600"), Multipla),
+        ("1620", Some("1600"), Multipla),
+        ("1700", Some("1001"), Multipla),
+        ("1800", Some("1001"), Multipla),
+        ("1809", Some("1800"), Multipla),
+        ("1900", Some("1001"), Multipla),
+        ("1990", Some("0000"), Unica),
+        // Bloco 9
+        ("9001", Some("0000"), Unica),
+        ("9900", Some("9001"), Multipla),
+        ("9990", Some("0000"), Unica),
+        ("9999", None, Unica),
+    ];
+
+    tabela
+        .iter()
+        .map(|&(registro, pai, ocorrencia)| (registro, RegraHierarquia { pai, ocorrencia }))
+        .collect()
+});
+
+/// Retorna a regra de hierarquia do registro (ex: "C170" -> pai "C100", várias ocorrências).
+pub fn regra_hierarquia(registro: &str) -> Option<RegraHierarquia> {
+    HIERARQUIA.get(registro).copied()
+}
+
+// ============================================================================
+// Validação da hierarquia
+// ============================================================================
+
+/// Registro que pode receber registros filhos, mantido na pilha durante a validação.
+struct Ancestral<'a> {
+    registro: &'a str,
+    nivel: u16,
+    linha: usize,
+    /// Registro filho -> linha da primeira ocorrência sob este ancestral.
+    filhos: HashMap<&'a str, usize>,
+}
+
+/// Percorre os registros na ordem das linhas e confronta o aninhamento definido
+/// pelo `nivel` de cada registro com a tabela de hierarquia do leiaute.
+///
+/// São informados:
+/// - registros filhos órfãos (sem o registro pai antes deles);
+/// - registros subordinados a um registro pai inesperado (ex: M105 sob M500);
+/// - repetições de registros com ocorrência única (ex: dois 0145 sob o mesmo 0140).
+///
+/// Registros ausentes da tabela de hierarquia não são validados. Registros não suportados
+/// (`RegistroGenerico`) têm nível apenas estimado e também não participam do aninhamento.
+pub fn validar_hierarquia(sped_file: &SpedFile, arquivo: &Path) -> Vec<Inconsistencia> {
+    let mut registros: Vec<&dyn SpedRecordTrait> = sped_file.iter_registros().collect();
+    registros.sort_by_key(|r| r.line_number());
+
+    let mut inconsistencias = Vec::new();
+
+    // Registros de nível 0 (0000 e 9999) -> linha da primeira ocorrência
+    let mut raiz: HashMap<&str, usize> = HashMap::new();
+    let mut pilha: Vec<Ancestral> = Vec::new();
+
+    for record in registros {
+        if record.as_any().is::<RegistroGenerico>() {
+            continue;
+        }
+
+        let registro = record.registro_name();
+        let nivel = record.nivel();
+        let linha = record.line_number();
+
+        // Retorna ao ancestral de nível imediatamente superior.
+        while pilha.last().is_some_and(|topo| topo.nivel >= nivel) {
+            pilha.pop();
+        }
+
+        if let Some(regra) = regra_hierarquia(registro) {
+            let (primeira_ocorrencia, contexto) = match (regra.pai, pilha.last_mut()) {
+                (None, _) => (raiz.insert(registro, linha), String::new()),
+                (Some(pai), Some(topo)) if topo.nivel + 1 == nivel && topo.registro == pai => {
+                    let contexto = format!(" sob o registro {pai} (linha nº {})", topo.linha);
+                    (topo.filhos.insert(registro, linha), contexto)
+                }
+                (Some(pai), Some(topo)) if topo.nivel + 1 == nivel => {
+                    inconsistencias.push(Inconsistencia::new(
+                        arquivo,
+                        linha,
+                        registro,
+                        format!(
+                            "registro pai inesperado {} (linha nº {}); registro pai esperado: {pai}.",
+                            topo.registro, topo.linha
+                        ),
+                    ));
+                    (None, String::new())
+                }
+                (Some(pai), _) => {
+                    inconsistencias.push(Inconsistencia::new(
+                        arquivo,
+                        linha,
+                        registro,
+                        format!("registro filho órfão: registro pai {pai} ausente."),
+                    ));
+                    (None, String::new())
+                }
+            };
+
+            if let Some(anterior) = primeira_ocorrencia
+                && regra.ocorrencia == Ocorrencia::Unica
+            {
+                inconsistencias.push(Inconsistencia::new(
+                    arquivo,
+                    linha,
+                    registro,
+                    format!(
+                        "ocorrência repetida{contexto}: registro já informado na linha nº {anterior}."
+                    ),
+                ));
+            }
+        }
+
+        pilha.push(Ancestral {
+            registro,
+            nivel,
+            linha,
+            filhos: HashMap::new(),
+        });
+    }
+
+    inconsistencias
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/hierarquia_tests.rs"]
+mod hierarquia_tests;
@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use std::{fmt, ops::RangeInclusive, path::Path, sync::LazyLock};
+
+use crate::{
+    BlocoM, EFDResult, Inconsistencia, Registro0000, RegistroGenerico, RegistroM210Antigo,
+    RegistroM610Antigo, SpedFile, SpedParser, SpedRecord,
+};
+
+// ============================================================================
+// Registro de versões do leiaute (COD_VER)
+// ============================================================================
+
+/// Converte os campos de uma linha no `SpedRecord` correspondente.
+pub type ParserDeRegistro = fn(&Path, usize, &[&str]) -> EFDResult<SpedRecord>;
+
+/// Versão do leiaute da EFD Contribuições, conforme o Guia Prático.
+///
+/// Descreve apenas as diferenças em relação ao despacho padrão de `parse_sped_fields`
+/// (que segue o leiaute mais recente): registros com parser próprio nesta versão e
+/// registros que ainda não existiam.
+pub struct Leiaute {
+    /// Descrição da versão (ex: "leiaute até 31/12/2018").
+    pub descricao: &'static str,
+
+    /// Códigos da versão do leiaute (Registro 0000, campo COD_VER).
+    pub versoes: RangeInclusive<u8>,
+
+    /// Períodos de apuração (Registro 0000, campo DT_INI) abrangidos pela versão.
+    pub vigencia: RangeInclusive<NaiveDate>,
+
+    /// Registros cujo leiaute nesta versão difere do leiaute mais recente.
+    pub parsers: Vec<(&'static str, ParserDeRegistro)>,
+
+    /// Registros inexistentes nesta versão.
+    pub registros_inexistentes: Vec<&'static str>,
+}
+
+impl fmt::Debug for Leiaute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Leiaute")
+            .field("descricao", &self.descricao)
+            .field("versoes", &self.versoes)
+            .field("vigencia", &self.vigencia)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Leiaute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (COD_VER {:03} a {:03})",
+            self.descricao,
+            self.versoes.start(),
+            self.versoes.end()
+        )
+    }
+}
+
+/// Cria um `ParserDeRegistro` para uma variante específica de um bloco.
+///
+/// Exemplo: `parser_de_registro!(BlocoM, RM210Antigo, RegistroM210Antigo)`.
+macro_rules! parser_de_registro {
+    ($bloco:ident, $variant:ident, $struct:ident) => {
+        |path: &Path, line_number: usize, fields: &[&str]| -> EFDResult<SpedRecord> {
+            let parsed = <$struct as SpedParser>::parse_reg(path, line_number, fields)?;
+            Ok(SpedRecord::$bloco(Box::new($bloco::$variant(parsed))))
+        }
+    };
+}
+
+fn data(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, mes, dia).expect("data válida na tabela de leiautes")
+}
+
+/// Versões do leiaute em ordem cronológica. A última entrada é o leiaute mais recente.
+///
+/// Para registrar uma nova versão, basta acrescentar uma entrada a esta tabela.
+static LEIAUTES: LazyLock<Vec<Leiaute>> = LazyLock::new(|| {
+    vec![
+        // ADE Cofis nº 82/2018: os registros M210 e M610 passaram a detalhar os ajustes da
+        // base de cálculo (VL_AJUS_ACRES_BC, VL_AJUS_REDUC_BC e VL_BC_CONT_AJUS), com os
+        // novos registros M215/M615 (ajustes da base), M225/M625 e 1050.
+        Leiaute {
+            descricao: "leiaute até 31/12/2018",
+            versoes: 1..=5,
+            vigencia: NaiveDate::MIN..=data(2018, 12, 31),
+            parsers: vec![
+                (
+                    "M210",
+                    parser_de_registro!(BlocoM, RM210Antigo, RegistroM210Antigo),
+                ),
+                (
+                    "M610",
+                    parser_de_registro!(BlocoM, RM610Antigo, RegistroM610Antigo),
+                ),
+            ],
+            registros_inexistentes: vec!["M215", "M225", "M615", "M625", "1050"],
+        },
+        Leiaute {
+            descricao: "leiaute a partir de 01/01/2019",
+            versoes: 6..=u8::MAX,
+            vigencia: data(2019, 1, 1)..=NaiveDate::MAX,
+            parsers: Vec::new(),
+            registros_inexistentes: Vec::new(),
+        },
+    ]
+});
+
+impl Leiaute {
+    /// Seleciona a versão do leiaute pela versão (COD_VER) e pelo período (DT_INI).
+    ///
+    /// Ordem de preferência: versão e período compatíveis; apenas o período;
+    /// apenas a versão; e, por fim, o leiaute mais recente.
+    pub fn selecionar(cod_ver: Option<u8>, dt_ini: Option<NaiveDate>) -> &'static Leiaute {
+        let pela_versao = |l: &&Leiaute| cod_ver.is_some_and(|v| l.versoes.contains(&v));
+        let pelo_periodo = |l: &&Leiaute| dt_ini.is_some_and(|d| l.vigencia.contains(&d));
+
+        LEIAUTES
+            .iter()
+            .find(|l| pela_versao(l) && pelo_periodo(l))
+            .or_else(|| LEIAUTES.iter().find(pelo_periodo))
+            .or_else(|| LEIAUTES.iter().find(pela_versao))
+            .unwrap_or_else(Self::mais_recente)
+    }
+
+    /// Seleciona a versão do leiaute informada no Registro 0000.
+    pub fn do_registro_0000(reg_0000: &Registro0000) -> &'static Leiaute {
+        Self::selecionar(reg_0000.cod_ver, Some(reg_0000.dt_ini))
+    }
+
+    /// Leiaute mais recente (usado antes da leitura do Registro 0000).
+    pub fn mais_recente() -> &'static Leiaute {
+        LEIAUTES.last().expect("tabela de leiautes não vazia")
+    }
+
+    /// Parser específico desta versão para o registro, se houver.
+    pub fn parser(&self, registro: &str) -> Option<ParserDeRegistro> {
+        self.parsers
+            .iter()
+            .find_map(|&(nome, parser)| (nome == registro).then_some(parser))
+    }
+
+    /// Informa se o registro existe nesta versão do leiaute.
+    pub fn contem(&self, registro: &str) -> bool {
+        !self.registros_inexistentes.contains(&registro)
+    }
+}
+
+// ============================================================================
+// Registros inexistentes na versão do leiaute
+// ============================================================================
+
+/// Registros inexistentes na versão do leiaute informada no Registro 0000
+/// (ex: 1050 em escrituração com COD_VER 003), em ordem de linha.
+///
+/// Estes registros são preservados pelo parser como `RegistroGenerico`, sem abortar a
+/// leitura, e rejeitados aqui como inconsistências da escrituração.
+pub fn validar_leiaute(sped_file: &SpedFile, arquivo: &Path) -> Vec<Inconsistencia> {
+    let registro_0000 = sped_file.obter_registro::<Registro0000>("0000").ok();
+    let leiaute = registro_0000.map_or_else(Leiaute::mais_recente, Leiaute::do_registro_0000);
+    let cod_ver = registro_0000
+        .and_then(|r| r.cod_ver)
+        .map_or("N/I".to_string(), |v| format!("{v:03}"));
+
+    let mut inconsistencias: Vec<Inconsistencia> = sped_file
+        .iter_registros()
+        .filter(|r| r.as_any().is::<RegistroGenerico>() && !leiaute.contem(r.registro_name()))
+        .map(|r| {
+            Inconsistencia::new(
+                arquivo,
+                r.line_number(),
+                r.registro_name(),
+                format!("registro inexistente no leiaute do COD_VER {cod_ver}: {leiaute}"),
+            )
+        })
+        .collect();
+
+    inconsistencias.sort_by_key(|i| i.linha);
+    inconsistencias
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/leiaute_tests.rs"]
+mod leiaute_tests;
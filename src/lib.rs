@@ -1,29 +1,58 @@
 mod analyze_all;
 mod analyze_one;
+mod apuracao_bloco_m;
+mod archive;
 mod args;
+mod assinatura;
 mod blocos;
+mod calculo_das_contribuicoes;
+mod chave_de_acesso;
+mod cnpj_cpf;
+mod comparacao_de_arquivos;
+mod conciliacao_de_totais;
+mod conferencias;
 mod config;
+mod consistencia_cst_cfop;
+mod continuidade_dos_periodos;
+mod contribuicao_previdenciaria;
+mod controle_de_creditos;
+mod documentos_duplicados;
 mod error;
+mod erros_de_leitura;
 mod excel_format;
 mod excel_worksheets;
 mod extractor;
+mod hierarquia;
+mod leiaute;
 mod macros;
+mod mmap_reader;
 mod model;
 mod parser;
+mod reader;
+mod receitas_nao_tributadas;
 mod regex;
+mod retencoes_na_fonte;
+mod retificadoras;
 mod structures;
 mod tabelas;
 mod tests;
 mod traits;
 mod utils;
+mod validacao;
+mod writer;
 
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 pub use self::{
-    analyze_all::*, analyze_one::*, args::*, blocos::*, config::*, error::*, excel_format::*,
-    excel_worksheets::*, extractor::*, model::*, parser::*, regex::*, structures::*, tabelas::*,
-    traits::*, utils::*,
+    analyze_all::*, analyze_one::*, apuracao_bloco_m::*, archive::*, args::*, assinatura::*,
+    blocos::*, calculo_das_contribuicoes::*, chave_de_acesso::*, cnpj_cpf::*,
+    comparacao_de_arquivos::*, conciliacao_de_totais::*, conferencias::*, config::*,
+    consistencia_cst_cfop::*, continuidade_dos_periodos::*, contribuicao_previdenciaria::*,
+    controle_de_creditos::*, documentos_duplicados::*, error::*, erros_de_leitura::*,
+    excel_format::*, excel_worksheets::*, extractor::*, hierarquia::*, leiaute::*, mmap_reader::*,
+    model::*, parser::*, reader::*, receitas_nao_tributadas::*, regex::*, retencoes_na_fonte::*,
+    retificadoras::*, structures::*, tabelas::*, traits::*, utils::*, validacao::*, writer::*,
 };
 
 // Definição da tolerância para comparações de ponto flutuante.
@@ -36,6 +65,7 @@ pub const ALIQ_BASICA_PIS: Decimal = dec!(1.65);
 pub const ALIQ_BASICA_COF: Decimal = dec!(7.6);
 pub const DECIMAL_VALOR: usize = 2;
 pub const DECIMAL_ALIQ: usize = 4;
+pub const TOLERANCIA_DO_CALCULO: Decimal = dec!(0.01); // diferença aceita no recálculo de PIS/COFINS
 pub const NEWLINE_BYTE: u8 = b'\n';
 pub const DATE_FORMAT: &str = "%d/%m/%Y %H:%M:%S";
 pub const BUFFER_CAPACITY: usize = 8 * 1024 * 1024; // 8MB
@@ -46,6 +46,39 @@ macro_rules! impl_reg_methods {
     };
 }
 
+/// Macro para implementar a SpedWriter (inverso de SpedParser) em cada registro.
+///
+/// Os campos devem ser listados na ordem do leiaute (campo 02 em diante).
+/// Modificadores opcionais de formatação:
+/// - `campo => N`: inteiro completado com zeros à esquerda até N dígitos (ex: CST_ICMS `060`).
+/// - `campo => mmyyyy`: data escrita como período `MMAAAA` (ex: PER_APU_CRED).
+#[macro_export]
+macro_rules! impl_sped_writer {
+    ($struct_name:ident { $( $field:ident $(=> $fmt:tt)? ),* $(,)? }) => {
+        impl $crate::writer::SpedWriter for $struct_name {
+            fn write_fields(&self, buffer: &mut String) {
+                $(
+                    buffer.push($crate::DELIMITER_CHAR);
+                    $crate::impl_sped_writer!(@field buffer, self.$field $(, $fmt)?);
+                )*
+            }
+
+            fn nomes_dos_campos(&self) -> &'static [&'static str] {
+                &[$( stringify!($field) ),*]
+            }
+        }
+    };
+    (@field $buf:ident, $value:expr) => {
+        $crate::writer::ToSpedField::write_sped_field(&$value, $buf)
+    };
+    (@field $buf:ident, $value:expr, mmyyyy) => {
+        $crate::writer::write_sped_periodo(&$value, $buf)
+    };
+    (@field $buf:ident, $value:expr, $width:literal) => {
+        $crate::writer::write_sped_com_zeros(&$value, $width, $buf)
+    };
+}
+
 // ============================================================================
 // Macro de Despacho (Evita repetição de SpedRecord::BlocoX(BlocoX::R...))
 // ============================================================================
@@ -55,8 +88,7 @@ macro_rules! impl_reg_methods {
 macro_rules! dispatch_sped_parsers {
     (
         $reg_id:expr, $path:expr, $line:expr, $fields:expr,
-        simple => [ $( ($name:literal, $bloco:ident, $registro:ident, $struct:ident) ),* $(,)? ],
-        conditional => [ $( ($reg_name:literal, $bloco_name:ident, $cond:expr, $reg_old:ident, $struct_old:ident, $reg_new:ident, $struct_new:ident) ),* $(,)? ]
+        simple => [ $( ($name:literal, $bloco:ident, $registro:ident, $struct:ident) ),* $(,)? ]
     ) => {
         match $reg_id {
             $(
@@ -65,22 +97,17 @@ macro_rules! dispatch_sped_parsers {
                     Ok(Some($crate::SpedRecord::$bloco(Box::new($bloco::$registro(parsed.into())))))
                 },
             )*
-            $(
-                $reg_name => {
-                    let record = if $cond {
-                        let parsed = <$struct_old as $crate::SpedParser>::parse_reg($path, $line, $fields)?;
-                        $crate::SpedRecord::$bloco_name(Box::new($bloco_name::$reg_old(parsed.into())))
-                    } else {
-                        let parsed = <$struct_new as $crate::SpedParser>::parse_reg($path, $line, $fields)?;
-                        $crate::SpedRecord::$bloco_name(Box::new($bloco_name::$reg_new(parsed.into())))
-                    };
+            // Registros não suportados são preservados como RegistroGenerico no respectivo bloco.
+            _ => match $crate::SpedRecord::generico($crate::RegistroGenerico::new($reg_id, $line, $fields)) {
+                Some(record) => {
+                    log::debug!("[{}:Linha {}] Registro '{}' não suportado: mantido como registro genérico.", $path.display(), $line, $reg_id);
                     Ok(Some(record))
                 }
-            )*
-            _ => {
-                log::warn!("[{}:Linha {}] Registro '{}' não suportado.", $path.display(), $line, $reg_id);
-                Ok(None)
-            }
+                None => {
+                    log::warn!("[{}:Linha {}] Registro '{}' não suportado.", $path.display(), $line, $reg_id);
+                    Ok(None)
+                }
+            },
         }
     };
 }
@@ -116,7 +143,9 @@ macro_rules! impl_dopai {
     (@map get_cod_mod, $v:ident) => { fn get_cod_mod(&self) -> Option<&str> { self.$v.as_deref() } };
     (@map get_cod_ncm, $v:ident) => { fn get_cod_ncm(&self) -> Option<&str> { self.$v.as_deref() } };
     (@map get_cod_part, $v:ident) => { fn get_cod_part(&self) -> Option<&str> { self.$v.as_deref() } };
-    (@map get_num_doc, $v:ident) => { fn get_num_doc(&self) -> Option<usize> { self.$v } };
+    (@map get_ind_emit, $v:ident) => { fn get_ind_emit(&self) -> Option<&str> { self.$v.as_deref() } };
+    (@map get_num_doc, $v:ident) => { fn get_num_doc(&self) -> Option<usize> { self.$v.as_deref().and_then(|n| n.parse().ok()) } };
+    (@map get_serie, $v:ident) => { fn get_serie(&self) -> Option<&str> { self.$v.as_deref() } };
     (@map get_valor_iss, $v:ident) => { fn get_valor_iss(&self) -> Option<Decimal> { self.$v } };
     (@map get_valor_bc_icms, $v:ident) => { fn get_valor_bc_icms(&self) -> Option<Decimal> { self.$v } };
     (@map get_valor_icms, $v:ident) => { fn get_valor_icms(&self) -> Option<Decimal> { self.$v } };
@@ -152,7 +181,7 @@ macro_rules! impl_filho {
     (@map get_descr_compl, $v:ident) => { fn get_descr_compl(&self) -> Option<&str> { self.$v.as_deref() } };
     (@map get_info_compl, $v:ident) => { fn get_info_compl(&self) -> Option<&str> { self.$v.as_deref() } };
     (@map get_part_override, $v:ident) => { fn get_part_override(&self) -> Option<&str> { self.$v.as_deref() } };
-    (@map get_num_doc, $v:ident) => { fn get_num_doc(&self) -> Option<usize> { self.$v } };
+    (@map get_num_doc, $v:ident) => { fn get_num_doc(&self) -> Option<usize> { self.$v.as_deref().and_then(|n| n.parse().ok()) } };
 
     // Classificação Fiscal
     (@map get_cst_pis, $v:ident) => { fn get_cst_pis(&self) -> Option<$crate::CodigoSituacaoTributaria> { self.$v } };
@@ -169,11 +198,15 @@ macro_rules! impl_filho {
     (@map get_valor_bc_pis, $v:ident) => { fn get_valor_bc_pis(&self) -> Option<Decimal> { self.$v } };
     (@map get_aliq_pis, $v:ident) => { fn get_aliq_pis(&self) -> Option<Decimal> { self.$v } };
     (@map get_valor_pis, $v:ident) => { fn get_valor_pis(&self) -> Option<Decimal> { self.$v } };
+    (@map get_quant_bc_pis, $v:ident) => { fn get_quant_bc_pis(&self) -> Option<Decimal> { $crate::impl_filho!(@quant self.$v) } };
+    (@map get_aliq_pis_quant, $v:ident) => { fn get_aliq_pis_quant(&self) -> Option<Decimal> { self.$v } };
 
     // COFINS
     (@map get_valor_bc_cofins, $v:ident) => { fn get_valor_bc_cofins(&self) -> Option<Decimal> { self.$v } };
     (@map get_aliq_cofins, $v:ident) => { fn get_aliq_cofins(&self) -> Option<Decimal> { self.$v } };
     (@map get_valor_cofins, $v:ident) => { fn get_valor_cofins(&self) -> Option<Decimal> { self.$v } };
+    (@map get_quant_bc_cofins, $v:ident) => { fn get_quant_bc_cofins(&self) -> Option<Decimal> { $crate::impl_filho!(@quant self.$v) } };
+    (@map get_aliq_cofins_quant, $v:ident) => { fn get_aliq_cofins_quant(&self) -> Option<Decimal> { self.$v } };
 
     // Outros Tributos (ICMS, ISS, IPI)
     (@map get_valor_iss, $v:ident) => { fn get_valor_iss(&self) -> Option<Decimal> { self.$v } };
@@ -185,6 +218,9 @@ macro_rules! impl_filho {
     (@map get_valor_icms_st, $v:ident) => { fn get_valor_icms_st(&self) -> Option<Decimal> { self.$v } };
     (@map get_valor_bc_icms_st, $v:ident) => { fn get_valor_bc_icms_st(&self) -> Option<Decimal> { self.$v } };
 
+    // Quantidades (QUANT_BC_PIS e QUANT_BC_COFINS) são armazenadas como texto: "1234,567"
+    (@quant $q:expr) => { $crate::decimal_textual($q.as_deref()) };
+
     // Safety catch
     (@method $other:ident, $v:ident) => { compile_error!(concat!("Chave desconhecida '", stringify!($other), "' em impl_filho!")); };
 }
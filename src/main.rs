@@ -3,7 +3,8 @@ use clap::Parser;
 use claudiofsr_lib::my_print;
 use colored::*;
 use efd_contribuicoes::{
-    AppConfig, Arguments, DATE_FORMAT, EFDResult, executar_programa, format_error,
+    AppConfig, Arguments, DATE_FORMAT, EFDError, EFDResult, comparar_arquivos, executar_programa,
+    format_error, validar_arquivos, verificar_assinaturas,
 };
 use execution_time::ExecutionTime;
 use log::{LevelFilter, debug};
@@ -99,9 +100,21 @@ fn run(args: &Arguments) -> EFDResult<()> {
     }
 
     // Se o usuário usou a flag -f (find), apenas listamos e encerramos.
+    // Com a flag -v (validar), apenas validamos a estrutura dos arquivos.
+    // Com a flag -a (assinatura), apenas verificamos a assinatura digital.
+    // Com a flag --diff, apenas comparamos dois arquivos.
+    // (As flags -v, -a e --diff são mutuamente exclusivas: ver args.rs.)
     // Caso contrário, executamos a análise completa
+    let mut inconsistencias = 0;
+    let mut assinaturas_divergentes = 0;
     if args.find {
         config.print_summary(&mut buffer)?;
+    } else if config.validar {
+        inconsistencias = validar_arquivos(&config, &mut buffer)?;
+    } else if config.assinatura {
+        assinaturas_divergentes = verificar_assinaturas(&config, &mut buffer)?;
+    } else if config.diff {
+        comparar_arquivos(&config, &mut buffer)?;
     } else {
         executar_programa(&config, &mut buffer)?;
     }
@@ -109,6 +122,15 @@ fn run(args: &Arguments) -> EFDResult<()> {
     // 3. Relatório de Finalização
     finalizar_execucao(&config, timer, &mut buffer)?;
 
+    // Arquivos com inconsistências estruturais encerram o programa com erro.
+    if inconsistencias > 0 {
+        return Err(EFDError::StructuralValidationFailed(inconsistencias));
+    }
+
+    if assinaturas_divergentes > 0 {
+        return Err(EFDError::SignatureCheckFailed(assinaturas_divergentes));
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,174 @@
+use memmap2::Mmap;
+use std::{
+    borrow::Cow,
+    fs::File,
+    iter::Enumerate,
+    path::{Path, PathBuf},
+    slice::Split,
+};
+
+use crate::{
+    DELIMITER_CHAR, EFDError, EFDResult, Leiaute, NEWLINE_BYTE, ResultExt, SpedRecord, decode_line,
+    parse_sped_fields,
+};
+
+// ============================================================================
+// SpedMmap: leitura zero-copy
+// ============================================================================
+
+/// Arquivo SPED EFD mapeado em memória (memory-mapped).
+///
+/// Linhas e campos são fatias emprestadas (`&[u8]`/`&str`) do próprio mapeamento,
+/// sem cópia para buffers intermediários. Dados próprios (`CompactString`, `Arc<str>`, etc)
+/// são alocados apenas quando a linha é convertida em `SpedRecord`.
+///
+/// Indicado para arquivos grandes (vários GB), nos quais a leitura via `BufRead`
+/// (uma `String` por linha) se torna o gargalo.
+pub struct SpedMmap {
+    mmap: Mmap,
+    path: PathBuf,
+}
+
+impl SpedMmap {
+    /// Mapeia o arquivo SPED EFD em memória.
+    pub fn open(path: impl AsRef<Path>) -> EFDResult<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_loc(|e| EFDError::InOut {
+            source: e,
+            path: path.to_path_buf(),
+        })?;
+
+        // SAFETY: o mapeamento é somente leitura. Se o arquivo for alterado por outro
+        // processo durante a análise, o conteúdo lido pode mudar (limitação do mmap).
+        let mmap = unsafe { Mmap::map(&file) }.map_loc(|e| EFDError::InOut {
+            source: e,
+            path: path.to_path_buf(),
+        })?;
+
+        Ok(Self {
+            mmap,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Caminho do arquivo mapeado.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Conteúdo bruto do arquivo.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Número de linhas do arquivo (inclusive a assinatura digital).
+    pub fn number_of_lines(&self) -> u64 {
+        let bytes = self.as_bytes();
+        let newlines = bytes.iter().filter(|&&b| b == NEWLINE_BYTE).count();
+        let ultima_sem_newline = bytes.last().is_some_and(|&b| b != NEWLINE_BYTE);
+
+        (newlines + usize::from(ultima_sem_newline)) as u64
+    }
+
+    /// Linhas não vazias até o Registro 9999 (a assinatura digital é ignorada).
+    pub fn linhas(&self) -> LinhasSped<'_> {
+        LinhasSped {
+            linhas: self
+                .as_bytes()
+                .split(is_newline as fn(&u8) -> bool)
+                .enumerate(),
+            finished: false,
+        }
+    }
+}
+
+/// Linhas brutas (numeradas) do mapeamento, separadas por NEWLINE_BYTE.
+type LinhasBrutas<'a> = Enumerate<Split<'a, u8, fn(&u8) -> bool>>;
+
+fn is_newline(byte: &u8) -> bool {
+    *byte == NEWLINE_BYTE
+}
+
+/// Linha não vazia de um arquivo SPED EFD, emprestada do mapeamento em memória.
+#[derive(Debug, Clone, Copy)]
+pub struct LinhaSped<'a> {
+    /// Número da linha no arquivo (a partir de 1).
+    pub line_number: usize,
+
+    /// Bytes da linha, sem espaços nas extremidades e sem o terminador \n.
+    pub bytes: &'a [u8],
+}
+
+impl<'a> LinhaSped<'a> {
+    /// Código do registro (ex: b"C100"), obtido sem decodificar a linha.
+    ///
+    /// Permite descartar linhas (filtros) antes de qualquer alocação.
+    pub fn registro(&self) -> &'a [u8] {
+        self.bytes
+            .split(|&b| b == DELIMITER_CHAR as u8)
+            .nth(1)
+            .map(<[u8]>::trim_ascii)
+            .unwrap_or_default()
+    }
+
+    /// Texto da linha: emprestado se UTF-8; convertido apenas se WINDOWS-1252.
+    pub fn texto(&self, path: &Path) -> EFDResult<Cow<'a, str>> {
+        decode_line(self.bytes, self.line_number, path)
+    }
+
+    /// Converte a linha em `SpedRecord` (neste momento os campos são alocados).
+    pub fn parse(
+        &self,
+        path: &Path,
+        reg_buffer: &mut [u8; 4],
+        leiaute: &Leiaute,
+    ) -> EFDResult<Option<SpedRecord>> {
+        let texto = self.texto(path)?;
+        parse_sped_fields(path, self.line_number, &texto, reg_buffer, leiaute)
+    }
+}
+
+/// Iterador sobre as linhas de um `SpedMmap`. Ver `SpedMmap::linhas`.
+pub struct LinhasSped<'a> {
+    linhas: LinhasBrutas<'a>,
+    finished: bool,
+}
+
+impl<'a> Iterator for LinhasSped<'a> {
+    type Item = LinhaSped<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let Some((idx, bytes)) = self.linhas.next() else {
+                self.finished = true;
+                break;
+            };
+
+            let trimmed = bytes.trim_ascii();
+
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Após o Registro 9999, consta na EFD a assinatura digital binária.
+            if trimmed.starts_with(b"|9999|") {
+                self.finished = true;
+            }
+
+            return Some(LinhaSped {
+                line_number: idx + 1,
+                bytes: trimmed,
+            });
+        }
+
+        None
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/mmap_reader_tests.rs"]
+mod mmap_reader_tests;
@@ -1,5 +1,9 @@
-use crate::{EFDError, EFDResult, ResultExt, SpedRecordTrait, blocos::*};
+use crate::{EFDError, EFDResult, ResultExt, SpedRecordTrait, SpedWriter, blocos::*};
 use rayon::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
 
 // ============================================================================
 // Hierarquia de Enums (Model)
@@ -66,23 +70,34 @@ macro_rules! define_sped_record {
             #[derive(Debug, Clone)]
             pub enum $bloco {
                 $( $variant($registro), )*
+                /// Registro não suportado pelo parser, preservado com os campos originais.
+                Generico(RegistroGenerico),
             }
 
             impl $bloco {
                 #[inline(always)]
                 pub fn nivel(&self) -> u16 {
-                    match self { $( Self::$variant(r) => r.nivel, )* }
+                    match self {
+                        $( Self::$variant(r) => r.nivel, )*
+                        Self::Generico(r) => r.nivel,
+                    }
                 }
 
                 #[inline(always)]
                 pub fn line_number(&self) -> usize {
-                    match self { $( Self::$variant(r) => r.line_number, )* }
+                    match self {
+                        $( Self::$variant(r) => r.line_number, )*
+                        Self::Generico(r) => r.line_number,
+                    }
                 }
 
                 #[inline(always)]
                 pub fn registro_name(&self) -> &str {
                     // O nome do registro é uma constante estática no binário
-                    match self { $( Self::$variant(_) => $reg_name, )* }
+                    match self {
+                        $( Self::$variant(_) => $reg_name, )*
+                        Self::Generico(r) => &r.registro,
+                    }
                 }
 
                 /// Obtém o caractere do Bloco (primeiro caractere do registro)
@@ -91,6 +106,7 @@ macro_rules! define_sped_record {
                     match self {
                         // Extrai o primeiro char da string literal em tempo de compilação
                         $( Self::$variant(_) => $reg_name.chars().next().unwrap_or('?'), )*
+                        Self::Generico(r) => r.bloco,
                     }
                 }
 
@@ -99,6 +115,7 @@ macro_rules! define_sped_record {
                 pub fn as_any(&self) -> &dyn std::any::Any {
                     match self {
                         $( Self::$variant(r) => r, )*
+                        Self::Generico(r) => r,
                     }
                 }
 
@@ -107,6 +124,7 @@ macro_rules! define_sped_record {
                 pub fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
                     match self {
                         $( Self::$variant(r) => r, )*
+                        Self::Generico(r) => r,
                     }
                 }
             }
@@ -151,6 +169,25 @@ macro_rules! define_sped_record {
                     self.as_any_mut() // Chama o método do Enum que faz o match
                 }
             }
+
+            // Implementa a escrita (SpedWriter) delegando para o registro interno
+            impl $crate::writer::SpedWriter for $bloco {
+                #[inline]
+                fn write_fields(&self, buffer: &mut String) {
+                    match self {
+                        $( Self::$variant(r) => r.write_fields(buffer), )*
+                        Self::Generico(r) => r.write_fields(buffer),
+                    }
+                }
+
+                #[inline]
+                fn nomes_dos_campos(&self) -> &'static [&'static str] {
+                    match self {
+                        $( Self::$variant(r) => r.nomes_dos_campos(), )*
+                        Self::Generico(r) => r.nomes_dos_campos(),
+                    }
+                }
+            }
         )*
 
         // 2. Enum Global SpedRecord com Dupla Indireção (Box)
@@ -201,6 +238,12 @@ macro_rules! define_sped_record {
                 )
             }
 
+            /// Retorna a linha do arquivo texto correspondente ao registro (ex: "|C100|0|1|...|").
+            #[inline]
+            pub fn to_sped_line(&self) -> String {
+                match self { $( Self::$bloco(b) => b.to_sped_line(), )* }
+            }
+
             /// Método de impressão para visualização formatada.
             ///
             /// Aproveita o #[derive(Debug)] implementado recursivamente em todos os blocos e registros.
@@ -376,6 +419,34 @@ define_sped_record! {
     }
 }
 
+impl SpedRecord {
+    /// Encapsula um registro não suportado no bloco indicado pelo primeiro caractere do código.
+    ///
+    /// Retorna `None` se o código não seguir o padrão do leiaute (bloco seguido de 3 dígitos)
+    /// ou se o bloco não existir (ex: registro "X100").
+    pub fn generico(registro: RegistroGenerico) -> Option<Self> {
+        let codigo = registro.registro.as_bytes();
+        if codigo.len() != 4 || !codigo[1..].iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let record = match registro.bloco {
+            '0' => Self::Bloco0(Box::new(Bloco0::Generico(registro))),
+            'A' => Self::BlocoA(Box::new(BlocoA::Generico(registro))),
+            'C' => Self::BlocoC(Box::new(BlocoC::Generico(registro))),
+            'D' => Self::BlocoD(Box::new(BlocoD::Generico(registro))),
+            'F' => Self::BlocoF(Box::new(BlocoF::Generico(registro))),
+            'I' => Self::BlocoI(Box::new(BlocoI::Generico(registro))),
+            'M' => Self::BlocoM(Box::new(BlocoM::Generico(registro))),
+            'P' => Self::BlocoP(Box::new(BlocoP::Generico(registro))),
+            '1' => Self::Bloco1(Box::new(Bloco1::Generico(registro))),
+            '9' => Self::Bloco9(Box::new(Bloco9::Generico(registro))),
+            _ => return None,
+        };
+        Some(record)
+    }
+}
+
 // ============================================================================
 // Estrutura Principal de Dados
 // ============================================================================
@@ -535,6 +606,78 @@ impl SpedFile {
         });
     }
 
+    /// Remove os registros das linhas informadas, em todos os blocos.
+    pub fn remover_linhas(&mut self, linhas: &BTreeSet<usize>) {
+        self.bloco_0.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_a.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_c.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_d.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_f.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_i.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_m.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_p.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_1.retain(|r| !linhas.contains(&r.line_number()));
+        self.bloco_9.retain(|r| !linhas.contains(&r.line_number()));
+    }
+
+    /// Itera sobre todos os registros do arquivo, bloco a bloco,
+    /// na sequência do leiaute (0, A, C, D, F, I, M, P, 1 e 9).
+    pub fn iter_registros(&self) -> impl Iterator<Item = &dyn SpedRecordTrait> {
+        #[inline(always)]
+        fn as_dyn<T: SpedRecordTrait>(registro: &T) -> &dyn SpedRecordTrait {
+            registro
+        }
+
+        self.bloco_0
+            .iter()
+            .map(as_dyn)
+            .chain(self.bloco_a.iter().map(as_dyn))
+            .chain(self.bloco_c.iter().map(as_dyn))
+            .chain(self.bloco_d.iter().map(as_dyn))
+            .chain(self.bloco_f.iter().map(as_dyn))
+            .chain(self.bloco_i.iter().map(as_dyn))
+            .chain(self.bloco_m.iter().map(as_dyn))
+            .chain(self.bloco_p.iter().map(as_dyn))
+            .chain(self.bloco_1.iter().map(as_dyn))
+            .chain(self.bloco_9.iter().map(as_dyn))
+    }
+
+    /// Registros não suportados pelo parser (`RegistroGenerico`) e suas quantidades,
+    /// em ordem alfabética.
+    pub fn contar_registros_genericos(&self) -> BTreeMap<&str, usize> {
+        let mut contagem = BTreeMap::new();
+        for registro in self
+            .iter_registros()
+            .filter(|r| r.as_any().is::<RegistroGenerico>())
+        {
+            *contagem.entry(registro.registro_name()).or_insert(0) += 1;
+        }
+        contagem
+    }
+
+    /// Relatório dos registros não suportados encontrados no arquivo.
+    ///
+    /// Retorna uma String vazia se todos os registros foram interpretados.
+    pub fn relatorio_registros_genericos(&self) -> String {
+        let contagem = self.contar_registros_genericos();
+
+        if contagem.is_empty() {
+            return String::new();
+        }
+
+        let mut relatorio =
+            String::from("Registros não suportados (preservados como registros genéricos):\n");
+        for (registro, quantidade) in contagem {
+            let _ = writeln!(
+                relatorio,
+                "   Registro {registro}: {quantidade} ocorrência(s)"
+            );
+        }
+        relatorio.push('\n');
+
+        relatorio
+    }
+
     /// Busca o primeiro registro imutável com máxima performance.
     /// O(1) para localizar o bloco e O(N) para localizar o registro dentro do bloco.
     ///
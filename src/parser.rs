@@ -1,4 +1,4 @@
-use crate::{DELIMITER_CHAR, EFDResult, blocos::*, dispatch_sped_parsers, model::*};
+use crate::{DELIMITER_CHAR, EFDResult, Leiaute, blocos::*, dispatch_sped_parsers, model::*};
 use std::path::Path;
 
 /// A trait for parsing different types of SPED records.
@@ -10,11 +10,16 @@ pub trait SpedParser {
 /// Função central de processamento de linhas do SPED EFD Contribuições.
 ///
 /// Converte uma linha bruta do arquivo texto em uma variante da hierarquia SpedRecord.
+///
+/// O `leiaute` (ver `Leiaute::do_registro_0000`) define o parser dos registros cujo
+/// leiaute varia entre as versões. Registros inexistentes na versão são preservados
+/// como `RegistroGenerico` e rejeitados na validação estrutural (ver `validar_leiaute`).
 pub fn parse_sped_fields(
     file_path: &Path,
     line_number: usize,
     line: &str,               // String reutilizada (scratchpad)
     reg_buffer: &mut [u8; 4], // Buffer de 4 bytes reutilizado
+    leiaute: &Leiaute,        // Versão do leiaute (COD_VER do Registro 0000)
 ) -> EFDResult<Option<SpedRecord>> {
     // O split cria referências para a 'line'.
     // O custo aqui é apenas alocar um pequeno vetor de ponteiros na Heap.
@@ -46,8 +51,24 @@ pub fn parse_sped_fields(
         reg_id
     };
 
+    // 4. Registros cujo leiaute depende da versão da escrituração
+    if let Some(parser) = leiaute.parser(registro_uppercase) {
+        return parser(file_path, line_number, &fields).map(Some);
+    }
+
+    // Registro inexistente na versão (ex: 1050 em arquivo anterior a 2019, ou COD_VER
+    // informado incorretamente): preservado como RegistroGenerico, sem abortar a leitura.
+    if !leiaute.contem(registro_uppercase) {
+        log::warn!(
+            "[{}:Linha {line_number}] Registro '{registro_uppercase}' não previsto no {leiaute}: mantido como registro genérico.",
+            file_path.display()
+        );
+        let registro = RegistroGenerico::new(registro_uppercase, line_number, &fields);
+        return Ok(SpedRecord::generico(registro));
+    }
+
     // Chamada da Macro com mapeamento simplificado
-    // 4. Despachante principal (Match exaustivo por Bloco)
+    // 5. Despachante principal (Match exaustivo por Bloco, conforme o leiaute mais recente)
     dispatch_sped_parsers!(
         registro_uppercase, // Passamos o &str normalizado (ex: "C100")
         file_path,
@@ -154,14 +175,15 @@ pub fn parse_sped_fields(
             ("M001", BlocoM, RM001, RegistroM001), ("M100", BlocoM, RM100, RegistroM100),
             ("M105", BlocoM, RM105, RegistroM105), ("M110", BlocoM, RM110, RegistroM110),
             ("M115", BlocoM, RM115, RegistroM115), ("M200", BlocoM, RM200, RegistroM200),
-            ("M205", BlocoM, RM205, RegistroM205), ("M211", BlocoM, RM211, RegistroM211),
-            ("M215", BlocoM, RM215, RegistroM215), ("M220", BlocoM, RM220, RegistroM220),
-            ("M225", BlocoM, RM225, RegistroM225), ("M230", BlocoM, RM230, RegistroM230),
-            ("M300", BlocoM, RM300, RegistroM300), ("M350", BlocoM, RM350, RegistroM350),
-            ("M400", BlocoM, RM400, RegistroM400), ("M410", BlocoM, RM410, RegistroM410),
-            ("M500", BlocoM, RM500, RegistroM500), ("M505", BlocoM, RM505, RegistroM505),
-            ("M510", BlocoM, RM510, RegistroM510), ("M515", BlocoM, RM515, RegistroM515),
-            ("M600", BlocoM, RM600, RegistroM600), ("M605", BlocoM, RM605, RegistroM605),
+            ("M205", BlocoM, RM205, RegistroM205), ("M210", BlocoM, RM210, RegistroM210),
+            ("M211", BlocoM, RM211, RegistroM211), ("M215", BlocoM, RM215, RegistroM215),
+            ("M220", BlocoM, RM220, RegistroM220), ("M225", BlocoM, RM225, RegistroM225),
+            ("M230", BlocoM, RM230, RegistroM230), ("M300", BlocoM, RM300, RegistroM300),
+            ("M350", BlocoM, RM350, RegistroM350), ("M400", BlocoM, RM400, RegistroM400),
+            ("M410", BlocoM, RM410, RegistroM410), ("M500", BlocoM, RM500, RegistroM500),
+            ("M505", BlocoM, RM505, RegistroM505), ("M510", BlocoM, RM510, RegistroM510),
+            ("M515", BlocoM, RM515, RegistroM515), ("M600", BlocoM, RM600, RegistroM600),
+            ("M605", BlocoM, RM605, RegistroM605), ("M610", BlocoM, RM610, RegistroM610),
             ("M611", BlocoM, RM611, RegistroM611), ("M615", BlocoM, RM615, RegistroM615),
             ("M620", BlocoM, RM620, RegistroM620), ("M625", BlocoM, RM625, RegistroM625),
             ("M630", BlocoM, RM630, RegistroM630), ("M700", BlocoM, RM700, RegistroM700),
@@ -177,11 +199,6 @@ pub fn parse_sped_fields(
             // Bloco 9
             ("9001", Bloco9, R9001, Registro9001), ("9900", Bloco9, R9900, Registro9900),
             ("9990", Bloco9, R9990, Registro9990), ("9999", Bloco9, R9999, Registro9999),
-        ],
-        conditional => [
-            // Registros com despacho condicional baseado na quantidade de campos (`fields.len()`).
-            ("M210", BlocoM, fields.len() == 15, RM210Antigo, RegistroM210Antigo, RM210, RegistroM210),
-            ("M610", BlocoM, fields.len() == 15, RM610Antigo, RegistroM610Antigo, RM610, RegistroM610),
         ]
     )
 }
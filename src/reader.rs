@@ -0,0 +1,290 @@
+use compact_str::CompactString;
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    DELIMITER_CHAR, EFDError, EFDResult, ErroDeLeitura, Leiaute, NEWLINE_BYTE, Registro0000,
+    RegistroDescartado, ResultExt, SpedRecord, erros_da_linha, get_string_utf8, open_efd_reader,
+    parse_sped_fields,
+};
+
+// ============================================================================
+// SpedReader
+// ============================================================================
+
+/// Leitor sequencial (streaming) de registros SPED EFD.
+///
+/// Ao contrário de `read_and_parse_file`, que carrega o arquivo inteiro em um `SpedFile`,
+/// o `SpedReader` produz um `SpedRecord` por vez a partir de qualquer `BufRead`.
+/// A leitura é encerrada após o registro 9999 (a assinatura digital é ignorada).
+///
+/// Exemplo: totais do M200 de um arquivo.
+///
+/// ```no_run
+/// use efd_contribuicoes::{EFDResult, RegistroM200, SpedReader};
+///
+/// fn main() -> EFDResult<()> {
+///     for record in SpedReader::open("PISCOFINS_20240101_20240131.txt")?.with_registros(&["M200"]) {
+///         let record = record?;
+///         let m200 = record.downcast_ref::<RegistroM200>()?;
+///         println!("{:?}", m200.vl_tot_cont_rec);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct SpedReader<R> {
+    reader: R,
+    path: PathBuf,
+    line_number: usize,
+    finished: bool,
+
+    // Versão do leiaute: a mais recente até a leitura do Registro 0000.
+    leiaute: &'static Leiaute,
+
+    // Filtros opcionais (None = sem filtro)
+    blocos: Option<Vec<char>>,
+    registros: Option<Vec<CompactString>>,
+
+    // Erros de leitura do modo leniente (None = as linhas com erro são produzidas como Err)
+    erros: Option<Vec<ErroDeLeitura>>,
+
+    // Registro pai descartado no modo leniente, cujos descendentes também são descartados
+    descartado: Option<RegistroDescartado>,
+
+    // Buffers reutilizados entre as linhas (scratchpads)
+    bytes_buffer: Vec<u8>,
+    line_buf: String,
+    reg_buf: [u8; 4],
+}
+
+impl SpedReader<Box<dyn BufRead + Send>> {
+    /// Abre o arquivo SPED EFD para leitura sequencial.
+    ///
+    /// Aceita também entradas de arquivos compactados (ex: `efd.zip!/PISCOFINS_...txt`).
+    pub fn open(path: impl AsRef<Path>) -> EFDResult<Self> {
+        let path = path.as_ref();
+        Ok(SpedReader::new(open_efd_reader(path)?).with_path(path))
+    }
+}
+
+impl<R: BufRead> SpedReader<R> {
+    /// Cria um leitor a partir de qualquer `BufRead` (arquivo, `&[u8]`, `Cursor`, etc).
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            path: PathBuf::new(),
+            line_number: 0,
+            finished: false,
+            leiaute: Leiaute::mais_recente(),
+            blocos: None,
+            registros: None,
+            erros: None,
+            descartado: None,
+            bytes_buffer: Vec::with_capacity(1024),
+            line_buf: String::with_capacity(1024),
+            reg_buf: [0u8; 4],
+        }
+    }
+
+    /// Caminho informado nas mensagens de erro.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Produz apenas os registros dos blocos informados (ex: `&['M', '1']`).
+    pub fn with_blocos(mut self, blocos: &[char]) -> Self {
+        self.blocos = Some(blocos.iter().map(char::to_ascii_uppercase).collect());
+        self
+    }
+
+    /// Produz apenas os registros informados (ex: `&["M200", "M600"]`).
+    pub fn with_registros(mut self, registros: &[&str]) -> Self {
+        self.registros = Some(
+            registros
+                .iter()
+                .map(|r| CompactString::new(r.to_ascii_uppercase()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Modo leniente: as linhas com erro de decodificação ou de conversão de campos são
+    /// descartadas e registradas (ver `erros_de_leitura`), e a leitura prossegue.
+    /// Os registros descendentes de uma linha descartada também são descartados.
+    /// Erros de I/O continuam sendo produzidos.
+    pub fn leniente(mut self) -> Self {
+        self.erros = Some(Vec::new());
+        self
+    }
+
+    /// Erros das linhas descartadas no modo leniente, na ordem das linhas.
+    pub fn erros_de_leitura(&self) -> &[ErroDeLeitura] {
+        self.erros.as_deref().unwrap_or_default()
+    }
+
+    /// Número da última linha lida.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Versão do leiaute em uso (definida pelo Registro 0000).
+    pub fn leiaute(&self) -> &'static Leiaute {
+        self.leiaute
+    }
+
+    /// Verifica os filtros apenas pelo código do registro, antes do parse (custo mínimo).
+    ///
+    /// O Registro 0000 é sempre analisado, pois define a versão do leiaute.
+    fn aceita(&self, registro: &str) -> bool {
+        if registro.eq_ignore_ascii_case("0000") {
+            return true;
+        }
+
+        let bloco_ok = self.blocos.as_ref().is_none_or(|blocos| {
+            registro
+                .chars()
+                .next()
+                .is_some_and(|c| blocos.contains(&c.to_ascii_uppercase()))
+        });
+
+        let registro_ok = self
+            .registros
+            .as_ref()
+            .is_none_or(|registros| registros.iter().any(|r| r.eq_ignore_ascii_case(registro)));
+
+        bloco_ok && registro_ok
+    }
+
+    /// Após o Registro 0000: ajusta a versão do leiaute e aplica os filtros ao próprio 0000.
+    fn registro_0000(&mut self, record: SpedRecord) -> Option<SpedRecord> {
+        let reg_0000 = record.downcast_ref::<Registro0000>().ok()?;
+        self.leiaute = Leiaute::do_registro_0000(reg_0000);
+
+        let bloco_ok = self.blocos.as_ref().is_none_or(|b| b.contains(&'0'));
+        let registro_ok = self
+            .registros
+            .as_ref()
+            .is_none_or(|r| r.iter().any(|r| r == "0000"));
+
+        (bloco_ok && registro_ok).then_some(record)
+    }
+
+    /// Lê e analisa a próxima linha. Retorna `Ok(None)` para linhas ignoradas.
+    fn proxima_linha(&mut self) -> EFDResult<Option<SpedRecord>> {
+        let trimmed = self.bytes_buffer.trim_ascii();
+
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+
+        // Após o Registro 9999, consta na EFD a assinatura digital binária.
+        if trimmed.starts_with(b"|9999|") {
+            self.finished = true;
+        }
+
+        get_string_utf8(trimmed, &mut self.line_buf, self.line_number, &self.path)?;
+
+        let registro = self
+            .line_buf
+            .split(DELIMITER_CHAR)
+            .nth(1)
+            .unwrap_or_default();
+
+        // Modo leniente: descendente do registro pai descartado.
+        let descendente = self
+            .descartado
+            .as_ref()
+            .filter(|pai| pai.contem(registro))
+            .map(|pai| pai.erro_do_descendente(&self.path, self.line_number, registro));
+        if descendente.is_none() {
+            self.descartado = None;
+        }
+
+        if !self.aceita(registro) {
+            return Ok(None);
+        }
+
+        let record = parse_sped_fields(
+            &self.path,
+            self.line_number,
+            &self.line_buf,
+            &mut self.reg_buf,
+            self.leiaute,
+        )?;
+
+        if let (Some(erro), Some(_)) = (descendente, &record) {
+            if let Some(erros) = self.erros.as_mut() {
+                erros.push(erro);
+            }
+            return Ok(None);
+        }
+
+        Ok(match record {
+            Some(record) if record.registro_name() == "0000" => self.registro_0000(record),
+            other => other,
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for SpedReader<R> {
+    type Item = EFDResult<SpedRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            self.bytes_buffer.clear();
+
+            match self.reader.read_until(NEWLINE_BYTE, &mut self.bytes_buffer) {
+                Ok(0) => self.finished = true,
+                Ok(_) => {
+                    self.line_number += 1;
+                    // Erros de parse não interrompem a leitura: o chamador decide se prossegue.
+                    match self.proxima_linha() {
+                        Ok(None) => {}
+                        // Modo leniente: a linha é descartada e os erros registrados.
+                        Err(error) if self.erros.is_some() => {
+                            let erros = erros_da_linha(
+                                &self.path,
+                                self.line_number,
+                                self.bytes_buffer.trim_ascii(),
+                                &self.line_buf,
+                                self.leiaute,
+                                error,
+                            );
+                            if let Some(erro) = erros.first() {
+                                RegistroDescartado::atualizar(&mut self.descartado, erro);
+                            }
+                            if let Some(acumulados) = self.erros.as_mut() {
+                                acumulados.extend(erros);
+                            }
+                        }
+                        result => return result.transpose(),
+                    }
+                }
+                Err(error) => {
+                    self.finished = true;
+                    return Some(
+                        Err(EFDError::InOutDetalhado {
+                            source: error,
+                            path: self.path.clone(),
+                            line_number: self.line_number + 1,
+                        })
+                        .loc(),
+                    );
+                }
+            }
+        }
+
+        None
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/reader_tests.rs"]
+mod reader_tests;
@@ -0,0 +1,381 @@
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    BlocoM, Contribuicao, DECIMAL_VALOR, DecimalExt, Divergencia, DocsFiscais, NaturezaDaReceita,
+    SpedFile, TipoDeOperacao, Verificador, natureza_da_receita, natureza_da_receita_do_ncm,
+    naturezas_da_receita, soma,
+};
+
+/// CSTs de saída sem incidência da contribuição (M400/M800): 04, 05, 06, 07, 08 e 09.
+const CST_NAO_TRIBUTADOS: [u16; 6] = [4, 5, 6, 7, 8, 9];
+
+// ============================================================================
+// Receitas sem Incidência da Contribuição (M400/M410 e M800/M810)
+// ============================================================================
+
+/*
+Receitas isentas, não alcançadas pela incidência, com alíquota zero ou com suspensão,
+por contribuição (PIS/PASEP: M400 e M410; COFINS: M800 e M810) e por CST (04 a 09):
+
+1. Em cada registro M400/M800:
+   - VL_TOT_REC = Σ VL_REC dos registros filhos M410/M810.
+
+2. Por CST, no período:
+   - Σ VL_TOT_REC dos registros M400/M800 = Σ VL_ITEM dos itens de saída dos documentos
+     fiscais (Blocos A, C, D e F) com o CST da contribuição (CST_PIS ou CST_COFINS).
+
+3. Natureza da receita (NAT_REC) pela NCM dos itens de saída, para os CSTs com tabela:
+   - CST 04: Tabela 4.3.10 (Produtos Sujeitos à Incidência Monofásica - Revenda);
+   - CST 06: Tabela 4.3.13 (Produtos Sujeitos à Alíquota Zero);
+   - Σ VL_REC dos registros M410/M810 da NAT_REC ≥ Σ VL_ITEM dos itens cuja NCM corresponde
+     à NAT_REC (itens sem NCM também podem compor a natureza);
+   - apenas os principais códigos das tabelas são conferidos (ver `naturezas_da_receita`):
+     itens com NCM de códigos não tabelados não são verificados.
+
+Sem apuração no Bloco M (M200/M600, M400/M800), a contribuição não é conciliada.
+Campos não informados em registros existentes valem zero.
+*/
+
+/// Receita detalhada por natureza (M410 ou M810).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaturezaDeclarada {
+    pub linha: usize,
+    pub nat_rec: CompactString,
+    pub vl_rec: Decimal,
+}
+
+/// Receita sem incidência da contribuição de um CST: declarada no Bloco M (M400/M800 e
+/// M410/M810) e apurada a partir dos itens de saída dos documentos fiscais.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceitaNaoTributada {
+    pub contribuicao: Contribuicao,
+    pub cst: u16,
+    /// Linha do primeiro registro M400/M800 do CST (`None` se não informado).
+    pub linha: Option<usize>,
+    /// Σ VL_TOT_REC dos registros M400/M800 do CST.
+    pub vl_tot_rec: Decimal,
+    /// Registros M410/M810 do CST.
+    pub naturezas: Vec<NaturezaDeclarada>,
+    /// Σ VL_ITEM dos itens de saída com o CST.
+    pub vl_rec_itens: Decimal,
+}
+
+impl ReceitaNaoTributada {
+    /// Σ VL_REC dos registros M410/M810 do CST.
+    pub fn vl_rec_detalhado(&self) -> Decimal {
+        self.naturezas.iter().map(|n| n.vl_rec).sum()
+    }
+}
+
+/// Campo das receitas sem incidência divergente do valor esperado.
+///
+/// Origem: contribuição e CST das receitas conciliadas.
+pub type DivergenciaDeReceita = Divergencia<(Contribuicao, u16)>;
+
+/// Receitas sem incidência de uma escrituração e divergências da conciliação.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConciliacaoDasReceitas {
+    pub periodo: Option<NaiveDate>,
+    pub receitas: Vec<ReceitaNaoTributada>,
+    pub divergencias: Vec<DivergenciaDeReceita>,
+}
+
+/// Registros de receitas sem incidência (pai, filho) de uma contribuição.
+const fn registros(contribuicao: Contribuicao) -> (&'static str, &'static str) {
+    match contribuicao {
+        Contribuicao::Pis => ("M400", "M410"),
+        Contribuicao::Cofins => ("M800", "M810"),
+    }
+}
+
+/// Registro M400/M800 com os seus registros filhos M410/M810.
+struct ReceitaDeclarada {
+    contribuicao: Contribuicao,
+    linha: usize,
+    cst: Option<u16>,
+    vl_tot_rec: Decimal,
+    naturezas: Vec<NaturezaDeclarada>,
+}
+
+/// Registros M400/M410 e M800/M810 na ordem das linhas e as contribuições com apuração
+/// declarada (M200/M600 ou M400/M800).
+fn coletar_receitas(sped_file: &SpedFile) -> (Vec<ReceitaDeclarada>, Vec<Contribuicao>) {
+    let mut registros: Vec<&BlocoM> = sped_file.bloco_m.iter().collect();
+    registros.sort_by_key(|r| r.line_number());
+
+    let mut receitas: Vec<ReceitaDeclarada> = Vec::new();
+    let mut apuradas: Vec<Contribuicao> = Vec::new();
+
+    for registro in registros {
+        let (contribuicao, natureza) = match registro {
+            BlocoM::RM200(_) => (Contribuicao::Pis, None),
+            BlocoM::RM600(_) => (Contribuicao::Cofins, None),
+            BlocoM::RM400(r) => {
+                receitas.push(ReceitaDeclarada {
+                    contribuicao: Contribuicao::Pis,
+                    linha: r.line_number,
+                    cst: r.cst_pis.map(|cst| cst.code()),
+                    vl_tot_rec: soma([r.vl_tot_rec]),
+                    naturezas: Vec::new(),
+                });
+                (Contribuicao::Pis, None)
+            }
+            BlocoM::RM800(r) => {
+                receitas.push(ReceitaDeclarada {
+                    contribuicao: Contribuicao::Cofins,
+                    linha: r.line_number,
+                    cst: r.cst_cofins.map(|cst| cst.code()),
+                    vl_tot_rec: soma([r.vl_tot_rec]),
+                    naturezas: Vec::new(),
+                });
+                (Contribuicao::Cofins, None)
+            }
+            BlocoM::RM410(r) => (
+                Contribuicao::Pis,
+                Some((r.line_number, &r.nat_rec, r.vl_rec)),
+            ),
+            BlocoM::RM810(r) => (
+                Contribuicao::Cofins,
+                Some((r.line_number, &r.nat_rec, r.vl_rec)),
+            ),
+            _ => continue,
+        };
+
+        if !apuradas.contains(&contribuicao) {
+            apuradas.push(contribuicao);
+        }
+
+        // Os registros M410/M810 são associados ao M400/M800 imediatamente anterior.
+        if let Some((linha, nat_rec, vl_rec)) = natureza
+            && let Some(pai) = receitas
+                .last_mut()
+                .filter(|pai| pai.contribuicao == contribuicao)
+        {
+            pai.naturezas.push(NaturezaDeclarada {
+                linha,
+                nat_rec: nat_rec.clone().unwrap_or_default(),
+                vl_rec: soma([vl_rec]),
+            });
+        }
+    }
+
+    apuradas.sort();
+    (receitas, apuradas)
+}
+
+/// Confronta as receitas sem incidência da contribuição declaradas no Bloco M
+/// (M400/M410 e M800/M810) com:
+/// - a soma dos registros filhos M410/M810 de cada registro M400/M800;
+/// - a receita dos itens de saída dos documentos fiscais, pelo CST da contribuição (04 a 09);
+/// - a natureza da receita (NAT_REC) dos produtos, conforme a NCM dos itens (CST 04 e 06),
+///   apenas para os códigos que constam de `naturezas_da_receita`.
+///
+/// Diferenças maiores que a `tolerancia` são retornadas por contribuição e na ordem das linhas.
+pub fn conciliar_receitas_nao_tributadas(
+    sped_file: &SpedFile,
+    docs: &[DocsFiscais],
+    periodo: Option<NaiveDate>,
+    tolerancia: Decimal,
+) -> ConciliacaoDasReceitas {
+    let (declaradas, apuradas) = coletar_receitas(sped_file);
+
+    let mut verificador = Verificador::new(tolerancia);
+
+    // (contribuição, CST) -> Σ VL_ITEM dos itens de saída
+    let mut itens_por_cst: BTreeMap<(Contribuicao, u16), Decimal> = BTreeMap::new();
+    // (contribuição, CST, NAT_REC) -> Σ VL_ITEM dos itens cuja NCM corresponde à natureza
+    let mut itens_por_natureza: BTreeMap<(Contribuicao, u16, &str), Decimal> = BTreeMap::new();
+
+    for doc in docs
+        .iter()
+        .filter(|doc| doc.tipo_de_operacao == Some(TipoDeOperacao::Saida))
+    {
+        let valor = soma([doc.valor_item]);
+        for (contribuicao, cst) in [
+            (Contribuicao::Pis, doc.cst_pis),
+            (Contribuicao::Cofins, doc.cst),
+        ] {
+            let Some(cst) = cst
+                .map(|cst| cst.code())
+                .filter(|cst| CST_NAO_TRIBUTADOS.contains(cst))
+            else {
+                continue;
+            };
+            *itens_por_cst.entry((contribuicao, cst)).or_default() += valor;
+            if let Some(natureza) = natureza_da_receita_do_ncm(cst, &doc.cod_ncm) {
+                *itens_por_natureza
+                    .entry((contribuicao, cst, natureza.codigo))
+                    .or_default() += valor;
+            }
+        }
+    }
+
+    let mut receitas = Vec::new();
+
+    for contribuicao in apuradas {
+        let (registro, registro_filho) = registros(contribuicao);
+
+        // CST -> receita declarada e apurada
+        let mut por_cst: BTreeMap<u16, ReceitaNaoTributada> = itens_por_cst
+            .range((contribuicao, 0)..=(contribuicao, u16::MAX))
+            .map(|(&(_, cst), &vl_rec_itens)| {
+                let receita = ReceitaNaoTributada {
+                    contribuicao,
+                    cst,
+                    linha: None,
+                    vl_tot_rec: Decimal::ZERO,
+                    naturezas: Vec::new(),
+                    vl_rec_itens,
+                };
+                (cst, receita)
+            })
+            .collect();
+
+        for declarada in declaradas.iter().filter(|d| d.contribuicao == contribuicao) {
+            let cst = declarada.cst.unwrap_or_default();
+            let vl_rec: Decimal = declarada.naturezas.iter().map(|n| n.vl_rec).sum();
+            verificador.conferir(
+                (contribuicao, cst),
+                (registro, Some(declarada.linha)),
+                "VL_TOT_REC",
+                (declarada.vl_tot_rec, vl_rec),
+                format!("Σ VL_REC dos registros {registro_filho}"),
+            );
+
+            let receita = por_cst.entry(cst).or_insert_with(|| ReceitaNaoTributada {
+                contribuicao,
+                cst,
+                linha: None,
+                vl_tot_rec: Decimal::ZERO,
+                naturezas: Vec::new(),
+                vl_rec_itens: Decimal::ZERO,
+            });
+            receita.linha = receita.linha.or(Some(declarada.linha));
+            receita.vl_tot_rec += declarada.vl_tot_rec;
+            receita
+                .naturezas
+                .extend(declarada.naturezas.iter().cloned());
+        }
+
+        for receita in por_cst.values() {
+            verificador.conferir(
+                (contribuicao, receita.cst),
+                (registro, receita.linha),
+                "VL_TOT_REC",
+                (receita.vl_tot_rec, receita.vl_rec_itens),
+                format!("Σ VL_ITEM dos itens de saída com CST {:02}", receita.cst),
+            );
+
+            for (&(.., codigo), &esperado) in itens_por_natureza
+                .range((contribuicao, receita.cst, "")..)
+                .take_while(|((c, cst, _), _)| (*c, *cst) == (contribuicao, receita.cst))
+            {
+                let declaradas: Vec<&NaturezaDeclarada> = receita
+                    .naturezas
+                    .iter()
+                    .filter(|n| n.nat_rec == codigo)
+                    .collect();
+                let declarado: Decimal = declaradas.iter().map(|n| n.vl_rec).sum();
+
+                // Itens sem NCM também podem compor a natureza da receita.
+                if declarado < esperado {
+                    let tabela = natureza_da_receita(receita.cst, codigo)
+                        .map_or("", |natureza| natureza.tabela);
+                    verificador.conferir(
+                        (contribuicao, receita.cst),
+                        (registro_filho, declaradas.first().map(|n| n.linha)),
+                        format!("VL_REC (NAT_REC {codigo})"),
+                        (declarado, esperado),
+                        format!("Σ VL_ITEM dos itens com NCM da Tabela {tabela}"),
+                    );
+                }
+            }
+        }
+
+        receitas.extend(por_cst.into_values());
+    }
+
+    let mut divergencias = verificador.into_divergencias();
+    divergencias.sort_by_key(|d| (d.origem, d.linha));
+
+    ConciliacaoDasReceitas {
+        periodo,
+        receitas,
+        divergencias,
+    }
+}
+
+/// Resumo das receitas sem incidência por contribuição e CST (Bloco M x itens dos documentos)
+/// e divergências da conciliação, ou String vazia se não houver receitas sem incidência.
+pub fn relatorio_receitas_nao_tributadas(conciliacao: &ConciliacaoDasReceitas) -> String {
+    if conciliacao.receitas.is_empty() && conciliacao.divergencias.is_empty() {
+        return String::new();
+    }
+
+    let valor = |v: Decimal| v.to_formatted_string(DECIMAL_VALOR);
+    let periodo = conciliacao
+        .periodo
+        .map_or("N/I".to_string(), |p| p.format("%m/%Y").to_string());
+
+    let mut relatorio = format!(
+        "Receitas sem incidência da contribuição (M400/M410 e M800/M810) x itens dos documentos (período {periodo}).\n"
+    );
+
+    for receita in &conciliacao.receitas {
+        let (registro, registro_filho) = registros(receita.contribuicao);
+        let _ = writeln!(
+            relatorio,
+            "   {}, CST {:02}: {registro} {}, {registro_filho} {}, itens {}",
+            receita.contribuicao.descricao(),
+            receita.cst,
+            valor(receita.vl_tot_rec),
+            valor(receita.vl_rec_detalhado()),
+            valor(receita.vl_rec_itens),
+        );
+    }
+
+    // A natureza da receita é conferida apenas para os códigos tabelados.
+    let mut csts_com_tabela: Vec<u16> = conciliacao
+        .receitas
+        .iter()
+        .map(|receita| receita.cst)
+        .filter(|&cst| naturezas_da_receita(cst).next().is_some())
+        .collect();
+    csts_com_tabela.sort_unstable();
+    csts_com_tabela.dedup();
+    for cst in csts_com_tabela {
+        let naturezas: Vec<&NaturezaDaReceita> = naturezas_da_receita(cst).collect();
+        let codigos: Vec<&str> = naturezas.iter().map(|n| n.codigo).collect();
+        let _ = writeln!(
+            relatorio,
+            "   NAT_REC do CST {cst:02} conferida pela NCM apenas para os códigos {} da Tabela {}",
+            codigos.join(", "),
+            naturezas[0].tabela,
+        );
+    }
+
+    if !conciliacao.divergencias.is_empty() {
+        let _ = writeln!(
+            relatorio,
+            "   Divergências da conciliação: {}",
+            conciliacao.divergencias.len()
+        );
+        for divergencia in &conciliacao.divergencias {
+            let _ = writeln!(relatorio, "      {divergencia}");
+        }
+    }
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/receitas_nao_tributadas_tests.rs"]
+mod receitas_nao_tributadas_tests;
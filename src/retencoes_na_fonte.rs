@@ -0,0 +1,666 @@
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use rust_decimal::Decimal;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use crate::{
+    Bloco1, BlocoF, BlocoM, Contribuicao, ControleMensal, DECIMAL_VALOR, DecimalExt, Divergencia,
+    SaldoMensal, SpedFile, Verificador, escrituracoes_analisadas, mes, mes_anterior, mes_seguinte,
+    mm_aaaa, razao, relatorio_divergencias_mensais, soma,
+};
+
+/// Indicador da declaração (IND_DEC) do F600 para a pessoa jurídica responsável pelo recolhimento:
+/// a retenção não foi sofrida pelo declarante e não pode ser deduzida.
+const IND_DEC_RESPONSAVEL: &str = "1";
+
+// ============================================================================
+// Retenções na Fonte (F600, M200/M600 e Registros 1300/1700)
+// ============================================================================
+
+/*
+Acompanhamento de cada retenção (IND_NAT_RET, PR_REC_RET) mês a mês:
+
+1. Em cada registro 1300/1700:
+   - SLD_RET = VL_RET_APU - VL_RET_DED - VL_RET_PER - VL_RET_DCOMP.
+
+2. Retenções sofridas no período (F600 com IND_DEC diferente de 1), por natureza (IND_NAT_RET):
+   - VL_RET_APU do 1300 (1700) com PR_REC_RET = período da escrituração
+     = Σ VL_RET_PIS (VL_RET_COFINS) dos registros F600.
+
+3. Retenção deduzida da contribuição do período:
+   - VL_RET_NC + VL_RET_CUM do M200 (M600) = Σ acréscimo de VL_RET_DED dos registros 1300 (1700)
+     em relação ao mês anterior (VL_RET_DED é acumulado: período da escrituração e anteriores).
+   - Sem a escrituração do mês anterior, só é possível conferir se todas as retenções
+     controladas forem do próprio período.
+
+4. Entre escriturações de meses consecutivos do mesmo CNPJ base:
+   - VL_RET_APU (mês seguinte) = VL_RET_APU (mês anterior);
+   - VL_RET_DED, VL_RET_PER e VL_RET_DCOMP (acumulados) não podem diminuir;
+   - SLD_RET positivo sem registro no mês seguinte: retenção não controlada.
+
+Campos não informados valem zero.
+*/
+
+/// Retenções sofridas no período (F600), totalizadas por natureza e fonte pagadora.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetencaoSofrida {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração.
+    pub periodo: NaiveDate,
+    /// Linha do primeiro registro F600 da natureza e fonte pagadora.
+    pub linha: usize,
+    pub ind_nat_ret: CompactString,
+    /// CNPJ da fonte pagadora responsável pela retenção.
+    pub cnpj_fonte: CompactString,
+    /// Quantidade de registros F600.
+    pub quantidade: usize,
+    /// Σ VL_RET_PIS.
+    pub vl_ret_pis: Decimal,
+    /// Σ VL_RET_COFINS.
+    pub vl_ret_cofins: Decimal,
+}
+
+impl RetencaoSofrida {
+    fn valor(&self, contribuicao: Contribuicao) -> Decimal {
+        match contribuicao {
+            Contribuicao::Pis => self.vl_ret_pis,
+            Contribuicao::Cofins => self.vl_ret_cofins,
+        }
+    }
+}
+
+/// Retenção deduzida da contribuição do período (M200 ou M600).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeducaoDoPeriodo {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração.
+    pub periodo: NaiveDate,
+    pub contribuicao: Contribuicao,
+    /// Código do registro: "M200" ou "M600".
+    pub registro: &'static str,
+    pub linha: usize,
+    pub vl_ret_nc: Decimal,
+    pub vl_ret_cum: Decimal,
+}
+
+impl DeducaoDoPeriodo {
+    fn total(&self) -> Decimal {
+        self.vl_ret_nc + self.vl_ret_cum
+    }
+}
+
+/// Retenção controlada no Registro 1300 ou 1700.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaldoDeRetencao {
+    pub cnpj_base: u32,
+    /// Período de apuração da escrituração que informa o registro.
+    pub periodo: NaiveDate,
+    pub contribuicao: Contribuicao,
+    /// Código do registro: "1300" ou "1700".
+    pub registro: &'static str,
+    pub linha: usize,
+    pub ind_nat_ret: CompactString,
+    /// Período do recebimento e da retenção.
+    pub pr_rec_ret: Option<NaiveDate>,
+    pub vl_ret_apu: Decimal,
+    pub vl_ret_ded: Decimal,
+    pub vl_ret_per: Decimal,
+    pub vl_ret_dcomp: Decimal,
+    pub sld_ret: Decimal,
+}
+
+/// Identificação de uma retenção ao longo dos meses.
+type ChaveDaRetencao = (u32, Contribuicao, CompactString, Option<NaiveDate>);
+
+impl SaldoMensal for SaldoDeRetencao {
+    type Chave = ChaveDaRetencao;
+
+    fn chave(&self) -> ChaveDaRetencao {
+        (
+            self.cnpj_base,
+            self.contribuicao,
+            self.ind_nat_ret.clone(),
+            self.pr_rec_ret,
+        )
+    }
+
+    fn controle(&self) -> ControleMensal {
+        ControleMensal {
+            cnpj_base: self.cnpj_base,
+            periodo: self.periodo,
+            contribuicao: self.contribuicao,
+            rotulo: self.rotulo(),
+        }
+    }
+
+    fn periodo(&self) -> NaiveDate {
+        self.periodo
+    }
+
+    fn registro(&self) -> (&'static str, usize) {
+        (self.registro, self.linha)
+    }
+}
+
+impl SaldoDeRetencao {
+    /// Identificação da retenção (ex: "natureza 03 de 11/2020").
+    pub fn rotulo(&self) -> String {
+        rotulo(&self.ind_nat_ret, self.pr_rec_ret)
+    }
+}
+
+/// Retenções na fonte (F600), deduções (M200/M600) e controle dos valores retidos (1300/1700)
+/// de uma ou mais escriturações.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RetencoesNaFonte {
+    pub retencoes: Vec<RetencaoSofrida>,
+    pub deducoes: Vec<DeducaoDoPeriodo>,
+    pub saldos: Vec<SaldoDeRetencao>,
+}
+
+/// PR_REC_RET é informado no formato MMAAAA.
+fn periodo_mmaaaa(valor: &Option<CompactString>) -> Option<NaiveDate> {
+    valor
+        .as_deref()
+        .and_then(|v| NaiveDate::parse_from_str(&format!("01{v}"), "%d%m%Y").ok())
+}
+
+fn rotulo(ind_nat_ret: &str, pr_rec_ret: Option<NaiveDate>) -> String {
+    format!("natureza {ind_nat_ret} de {}", mm_aaaa(pr_rec_ret))
+}
+
+macro_rules! saldo_de_retencao {
+    ($r:expr, $registro:literal, $contribuicao:expr, $cnpj_base:expr, $periodo:expr) => {
+        SaldoDeRetencao {
+            cnpj_base: $cnpj_base,
+            periodo: $periodo,
+            contribuicao: $contribuicao,
+            registro: $registro,
+            linha: $r.line_number,
+            ind_nat_ret: $r.ind_nat_ret.clone().unwrap_or_default(),
+            pr_rec_ret: periodo_mmaaaa(&$r.pr_rec_ret),
+            vl_ret_apu: soma([$r.vl_ret_apu]),
+            vl_ret_ded: soma([$r.vl_ret_ded]),
+            vl_ret_per: soma([$r.vl_ret_per]),
+            vl_ret_dcomp: soma([$r.vl_ret_dcomp]),
+            sld_ret: soma([$r.sld_ret]),
+        }
+    };
+}
+
+impl RetencoesNaFonte {
+    /// Extrai as retenções F600 (por natureza e fonte pagadora), as deduções M200/M600
+    /// e os registros 1300/1700 de uma escrituração.
+    pub fn extrair(sped_file: &SpedFile, cnpj_base: u32, periodo: NaiveDate) -> Self {
+        let periodo = mes(periodo);
+        let mut retencoes_na_fonte = Self::default();
+
+        // (IND_NAT_RET, CNPJ da fonte pagadora) -> retenções sofridas
+        let mut retencoes: BTreeMap<(CompactString, CompactString), RetencaoSofrida> =
+            BTreeMap::new();
+        for registro in &sped_file.bloco_f {
+            let BlocoF::RF600(r) = registro else {
+                continue;
+            };
+            if r.ind_dec.as_deref() == Some(IND_DEC_RESPONSAVEL) {
+                continue;
+            }
+            let ind_nat_ret = r.ind_nat_ret.clone().unwrap_or_default();
+            let cnpj_fonte = r.cnpj.clone().unwrap_or_default();
+
+            let retencao = retencoes
+                .entry((ind_nat_ret.clone(), cnpj_fonte.clone()))
+                .or_insert_with(|| RetencaoSofrida {
+                    cnpj_base,
+                    periodo,
+                    linha: r.line_number,
+                    ind_nat_ret,
+                    cnpj_fonte,
+                    quantidade: 0,
+                    vl_ret_pis: Decimal::ZERO,
+                    vl_ret_cofins: Decimal::ZERO,
+                });
+            retencao.linha = retencao.linha.min(r.line_number);
+            retencao.quantidade += 1;
+            retencao.vl_ret_pis += soma([r.vl_ret_pis]);
+            retencao.vl_ret_cofins += soma([r.vl_ret_cofins]);
+        }
+        retencoes_na_fonte.retencoes = retencoes.into_values().collect();
+
+        for registro in &sped_file.bloco_m {
+            let (contribuicao, nome, linha, vl_ret_nc, vl_ret_cum) = match registro {
+                BlocoM::RM200(r) => (
+                    Contribuicao::Pis,
+                    "M200",
+                    r.line_number,
+                    r.vl_ret_nc,
+                    r.vl_ret_cum,
+                ),
+                BlocoM::RM600(r) => (
+                    Contribuicao::Cofins,
+                    "M600",
+                    r.line_number,
+                    r.vl_ret_nc,
+                    r.vl_ret_cum,
+                ),
+                _ => continue,
+            };
+            retencoes_na_fonte.deducoes.push(DeducaoDoPeriodo {
+                cnpj_base,
+                periodo,
+                contribuicao,
+                registro: nome,
+                linha,
+                vl_ret_nc: soma([vl_ret_nc]),
+                vl_ret_cum: soma([vl_ret_cum]),
+            });
+        }
+
+        for registro in &sped_file.bloco_1 {
+            let saldo = match registro {
+                Bloco1::R1300(r) => {
+                    saldo_de_retencao!(r, "1300", Contribuicao::Pis, cnpj_base, periodo)
+                }
+                Bloco1::R1700(r) => {
+                    saldo_de_retencao!(r, "1700", Contribuicao::Cofins, cnpj_base, periodo)
+                }
+                _ => continue,
+            };
+            retencoes_na_fonte.saldos.push(saldo);
+        }
+
+        retencoes_na_fonte
+    }
+
+    /// Move os registros de `other` para `self`, deixando `other` vazio.
+    pub fn append(&mut self, other: &mut Self) {
+        self.retencoes.append(&mut other.retencoes);
+        self.deducoes.append(&mut other.deducoes);
+        self.saldos.append(&mut other.saldos);
+    }
+
+    /// Escriturações (CNPJ base, período) com retenções a informar.
+    fn escrituracoes(&self) -> BTreeSet<(u32, NaiveDate)> {
+        let retencoes = self.retencoes.iter().map(|r| (r.cnpj_base, r.periodo));
+        let deducoes = self
+            .deducoes
+            .iter()
+            .filter(|d| !d.total().is_zero())
+            .map(|d| (d.cnpj_base, d.periodo));
+        let saldos = self.saldos.iter().map(|s| (s.cnpj_base, s.periodo));
+
+        retencoes.chain(deducoes).chain(saldos).collect()
+    }
+}
+
+/// Campo das retenções na fonte (1300/1700 ou M200/M600) divergente do valor esperado,
+/// identificado pela escrituração, contribuição e retenção (ex: "natureza 03 de 11/2020")
+/// ou "retenções deduzidas".
+pub type DivergenciaDeRetencao = Divergencia<ControleMensal>;
+
+/// Registro de controle dos valores retidos correspondente a uma contribuição.
+const fn registro_de_controle(contribuicao: Contribuicao) -> &'static str {
+    match contribuicao {
+        Contribuicao::Pis => "1300",
+        Contribuicao::Cofins => "1700",
+    }
+}
+
+/// Registro de apuração da contribuição correspondente a uma contribuição.
+const fn registro_de_apuracao(contribuicao: Contribuicao) -> &'static str {
+    match contribuicao {
+        Contribuicao::Pis => "M200",
+        Contribuicao::Cofins => "M600",
+    }
+}
+
+/// Campo do F600 com o valor retido da contribuição.
+const fn campo_retido(contribuicao: Contribuicao) -> &'static str {
+    match contribuicao {
+        Contribuicao::Pis => "VL_RET_PIS",
+        Contribuicao::Cofins => "VL_RET_COFINS",
+    }
+}
+
+/// Retenções deduzidas no período (M200/M600) não vinculadas a uma natureza.
+const RETENCOES_DEDUZIDAS: &str = "retenções deduzidas";
+
+fn conferir_saldo(verificador: &mut Verificador<ControleMensal>, s: &SaldoDeRetencao) {
+    verificador.conferir_registro(
+        s,
+        "SLD_RET",
+        (
+            s.sld_ret,
+            s.vl_ret_apu - s.vl_ret_ded - s.vl_ret_per - s.vl_ret_dcomp,
+        ),
+        "VL_RET_APU menos os valores utilizados",
+    );
+}
+
+/// Registro de um mês x registro da mesma retenção no mês seguinte: o valor retido se mantém
+/// e os valores utilizados (acumulados) não podem diminuir.
+fn conferir_transporte(
+    verificador: &mut Verificador<ControleMensal>,
+    anterior: &SaldoDeRetencao,
+    seguinte: &SaldoDeRetencao,
+) {
+    verificador.conferir_transporte(
+        anterior,
+        seguinte,
+        &[(
+            "VL_RET_APU",
+            seguinte.vl_ret_apu,
+            "VL_RET_APU",
+            anterior.vl_ret_apu,
+        )],
+    );
+
+    let periodo = mm_aaaa(Some(anterior.periodo));
+    for (campo, declarado, valor_anterior) in [
+        ("VL_RET_DED", seguinte.vl_ret_ded, anterior.vl_ret_ded),
+        ("VL_RET_PER", seguinte.vl_ret_per, anterior.vl_ret_per),
+        ("VL_RET_DCOMP", seguinte.vl_ret_dcomp, anterior.vl_ret_dcomp),
+    ] {
+        if valor_anterior - declarado > verificador.tolerancia() {
+            verificador.conferir_registro(
+                seguinte,
+                campo,
+                (declarado, valor_anterior),
+                format!("{campo} acumulado até {periodo}"),
+            );
+        }
+    }
+}
+
+/// Acompanha cada retenção na fonte mês a mês e confronta:
+/// - o saldo de cada registro 1300/1700 com os valores utilizados;
+/// - as retenções sofridas no período (F600) com o VL_RET_APU do período;
+/// - a retenção deduzida no M200/M600 com o acréscimo de VL_RET_DED dos registros 1300/1700;
+/// - cada registro com o registro da mesma retenção no mês seguinte.
+///
+/// `escrituracoes` contém o CNPJ base e o período de apuração dos arquivos analisados:
+/// as verificações entre meses exigem a escrituração do mês vizinho.
+///
+/// Diferenças maiores que a `tolerancia` são retornadas por CNPJ base, período e contribuição.
+pub fn verificar_retencoes_na_fonte(
+    retencoes_na_fonte: &RetencoesNaFonte,
+    escrituracoes: &[(u32, NaiveDate)],
+    tolerancia: Decimal,
+) -> Vec<DivergenciaDeRetencao> {
+    let escrituracoes = escrituracoes_analisadas(escrituracoes);
+    let analisada = |cnpj_base: u32, periodo: Option<NaiveDate>| -> Option<NaiveDate> {
+        periodo.filter(|p| escrituracoes.contains(&(cnpj_base, *p)))
+    };
+
+    let mut verificador = Verificador::new(tolerancia);
+
+    retencoes_na_fonte
+        .saldos
+        .iter()
+        .for_each(|saldo| conferir_saldo(&mut verificador, saldo));
+    let razao = razao(&retencoes_na_fonte.saldos);
+
+    // Transporte dos registros 1300/1700 para o mês seguinte.
+    for ((cnpj_base, contribuicao, ..), meses) in &razao {
+        for (periodo, anterior) in meses {
+            let Some(seguinte) = analisada(*cnpj_base, mes_seguinte(*periodo)) else {
+                continue;
+            };
+            match meses.get(&seguinte) {
+                Some(saldo) => conferir_transporte(&mut verificador, anterior, saldo),
+                None => verificador.nao_informado(
+                    ControleMensal {
+                        periodo: seguinte,
+                        ..anterior.controle()
+                    },
+                    registro_de_controle(*contribuicao),
+                    "SLD_RET",
+                    anterior.sld_ret,
+                    format!("SLD_RET de {}", mm_aaaa(Some(*periodo))),
+                ),
+            }
+        }
+    }
+
+    // Escriturações com retenções, por contribuição.
+    let periodos: BTreeSet<(u32, NaiveDate)> = retencoes_na_fonte
+        .escrituracoes()
+        .into_iter()
+        .filter(|escrituracao| escrituracoes.contains(escrituracao))
+        .collect();
+
+    for (cnpj_base, periodo) in periodos {
+        for contribuicao in [Contribuicao::Pis, Contribuicao::Cofins] {
+            let saldos: Vec<&SaldoDeRetencao> = razao
+                .iter()
+                .filter(|((cnpj, contrib, ..), _)| *cnpj == cnpj_base && *contrib == contribuicao)
+                .filter_map(|(_, meses)| meses.get(&periodo).copied())
+                .collect();
+
+            conferir_retencoes_sofridas(
+                &mut verificador,
+                retencoes_na_fonte,
+                (cnpj_base, periodo, contribuicao),
+                &saldos,
+            );
+
+            // Retenção deduzida no período: acréscimo de VL_RET_DED em relação ao mês anterior.
+            let anterior = analisada(cnpj_base, mes_anterior(periodo));
+            let mut deducao_esperada = Decimal::ZERO;
+            let mut conferivel = true;
+            for saldo in &saldos {
+                let valor_anterior = match anterior {
+                    Some(mes_anterior) => razao
+                        .get(&saldo.chave())
+                        .and_then(|meses| meses.get(&mes_anterior))
+                        .map_or(Decimal::ZERO, |s| s.vl_ret_ded),
+                    None if saldo.pr_rec_ret == Some(periodo) => Decimal::ZERO,
+                    None => {
+                        conferivel = false;
+                        break;
+                    }
+                };
+                deducao_esperada += saldo.vl_ret_ded - valor_anterior;
+            }
+            if !conferivel {
+                continue;
+            }
+
+            let referencia = format!(
+                "acréscimo de VL_RET_DED dos registros {} de {}",
+                registro_de_controle(contribuicao),
+                mm_aaaa(Some(periodo))
+            );
+            let deducoes = ControleMensal {
+                cnpj_base,
+                periodo,
+                contribuicao,
+                rotulo: RETENCOES_DEDUZIDAS.to_string(),
+            };
+            let deducao = retencoes_na_fonte.deducoes.iter().find(|d| {
+                (d.cnpj_base, d.periodo, d.contribuicao) == (cnpj_base, periodo, contribuicao)
+            });
+            match deducao {
+                Some(d) => verificador.conferir(
+                    deducoes,
+                    (d.registro, Some(d.linha)),
+                    "VL_RET_NC + VL_RET_CUM",
+                    (d.total(), deducao_esperada),
+                    referencia,
+                ),
+                None => verificador.nao_informado(
+                    deducoes,
+                    registro_de_apuracao(contribuicao),
+                    "VL_RET_NC + VL_RET_CUM",
+                    deducao_esperada,
+                    referencia,
+                ),
+            }
+        }
+    }
+
+    verificador.divergencias_ordenadas()
+}
+
+/// Retenções sofridas no período (F600) x VL_RET_APU dos registros 1300/1700 do próprio período.
+fn conferir_retencoes_sofridas(
+    verificador: &mut Verificador<ControleMensal>,
+    retencoes_na_fonte: &RetencoesNaFonte,
+    (cnpj_base, periodo, contribuicao): (u32, NaiveDate, Contribuicao),
+    saldos: &[&SaldoDeRetencao],
+) {
+    // IND_NAT_RET -> Σ valor retido
+    let mut retido: BTreeMap<&str, Decimal> = BTreeMap::new();
+    for retencao in retencoes_na_fonte
+        .retencoes
+        .iter()
+        .filter(|r| (r.cnpj_base, r.periodo) == (cnpj_base, periodo))
+    {
+        *retido.entry(&retencao.ind_nat_ret).or_default() += retencao.valor(contribuicao);
+    }
+
+    let referencia = format!("Σ {} dos registros F600", campo_retido(contribuicao));
+    let do_periodo: Vec<&SaldoDeRetencao> = saldos
+        .iter()
+        .filter(|s| s.pr_rec_ret == Some(periodo))
+        .copied()
+        .collect();
+
+    for saldo in &do_periodo {
+        let esperado = retido
+            .get(saldo.ind_nat_ret.as_str())
+            .copied()
+            .unwrap_or_default();
+        verificador.conferir_registro(
+            *saldo,
+            "VL_RET_APU",
+            (saldo.vl_ret_apu, esperado),
+            referencia.clone(),
+        );
+    }
+
+    for (ind_nat_ret, valor) in retido {
+        if !do_periodo.iter().any(|s| s.ind_nat_ret == ind_nat_ret) {
+            verificador.nao_informado(
+                ControleMensal {
+                    cnpj_base,
+                    periodo,
+                    contribuicao,
+                    rotulo: rotulo(ind_nat_ret, Some(periodo)),
+                },
+                registro_de_controle(contribuicao),
+                "VL_RET_APU",
+                valor,
+                referencia.clone(),
+            );
+        }
+    }
+}
+
+/// Retenções na fonte de cada escrituração: retenções sofridas (F600), deduções da contribuição
+/// (M200/M600) e saldos a utilizar (1300/1700), seguidas das divergências,
+/// ou String vazia se não houver o que informar.
+pub fn relatorio_retencoes_na_fonte(
+    retencoes_na_fonte: &RetencoesNaFonte,
+    divergencias: &[DivergenciaDeRetencao],
+) -> String {
+    let mut relatorio = String::new();
+    let valor = |v: Decimal| v.to_formatted_string(DECIMAL_VALOR);
+
+    let escrituracoes = retencoes_na_fonte.escrituracoes();
+    if !escrituracoes.is_empty() {
+        let _ = writeln!(
+            relatorio,
+            "Retenções na fonte (F600, M200/M600 e Registros 1300/1700): {} escrituração(ões).",
+            escrituracoes.len()
+        );
+    }
+
+    for (cnpj_base, periodo) in escrituracoes {
+        let do_periodo = |c: u32, p: NaiveDate| (c, p) == (cnpj_base, periodo);
+        let _ = writeln!(
+            relatorio,
+            "   CNPJ base {cnpj_base:08}, período {}:",
+            mm_aaaa(Some(periodo))
+        );
+
+        for r in retencoes_na_fonte
+            .retencoes
+            .iter()
+            .filter(|r| do_periodo(r.cnpj_base, r.periodo))
+        {
+            let _ = writeln!(
+                relatorio,
+                "      F600 [linha nº {}] natureza {}, fonte pagadora {}: {} retenção(ões), \
+                 PIS/PASEP {}, COFINS {}",
+                r.linha,
+                r.ind_nat_ret,
+                r.cnpj_fonte,
+                r.quantidade,
+                valor(r.vl_ret_pis),
+                valor(r.vl_ret_cofins),
+            );
+        }
+
+        for d in retencoes_na_fonte
+            .deducoes
+            .iter()
+            .filter(|d| do_periodo(d.cnpj_base, d.periodo) && !d.total().is_zero())
+        {
+            let _ = writeln!(
+                relatorio,
+                "      {} [linha nº {}] {} deduzida: {} (não cumulativa {}, cumulativa {})",
+                d.registro,
+                d.linha,
+                d.contribuicao.descricao(),
+                valor(d.total()),
+                valor(d.vl_ret_nc),
+                valor(d.vl_ret_cum),
+            );
+        }
+
+        let mut saldos: Vec<&SaldoDeRetencao> = retencoes_na_fonte
+            .saldos
+            .iter()
+            .filter(|s| do_periodo(s.cnpj_base, s.periodo))
+            .collect();
+        saldos.sort_by_key(|s| (s.contribuicao, s.linha));
+        for s in saldos {
+            let _ = writeln!(
+                relatorio,
+                "      {} [linha nº {}] {}, {}: retido {}, deduzido {}, ressarcimento {}, \
+                 compensação {}, saldo {}",
+                s.registro,
+                s.linha,
+                s.contribuicao.descricao(),
+                s.rotulo(),
+                valor(s.vl_ret_apu),
+                valor(s.vl_ret_ded),
+                valor(s.vl_ret_per),
+                valor(s.vl_ret_dcomp),
+                valor(s.sld_ret),
+            );
+        }
+    }
+
+    if !relatorio.is_empty() {
+        relatorio.push('\n');
+    }
+
+    relatorio.push_str(&relatorio_divergencias_mensais(
+        "Retenções na fonte divergentes dos valores esperados",
+        divergencias,
+    ));
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/retencoes_na_fonte_tests.rs"]
+mod retencoes_na_fonte_tests;
@@ -0,0 +1,328 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{EFDResult, Registro0000, SpedReader};
+
+// ============================================================================
+// Seleção das Escriturações Retificadoras
+// ============================================================================
+
+/*
+Registro 0000:
+- TIPO_ESCRIT: 0 (original) ou 1 (retificadora);
+- NUM_REC_ANTERIOR: nº do recibo da escrituração anterior a ser retificada.
+
+Quando vários arquivos se referem ao mesmo CNPJ e período de apuração, apenas a
+última retificadora deve ser analisada. Caso contrário, a escrituração original e
+a sua retificação são consolidadas em conjunto e os totais são duplicados.
+
+A escrituração substituída é identificada, nesta ordem:
+1. pela cadeia de recibos: o arquivo cujo nome contém o NUM_REC_ANTERIOR de uma
+   retificadora (nomes gerados pelo PVA) é substituído por ela;
+2. pelo tipo de escrituração: a original é substituída pela retificadora;
+3. pela data da assinatura digital, entre retificadoras do mesmo período.
+
+Se não for possível determinar a escrituração mais recente, os arquivos são mantidos.
+*/
+
+/// Tamanho mínimo do nº do recibo (apenas letras e dígitos) para a busca no nome do arquivo.
+const TAMANHO_MINIMO_DO_RECIBO: usize = 10;
+
+/// Identificação da escrituração (Registro 0000) de um arquivo SPED EFD.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Escrituracao {
+    pub arquivo: PathBuf,
+    pub cnpj: Arc<str>,
+    pub periodo_de_apuracao: NaiveDate,
+    pub retificadora: bool,
+    pub num_rec_anterior: Option<Arc<str>>,
+}
+
+impl Escrituracao {
+    /// Lê apenas o Registro 0000 do arquivo.
+    ///
+    /// Retorna `Ok(None)` se o arquivo não contém o Registro 0000.
+    pub fn ler(arquivo: &Path) -> EFDResult<Option<Self>> {
+        let Some(record) = SpedReader::open(arquivo)?.with_registros(&["0000"]).next() else {
+            return Ok(None);
+        };
+
+        let record = record?;
+        let registro = record.downcast_ref::<Registro0000>()?;
+
+        Ok(Some(Self {
+            arquivo: arquivo.to_path_buf(),
+            cnpj: registro.get_cnpj(),
+            periodo_de_apuracao: registro.obter_periodo_de_apuracao(),
+            retificadora: registro.tipo_escrit == Some(1),
+            num_rec_anterior: registro.num_rec_anterior.clone(),
+        }))
+    }
+
+    /// O nome do arquivo contém o nº do recibo informado.
+    fn nome_contem_recibo(&self, recibo: &str) -> bool {
+        let recibo = apenas_alfanumericos(recibo);
+
+        recibo.len() >= TAMANHO_MINIMO_DO_RECIBO
+            && self
+                .arquivo
+                .file_name()
+                .is_some_and(|nome| apenas_alfanumericos(&nome.to_string_lossy()).contains(&recibo))
+    }
+}
+
+fn apenas_alfanumericos(texto: &str) -> String {
+    texto
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Motivo pelo qual a escrituração foi substituída.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotivoDaSubstituicao {
+    /// O nome do arquivo contém o NUM_REC_ANTERIOR da retificadora.
+    ReciboAnterior,
+    /// Escrituração original do mesmo CNPJ e período de uma retificadora.
+    OriginalRetificada,
+    /// Retificadora assinada antes de outra retificadora do mesmo CNPJ e período.
+    AssinaturaAnterior,
+}
+
+impl fmt::Display for MotivoDaSubstituicao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let descricao = match self {
+            Self::ReciboAnterior => "recibo informado no NUM_REC_ANTERIOR da retificadora",
+            Self::OriginalRetificada => "escrituração original com retificadora do mesmo período",
+            Self::AssinaturaAnterior => "retificadora com assinatura digital anterior",
+        };
+        write!(f, "{descricao}")
+    }
+}
+
+/// Escrituração substituída por uma retificadora do mesmo CNPJ e período.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscrituracaoSubstituida {
+    pub arquivo: PathBuf,
+    /// Retificadora substituta ou, sem retificadora mais recente identificável,
+    /// todas as retificadoras candidatas do período.
+    pub substitutas: Vec<PathBuf>,
+    pub motivo: MotivoDaSubstituicao,
+}
+
+/// Resultado da seleção das escriturações.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SelecaoDeEscrituracoes {
+    /// Escriturações substituídas, na ordem de CNPJ e período.
+    pub substituidas: Vec<EscrituracaoSubstituida>,
+    /// Arquivos do mesmo CNPJ e período sem escrituração mais recente identificável.
+    pub indeterminadas: Vec<Vec<PathBuf>>,
+}
+
+impl SelecaoDeEscrituracoes {
+    /// O arquivo foi substituído por uma retificadora.
+    pub fn substituido(&self, arquivo: &Path) -> bool {
+        self.substituidas.iter().any(|s| s.arquivo == arquivo)
+    }
+}
+
+/// Identifica as escriturações substituídas por retificadoras do mesmo CNPJ e período.
+///
+/// A data da assinatura digital é consultada apenas quando há mais de uma
+/// retificadora no mesmo período.
+pub fn selecionar_escrituracoes<F>(
+    escrituracoes: &[Escrituracao],
+    data_da_assinatura: F,
+) -> SelecaoDeEscrituracoes
+where
+    F: Fn(&Path) -> Option<NaiveDateTime>,
+{
+    let mut grupos: BTreeMap<(&str, NaiveDate), Vec<&Escrituracao>> = BTreeMap::new();
+    for escrituracao in escrituracoes {
+        grupos
+            .entry((&escrituracao.cnpj, escrituracao.periodo_de_apuracao))
+            .or_default()
+            .push(escrituracao);
+    }
+
+    let mut selecao = SelecaoDeEscrituracoes::default();
+
+    for grupo in grupos.into_values().filter(|grupo| grupo.len() > 1) {
+        let mut ativas: Vec<&Escrituracao> = grupo.clone();
+
+        // 1. Cadeia de recibos
+        for retificadora in grupo.iter().filter(|e| e.retificadora) {
+            let Some(recibo) = retificadora.num_rec_anterior.as_deref() else {
+                continue;
+            };
+
+            ativas.retain(|anterior| {
+                let substituida =
+                    anterior.arquivo != retificadora.arquivo && anterior.nome_contem_recibo(recibo);
+
+                if substituida {
+                    selecao.substituidas.push(EscrituracaoSubstituida {
+                        arquivo: anterior.arquivo.clone(),
+                        substitutas: vec![retificadora.arquivo.clone()],
+                        motivo: MotivoDaSubstituicao::ReciboAnterior,
+                    });
+                }
+
+                !substituida
+            });
+        }
+
+        let (retificadoras, originais): (Vec<&Escrituracao>, Vec<&Escrituracao>) =
+            ativas.into_iter().partition(|e| e.retificadora);
+
+        // 3. Data da assinatura digital entre as retificadoras remanescentes
+        let assinaturas: Option<Vec<NaiveDateTime>> = match retificadoras.len() {
+            0 | 1 => None,
+            _ => retificadoras
+                .iter()
+                .map(|e| data_da_assinatura(&e.arquivo))
+                .collect(),
+        };
+
+        // Última retificadora (ou as candidatas, se não identificável) e
+        // se as demais retificadoras são substituídas por ela.
+        let (ultimas, substitui_retificadoras) = match (&retificadoras[..], assinaturas) {
+            ([], _) => {
+                if originais.len() > 1 {
+                    selecao.indeterminadas.push(caminhos(&originais));
+                }
+                continue;
+            }
+            ([unica], _) => (vec![*unica], false),
+            (_, Some(datas)) => {
+                let (indice, _) = datas
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, data)| **data)
+                    .expect("ao menos duas retificadoras");
+                (vec![retificadoras[indice]], true)
+            }
+            (_, None) => {
+                selecao.indeterminadas.push(caminhos(&retificadoras));
+                (retificadoras.clone(), false)
+            }
+        };
+
+        if substitui_retificadoras {
+            for anterior in retificadoras
+                .iter()
+                .filter(|e| ultimas.iter().all(|u| u.arquivo != e.arquivo))
+            {
+                selecao.substituidas.push(EscrituracaoSubstituida {
+                    arquivo: anterior.arquivo.clone(),
+                    substitutas: caminhos(&ultimas),
+                    motivo: MotivoDaSubstituicao::AssinaturaAnterior,
+                });
+            }
+        }
+
+        // 2. Tipo de escrituração
+        for original in originais {
+            selecao.substituidas.push(EscrituracaoSubstituida {
+                arquivo: original.arquivo.clone(),
+                substitutas: caminhos(&ultimas),
+                motivo: MotivoDaSubstituicao::OriginalRetificada,
+            });
+        }
+    }
+
+    selecao
+}
+
+fn caminhos(escrituracoes: &[&Escrituracao]) -> Vec<PathBuf> {
+    escrituracoes.iter().map(|e| e.arquivo.clone()).collect()
+}
+
+/// Relatório das escriturações substituídas, ou String vazia se não houver.
+///
+/// Com `manter` (opção --manter-retificadas), os arquivos são apenas informados.
+pub fn relatorio_retificadoras(
+    selecao: &SelecaoDeEscrituracoes,
+    escrituracoes: &[Escrituracao],
+    manter: bool,
+) -> String {
+    if selecao.substituidas.is_empty() && selecao.indeterminadas.is_empty() {
+        return String::new();
+    }
+
+    let periodo = |arquivo: &Path| {
+        escrituracoes
+            .iter()
+            .find(|e| e.arquivo == arquivo)
+            .map(|e| {
+                let pa = e.periodo_de_apuracao;
+                format!("CNPJ {}, período {:02}/{}", e.cnpj, pa.month(), pa.year())
+            })
+            .unwrap_or_default()
+    };
+
+    let mut relatorio = String::new();
+
+    if !selecao.substituidas.is_empty() {
+        let situacao = match manter {
+            true => "mantido(s) na análise (--manter-retificadas)",
+            false => "ignorado(s)",
+        };
+
+        let _ = writeln!(
+            relatorio,
+            "Escriturações substituídas por retificadoras: {} arquivo(s) {situacao}.",
+            selecao.substituidas.len()
+        );
+
+        for substituida in &selecao.substituidas {
+            let substitutas: Vec<String> = substituida
+                .substitutas
+                .iter()
+                .map(|arquivo| arquivo.display().to_string())
+                .collect();
+            let _ = writeln!(
+                relatorio,
+                "   {} ({}): {}.\n      Substituído por{}: {}",
+                substituida.arquivo.display(),
+                periodo(&substituida.arquivo),
+                substituida.motivo,
+                match substitutas.len() {
+                    1 => "",
+                    _ => " uma das retificadoras",
+                },
+                substitutas.join(", "),
+            );
+        }
+
+        relatorio.push('\n');
+    }
+
+    for arquivos in &selecao.indeterminadas {
+        let _ = writeln!(
+            relatorio,
+            "Escriturações do mesmo CNPJ e período sem retificadora mais recente identificável ({}): arquivos mantidos.",
+            periodo(&arquivos[0])
+        );
+        for arquivo in arquivos {
+            let _ = writeln!(relatorio, "   {}", arquivo.display());
+        }
+        relatorio.push('\n');
+    }
+
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/retificadoras_tests.rs"]
+mod retificadoras_tests;
@@ -208,6 +208,16 @@ impl Valores {
         }
     }
 
+    /// Retorna o valor do campo correspondente ao tipo de rateio.
+    pub fn obter_campo_de_rateio(&self, rateio: TipoDeRateio) -> Decimal {
+        match rateio {
+            TipoDeRateio::RecBrutaNCumTribMercInterno => self.valor_rbnc_trib,
+            TipoDeRateio::RecBrutaNCumNTribMercInterno => self.valor_rbnc_ntrib,
+            TipoDeRateio::RecBrutaNCumDeExportacao => self.valor_rbnc_exp,
+            TipoDeRateio::RecBrutaCumulativa => self.valor_rb_cum,
+        }
+    }
+
     /// Distribui os valores de acordo com o rateio usando CodigoDoCredito
     pub fn distribuir_conforme_rateio(
         &mut self,
@@ -544,7 +554,26 @@ pub fn obter_chaves_valores(linha: &DocsFiscais) -> (Chaves, Valores) {
     (chaves, valores)
 }
 
-fn distribuir_creditos_rateados(
+/// Chaves da base de créditos (Tipo de Operação: Entrada) correspondentes
+/// a uma linha de Detalhamento (M505).
+pub(crate) fn chaves_do_detalhamento(linha: &DocsFiscais) -> Chaves {
+    Chaves {
+        //path: linha.arquivo_efd.to_compact_string(),
+        cnpj_base: linha.get_cnpj_base(),
+        ano: linha.ano,
+        trimestre: linha.trimestre,
+        mes: linha.mes,
+        tipo_de_operacao: Some(TipoDeOperacao::Entrada), // atualizar valor: Entrada
+        tipo_de_credito: linha.tipo_de_credito,
+        cst: linha.cst,
+        cfop: linha.cfop, // None
+        aliq_pis: linha.aliq_pis,
+        aliq_cofins: linha.aliq_cofins,
+        natureza_bc: linha.natureza_bc,
+    }
+}
+
+pub(crate) fn distribuir_creditos_rateados(
     linhas: &[DocsFiscais],
     base_creditos: &mut HashMap<Chaves, Valores>,
 ) {
@@ -555,20 +584,7 @@ fn distribuir_creditos_rateados(
         .iter()
         .filter(|&linha| linha.tipo_de_operacao == Some(TipoDeOperacao::Detalhamento))
     {
-        let chaves = Chaves {
-            //path: linha.arquivo_efd.to_compact_string(),
-            cnpj_base: linha.get_cnpj_base(),
-            ano: linha.ano,
-            trimestre: linha.trimestre,
-            mes: linha.mes,
-            tipo_de_operacao: Some(TipoDeOperacao::Entrada), // atualizar valor: Entrada
-            tipo_de_credito: linha.tipo_de_credito,
-            cst: linha.cst,
-            cfop: linha.cfop, // None
-            aliq_pis: linha.aliq_pis,
-            aliq_cofins: linha.aliq_cofins,
-            natureza_bc: linha.natureza_bc,
-        };
+        let chaves = chaves_do_detalhamento(linha);
 
         // usar base_creditos.get_mut(&chaves) para obter uma referência mutável do valor associado à chave.
 
@@ -11,9 +11,10 @@ use std::sync::Arc;
 use struct_iterable::Iterable;
 
 use crate::{
-    CodigoDoCredito, CodigoSituacaoTributaria, ExcelExtension, FloatExt, GrupoDeContas,
-    IndicadorDeOrigem, MesesDoAno, NaturezaBaseCalculo, TipoDeCredito, TipoDeOperacao,
-    TipoDeRateio, TipoDoItem, obter_descricao_do_cfop,
+    CALCULO_DIVERGENTE, CodigoDoCredito, CodigoSituacaoTributaria, ContribuicaoPrevidenciaria,
+    ControleDeCreditos, ErroDeLeitura, ExcelExtension, FloatExt, GrupoDeContas, IndicadorDeOrigem,
+    MesesDoAno, NaturezaBaseCalculo, RetencoesNaFonte, TipoDeCredito, TipoDeOperacao, TipoDeRateio,
+    TipoDoItem, obter_descricao_do_cfop,
 };
 
 #[derive(Debug, Clone)]
@@ -22,6 +23,14 @@ pub struct Informacoes {
     pub periodo_de_apuracao: NaiveDate,
     pub messages: String,
     pub all_docs: Vec<DocsFiscais>,
+    /// Linhas descartadas no modo leniente.
+    pub erros_de_leitura: Vec<ErroDeLeitura>,
+    /// Registros 1100/1500 e créditos do Bloco M (M100/M500).
+    pub controle_de_creditos: ControleDeCreditos,
+    /// Retenções na fonte (F600), deduções (M200/M600) e Registros 1300/1700.
+    pub retencoes_na_fonte: RetencoesNaFonte,
+    /// Contribuição Previdenciária sobre a Receita Bruta (P100).
+    pub cprb: Vec<ContribuicaoPrevidenciaria>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Clone, Iterable)]
@@ -124,8 +133,9 @@ pub struct DocsFiscais {
     #[serde(rename = "Chave do Documento")]
     pub chave_doc: Arc<str>,
 
-    //#[serde(rename = "Verificação da Chave")]
-    //pub verificacao_chave: Arc<str>,
+    #[serde(rename = "Verificação da Chave")]
+    pub verificacao_chave: Arc<str>,
+
     #[serde(rename = "Modelo do Documento Fiscal")]
     pub modelo_doc_fiscal: Arc<str>,
 
@@ -206,6 +216,9 @@ pub struct DocsFiscais {
     )]
     pub valor_cofins: Option<Decimal>,
 
+    #[serde(rename = "Verificação do Cálculo")]
+    pub verificacao_calculo: Arc<str>,
+
     #[serde(
         rename = "Valor de ISS",
         serialize_with = "serialize_option_decimal",
@@ -233,6 +246,22 @@ pub struct DocsFiscais {
         deserialize_with = "csv::invalid_option"
     )]
     pub valor_icms: Option<Decimal>,
+
+    /// CST de PIS/PASEP: a coluna "Código de Situação Tributária (CST)" informa o CST de COFINS.
+    #[serde(
+        rename = "Código de Situação Tributária de PIS/PASEP (CST)",
+        serialize_with = "serialize_cst_opt"
+    )]
+    pub cst_pis: Option<CodigoSituacaoTributaria>,
+
+    /// Série do documento: identifica, com emitente, participante, modelo e número, os documentos duplicados.
+    #[serde(rename = "Série do Documento Fiscal")]
+    pub serie_doc: Arc<str>,
+
+    /// Emitente do documento: o estabelecimento (emissão própria) ou o participante
+    /// (emissão por terceiros).
+    #[serde(rename = "CNPJ/CPF do Emitente")]
+    pub emitente: Arc<str>,
 }
 
 impl ExcelExtension for DocsFiscais {}
@@ -388,6 +417,11 @@ impl DocsFiscais {
         }
     }
 
+    /// Verifica se o valor de PIS/PASEP ou de COFINS diverge do valor recalculado.
+    pub fn calculo_divergente(&self) -> bool {
+        self.verificacao_calculo.starts_with(CALCULO_DIVERGENTE)
+    }
+
     /// Verifica se o campo CST está preenchido (se é um Enum válido).
     pub fn cst_valido(&self) -> bool {
         // Se foi possível fazer o parse para o Enum, ele é semanticamente válido por definição.
@@ -496,6 +530,12 @@ impl DocsFiscais {
         format_if_needed(&mut self.participante_cpf, 11, char::is_alphanumeric, |s| {
             s.format_cpf()
         });
+        format_if_needed(&mut self.emitente, 14, char::is_alphanumeric, |s| {
+            s.format_cnpj()
+        });
+        format_if_needed(&mut self.emitente, 11, char::is_alphanumeric, |s| {
+            s.format_cpf()
+        });
         format_if_needed(&mut self.cod_ncm, 8, char::is_alphanumeric, |s| {
             s.format_ncm()
         });
@@ -522,9 +562,6 @@ impl DocsFiscais {
                 &chave[43..]
             );
             self.chave_doc = Arc::from(formatted);
-            //self.verificacao_chave = Arc::from("Válida");
-        } else if !chave.is_empty() {
-            //self.verificacao_chave = Arc::from("Inválida/Tamanho Incorreto");
         }
     }
 }
@@ -564,6 +601,5 @@ mod docs_fiscais_new_tests {
             colunas.chave_doc.as_ref(),
             "01-2345-67.890.123/4567-89-01-234-567.890.123-456.789.012-3"
         );
-        // assert_eq!(colunas.verificacao_chave.as_ref(), "Válida");
     }
 }
@@ -1,10 +1,11 @@
 pub mod analise_dos_creditos;
 pub mod consolidacao_cst;
 pub mod docs_fiscais;
+pub mod rateio_dos_creditos;
 pub mod receita_bruta_segregada;
 pub mod sped_context;
 
 pub use self::{
-    analise_dos_creditos::*, consolidacao_cst::*, docs_fiscais::*, receita_bruta_segregada::*,
-    sped_context::*,
+    analise_dos_creditos::*, consolidacao_cst::*, docs_fiscais::*, rateio_dos_creditos::*,
+    receita_bruta_segregada::*, sped_context::*,
 };
@@ -0,0 +1,364 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    CodigoDoCredito, CodigoSituacaoTributaria, DECIMAL_ALIQ, DECIMAL_VALOR, DecimalExt,
+    DocsFiscais, EFDResult, ReceitaBruta, ReceitaBrutaSegregadaPorCST, Registro0111, SpedContext,
+    TipoDeOperacao, TipoDeRateio,
+    analise_dos_creditos::{
+        Chaves, Valores, chaves_do_detalhamento, distribuir_creditos_rateados, obter_chaves_valores,
+    },
+    apurar_receita_bruta, consolidar_registros,
+};
+
+use TipoDeRateio::*;
+
+// ============================================================================
+// Conferência do rateio dos créditos comuns (Registro 0111)
+// ============================================================================
+
+/*
+Método de Apropriação Direta x Rateio Proporcional (Registro 0110, IND_APRO_CRED = 2):
+
+Os créditos comuns (CST 53 a 56 e 63 a 66) são rateados conforme a participação de cada
+tipo de receita bruta (Registro 0111) na receita bruta total do período:
+
+    percentual (%) = receita bruta do tipo / receita bruta total * 100
+
+A receita bruta do Registro 0111 é confrontada com a receita bruta apurada a partir dos
+itens dos documentos (apurar_receita_bruta). Os créditos são então recalculados com os
+percentuais dos documentos (distribuir_creditos_rateados), indicando o impacto da diferença.
+
+| CST     | Receitas vinculadas                                          |
+|---------|--------------------------------------------------------------|
+| 53 e 63 | Tributada e Não Tributada no Mercado Interno                 |
+| 54 e 64 | Tributada no Mercado Interno e de Exportação                 |
+| 55 e 65 | Não Tributada no Mercado Interno e de Exportação             |
+| 56 e 66 | Tributada e Não Tributada no Mercado Interno e de Exportação |
+*/
+
+/// Tipos de rateio dos créditos vinculados à receita bruta não cumulativa.
+const RATEIOS_DE_CREDITO: [TipoDeRateio; 3] = [
+    RecBrutaNCumTribMercInterno,
+    RecBrutaNCumNTribMercInterno,
+    RecBrutaNCumDeExportacao,
+];
+
+/// Receita bruta segregada para fins de rateio dos créditos comuns.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ReceitaBrutaDoRateio {
+    /// Receita Bruta Não Cumulativa: Tributada no Mercado Interno.
+    pub trib_mi: Decimal,
+    /// Receita Bruta Não Cumulativa: Não Tributada no Mercado Interno.
+    pub nt_mi: Decimal,
+    /// Receita Bruta Não Cumulativa: de Exportação.
+    pub exp: Decimal,
+    /// Receita Bruta Cumulativa.
+    pub cum: Decimal,
+}
+
+impl ReceitaBrutaDoRateio {
+    /// Receita bruta escriturada no Registro 0111.
+    pub fn escriturada(registro: &Registro0111) -> Self {
+        Self {
+            trib_mi: registro.rec_bru_ncum_trib_mi.unwrap_or_default(),
+            nt_mi: registro.rec_bru_ncum_nt_mi.unwrap_or_default(),
+            exp: registro.rec_bru_ncum_exp.unwrap_or_default(),
+            cum: registro.rec_bru_cum.unwrap_or_default(),
+        }
+    }
+
+    /// Receita bruta apurada a partir dos itens dos documentos, segregada por `apurar_receita_bruta`.
+    pub fn apurada(receitas: &[ReceitaBrutaSegregadaPorCST]) -> Self {
+        receitas.iter().fold(Self::default(), |mut rb, receita| {
+            match receita.rec_bruta {
+                Some(ReceitaBruta::RbnTrmi) => rb.trib_mi += receita.valor,
+                Some(ReceitaBruta::RbnNtmi) => rb.nt_mi += receita.valor,
+                Some(ReceitaBruta::RbnExpo) => rb.exp += receita.valor,
+                Some(ReceitaBruta::RbCumul) => rb.cum += receita.valor,
+                _ => {} // Totais
+            }
+            rb
+        })
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.trib_mi + self.nt_mi + self.exp + self.cum
+    }
+
+    pub fn valor(&self, rateio: TipoDeRateio) -> Decimal {
+        match rateio {
+            RecBrutaNCumTribMercInterno => self.trib_mi,
+            RecBrutaNCumNTribMercInterno => self.nt_mi,
+            RecBrutaNCumDeExportacao => self.exp,
+            RecBrutaCumulativa => self.cum,
+        }
+    }
+
+    /// Percentual de rateio (4 casas decimais) do tipo de receita na receita bruta total.
+    pub fn percentual(&self, rateio: TipoDeRateio) -> Decimal {
+        let total = self.total();
+        if total.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.valor(rateio) / total * dec!(100)).round_dp(4)
+    }
+}
+
+/// Créditos comuns de um tipo de rateio: declarados (M105/M505) e recalculados com os
+/// percentuais de rateio da receita bruta dos documentos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpactoNoCredito {
+    pub rateio: TipoDeRateio,
+    pub bc_declarada: Decimal,
+    pub bc_recalculada: Decimal,
+    pub pis_declarado: Decimal,
+    pub pis_recalculado: Decimal,
+    pub cofins_declarado: Decimal,
+    pub cofins_recalculado: Decimal,
+}
+
+impl ImpactoNoCredito {
+    /// Soma a base de cálculo do tipo de rateio e os créditos correspondentes
+    /// (base de cálculo × alíquota) das duas distribuições.
+    fn new(
+        rateio: TipoDeRateio,
+        declarado: &HashMap<Chaves, Valores>,
+        recalculado: &HashMap<Chaves, Valores>,
+    ) -> Self {
+        let somar = |base_creditos: &HashMap<Chaves, Valores>| {
+            base_creditos.iter().fold(
+                (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+                |(bc, pis, cofins), (chaves, valores)| {
+                    let valor = valores.obter_campo_de_rateio(rateio);
+                    (
+                        bc + valor,
+                        pis + valor * chaves.aliq_pis.unwrap_or_default() / dec!(100),
+                        cofins + valor * chaves.aliq_cofins.unwrap_or_default() / dec!(100),
+                    )
+                },
+            )
+        };
+
+        let (bc_declarada, pis_declarado, cofins_declarado) = somar(declarado);
+        let (bc_recalculada, pis_recalculado, cofins_recalculado) = somar(recalculado);
+
+        Self {
+            rateio,
+            bc_declarada: bc_declarada.round_dp(2),
+            bc_recalculada: bc_recalculada.round_dp(2),
+            pis_declarado: pis_declarado.round_dp(2),
+            pis_recalculado: pis_recalculado.round_dp(2),
+            cofins_declarado: cofins_declarado.round_dp(2),
+            cofins_recalculado: cofins_recalculado.round_dp(2),
+        }
+    }
+}
+
+/// Receita bruta do Registro 0111 confrontada com a receita bruta dos documentos,
+/// e o impacto da diferença nos créditos comuns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConferenciaDoRateio {
+    pub periodo: Option<NaiveDate>,
+    pub escriturada: ReceitaBrutaDoRateio,
+    pub apurada: ReceitaBrutaDoRateio,
+    pub impactos: Vec<ImpactoNoCredito>,
+}
+
+impl ConferenciaDoRateio {
+    /// Diferença total dos créditos de PIS/PASEP e de COFINS (recalculado - declarado).
+    pub fn diferenca_de_credito(&self) -> (Decimal, Decimal) {
+        self.impactos
+            .iter()
+            .fold((Decimal::ZERO, Decimal::ZERO), |(pis, cofins), i| {
+                (
+                    pis + i.pis_recalculado - i.pis_declarado,
+                    cofins + i.cofins_recalculado - i.cofins_declarado,
+                )
+            })
+    }
+}
+
+/// Tipos de rateio aos quais o crédito comum do CST está vinculado.
+fn rateios_do_cst(cst: CodigoSituacaoTributaria) -> &'static [TipoDeRateio] {
+    match cst.code() {
+        53 | 63 => &[RecBrutaNCumTribMercInterno, RecBrutaNCumNTribMercInterno],
+        54 | 64 => &[RecBrutaNCumTribMercInterno, RecBrutaNCumDeExportacao],
+        55 | 65 => &[RecBrutaNCumNTribMercInterno, RecBrutaNCumDeExportacao],
+        56 | 66 => &RATEIOS_DE_CREDITO,
+        _ => &[],
+    }
+}
+
+/// Confronta a receita bruta do Registro 0111 com a receita bruta apurada a partir
+/// dos documentos e recalcula os créditos comuns com os percentuais dos documentos.
+///
+/// Retorna `None` se o arquivo não possui o Registro 0111.
+pub fn conferir_rateio_dos_creditos(
+    ctx: &SpedContext,
+    linhas: &[DocsFiscais],
+    excluir_cst_49: bool,
+) -> EFDResult<Option<ConferenciaDoRateio>> {
+    let Some(registro_0111) = &ctx.registro_0111 else {
+        return Ok(None);
+    };
+
+    let receita_bruta: HashMap<Chaves, Valores> = consolidar_registros(
+        linhas,
+        |linha| {
+            linha.saida_de_receita_bruta()
+                && linha
+                    .cst
+                    .is_some_and(|cst| cst.eh_receita_bruta(excluir_cst_49))
+        },
+        obter_chaves_valores,
+    );
+
+    let escriturada = ReceitaBrutaDoRateio::escriturada(registro_0111);
+    let apurada = ReceitaBrutaDoRateio::apurada(&apurar_receita_bruta(&receita_bruta)?);
+
+    // Base de cálculo dos créditos comuns (apenas CSTs com rateio).
+    let base_creditos: HashMap<Chaves, Valores> = consolidar_registros(
+        linhas,
+        |linha| {
+            linha.entrada_de_credito()
+                && linha.cst.is_some_and(|cst| !rateios_do_cst(cst).is_empty())
+        },
+        obter_chaves_valores,
+    );
+
+    let mut declarado = base_creditos.clone();
+    distribuir_creditos_rateados(linhas, &mut declarado);
+
+    let detalhamentos = redistribuir_detalhamentos(linhas, &base_creditos, &apurada);
+    let mut recalculado = base_creditos;
+    distribuir_creditos_rateados(&detalhamentos, &mut recalculado);
+
+    let impactos = RATEIOS_DE_CREDITO
+        .iter()
+        .map(|&rateio| ImpactoNoCredito::new(rateio, &declarado, &recalculado))
+        .collect();
+
+    Ok(Some(ConferenciaDoRateio {
+        periodo: ctx.periodo_de_apuracao,
+        escriturada,
+        apurada,
+        impactos,
+    }))
+}
+
+/// Gera as linhas de Detalhamento (M505) dos créditos comuns com a base de cálculo
+/// rateada conforme os percentuais da receita bruta `apurada`.
+///
+/// Para cada chave da base de créditos, a primeira linha de Detalhamento serve de modelo
+/// para um Detalhamento por tipo de rateio vinculado ao CST.
+fn redistribuir_detalhamentos(
+    linhas: &[DocsFiscais],
+    base_creditos: &HashMap<Chaves, Valores>,
+    apurada: &ReceitaBrutaDoRateio,
+) -> Vec<DocsFiscais> {
+    let mut modelos: HashMap<Chaves, &DocsFiscais> = HashMap::new();
+
+    for linha in linhas
+        .iter()
+        .filter(|linha| linha.tipo_de_operacao == Some(TipoDeOperacao::Detalhamento))
+    {
+        let chaves = chaves_do_detalhamento(linha);
+        if base_creditos.contains_key(&chaves) {
+            modelos.entry(chaves).or_insert(linha);
+        }
+    }
+
+    let mut detalhamentos = Vec::new();
+
+    for (chaves, modelo) in modelos {
+        let (Some(cst), Some(cod_credito)) = (modelo.cst, modelo.cod_credito) else {
+            continue;
+        };
+        let valor_bc = base_creditos[&chaves].valor_bc;
+
+        for &rateio in rateios_do_cst(cst) {
+            let mut detalhamento = modelo.clone();
+            detalhamento.cod_credito = Some(CodigoDoCredito {
+                rateio,
+                credito: cod_credito.credito,
+            });
+            detalhamento.valor_bc =
+                Some((valor_bc * apurada.percentual(rateio) / dec!(100)).round_dp(2));
+            detalhamentos.push(detalhamento);
+        }
+    }
+
+    detalhamentos
+}
+
+/// Resumo da conferência do rateio: receita bruta e percentuais do Registro 0111 e dos
+/// documentos, e créditos comuns declarados e recalculados por tipo de rateio.
+pub fn relatorio_rateio_dos_creditos(conferencia: &ConferenciaDoRateio) -> String {
+    let fmt_valor = |valor: Decimal| valor.to_formatted_string(DECIMAL_VALOR);
+    let fmt_pct = |pct: Decimal| format!("{}%", pct.to_formatted_string(DECIMAL_ALIQ));
+
+    let periodo = conferencia
+        .periodo
+        .map_or("N/I".to_string(), |p| p.format("%m/%Y").to_string());
+
+    let mut relatorio = format!(
+        "Rateio dos créditos comuns: receita bruta do Registro 0111 x receita bruta dos documentos (período {periodo}).\n"
+    );
+
+    let (escriturada, apurada) = (&conferencia.escriturada, &conferencia.apurada);
+
+    for rateio in RATEIOS_DE_CREDITO.into_iter().chain([RecBrutaCumulativa]) {
+        let _ = writeln!(
+            relatorio,
+            "   {rateio}: 0111 = {} ({}), documentos = {} ({})",
+            fmt_valor(escriturada.valor(rateio)),
+            fmt_pct(escriturada.percentual(rateio)),
+            fmt_valor(apurada.valor(rateio)),
+            fmt_pct(apurada.percentual(rateio)),
+        );
+    }
+
+    let _ = writeln!(
+        relatorio,
+        "   Receita Bruta Total: 0111 = {}, documentos = {}",
+        fmt_valor(escriturada.total()),
+        fmt_valor(apurada.total()),
+    );
+
+    for impacto in &conferencia.impactos {
+        let _ = writeln!(
+            relatorio,
+            "   Créditos comuns vinculados à {}: base de cálculo declarada {}, recalculada {}; \
+             PIS/PASEP declarado {}, recalculado {}; COFINS declarado {}, recalculado {}",
+            impacto.rateio,
+            fmt_valor(impacto.bc_declarada),
+            fmt_valor(impacto.bc_recalculada),
+            fmt_valor(impacto.pis_declarado),
+            fmt_valor(impacto.pis_recalculado),
+            fmt_valor(impacto.cofins_declarado),
+            fmt_valor(impacto.cofins_recalculado),
+        );
+    }
+
+    let (pis, cofins) = conferencia.diferenca_de_credito();
+    let _ = writeln!(
+        relatorio,
+        "   Diferença de crédito com os percentuais dos documentos: PIS/PASEP = {}, COFINS = {}",
+        fmt_valor(pis),
+        fmt_valor(cofins),
+    );
+
+    relatorio.push('\n');
+    relatorio
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "../tests/rateio_dos_creditos_tests.rs"]
+mod rateio_dos_creditos_tests;
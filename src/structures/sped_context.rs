@@ -7,7 +7,9 @@ use std::{
     sync::Arc,
 };
 
-use crate::{Bloco0, EFDError, EFDResult, ResultExt, blocos::*};
+use crate::{
+    Bloco0, EFDError, EFDResult, ResultExt, StringParser, TOLERANCIA_DO_CALCULO, blocos::*,
+};
 
 // ============================================================================
 // 1. Contexto Imutável (Dados Globais e Tabelas)
@@ -30,6 +32,7 @@ pub struct SpedContext {
     // Registro0000
     pub matriz_estabelecimento_cnpj: Arc<str>,
     pub matriz_estabelecimento_nome: Arc<str>,
+    pub matriz_estabelecimento_uf: Arc<str>,
 
     // Estabelecimento das Filiais
     pub estabelecimento_cnpj: Arc<str>,
@@ -42,6 +45,9 @@ pub struct SpedContext {
     pub dt_ini: NaiveDate,
     pub dt_fin: NaiveDate,
 
+    /// Diferença aceita entre os valores de PIS/COFINS recalculados e os declarados.
+    pub tolerancia: Decimal,
+
     // Registro0110
     pub ind_apro_cred: Option<Arc<str>>,
 
@@ -55,11 +61,16 @@ pub struct SpedContext {
     /// Armazena a struct completa para gerar o relatório apenas no final
     pub registro_0111: Option<Registro0111>,
 
+    /// Registro 0145: Regime de Apuração da Contribuição Previdenciária sobre a Receita Bruta
+    pub registro_0145: Option<Registro0145>,
+
     // --- Tabelas de consulta (Lookups com Arc<str>) ---
     // (Chaves Arc<str> economizam RAM se repetidas, Valores Arc<str> evitam cópia)
     pub complementar: HashMap<Arc<str>, Arc<str>>,
     pub contabil: HashMap<Arc<str>, Arc<str>>,
     pub estabelecimentos: HashMap<Arc<str>, Arc<str>>,
+    /// CNPJ do estabelecimento -> Código do município (0000 e 0140)
+    pub municipios_dos_estabelecimentos: HashMap<Arc<str>, Arc<str>>,
     pub nat_operacao: HashMap<Arc<str>, Arc<str>>,
     pub participantes: BTreeMap<Arc<str>, Registro0150>,
     pub produtos: BTreeMap<Arc<str>, Registro0200>,
@@ -88,6 +99,7 @@ impl SpedContext {
         let mut ctx = Self {
             path: path.to_path_buf(),
             arquivo_efd: path.to_string_lossy().into(),
+            tolerancia: TOLERANCIA_DO_CALCULO,
             ..Self::default()
         };
 
@@ -119,13 +131,14 @@ impl SpedContext {
         }
         */
 
-        // 1. FASE SEQUENCIAL: Metadados Críticos (0000, 0110, 0111)
+        // 1. FASE SEQUENCIAL: Metadados Críticos (0000, 0110, 0111, 0145)
         // Processamos o que é global e único antes do paralelismo
         for reg in bloco_0.iter() {
             match reg {
                 Bloco0::R0000(r) => ctx.handle_0000(r),
                 Bloco0::R0110(r) => ctx.handle_0110(r),
                 Bloco0::R0111(r) => ctx.handle_0111(r),
+                Bloco0::R0145(r) => ctx.registro_0145 = Some(r.clone()),
                 _ => {}
             }
         }
@@ -194,6 +207,8 @@ impl SpedContext {
         self.complementar.extend(other.complementar);
         self.contabil.extend(other.contabil);
         self.estabelecimentos.extend(other.estabelecimentos);
+        self.municipios_dos_estabelecimentos
+            .extend(other.municipios_dos_estabelecimentos);
         self.nat_operacao.extend(other.nat_operacao);
         self.unidade_de_medida.extend(other.unidade_de_medida);
 
@@ -207,13 +222,22 @@ impl SpedContext {
     // --- Handlers Específicos (Inline logic separation) ---
 
     /// Registro 0000: Abertura do Arquivo Digital e Identificação da Pessoa Jurídica
+    ///
+    /// Os registros preservam o texto escriturado; nomes, descrições e a UF são
+    /// normalizados para maiúsculas apenas no contexto da análise.
     fn handle_0000(&mut self, r: &Registro0000) {
         self.matriz_estabelecimento_cnpj = r.get_cnpj();
-        self.matriz_estabelecimento_nome = r.get_nome();
+        self.matriz_estabelecimento_nome = r.nome.to_upper_arc().unwrap_or_default();
+        self.matriz_estabelecimento_uf = r.uf.to_upper_arc().unwrap_or_default();
         self.estabelecimento_cnpj_base = r.get_cnpj_base();
         self.periodo_de_apuracao = Some(r.dt_ini);
         self.dt_ini = r.dt_ini;
         self.dt_fin = r.dt_fin;
+
+        if let (Some(cnpj), Some(cod_mun)) = (&r.cnpj, &r.cod_mun) {
+            self.municipios_dos_estabelecimentos
+                .insert(cnpj.clone(), cod_mun.clone());
+        }
     }
 
     /// Registro 0110: Regimes de Apuração da Contribuição Social e de Apropriação de Crédito
@@ -236,11 +260,16 @@ impl SpedContext {
 
     /// Registro 0140: Tabela de Cadastro de Estabelecimentos
     fn handle_0140(&mut self, r: &Registro0140) {
-        if let (Some(cnpj), Some(nome)) = (&r.cnpj, &r.nome)
+        if let (Some(cnpj), Some(nome)) = (&r.cnpj, r.nome.to_upper_arc())
             && !cnpj.is_empty()
             && !nome.is_empty()
         {
-            self.estabelecimentos.insert(cnpj.clone(), nome.clone());
+            self.estabelecimentos.insert(cnpj.clone(), nome);
+        }
+
+        if let (Some(cnpj), Some(cod_mun)) = (&r.cnpj, &r.cod_mun) {
+            self.municipios_dos_estabelecimentos
+                .insert(cnpj.clone(), cod_mun.clone());
         }
     }
 
@@ -279,7 +308,9 @@ impl SpedContext {
     fn handle_0200(&mut self, r: &Registro0200) {
         if let Some(cod_item) = r.cod_item.as_ref().filter(|s| !s.is_empty()) {
             // Clone de registro 0200 é eficiente (contém Arcs).
-            self.produtos.insert(cod_item.clone(), r.clone());
+            let mut produto = r.clone();
+            produto.descr_item = r.descr_item.to_upper_arc();
+            self.produtos.insert(cod_item.clone(), produto);
         }
     }
 
@@ -296,11 +327,11 @@ impl SpedContext {
     /// Registro 0450: Tabela de Informação Complementar do Documento Fiscal
     /// Ver utilização nos registros A110, C110, C500, D100, D500
     fn handle_0450(&mut self, r: &Registro0450) {
-        if let (Some(cod_inf), Some(txt)) = (&r.cod_inf, &r.txt)
+        if let (Some(cod_inf), Some(txt)) = (&r.cod_inf, r.txt.to_upper_arc())
             && !cod_inf.is_empty()
             && !txt.is_empty()
         {
-            self.complementar.insert(cod_inf.clone(), txt.clone());
+            self.complementar.insert(cod_inf.clone(), txt);
         }
     }
 
@@ -309,7 +340,7 @@ impl SpedContext {
         if let Some(cod_conta) = reg.cod_cta.as_ref().filter(|s| !s.is_empty()) {
             // Match funcional para decidir o formato final
             // Evita alocações (format!) a menos que necessário
-            let conta_contabil: Arc<str> = match (&reg.cod_nat_cc, &reg.nome_cta) {
+            let conta_contabil: Arc<str> = match (&reg.cod_nat_cc, reg.nome_cta.to_upper_arc()) {
                 (Some(grupo), Some(nome)) => {
                     // Caso ambos existam: Alocação necessária
                     Arc::from(format!("{}: {}", grupo.descricao_com_codigo(), nome))
@@ -320,7 +351,7 @@ impl SpedContext {
                 }
                 (None, Some(nome)) => {
                     // Apenas nome: Zero Copy (move o Arc existente)
-                    nome.clone()
+                    nome
                 }
                 (None, None) => {
                     // A forma mais idiomática e garantidamente "grátis"
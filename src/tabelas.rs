@@ -892,6 +892,15 @@ impl ModeloDocFiscal {
     pub fn descricao_com_codigo(&self) -> String {
         format!("{} - {}", self.codigo(), self)
     }
+
+    /// Documentos eletrônicos identificados por chave de acesso de 44 dígitos
+    /// (cUF, AAMM, CNPJ, modelo, série, número, tpEmis, código numérico e DV).
+    pub const fn possui_chave_de_acesso(&self) -> bool {
+        matches!(
+            self,
+            Self::NFe | Self::CTe | Self::BPe | Self::NFCe | Self::NF3e | Self::CTeOS
+        )
+    }
 }
 
 impl FromStr for ModeloDocFiscal {
@@ -4206,3 +4215,163 @@ pub fn obter_descricao_do_cfop(cfop_opt: Option<u16>) -> String {
 pub fn is_importacao(cfop: u16) -> bool {
     (3000..=3999).contains(&cfop)
 }
+
+// ============================================================================
+// Tabelas de Natureza da Receita por NCM (CST 04 e 06)
+// ============================================================================
+
+/// Natureza da receita (NAT_REC dos registros M410/M810) de produtos identificados pela NCM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NaturezaDaReceita {
+    /// Tabela do SPED (ex: "4.3.10").
+    pub tabela: &'static str,
+    /// CST das receitas da tabela: 04 (monofásica) ou 06 (alíquota zero).
+    pub cst: u16,
+    /// Código da natureza da receita (NAT_REC).
+    pub codigo: &'static str,
+    pub descricao: &'static str,
+    /// Prefixos da NCM (apenas dígitos) dos produtos da natureza da receita.
+    pub ncm: &'static [&'static str],
+}
+
+/// Principais códigos das Tabelas 4.3.10 (Produtos Sujeitos à Incidência Monofásica - Revenda, CST 04)
+/// e 4.3.13 (Produtos Sujeitos à Alíquota Zero, CST 06).
+///
+/// Códigos ausentes desta tabela não são verificados.
+const NATUREZAS_DA_RECEITA: &[NaturezaDaReceita] = &[
+    // 4.3.10 - Produtos Sujeitos à Incidência Monofásica da Contribuição Social (Revenda)
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "101",
+        descricao: "Gasolinas, exceto gasolina de aviação",
+        ncm: &["27101259"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "102",
+        descricao: "Óleo diesel",
+        ncm: &["27101921", "27101929"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "103",
+        descricao: "Gás liquefeito de petróleo (GLP)",
+        ncm: &["27111210", "27111300", "27111910"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "104",
+        descricao: "Querosene de aviação",
+        ncm: &["27101911"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "105",
+        descricao: "Biodiesel",
+        ncm: &["38260000"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "201",
+        descricao: "Produtos farmacêuticos",
+        ncm: &["3001", "3003", "3004", "30021", "30051010", "30066000"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "301",
+        descricao: "Produtos de perfumaria, de toucador ou de higiene pessoal",
+        ncm: &["3303", "3304", "3305", "33061000", "33071000", "96032100"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "401",
+        descricao: "Máquinas e veículos",
+        ncm: &["8701", "8702", "8703", "8704", "8705", "8706", "8711"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.10",
+        cst: 4,
+        codigo: "501",
+        descricao: "Pneus novos de borracha e câmaras de ar",
+        ncm: &["4011", "4013"],
+    },
+    // 4.3.13 - Produtos Sujeitos à Alíquota Zero da Contribuição Social
+    NaturezaDaReceita {
+        tabela: "4.3.13",
+        cst: 6,
+        codigo: "101",
+        descricao: "Adubos ou fertilizantes",
+        ncm: &["31"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.13",
+        cst: 6,
+        codigo: "102",
+        descricao: "Defensivos agropecuários",
+        ncm: &["3808"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.13",
+        cst: 6,
+        codigo: "103",
+        descricao: "Sementes e mudas destinadas à semeadura e plantio",
+        ncm: &["1209"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.13",
+        cst: 6,
+        codigo: "105",
+        descricao: "Feijão, arroz e farinha de mandioca",
+        ncm: &["071333", "100620", "100630", "110620"],
+    },
+    NaturezaDaReceita {
+        tabela: "4.3.13",
+        cst: 6,
+        codigo: "114",
+        descricao: "Produtos hortícolas e frutas (Capítulos 7 e 8) e ovos",
+        ncm: &["07", "08", "0407"],
+    },
+];
+
+/// Natureza da receita do produto com a NCM e o CST informados (o prefixo mais longo prevalece),
+/// ou `None` se a NCM não consta das tabelas.
+pub fn natureza_da_receita_do_ncm(cst: u16, ncm: &str) -> Option<&'static NaturezaDaReceita> {
+    let ncm: String = ncm.chars().filter(char::is_ascii_digit).collect();
+
+    NATUREZAS_DA_RECEITA
+        .iter()
+        .filter(|natureza| natureza.cst == cst)
+        .filter_map(|natureza| {
+            let tamanho = natureza
+                .ncm
+                .iter()
+                .filter(|prefixo| ncm.starts_with(*prefixo))
+                .map(|prefixo| prefixo.len())
+                .max()?;
+            Some((tamanho, natureza))
+        })
+        .max_by_key(|(tamanho, _)| *tamanho)
+        .map(|(_, natureza)| natureza)
+}
+
+/// Códigos da natureza da receita (NAT_REC) do CST que constam das tabelas.
+pub fn naturezas_da_receita(cst: u16) -> impl Iterator<Item = &'static NaturezaDaReceita> {
+    NATUREZAS_DA_RECEITA
+        .iter()
+        .filter(move |natureza| natureza.cst == cst)
+}
+
+/// Natureza da receita (NAT_REC) do CST informado, se constar das tabelas.
+pub fn natureza_da_receita(cst: u16, codigo: &str) -> Option<&'static NaturezaDaReceita> {
+    NATUREZAS_DA_RECEITA
+        .iter()
+        .find(|natureza| natureza.cst == cst && natureza.codigo == codigo)
+}
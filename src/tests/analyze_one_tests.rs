@@ -68,14 +68,16 @@ fn test_analyze_one_file_new() -> EFDResult<()> {
     let index = 0;
 
     let informacaoes =
-        analyze_one_file(&multiprogressbar, path, index, total).map_loc(|error| {
-            // Aqui mapeamos o EFDError retornado por analyze_one_file
-            // para a nossa nova variante AnalyzeFileError
-            EFDError::AnalyzeFileError {
-                source: Box::new(error),
-                arquivo: path.clone(),
-            }
-        })?;
+        analyze_one_file(&multiprogressbar, path, index, total, &AppConfig::default()).map_loc(
+            |error| {
+                // Aqui mapeamos o EFDError retornado por analyze_one_file
+                // para a nossa nova variante AnalyzeFileError
+                EFDError::AnalyzeFileError {
+                    source: Box::new(error),
+                    arquivo: path.clone(),
+                }
+            },
+        )?;
 
     println!("cnpj_base: {}", informacaoes.cnpj_base);
     println!("periodo_de_apuracao: {}", informacaoes.periodo_de_apuracao);
@@ -114,14 +116,16 @@ fn test_analyze_one_sped_file() -> EFDResult<()> {
     let index = 0;
 
     let informacaoes =
-        analyze_one_file(&multiprogressbar, path, index, total).map_loc(|error| {
-            // Aqui mapeamos o EFDError retornado por analyze_one_file
-            // para a nossa nova variante AnalyzeFileError
-            EFDError::AnalyzeFileError {
-                source: Box::new(error),
-                arquivo: path.to_path_buf(),
-            }
-        })?;
+        analyze_one_file(&multiprogressbar, path, index, total, &AppConfig::default()).map_loc(
+            |error| {
+                // Aqui mapeamos o EFDError retornado por analyze_one_file
+                // para a nossa nova variante AnalyzeFileError
+                EFDError::AnalyzeFileError {
+                    source: Box::new(error),
+                    arquivo: path.to_path_buf(),
+                }
+            },
+        )?;
 
     println!("cnpj_base: {}", informacaoes.cnpj_base);
     println!("periodo_de_apuracao: {}", informacaoes.periodo_de_apuracao);
@@ -0,0 +1,185 @@
+use super::*;
+use crate::{
+    CodigoSituacaoTributaria, EFDResult, TOLERANCIA_DO_CALCULO, create_a_temp_file,
+    read_and_parse_file,
+};
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+
+/// Apuração de PIS/PASEP e COFINS sem divergências:
+/// - M210: 5.000,00 × 1,65% = 82,50 (+ 1,00 de ajuste de acréscimo);
+/// - M200: 83,50 - 16,50 (M100) - 10,00 (1100) = 57,00;
+/// - M610: 5.000,00 × 7,6% = 380,00;
+/// - M600: 380,00 - 76,00 (M500) - 50,00 (1500) = 254,00.
+const APURACAO: &[&str] = &[
+    "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678901230|SP|2360575|786800285|00|9|",
+    "|M001|0|",
+    "|M100|101|0|1000,00|1,6500|||16,50|0,00|0,00|0,00|16,50|1|16,50|0,00|",
+    "|M105|01|50|1000,00||1000,00|1000,00||||",
+    "|M200|83,50|16,50|10,00|57,00|0,00|0,00|57,00|0,00|0,00|0,00|0,00|57,00|",
+    "|M210|01|5000,00|5000,00|0,00|0,00|5000,00|1,6500|||82,50|1,00|0,00|0,00|0,00|83,50|",
+    "|M500|101|0|1000,00|7,6000|||76,00|0,00|0,00|0,00|76,00|1|76,00|0,00|",
+    "|M505|01|50|1000,00||1000,00|1000,00||||",
+    "|M600|380,00|76,00|50,00|254,00|0,00|0,00|254,00|0,00|0,00|0,00|0,00|254,00|",
+    "|M610|01|5000,00|5000,00|0,00|0,00|5000,00|7,6000|||380,00|0,00|0,00|0,00|0,00|380,00|",
+    "|M990|10|",
+    "|1001|0|",
+    "|1100|102020|01||101|100,00|0,00|100,00|0,00|0,00|0,00|100,00|10,00|0,00|0,00|0,00|0,00|90,00|",
+    "|1500|102020|01||101|500,00|0,00|500,00|0,00|0,00|0,00|500,00|50,00|0,00|0,00|0,00|0,00|450,00|",
+    "|1990|4|",
+    "|9999|16|",
+];
+
+/// Item de saída tributado (CST 01) com a contribuição declarada no documento fiscal.
+fn item_de_saida(
+    aliq_pis: Decimal,
+    valor_pis: Decimal,
+    aliq_cofins: Decimal,
+    valor_cofins: Decimal,
+) -> DocsFiscais {
+    DocsFiscais {
+        tipo_de_operacao: Some(TipoDeOperacao::Saida),
+        cst: CodigoSituacaoTributaria::from_u16(1),
+        cst_pis: CodigoSituacaoTributaria::from_u16(1),
+        aliq_pis: Some(aliq_pis),
+        valor_pis: Some(valor_pis),
+        aliq_cofins: Some(aliq_cofins),
+        valor_cofins: Some(valor_cofins),
+        ..Default::default()
+    }
+}
+
+fn apurar(linhas: &[String], docs: &[DocsFiscais]) -> EFDResult<Vec<DivergenciaDeApuracao>> {
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+    let periodo = NaiveDate::from_ymd_opt(2020, 11, 1);
+    Ok(apurar_bloco_m(
+        &sped_file,
+        docs,
+        periodo,
+        TOLERANCIA_DO_CALCULO,
+    ))
+}
+
+#[test]
+/// cargo test -- --show-output apuracao_sem_divergencias
+fn apuracao_sem_divergencias() -> EFDResult<()> {
+    let linhas: Vec<String> = APURACAO.iter().map(|l| l.to_string()).collect();
+    let docs = [
+        item_de_saida(dec!(1.65), dec!(50.00), dec!(7.6), dec!(230.00)),
+        item_de_saida(dec!(1.6500), dec!(32.50), dec!(7.60), dec!(150.00)),
+    ];
+
+    let divergencias = apurar(&linhas, &docs)?;
+
+    assert_eq!(divergencias, Vec::new());
+    assert_eq!(relatorio_apuracao_bloco_m(&divergencias), "");
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output cst_de_cada_contribuicao
+fn cst_de_cada_contribuicao() -> EFDResult<()> {
+    let linhas: Vec<String> = APURACAO.iter().map(|l| l.to_string()).collect();
+
+    // Item com CST_PIS 06 (alíquota zero) e CST_COFINS 01: apenas a COFINS é conferida.
+    let item_com_pis_aliquota_zero = DocsFiscais {
+        cst_pis: CodigoSituacaoTributaria::from_u16(6),
+        ..item_de_saida(dec!(1.65), dec!(10.00), dec!(7.6), dec!(0.00))
+    };
+    let docs = [
+        item_de_saida(dec!(1.65), dec!(82.50), dec!(7.6), dec!(380.00)),
+        item_com_pis_aliquota_zero,
+    ];
+
+    assert_eq!(apurar(&linhas, &docs)?, Vec::new());
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output apuracao_com_divergencias
+fn apuracao_com_divergencias() -> EFDResult<()> {
+    let linhas: Vec<String> = APURACAO
+        .iter()
+        .map(|linha| match linha.starts_with("|M210|") {
+            // Contribuição apurada com erro de arredondamento: 82,00 em vez de 82,50.
+            true => linha
+                .replace("|82,50|", "|82,00|")
+                .replace("|83,50|", "|83,00|"),
+            false => linha.to_string(),
+        })
+        .collect();
+    let docs = [
+        item_de_saida(dec!(1.65), dec!(82.50), dec!(7.6), dec!(380.00)),
+        // Receita financeira sem o registro M210/M610 correspondente.
+        item_de_saida(dec!(0.65), dec!(6.50), dec!(4), dec!(40.00)),
+    ];
+
+    let divergencias = apurar(&linhas, &docs)?;
+
+    let relatorio = relatorio_apuracao_bloco_m(&divergencias);
+    println!("{relatorio}");
+
+    let campos: Vec<(&str, Option<usize>, &str, &str)> = divergencias
+        .iter()
+        .filter(|d| d.contribuicao == Contribuicao::Pis)
+        .map(|d| (d.registro, d.linha, d.codigo.as_str(), d.campo.as_str()))
+        .collect();
+
+    assert_eq!(
+        campos,
+        [
+            ("M200", Some(5), "", "VL_TOT_CONT_NC_PER"),
+            ("M200", Some(5), "", "VL_TOT_CONT_NC_DEV"),
+            ("M200", Some(5), "", "VL_CONT_NC_REC"),
+            ("M200", Some(5), "", "VL_TOT_CONT_REC"),
+            ("M210", Some(6), "01", "VL_CONT_APUR"),
+            (
+                "M210",
+                Some(6),
+                "01",
+                "VL_CONT_APUR (itens dos documentos com alíquota de 1,6500%)"
+            ),
+            (
+                "M210",
+                None,
+                "",
+                "VL_CONT_APUR (itens dos documentos com alíquota de 0,6500%)"
+            ),
+        ]
+    );
+
+    let vl_cont_apur = &divergencias[4];
+    assert_eq!(
+        vl_cont_apur.to_string(),
+        "M210 [linha nº 6] VL_CONT_APUR: declarado 82,00, apurado 82,50"
+    );
+
+    // COFINS: apenas a receita financeira (alíquota de 4%) não tem M610 correspondente.
+    assert_eq!(
+        divergencias
+            .iter()
+            .filter(|d| d.contribuicao == Contribuicao::Cofins)
+            .count(),
+        1
+    );
+
+    assert!(relatorio.contains("   Período 11/2020, PIS/PASEP, COD_CONT 01:\n"));
+    assert!(relatorio.contains("   Período 11/2020, PIS/PASEP, Totais (M200):\n"));
+    assert!(relatorio.contains(
+        "   Período 11/2020, PIS/PASEP, Sem registro M210:\n\
+         \x20     sem registro M210 VL_CONT_APUR (itens dos documentos com alíquota de 0,6500%): \
+         apurado 6,50\n"
+    ));
+    assert!(!relatorio.contains("linha nº 0"));
+    assert!(
+        relatorio.contains(
+            "      M200 [linha nº 5] VL_TOT_CONT_NC_PER: declarado 83,50, apurado 83,00\n"
+        )
+    );
+
+    Ok(())
+}
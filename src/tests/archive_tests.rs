@@ -0,0 +1,198 @@
+use super::*;
+use crate::{SpedReader, read_and_parse_file};
+use flate2::{Compression, GzBuilder};
+use indicatif::MultiProgress;
+use std::io::Write;
+use tempfile::TempDir;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+const EFD_NOME: &str = "PISCOFINS_20201101_20201130_12345678000195_Original_2020.txt";
+
+const OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+fn pattern() -> Pattern {
+    Pattern::new("PISCOFINS_[0-9][0-9]*_[0-9][0-9]*.txt").unwrap()
+}
+
+/// Cria `efd.zip` (com o exemplo e um arquivo alheio) e `efd.gz` no diretório temporário.
+fn criar_arquivos_compactados(dir: &TempDir) -> EFDResult<(PathBuf, PathBuf)> {
+    let conteudo = std::fs::read(EFD_EXEMPLO)?;
+
+    let zip_path = dir.path().join("efd.zip");
+    let mut zip = ZipWriter::new(File::create(&zip_path)?);
+    zip.start_file(format!("2020/{EFD_NOME}"), SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(&conteudo)?;
+    zip.start_file("LEIAME.txt", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"outro arquivo")?;
+    zip.finish().unwrap();
+
+    let gz_path = dir.path().join("efd.gz");
+    let mut gz = GzBuilder::new()
+        .filename(EFD_NOME)
+        .write(File::create(&gz_path)?, Compression::default());
+    gz.write_all(&conteudo)?;
+    gz.finish()?;
+
+    Ok((zip_path, gz_path))
+}
+
+#[test]
+/// cargo test -- --show-output caminho_virtual
+fn caminho_virtual() {
+    let path = archive_entry_path(Path::new("dados/efd.ZIP"), "PISCOFINS_1.txt");
+    assert_eq!(path, PathBuf::from("dados/efd.ZIP!/PISCOFINS_1.txt"));
+    assert_eq!(
+        split_archive_path(&path),
+        Some((Path::new("dados/efd.ZIP"), "PISCOFINS_1.txt"))
+    );
+
+    // Separador em arquivo que não é compactado.
+    assert_eq!(split_archive_path(Path::new("dados/efd.txt!/x.txt")), None);
+    assert_eq!(split_archive_path(Path::new(EFD_EXEMPLO)), None);
+}
+
+#[test]
+/// cargo test -- --show-output ler_arquivos_compactados
+fn ler_arquivos_compactados() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let (zip_path, gz_path) = criar_arquivos_compactados(&dir)?;
+
+    let entradas_zip = list_archive_entries(&zip_path, &pattern(), OPTIONS)?;
+    let entradas_gz = list_archive_entries(&gz_path, &pattern(), OPTIONS)?;
+
+    assert_eq!(
+        entradas_zip,
+        [zip_path.display().to_string() + "!/2020/" + EFD_NOME].map(PathBuf::from)
+    );
+    assert_eq!(
+        entradas_gz,
+        [gz_path.display().to_string() + "!/" + EFD_NOME].map(PathBuf::from)
+    );
+
+    let multiprogressbar = MultiProgress::new();
+    let original = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+
+    for entrada in entradas_zip.iter().chain(&entradas_gz) {
+        assert_eq!(count_efd_lines(entrada)?, 544);
+
+        let sped_file = read_and_parse_file(entrada, &multiprogressbar, 0, 1)?;
+        assert_eq!(
+            sped_file.iter_registros().count(),
+            original.iter_registros().count()
+        );
+
+        let records = SpedReader::open(entrada)?.with_registros(&["C170"]).count();
+        assert_eq!(records, 23);
+    }
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output ler_entradas_em_fluxo
+fn ler_entradas_em_fluxo() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let conteudo = std::fs::read(EFD_EXEMPLO)?;
+
+    let zip_path = dir.path().join("efd.zip");
+    let mut zip = ZipWriter::new(File::create(&zip_path)?);
+    for (nome, metodo) in [
+        ("armazenado.txt", CompressionMethod::Stored),
+        ("compactado.txt", CompressionMethod::Deflated),
+    ] {
+        let options = SimpleFileOptions::default().compression_method(metodo);
+        zip.start_file(nome, options).unwrap();
+        zip.write_all(&conteudo)?;
+    }
+    zip.finish().unwrap();
+
+    for nome in ["armazenado.txt", "compactado.txt"] {
+        let mut lido = Vec::new();
+        open_efd_reader(&archive_entry_path(&zip_path, nome))?.read_to_end(&mut lido)?;
+        assert_eq!(lido, conteudo);
+    }
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output entrada_corrompida
+fn entrada_corrompida() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let conteudo = std::fs::read(EFD_EXEMPLO)?;
+
+    let zip_path = dir.path().join("efd.zip");
+    let mut zip = ZipWriter::new(File::create(&zip_path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file(EFD_NOME, options).unwrap();
+    zip.write_all(&conteudo)?;
+    zip.finish().unwrap();
+
+    // Altera um byte dos dados armazenados (sem alterar o CRC-32 do diretório central).
+    let mut bytes = std::fs::read(&zip_path)?;
+    let pos = bytes.windows(6).position(|w| w == b"|M200|").unwrap();
+    bytes[pos + 6] = if bytes[pos + 6] == b'1' { b'2' } else { b'1' };
+    std::fs::write(&zip_path, bytes)?;
+
+    let mut lido = Vec::new();
+    let error = open_efd_reader(&archive_entry_path(&zip_path, EFD_NOME))?
+        .read_to_end(&mut lido)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("CRC-32"));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output gzip_com_varios_membros
+fn gzip_com_varios_membros() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let conteudo = std::fs::read(EFD_EXEMPLO)?;
+    let (inicio, fim) = conteudo.split_at(conteudo.len() / 2);
+
+    // Arquivo .gz com dois membros concatenados (ex: `cat a.gz b.gz`).
+    let gz_path = dir.path().join("efd.gz");
+    let mut file = File::create(&gz_path)?;
+    for (index, parte) in [inicio, fim].into_iter().enumerate() {
+        let mut builder = GzBuilder::new();
+        if index == 0 {
+            builder = builder.filename(EFD_NOME);
+        }
+        let mut gz = builder.write(&mut file, Compression::default());
+        gz.write_all(parte)?;
+        gz.finish()?;
+    }
+
+    let entradas = list_archive_entries(&gz_path, &pattern(), OPTIONS)?;
+    assert_eq!(entradas, [archive_entry_path(&gz_path, EFD_NOME)]);
+
+    let mut lido = Vec::new();
+    open_efd_reader(&entradas[0])?.read_to_end(&mut lido)?;
+    assert_eq!(lido, conteudo);
+    assert_eq!(count_efd_lines(&entradas[0])?, 544);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output entrada_inexistente
+fn entrada_inexistente() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let (zip_path, _gz_path) = criar_arquivos_compactados(&dir)?;
+
+    let path = archive_entry_path(&zip_path, "PISCOFINS_INEXISTENTE.txt");
+    let error = open_efd_reader(&path).err().unwrap().flatten();
+
+    assert!(matches!(error, EFDError::Zip { .. }));
+
+    Ok(())
+}
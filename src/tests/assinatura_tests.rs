@@ -0,0 +1,183 @@
+use super::*;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+const EFD_ASSINADA: &str = "examples/efd_data_random_assinado";
+
+fn bytes_assinados() -> EFDResult<Vec<u8>> {
+    Ok(std::fs::read(EFD_ASSINADA)?)
+}
+
+#[test]
+/// cargo test -- --show-output extrair_assinatura_digital
+fn extrair_assinatura_digital() -> EFDResult<()> {
+    let assinatura = AssinaturaDigital::ler(Path::new(EFD_ASSINADA))?.unwrap();
+    println!("{assinatura:#?}");
+
+    assert!(
+        assinatura
+            .titular
+            .contains("CN=EMPRESA FICTICIA 89631 SA:12345678901230")
+    );
+    assert!(assinatura.emissor.contains("CN=AC Teste EFD"));
+    assert_eq!(assinatura.cnpj_cpf.as_deref(), Some("12345678901230"));
+    assert_eq!(assinatura.algoritmo, AlgoritmoHash::Sha256);
+    assert!(assinatura.validade_inicio < assinatura.validade_fim);
+    assert!(assinatura.data_assinatura.is_some());
+
+    assert_eq!(assinatura.resumo_confere, Some(true));
+    assert_eq!(assinatura.assinatura_valida, Some(true));
+    assert!(assinatura.arquivo_integro());
+
+    // Arquivo sem assinatura.
+    assert_eq!(AssinaturaDigital::ler(Path::new(EFD_EXEMPLO))?, None);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output arquivo_alterado_apos_assinatura
+fn arquivo_alterado_apos_assinatura() -> EFDResult<()> {
+    let mut bytes = bytes_assinados()?;
+
+    // Altera um valor do primeiro registro M200 (conteúdo assinado).
+    let (conteudo, _bloco) = separar_assinatura(&bytes).unwrap();
+    let pos = conteudo.windows(6).position(|w| w == b"|M200|").unwrap();
+    bytes[pos + 6] = if bytes[pos + 6] == b'1' { b'2' } else { b'1' };
+
+    let assinatura = AssinaturaDigital::extrair(&bytes)?.unwrap();
+
+    assert_eq!(assinatura.resumo_confere, Some(false));
+    assert_eq!(assinatura.assinatura_valida, Some(true));
+    assert!(!assinatura.arquivo_integro());
+    assert_eq!(assinatura.integridade(), Integridade::Alterado);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output integridade_nao_verificavel
+fn integridade_nao_verificavel() -> EFDResult<()> {
+    let mut verificacao = VerificacaoAssinatura::new(Path::new(EFD_ASSINADA))?;
+    assert_eq!(
+        verificacao
+            .assinatura
+            .as_ref()
+            .map(AssinaturaDigital::integridade),
+        Some(Integridade::Inalterado)
+    );
+
+    // Chave não RSA: apenas o messageDigest (não autenticado) é conferido.
+    let assinatura = verificacao.assinatura.as_mut().unwrap();
+    assinatura.assinatura_valida = None;
+
+    assert_eq!(assinatura.resumo_confere, Some(true));
+    assert_eq!(assinatura.integridade(), Integridade::NaoVerificavel);
+    assert!(!assinatura.arquivo_integro());
+
+    println!("{verificacao}");
+    assert!(verificacao.signatario_confere());
+    assert!(!verificacao.confere());
+    assert!(
+        verificacao
+            .to_string()
+            .contains("Integridade: NÃO VERIFICÁVEL")
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output bloco_de_assinatura_com_cabecalho
+fn bloco_de_assinatura_com_cabecalho() -> EFDResult<()> {
+    let bytes = bytes_assinados()?;
+    let (conteudo, bloco) = separar_assinatura(&bytes).unwrap();
+
+    assert!(conteudo.ends_with(b"|9999|434|\n"));
+
+    // Bytes arbitrários entre o Registro 9999 e a estrutura PKCS#7.
+    let mut com_cabecalho = conteudo.to_vec();
+    com_cabecalho.extend_from_slice(b"\r\nCABECALHO\x00\x01\x30");
+    com_cabecalho.extend_from_slice(bloco);
+
+    let assinatura = AssinaturaDigital::extrair(&com_cabecalho)?.unwrap();
+    assert!(assinatura.arquivo_integro());
+
+    // Bloco sem estrutura PKCS#7.
+    let mut invalido = conteudo.to_vec();
+    invalido.extend_from_slice(b"ASSINATURA");
+    let error = AssinaturaDigital::extrair(&invalido).unwrap_err().flatten();
+    assert!(matches!(error, EFDError::InvalidSignature(_)));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output signatario_do_registro_0000
+fn signatario_do_registro_0000() -> EFDResult<()> {
+    let verificacao = VerificacaoAssinatura::new(Path::new(EFD_ASSINADA))?;
+    println!("{verificacao}");
+
+    assert_eq!(verificacao.cnpj_0000.as_deref(), Some("12345678901230"));
+    assert_eq!(verificacao.signatario(), Signatario::Contribuinte);
+    assert!(verificacao.signatario_confere());
+    assert!(verificacao.confere());
+    assert!(verificacao.to_string().contains("Signatário: confere"));
+    assert!(
+        verificacao
+            .to_string()
+            .contains("Cadeia de certificação: NÃO VERIFICADA")
+    );
+
+    let sem_assinatura = VerificacaoAssinatura::new(Path::new(EFD_EXEMPLO))?;
+    assert!(!sem_assinatura.confere());
+    assert_eq!(
+        sem_assinatura.to_string(),
+        "   Arquivo sem assinatura digital.\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output signatario_de_outro_estabelecimento
+fn signatario_de_outro_estabelecimento() -> EFDResult<()> {
+    let mut verificacao = VerificacaoAssinatura::new(Path::new(EFD_ASSINADA))?;
+
+    // Escrituração da filial assinada com o certificado da matriz (mesmo CNPJ base).
+    verificacao.cnpj_0000 = Some("12345678000299".to_string());
+    println!("{verificacao}");
+
+    assert_eq!(verificacao.signatario(), Signatario::OutroEstabelecimento);
+    assert!(verificacao.confere());
+    assert!(verificacao.to_string().contains(
+        "Signatário: assinado por outro estabelecimento da mesma empresa \
+         (titular 12345678901230; Registro 0000: CNPJ 12345678000299)."
+    ));
+
+    // CNPJ de outra empresa.
+    verificacao.cnpj_0000 = Some("87654321000199".to_string());
+    assert_eq!(verificacao.signatario(), Signatario::Divergente);
+    assert!(!verificacao.confere());
+    assert!(verificacao.to_string().contains("Signatário: DIVERGENTE"));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output signatario_nao_identificado
+fn signatario_nao_identificado() -> EFDResult<()> {
+    let bytes = bytes_assinados()?;
+    let (_conteudo, bloco) = separar_assinatura(&bytes).unwrap();
+    let signed_data = localizar_signed_data(bloco).unwrap();
+    let mut signer_info = signed_data.signer_infos.0.get(0).unwrap().clone();
+
+    assert!(certificado_do_signatario(&signed_data, &signer_info).is_some());
+
+    // Identificador sem certificado correspondente: os demais certificados não são usados.
+    let chave = OctetString::new(vec![0u8; 20]).unwrap();
+    signer_info.sid = SignerIdentifier::SubjectKeyIdentifier(SubjectKeyIdentifier(chave));
+
+    assert_eq!(certificado_do_signatario(&signed_data, &signer_info), None);
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use super::*;
+use crate::{AppConfig, EFDResult, TOLERANCIA_DO_CALCULO, analyze_one_file};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+/// Cópia do arquivo de exemplo com os valores de PIS/COFINS recalculados no C170 da linha 57
+/// e com alíquotas em reais (por quantidade) no C170 da linha 59 (COFINS divergente em R$ 0,10).
+fn arquivo_com_calculos(dir: &TempDir) -> EFDResult<PathBuf> {
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let texto = texto
+        .replacen(
+            "|70|7609|2,1|||159|70|7609|9,65|||734|",
+            "|70|7609|2,1|||159,79|70|7609|9,65|||734,27|",
+            1,
+        )
+        .replacen(
+            "|70|1422|0,825|||11|70|1422|3,8|||54|",
+            "|70|||1000,000|0,0112|11,20|70|||1000,000|0,0518|51,90|",
+            1,
+        );
+
+    let path = dir.path().join("efd_com_calculos.txt");
+    std::fs::write(&path, texto)?;
+
+    Ok(path)
+}
+
+#[test]
+/// cargo test -- --show-output calcular_valor_da_contribuicao
+fn calcular_valor_da_contribuicao() {
+    // Alíquota em percentual: 7.609,00 × 2,1% = 159,789 (arredondado para 159,79).
+    let percentual = CalculoDaContribuicao {
+        valor_bc: Some(dec!(7609)),
+        aliquota: Some(dec!(2.1)),
+        ..Default::default()
+    };
+    assert_eq!(percentual.valor_calculado(), Some(dec!(159.79)));
+
+    // Alíquota em reais: 1.000 unidades × R$ 0,0518.
+    let quantidade = CalculoDaContribuicao {
+        quant_bc: Some(dec!(1000)),
+        aliquota_quant: Some(dec!(0.0518)),
+        ..Default::default()
+    };
+    assert_eq!(quantidade.valor_calculado(), Some(dec!(51.80)));
+
+    // Sem alíquota não há o que calcular.
+    let sem_aliquota = CalculoDaContribuicao {
+        valor_bc: Some(dec!(7609)),
+        ..Default::default()
+    };
+    assert_eq!(sem_aliquota.valor_calculado(), None);
+}
+
+#[test]
+/// cargo test -- --show-output verificar_calculo_com_tolerancia
+fn verificar_calculo_com_tolerancia() {
+    let pis = CalculoDaContribuicao {
+        valor_bc: Some(dec!(1000)),
+        aliquota: Some(dec!(1.65)),
+        valor_declarado: Some(dec!(16.49)),
+        ..Default::default()
+    };
+    let cofins = CalculoDaContribuicao {
+        valor_bc: Some(dec!(1000)),
+        aliquota: Some(dec!(7.6)),
+        valor_declarado: Some(dec!(75.00)),
+        ..Default::default()
+    };
+
+    // PIS/PASEP: diferença de R$ 0,01 (dentro da tolerância); COFINS: R$ 1,00.
+    let verificacao = verificar_calculo(&pis, &cofins, dec!(0.01)).unwrap();
+    println!("{verificacao}");
+    assert_eq!(
+        verificacao,
+        VerificacaoCalculo::Divergente(vec![DivergenciaDeCalculo {
+            contribuicao: Contribuicao::Cofins,
+            calculado: dec!(76.00),
+            declarado: dec!(75.00),
+        }])
+    );
+    assert_eq!(
+        verificacao.to_string(),
+        "Divergente: COFINS calculado 76,00, declarado 75,00"
+    );
+
+    // Com tolerância zero, a diferença de um centavo também é divergente.
+    let verificacao = verificar_calculo(&pis, &cofins, Decimal::ZERO).unwrap();
+    assert!(
+        verificacao
+            .to_string()
+            .starts_with("Divergente: PIS/PASEP calculado 16,50, declarado 16,49; COFINS")
+    );
+
+    assert!(
+        verificar_calculo(&pis, &cofins, dec!(1.00))
+            .unwrap()
+            .is_conforme()
+    );
+
+    // Sem valores declarados, nenhuma contribuição é verificada.
+    let sem_valores = CalculoDaContribuicao {
+        valor_declarado: None,
+        ..pis
+    };
+    assert_eq!(
+        verificar_calculo(&sem_valores, &sem_valores, dec!(0.01)),
+        None
+    );
+}
+
+#[test]
+/// cargo test -- --show-output divergencias_no_arquivo
+fn divergencias_no_arquivo() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let path = arquivo_com_calculos(&dir)?;
+    let multiprogressbar = MultiProgress::new();
+
+    let info = analyze_one_file(&multiprogressbar, &path, 0, 1, &AppConfig::default())?;
+
+    let na_linha = |linha: usize| {
+        info.all_docs
+            .iter()
+            .find(|doc| doc.num_linha_efd == Some(linha))
+            .unwrap()
+    };
+
+    assert_eq!(na_linha(57).verificacao_calculo.as_ref(), CALCULO_CONFORME);
+    assert_eq!(
+        na_linha(59).verificacao_calculo.as_ref(),
+        "Divergente: COFINS calculado 51,80, declarado 51,90"
+    );
+
+    // Os valores do arquivo de exemplo são aleatórios (ex: 8.198,00 × 2,1% ≠ 172,00).
+    assert!(na_linha(61).calculo_divergente());
+
+    let relatorio = relatorio_divergencias_de_calculo(&info.all_docs, TOLERANCIA_DO_CALCULO);
+    println!("{relatorio}");
+    assert!(
+        relatorio
+            .starts_with("Divergências no cálculo de PIS/PASEP e COFINS (tolerância de R$ 0,01):")
+    );
+    assert!(info.messages.contains(&relatorio));
+
+    // A tolerância é configurável.
+    let config = AppConfig {
+        tolerancia: dec!(0.10),
+        ..AppConfig::default()
+    };
+    let info = analyze_one_file(&multiprogressbar, &path, 0, 1, &config)?;
+    let c170 = info
+        .all_docs
+        .iter()
+        .find(|doc| doc.num_linha_efd == Some(59))
+        .unwrap();
+    assert_eq!(c170.verificacao_calculo.as_ref(), CALCULO_CONFORME);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output verificar_apenas_contribuicao_do_registro
+fn verificar_apenas_contribuicao_do_registro() -> EFDResult<()> {
+    // C181 (PIS/PASEP) com valor divergente: 1.000,00 × 1,65% = 16,50 ≠ 16,00.
+    // O C185 (COFINS) recebe o PIS/PASEP do C181 por correlação, mas apenas a COFINS
+    // declarada no próprio registro é verificada (o C181 não gera linha própria).
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let mut linhas: Vec<&str> = texto.lines().collect();
+    let c010 = linhas.iter().position(|l| l.starts_with("|C010|")).unwrap();
+    linhas.splice(
+        c010 + 1..c010 + 1,
+        [
+            "|C180|55|01112020|30112020|ITEM01|31052000||1000,00|",
+            "|C181|01|5102|1000,00|0,00|1000,00|1,65|||16,00||",
+            "|C185|01|5102|1000,00|0,00|1000,00|7,60|||76,00||",
+        ],
+    );
+
+    let dir = TempDir::new()?;
+    let path = dir.path().join("efd_com_c180.txt");
+    std::fs::write(&path, linhas.join("\n"))?;
+
+    let multiprogressbar = MultiProgress::new();
+    let info = analyze_one_file(&multiprogressbar, &path, 0, 1, &AppConfig::default())?;
+
+    let na_linha = |linha: usize| {
+        info.all_docs
+            .iter()
+            .find(|doc| doc.num_linha_efd == Some(linha))
+            .unwrap()
+    };
+
+    let c185 = na_linha(c010 + 4);
+    assert_eq!(c185.registro.as_ref(), "C185");
+    assert_eq!(c185.valor_pis, Some(dec!(16.00)));
+    assert_eq!(c185.verificacao_calculo.as_ref(), CALCULO_CONFORME);
+
+    Ok(())
+}
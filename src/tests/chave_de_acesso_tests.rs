@@ -0,0 +1,136 @@
+use super::*;
+use crate::{AppConfig, EFDResult, analyze_one_file};
+use indicatif::MultiProgress;
+use std::path::Path;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+/// NF-e modelo 55, série 1, nº 193391, emitida em 17/11/2020 por 12.345.678/9012-30 (SP).
+const CHAVE_NFE: &str = "35201112345678901230550010001933911123456783";
+
+fn dados_da_nfe() -> DadosDoDocumento<'static> {
+    DadosDoDocumento {
+        cod_mod: Some("55"),
+        serie: Some("1"),
+        num_doc: Some(193391),
+        dt_doc: NaiveDate::from_ymd_opt(2020, 11, 17),
+        cnpj_emitente: Some("12345678901230"),
+        cod_mun_emitente: Some("3550308"),
+    }
+}
+
+#[test]
+/// cargo test -- --show-output calcular_digito_verificador
+fn calcular_digito_verificador() {
+    let chave = "35080599999090910270550010000000015180051273";
+    assert_eq!(digito_verificador_da_chave(&chave[..43]), Some(3));
+    assert_eq!(digito_verificador_da_chave(&CHAVE_NFE[..43]), Some(3));
+
+    // CNPJ alfanumérico: letras valem o código ASCII menos 48.
+    let alfanumerica = "352011ABCDEFGHIJKL1255001000193391112345678";
+    assert_eq!(digito_verificador_da_chave(alfanumerica), Some(0));
+
+    assert_eq!(digito_verificador_da_chave(&chave[..42]), None);
+    assert_eq!(digito_verificador_da_chave(&chave[1..]), Some(4));
+}
+
+#[test]
+/// cargo test -- --show-output verificar_campos_da_chave
+fn verificar_campos_da_chave() {
+    let dados = dados_da_nfe();
+    assert_eq!(verificar_chave(CHAVE_NFE, &dados), VerificacaoChave::Valida);
+    assert_eq!(verificar_chave(CHAVE_NFE, &dados).to_string(), "Válida");
+
+    // Documento de outro número, outro mês e de outro emitente.
+    let outro = DadosDoDocumento {
+        num_doc: Some(193392),
+        dt_doc: NaiveDate::from_ymd_opt(2020, 12, 1),
+        cnpj_emitente: Some("98765432000198"),
+        ..dados
+    };
+    let verificacao = verificar_chave(CHAVE_NFE, &outro);
+    println!("{verificacao}");
+    assert_eq!(
+        verificacao,
+        VerificacaoChave::Divergente(vec![
+            CampoDaChave::AnoMes,
+            CampoDaChave::Cnpj,
+            CampoDaChave::Numero
+        ])
+    );
+    assert_eq!(
+        verificacao.to_string(),
+        "Inválida: AAMM, CNPJ do emitente, número"
+    );
+
+    // Dígito verificador incorreto e chave truncada.
+    let dv_errado = CHAVE_NFE.replace("783", "784");
+    assert_eq!(
+        verificar_chave(&dv_errado, &dados),
+        VerificacaoChave::Divergente(vec![CampoDaChave::DigitoVerificador])
+    );
+    assert_eq!(
+        verificar_chave(&CHAVE_NFE[..43], &dados).to_string(),
+        "Inválida/Tamanho Incorreto"
+    );
+
+    // Campos não informados não são verificados.
+    assert!(verificar_chave(CHAVE_NFE, &DadosDoDocumento::default()).is_valida());
+}
+
+#[test]
+/// cargo test -- --show-output emitente_pessoa_fisica
+fn emitente_pessoa_fisica() {
+    // Nota Fiscal de produtor rural (CPF 123.456.789-01) no campo CNPJ da chave.
+    let chave_sem_dv = "3520110001234567890155001000193391112345678";
+    let dv = digito_verificador_da_chave(chave_sem_dv).unwrap();
+    let chave = format!("{chave_sem_dv}{dv}");
+
+    let dados = DadosDoDocumento {
+        cnpj_emitente: Some("12345678901"),
+        ..dados_da_nfe()
+    };
+
+    assert!(verificar_chave(&chave, &dados).is_valida());
+}
+
+#[test]
+/// cargo test -- --show-output relatorio_de_chaves_invalidas
+fn relatorio_de_chaves_invalidas() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let info = analyze_one_file(
+        &multiprogressbar,
+        Path::new(EFD_EXEMPLO),
+        0,
+        1,
+        &AppConfig::default(),
+    )?;
+
+    // As chaves do arquivo de exemplo são aleatórias.
+    let c170 = info
+        .all_docs
+        .iter()
+        .find(|doc| doc.num_linha_efd == Some(57))
+        .unwrap();
+    assert_eq!(c170.registro.as_ref(), "C170");
+    assert!(c170.verificacao_chave.starts_with("Inválida"));
+
+    // Documentos sem chave de acesso (ex: Bloco F) não são verificados.
+    assert!(
+        info.all_docs
+            .iter()
+            .filter(|doc| doc.registro.starts_with('F'))
+            .all(|doc| doc.verificacao_chave.is_empty())
+    );
+
+    let relatorio = relatorio_chaves_invalidas(&info.all_docs);
+    println!("{relatorio}");
+
+    assert!(relatorio.starts_with("Chaves de acesso inválidas:"));
+    assert!(relatorio.contains(
+        "[linha nº 57], Nº do Documento 193391: 83-8608-15.277.782/0991-09-10-764-278.568.686-056.769.603-1"
+    ));
+    assert!(info.messages.contains(&relatorio));
+
+    Ok(())
+}
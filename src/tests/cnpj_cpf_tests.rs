@@ -0,0 +1,95 @@
+use super::*;
+use crate::{AppConfig, EFDResult, analyze_one_file, read_and_parse_file};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+/// Cópia do arquivo de exemplo com o CPF do primeiro participante (0150)
+/// e o CNPJ do estabelecimento C010 alterados.
+fn arquivo_com_identificadores_invalidos(dir: &TempDir) -> EFDResult<PathBuf> {
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let texto = texto
+        .replacen("|97310873033|", "|97310873034|", 1)
+        .replacen("|C010|12345678901230|", "|C010|12345678901231|", 1);
+
+    let path = dir.path().join("efd_identificadores.txt");
+    std::fs::write(&path, texto)?;
+
+    Ok(path)
+}
+
+#[test]
+/// cargo test -- --show-output validar_cnpj
+fn validar_cnpj() {
+    assert!(cnpj_valido("12345678000195"));
+    assert!(cnpj_valido("12345678901230"));
+
+    // CNPJ alfanumérico (exemplo oficial da Receita Federal: 12.ABC.345/01DE-35).
+    assert!(cnpj_valido("12ABC34501DE35"));
+    assert!(!cnpj_valido("12ABC34501DE36"));
+
+    assert!(!cnpj_valido("12345678000196"));
+    assert!(!cnpj_valido("00000000000000"));
+    assert!(!cnpj_valido("12.345.678/0001-95"));
+    assert!(!cnpj_valido("12abc34501de35"));
+    assert!(!cnpj_valido("12ABC34501DEA5"));
+}
+
+#[test]
+/// cargo test -- --show-output validar_cpf
+fn validar_cpf() {
+    assert!(cpf_valido("97310873033"));
+    assert!(cpf_valido("12345678909"));
+
+    assert!(!cpf_valido("12345678901"));
+    assert!(!cpf_valido("11111111111"));
+    assert!(!cpf_valido("1234567890"));
+    assert!(!cpf_valido("1234567890A"));
+}
+
+#[test]
+/// cargo test -- --show-output relatorio_de_identificadores_invalidos
+fn relatorio_de_identificadores_invalidos() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+
+    // Os identificadores do arquivo de exemplo são válidos.
+    let sped_file = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+    assert_eq!(sped_file.identificadores_invalidos(), Vec::new());
+    assert_eq!(sped_file.relatorio_identificadores_invalidos(), "");
+
+    let dir = TempDir::new()?;
+    let path = arquivo_com_identificadores_invalidos(&dir)?;
+    let sped_file = read_and_parse_file(&path, &multiprogressbar, 0, 1)?;
+
+    let invalidos = sped_file.identificadores_invalidos();
+    let relatorio = sped_file.relatorio_identificadores_invalidos();
+    println!("{relatorio}");
+
+    let resumo: Vec<(&str, &str, &str)> = invalidos
+        .iter()
+        .map(|i| (i.registro.as_str(), i.campo, i.valor.as_str()))
+        .collect();
+    assert_eq!(
+        resumo,
+        [
+            ("0150", "CPF", "97310873034"),
+            ("C010", "CNPJ", "12345678901231")
+        ]
+    );
+    assert!(relatorio.starts_with("CNPJ/CPF com dígitos verificadores inválidos:\n"));
+
+    // O CPF inválido não é propagado para as linhas dos documentos fiscais.
+    let info = analyze_one_file(&multiprogressbar, &path, 0, 1, &AppConfig::default())?;
+    assert!(info.messages.contains(&relatorio));
+    assert!(
+        info.all_docs
+            .iter()
+            .all(|doc| doc.participante_cpf.as_ref() != "97310873034")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,354 @@
+use super::*;
+use crate::{EFDResult, create_a_temp_file};
+use std::path::Path;
+
+const CHAVE: &str = "83860815277782099109107642785686860567696031";
+
+/// Escrituração original:
+/// - NF-e com dois itens;
+/// - Nota Fiscal modelo 01 (sem chave) com um item;
+/// - crédito (M100/M105) e contribuição (M200) do período.
+const ORIGINAL: &[&str] = &[
+    "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678901230|SP|2360575|786800285|00|9|",
+    "|0150|ABC123450002|Empresa Ficticia LTDA|01058|12345678000195|||||||||",
+    "|C100|0|1|ABC123450002|55|00|1|193391|83860815277782099109107642785686860567696031|17112020|17112020|150,00|1|0,00|0,00|150,00|3|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|",
+    "|C170|1|12345678|Item 1|1,00000|UN|100,00|0,00|0|060|1653|2006|0,00|0,00|0,00|0,00|0,00|0,00||||0,00|0,00|0,00|50|100,00|1,6500|||1,65|50|100,00|7,6000|||7,60|591|",
+    "|C170|2|12345679|Item 2|1,00000|UN|50,00|0,00|0|060|1653|2006|0,00|0,00|0,00|0,00|0,00|0,00||||0,00|0,00|0,00|50|50,00|1,6500|||0,83|50|50,00|7,6000|||3,80|591|",
+    "|C100|0|1|ABC123450002|01|00|1|555||18112020|18112020|80,00|1|0,00|0,00|80,00|3|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|",
+    "|C170|1|12345678|Item 1|1,00000|UN|80,00|0,00|0|060|1653|2006|0,00|0,00|0,00|0,00|0,00|0,00||||0,00|0,00|0,00|50|80,00|1,6500|||1,32|50|80,00|7,6000|||6,08|591|",
+    "|M100|101|0|230,00|1,6500|||3,80|0,00|0,00|0,00|3,80|1|3,80|0,00|",
+    "|M105|01|50|230,00||230,00|230,00||||",
+    "|M200|10,00|3,80|0,00|6,20|0,00|0,00|6,20|0,00|0,00|0,00|0,00|6,20|",
+    "|9999|11|",
+];
+
+/// Escrituração retificadora:
+/// - novo participante (0150) antes dos documentos, deslocando as linhas;
+/// - NF-e com o valor do item 1 alterado e o item 2 excluído;
+/// - Nota Fiscal modelo 01 excluída;
+/// - crédito recalculado (M100/M105) e contribuição ajustada (M200).
+const RETIFICADORA: &[&str] = &[
+    "|0000|006|1||99887766554433221100|01112020|30112020|Empresa Ficticia SA|12345678901230|SP|2360575|786800285|00|9|",
+    "|0150|ABC123450002|Empresa Ficticia LTDA|01058|12345678000195|||||||||",
+    "|0150|ABC123450003|Outra Empresa Ficticia LTDA|01058|98765432000198|||||||||",
+    "|C100|0|1|ABC123450002|55|00|1|193391|83860815277782099109107642785686860567696031|17112020|17112020|120,00|1|0,00|0,00|120,00|3|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|",
+    "|C170|1|12345678|Item 1|1,00000|UN|120,00|0,00|0|060|1653|2006|0,00|0,00|0,00|0,00|0,00|0,00||||0,00|0,00|0,00|50|120,00|1,6500|||1,98|50|120,00|7,6000|||9,12|591|",
+    "|M100|101|0|120,00|1,6500|||1,98|0,00|0,00|0,00|1,98|1|1,98|0,00|",
+    "|M105|01|50|120,00||120,00|120,00||||",
+    "|M200|10,00|1,98|0,00|8,02|0,00|0,00|8,02|0,00|0,00|0,00|0,00|8,02|",
+    "|9999|9|",
+];
+
+fn ler(linhas: &[&str]) -> EFDResult<SpedFile> {
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)
+}
+
+/// Leitura dividida entre várias threads, mesmo em máquinas com um único núcleo.
+fn ler_em_paralelo(linhas: &[&str]) -> EFDResult<SpedFile> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(8)
+        .build()
+        .unwrap();
+    pool.install(|| ler(linhas))
+}
+
+#[test]
+/// cargo test -- --show-output comparar_original_e_retificadora
+fn comparar_original_e_retificadora() -> EFDResult<()> {
+    let diferencas = comparar_sped_files(&ler(ORIGINAL)?, &ler(RETIFICADORA)?);
+    let relatorio = relatorio_comparacao(&diferencas);
+    println!("{relatorio}");
+
+    let resumo: Vec<(&str, &str, TipoDeDiferenca)> = diferencas
+        .iter()
+        .map(|d| (d.registro.as_str(), d.identidade.as_str(), d.tipo))
+        .collect();
+
+    let nf_modelo_01 = "IND_OPER 0, COD_PART ABC123450002, COD_MOD 01, SER 1, NUM_DOC 555";
+    let item_1 = format!("CHV_NFE {CHAVE} > NUM_ITEM 1");
+    let item_2 = format!("CHV_NFE {CHAVE} > NUM_ITEM 2");
+
+    assert_eq!(
+        resumo,
+        [
+            ("0000", "ocorrência nº 1", TipoDeDiferenca::Alterado),
+            ("0150", "COD_PART ABC123450003", TipoDeDiferenca::Incluido),
+            (
+                "C100",
+                format!("CHV_NFE {CHAVE}").as_str(),
+                TipoDeDiferenca::Alterado
+            ),
+            ("C100", nf_modelo_01, TipoDeDiferenca::Excluido),
+            ("C170", item_1.as_str(), TipoDeDiferenca::Alterado),
+            ("C170", item_2.as_str(), TipoDeDiferenca::Excluido),
+            (
+                "C170",
+                format!("{nf_modelo_01} > NUM_ITEM 1").as_str(),
+                TipoDeDiferenca::Excluido
+            ),
+            (
+                "M100",
+                "COD_CRED 101, IND_CRED_ORI 0, ALIQ_PIS 1,6500",
+                TipoDeDiferenca::Alterado
+            ),
+            (
+                "M105",
+                "COD_CRED 101, IND_CRED_ORI 0, ALIQ_PIS 1,6500 > NAT_BC_CRED 01, CST_PIS 50",
+                TipoDeDiferenca::Alterado
+            ),
+            ("M200", REGISTRO_UNICO, TipoDeDiferenca::Alterado),
+        ]
+    );
+
+    // O documento é pareado pela chave, apesar do deslocamento das linhas.
+    let c100 = &diferencas[2];
+    assert_eq!(
+        (c100.linha_anterior, c100.linha_posterior),
+        (Some(3), Some(4))
+    );
+    assert_eq!(
+        c100.campos,
+        [
+            CampoAlterado {
+                campo: "VL_DOC".to_string(),
+                anterior: "150,00".to_string(),
+                posterior: "120,00".to_string(),
+            },
+            CampoAlterado {
+                campo: "VL_MERC".to_string(),
+                anterior: "150,00".to_string(),
+                posterior: "120,00".to_string(),
+            },
+        ]
+    );
+
+    let campos_do_item: Vec<&str> = diferencas[4]
+        .campos
+        .iter()
+        .map(|c| c.campo.as_str())
+        .collect();
+    assert_eq!(
+        campos_do_item,
+        [
+            "VL_ITEM",
+            "VL_BC_PIS",
+            "VL_PIS",
+            "VL_BC_COFINS",
+            "VL_COFINS"
+        ]
+    );
+
+    // Registro 0000, sem identidade de negócio: comparado pela posição.
+    assert!(relatorio.starts_with(
+        "Registro 0000: 0 incluído(s), 0 excluído(s), 1 alterado(s).\n\
+         \x20  Alterado [linhas nº 1 -> 1]: ocorrência nº 1\n\
+         \x20     TIPO_ESCRIT: '0' -> '1'\n\
+         \x20     NUM_REC_ANTERIOR: '' -> '99887766554433221100'\n\n\
+         Registro 0150: 1 incluído(s), 0 excluído(s), 0 alterado(s).\n\
+         \x20  Incluído [linha nº 3]: COD_PART ABC123450003\n\
+         \x20     |0150|ABC123450003|Outra Empresa Ficticia LTDA|01058|98765432000198|||||||||\n\n"
+    ));
+    assert!(relatorio.contains("Registro C170: 0 incluído(s), 2 excluído(s), 1 alterado(s).\n"));
+    assert!(relatorio.contains("      VL_TOT_CONT_REC: '6,20' -> '8,02'\n"));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output comparar_demais_registros
+fn comparar_demais_registros() -> EFDResult<()> {
+    let original = [
+        "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678901230|SP|2360575|786800285|00|9|",
+        "|0110|1|2|2||",
+        "|C500|ABC123450006|06|00|C||62021256|17112020|17112020|400,60|0,00||4,61|21,24||",
+        "|C501|50|400,60|04|400,60|1,6500|4,61|637|",
+        "|C700|01|Campo Livre||123,45|",
+        "|P010|12345678000195|",
+        "|P100|01112020|30112020|200000,00|12345678|10000,00|0,00|10000,00|4,5000|500,00|||",
+        "|1300|03|102020|10,00|4,00|0,00|0,00|6,00|",
+        "|9999|9|",
+    ];
+
+    // Retificadora: 0110 e C501 alterados, C700 repetido, nova atividade no P100
+    // e valor deduzido no 1300.
+    let retificadora = [
+        original[0],
+        "|0110|1|2|1||",
+        original[2],
+        "|C501|50|400,60|04|400,60|1,6500|4,62|637|",
+        original[4],
+        original[4],
+        original[5],
+        original[6],
+        "|P100|01112020|30112020|200000,00|87654321|5000,00|0,00|5000,00|2,5000|125,00|||",
+        "|1300|03|102020|10,00|7,00|0,00|0,00|3,00|",
+        "|9999|11|",
+    ];
+
+    let diferencas = comparar_sped_files(&ler(&original)?, &ler(&retificadora)?);
+    println!("{}", relatorio_comparacao(&diferencas));
+
+    let resumo: Vec<(&str, &str, TipoDeDiferenca)> = diferencas
+        .iter()
+        .map(|d| (d.registro.as_str(), d.identidade.as_str(), d.tipo))
+        .collect();
+
+    let c500 = "COD_PART ABC123450006, COD_MOD 06, SER C, SUB , NUM_DOC 62021256";
+    assert_eq!(
+        resumo,
+        [
+            ("0110", "ocorrência nº 1", TipoDeDiferenca::Alterado),
+            (
+                "C501",
+                format!("{c500} > CST_PIS 50, NAT_BC_CRED 04, ALIQ_PIS 1,6500, COD_CTA 637")
+                    .as_str(),
+                TipoDeDiferenca::Alterado
+            ),
+            ("C700", "ocorrência nº 2", TipoDeDiferenca::Incluido),
+            (
+                "P100",
+                "CNPJ 12345678000195 > DT_INI 01112020, DT_FIN 30112020, \
+                 COD_ATIV_ECON 87654321, COD_CTA ",
+                TipoDeDiferenca::Incluido
+            ),
+            (
+                "1300",
+                "IND_NAT_RET 03, PR_REC_RET 102020",
+                TipoDeDiferenca::Alterado
+            ),
+        ]
+    );
+
+    // Registro não suportado pelo parser: campos identificados pela posição.
+    assert_eq!(diferencas[2].linha_posterior, Some(6));
+    assert_eq!(diferencas[0].campos[0].campo, "COD_TIPO_CONT");
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output comparar_arquivo_identico
+fn comparar_arquivo_identico() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+
+    let diferencas = comparar_sped_files(&sped_file, &sped_file);
+
+    assert_eq!(diferencas, Vec::new());
+    assert_eq!(
+        relatorio_comparacao(&diferencas),
+        "Nenhuma diferença encontrada nos registros comparados.\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output identidades_repetidas
+fn identidades_repetidas() -> EFDResult<()> {
+    // O mesmo item informado duas vezes: as ocorrências são pareadas na ordem das linhas.
+    let mut linhas = ORIGINAL.to_vec();
+    linhas.insert(4, ORIGINAL[3]);
+    let repetido = ler(&linhas)?;
+
+    let diferencas = comparar_sped_files(&ler(ORIGINAL)?, &repetido);
+
+    assert_eq!(diferencas.len(), 1);
+    assert_eq!(diferencas[0].tipo, TipoDeDiferenca::Incluido);
+    assert_eq!(
+        diferencas[0].identidade,
+        format!("CHV_NFE {CHAVE} > NUM_ITEM 1 #2")
+    );
+    assert_eq!(diferencas[0].linha_posterior, Some(5));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output comparar_arquivos_grandes
+fn comparar_arquivos_grandes() -> EFDResult<()> {
+    // Milhares de notas modelo 01 com a mesma identidade (numeradas " #n" na ordem das
+    // linhas), cada uma com um item de descrição própria: a leitura é dividida entre as
+    // threads do rayon e os registros não chegam na ordem das linhas.
+    let quantidade = 5000;
+    let mut linhas: Vec<String> = ORIGINAL[..2].iter().map(|l| l.to_string()).collect();
+    for n in 1..=quantidade {
+        linhas.push(ORIGINAL[5].to_string());
+        linhas.push(ORIGINAL[6].replace("|Item 1|", &format!("|Item {n}|")));
+    }
+    linhas.push(format!("|9999|{}|", linhas.len() + 1));
+
+    let texto: Vec<&str> = linhas.iter().map(String::as_str).collect();
+    let diferencas = comparar_sped_files(&ler_em_paralelo(&texto)?, &ler_em_paralelo(&texto)?);
+    assert_eq!(diferencas, Vec::new());
+
+    // Apenas o item da nota nº 4321 é alterado.
+    let indice = linhas
+        .iter()
+        .position(|l| l.contains("|Item 4321|"))
+        .unwrap();
+    let mut alteradas = texto.clone();
+    let item_alterado = linhas[indice].replace("|Item 4321|", "|Item alterado|");
+    alteradas[indice] = &item_alterado;
+
+    let diferencas = comparar_sped_files(&ler_em_paralelo(&texto)?, &ler_em_paralelo(&alteradas)?);
+
+    let nf_modelo_01 = "IND_OPER 0, COD_PART ABC123450002, COD_MOD 01, SER 1, NUM_DOC 555";
+    assert_eq!(diferencas.len(), 1);
+    assert_eq!(diferencas[0].tipo, TipoDeDiferenca::Alterado);
+    assert_eq!(
+        diferencas[0].identidade,
+        format!("{nf_modelo_01} #4321 > NUM_ITEM 1")
+    );
+    assert_eq!(diferencas[0].linha_posterior, Some(indice + 1));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output comparar_leiaute_ate_2018
+fn comparar_leiaute_ate_2018() -> EFDResult<()> {
+    // Até 2018 (COD_VER 003), os registros M210 e M610 seguem o leiaute antigo.
+    let original = [
+        "|0000|003|0|||01112018|30112018|Empresa Ficticia SA|12345678901230|SP|2360575|786800285|00|9|",
+        "|M210|01|12345678,47|12345678,47|1,65|0||203703,69|0|0|0|0|203703,69|",
+        "|M610|01|12345678,47|12345678,47|7,60|0||938271,56|0|0|0|0|938271,56|",
+        "|9999|4|",
+    ];
+    let mut retificadora = original;
+    retificadora[1] = "|M210|01|12345678,47|12345678,47|1,65|0||203703,69|0|0|0|0|203700,00|";
+
+    let diferencas = comparar_sped_files(&ler(&original)?, &ler(&retificadora)?);
+
+    assert_eq!(diferencas.len(), 1);
+    assert_eq!(diferencas[0].registro, "M210");
+    assert_eq!(diferencas[0].tipo, TipoDeDiferenca::Alterado);
+    assert_eq!(diferencas[0].campos.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output comparacao_exige_dois_arquivos
+fn comparacao_exige_dois_arquivos() {
+    let arquivo = PathBuf::from("examples/efd_data_random");
+
+    // Um arquivo ou mais de dois arquivos selecionados.
+    for all_files in [vec![arquivo.clone()], vec![arquivo; 3]] {
+        let config = AppConfig {
+            all_files,
+            diff: true,
+            ..AppConfig::default()
+        };
+
+        let mut buffer = Vec::new();
+        let error = comparar_arquivos(&config, &mut buffer)
+            .unwrap_err()
+            .flatten();
+
+        assert!(matches!(error, EFDError::DiffRequiresTwoFiles));
+    }
+}
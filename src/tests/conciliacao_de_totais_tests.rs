@@ -0,0 +1,94 @@
+use super::*;
+use crate::{EFDResult, TOLERANCIA_DO_CALCULO, create_a_temp_file, read_and_parse_file};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+fn conciliar(path: &Path) -> EFDResult<Vec<Inconsistencia>> {
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    Ok(conciliar_totais(&sped_file, path, TOLERANCIA_DO_CALCULO))
+}
+
+fn na_linha(inconsistencias: &[Inconsistencia], linha: usize) -> Vec<&str> {
+    inconsistencias
+        .iter()
+        .filter(|i| i.linha == linha)
+        .map(|i| i.descricao.as_str())
+        .collect()
+}
+
+#[test]
+/// cargo test -- --show-output conciliar_arquivo_de_exemplo
+fn conciliar_arquivo_de_exemplo() -> EFDResult<()> {
+    let inconsistencias = conciliar(Path::new(EFD_EXEMPLO))?;
+
+    let relatorio = relatorio_conciliacao_de_totais(&inconsistencias);
+    println!("{relatorio}");
+
+    // |C100|...|150,00|...| com um único C170 de VL_ITEM = 70.398,00, VL_PIS = 159 e VL_COFINS = 734
+    assert_eq!(
+        na_linha(&inconsistencias, 56),
+        [
+            "VL_MERC = 150,00, soma de VL_ITEM dos registros C170 (linha nº 57) = 70.398,00.",
+            "VL_PIS = 0,00, soma de VL_PIS dos registros C170 (linha nº 57) = 159,00.",
+            "VL_COFINS = 0,00, soma de VL_COFINS dos registros C170 (linha nº 57) = 734,00.",
+        ]
+    );
+
+    // D101 e D105 são conciliados separadamente com o mesmo D100.
+    assert_eq!(
+        na_linha(&inconsistencias, 131),
+        [
+            "VL_DOC = 250,00, soma de VL_ITEM dos registros D101 (linha nº 132) = 12.092,00.",
+            "VL_DOC = 250,00, soma de VL_ITEM dos registros D105 (linha nº 133) = 12.092,00.",
+        ]
+    );
+
+    assert!(inconsistencias.is_sorted_by_key(|i| i.linha));
+    assert!(relatorio.starts_with("Totais dos documentos divergentes da soma dos itens:"));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output conciliar_documento_com_varios_itens
+fn conciliar_documento_com_varios_itens() -> EFDResult<()> {
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let mut linhas: Vec<String> = texto.lines().map(String::from).collect();
+
+    // C100 da linha 56 com dois itens iguais (linhas 57 e 58): VL_MERC, VL_PIS e VL_COFINS conferem.
+    // O frete de 500,00 integra o VL_DOC, mas não o VL_ITEM dos itens.
+    linhas[55] = [
+        "|C100|0|1|ABC123450387|55|00|1|193391|83860815277782099109107642785686860567696031",
+        "|17112020|17112020|141296,00|1|0,00|0,00|140796,00|0|500,00|0,00|0,00|0,00|0,00|0,00",
+        "|0,00|0,00|318,00|1468,00|0,00|0,00|",
+    ]
+    .concat();
+    let item = linhas[56].clone();
+    linhas.insert(57, item.replacen("|70398|0,00|", "|70398|10,00|", 1));
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let inconsistencias = conciliar(temp_file.path())?;
+
+    for inconsistencia in &inconsistencias {
+        println!("{inconsistencia}");
+    }
+
+    // Apenas o desconto do segundo item não consta do documento.
+    assert_eq!(
+        na_linha(&inconsistencias, 56),
+        ["VL_DESC = 0,00, soma de VL_DESC dos registros C170 (linhas nº 57 a 58) = 10,00."]
+    );
+
+    // Com tolerância de R$ 10,00 o documento é conciliado.
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+    let inconsistencias = conciliar_totais(&sped_file, temp_file.path(), dec!(10));
+    assert!(na_linha(&inconsistencias, 56).is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use super::*;
+use crate::{AppConfig, EFDResult, analyze_one_file};
+use indicatif::MultiProgress;
+use std::path::Path;
+
+fn item(linha: usize, cst: u16, cfop: u16) -> DocsFiscais {
+    let cst = CodigoSituacaoTributaria::from_u16(cst);
+    DocsFiscais {
+        num_linha_efd: Some(linha),
+        registro: Arc::from("C170"),
+        tipo_de_operacao: cst.map(|c| match c.code() {
+            1..=49 => TipoDeOperacao::Saida,
+            _ => TipoDeOperacao::Entrada,
+        }),
+        cst,
+        cfop: Some(cfop),
+        ..Default::default()
+    }
+}
+
+fn regras(doc: &DocsFiscais) -> Vec<&'static str> {
+    RegraCstCfop::TODAS
+        .iter()
+        .filter(|regra| regra.violada(doc))
+        .map(|regra| regra.codigo())
+        .collect()
+}
+
+#[test]
+/// cargo test -- --show-output regras_de_consistencia
+fn regras_de_consistencia() {
+    // Combinações coerentes
+    assert!(regras(&item(1, 1, 5102)).is_empty());
+    assert!(regras(&item(1, 50, 1102)).is_empty());
+    assert!(regras(&item(1, 8, 7101)).is_empty());
+    assert!(regras(&item(1, 9, 5501)).is_empty());
+    assert!(regras(&item(1, 70, 3102)).is_empty());
+
+    // R1: crédito na saída
+    assert_eq!(regras(&item(1, 50, 5102)), ["R1"]);
+    assert_eq!(regras(&item(1, 66, 6102)), ["R1"]);
+
+    // R2: receita na entrada
+    assert_eq!(regras(&item(1, 1, 1102)), ["R2"]);
+
+    // R2 e R3: receita com CFOP de importação
+    assert_eq!(regras(&item(1, 6, 3102)), ["R2", "R3"]);
+
+    // R3: importação com crédito de mercado interno
+    let mut importacao = item(1, 50, 3102);
+    importacao.tipo_de_credito = Some(TipoDeCredito::AliquotaBasica);
+    assert_eq!(regras(&importacao), ["R3"]);
+    importacao.tipo_de_credito = Some(TipoDeCredito::Importacao);
+    assert!(regras(&importacao).is_empty());
+
+    // R4: exportação sem CST 08 ou 09
+    assert_eq!(regras(&item(1, 1, 7101)), ["R4"]);
+    assert_eq!(regras(&item(1, 6, 5501)), ["R4"]);
+
+    // Sem CFOP não há verificação.
+    let mut sem_cfop = item(1, 50, 5102);
+    sem_cfop.cfop = None;
+    assert!(regras(&sem_cfop).is_empty());
+}
+
+#[test]
+/// cargo test -- --show-output relatorio_por_regra
+fn relatorio_por_regra() {
+    let mut ajuste = item(40, 50, 5102);
+    ajuste.tipo_de_operacao = Some(TipoDeOperacao::AjusteAcrescimo);
+
+    let docs = [
+        item(30, 1, 7101),
+        item(20, 50, 5102),
+        ajuste,
+        item(10, 1, 5102),
+    ];
+
+    let ocorrencias = verificar_cst_cfop(&docs);
+    let relatorio = relatorio_cst_cfop(&ocorrencias);
+    println!("{relatorio}");
+
+    // Apenas itens de Entrada ou Saída, na ordem das linhas.
+    let linhas: Vec<(usize, &str)> = ocorrencias
+        .iter()
+        .map(|o| (o.linha, o.regra.codigo()))
+        .collect();
+    assert_eq!(linhas, [(20, "R1"), (30, "R4")]);
+
+    assert_eq!(
+        relatorio,
+        "Combinações incoerentes de CST, CFOP e tipo de operação: 2 ocorrência(s).\n\
+         \x20  Regra R1 (CST de crédito (50 a 66) com CFOP de saída (5xxx, 6xxx ou 7xxx)):\n\
+         \x20     Registro C170 [linha nº 20]: CST 50, CFOP 5102\n\
+         \x20  Regra R4 (saída com CFOP de exportação sem CST 08 (sem incidência) ou 09 (suspensão)):\n\
+         \x20     Registro C170 [linha nº 30]: CST 01, CFOP 7101\n\n"
+    );
+
+    assert_eq!(relatorio_cst_cfop(&[]), "");
+}
+
+#[test]
+/// cargo test -- --show-output verificar_arquivo_de_exemplo
+fn verificar_arquivo_de_exemplo() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let info = analyze_one_file(&multiprogressbar, path, 0, 1, &AppConfig::default())?;
+
+    // Os itens do arquivo de exemplo possuem CST e CFOP coerentes.
+    assert_eq!(verificar_cst_cfop(&info.all_docs), Vec::new());
+    assert!(!info.messages.contains("Combinações incoerentes de CST"));
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use super::*;
+use crate::{EFDResult, create_a_temp_file};
+
+/// Escrituração mensal completa do CNPJ base informado.
+fn periodo(arquivo: &str, cnpj_base: &str, ano: i32, mes: u32) -> PeriodoEscriturado {
+    let dt_ini = NaiveDate::from_ymd_opt(ano, mes, 1).unwrap();
+    PeriodoEscriturado {
+        arquivo: PathBuf::from(arquivo),
+        cnpj_base: Arc::from(cnpj_base),
+        dt_ini,
+        dt_fin: ultimo_dia(dt_ini),
+        situacao_especial: false,
+        periodos_dispensados: Vec::new(),
+    }
+}
+
+fn data(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, mes, dia).unwrap()
+}
+
+#[test]
+/// cargo test -- --show-output lacunas_dispensas_e_duplicidades
+fn lacunas_dispensas_e_duplicidades() {
+    // Março dispensado no Registro 0120 da escrituração de abril.
+    let mut abril = periodo("efd_04.txt", "12345678", 2020, 4);
+    abril.periodos_dispensados = vec![data(2020, 3, 1)];
+
+    let periodos = [
+        periodo("efd_01.txt", "12345678", 2020, 1),
+        periodo("efd_02.txt", "12345678", 2020, 2),
+        periodo("efd_02_copia.txt", "12345678", 2020, 2),
+        abril,
+        periodo("efd_06.txt", "12345678", 2020, 6),
+        // Outro CNPJ base com um único arquivo regular: não informado.
+        periodo("outra_empresa.txt", "87654321", 2020, 1),
+    ];
+
+    let continuidades = verificar_continuidade(&periodos);
+    let relatorio = relatorio_continuidade(&continuidades);
+    println!("{relatorio}");
+
+    assert_eq!(continuidades.len(), 2);
+
+    let continuidade = &continuidades[0];
+    assert_eq!(continuidade.meses_escriturados, 4);
+    assert_eq!(continuidade.total_de_meses(), 6);
+    assert_eq!(continuidade.meses_ausentes, [data(2020, 5, 1)]);
+    assert_eq!(continuidade.meses_dispensados, [data(2020, 3, 1)]);
+    assert_eq!(
+        continuidade.meses_duplicados,
+        [(
+            data(2020, 2, 1),
+            vec![
+                PathBuf::from("efd_02.txt"),
+                PathBuf::from("efd_02_copia.txt")
+            ]
+        )]
+    );
+    assert!(!continuidade.regular());
+    assert!(continuidades[1].regular());
+
+    assert_eq!(
+        relatorio,
+        "Continuidade dos períodos de apuração (CNPJ base 12345678): 01/2020 a 06/2020, 4 de 6 mês(es) escriturado(s).\n\
+         \x20  Meses ausentes: 05/2020\n\
+         \x20  Meses dispensados da escrituração (Registro 0120): 03/2020\n\
+         \x20  Mês 02/2020 em duplicidade: 2 arquivos\n\
+         \x20     efd_02.txt\n\
+         \x20     efd_02_copia.txt\n\n"
+    );
+}
+
+#[test]
+/// cargo test -- --show-output periodos_irregulares
+fn periodos_irregulares() {
+    // Período parcial sem situação especial.
+    let mut parcial = periodo("parcial.txt", "12345678", 2020, 1);
+    parcial.dt_ini = data(2020, 1, 15);
+
+    // Período parcial de encerramento (IND_SIT_ESP): regular.
+    let mut encerramento = periodo("encerramento.txt", "12345678", 2020, 2);
+    encerramento.dt_fin = data(2020, 2, 10);
+    encerramento.situacao_especial = true;
+
+    // Período de mais de um mês.
+    let mut bimestral = periodo("bimestral.txt", "12345678", 2020, 3);
+    bimestral.dt_fin = data(2020, 4, 30);
+
+    // Nome gerado pelo PVA com período distinto do Registro 0000.
+    let nome_divergente = periodo(
+        "dir/PISCOFINS_20200401_20200430_12345678000195_Original_2020.txt",
+        "12345678",
+        2020,
+        5,
+    );
+
+    let continuidades =
+        verificar_continuidade(&[parcial, encerramento, bimestral, nome_divergente]);
+    let relatorio = relatorio_continuidade(&continuidades);
+    println!("{relatorio}");
+
+    let irregulares: Vec<(&str, &str)> = continuidades[0]
+        .periodos_irregulares
+        .iter()
+        .map(|p| (p.arquivo.to_str().unwrap(), p.motivo.as_str()))
+        .collect();
+
+    assert_eq!(
+        irregulares,
+        [
+            (
+                "parcial.txt",
+                "período parcial de 15/01/2020 a 31/01/2020 sem situação especial (IND_SIT_ESP)"
+            ),
+            (
+                "bimestral.txt",
+                "período de 01/03/2020 a 30/04/2020 abrange mais de um mês"
+            ),
+            (
+                "dir/PISCOFINS_20200401_20200430_12345678000195_Original_2020.txt",
+                "o nome do arquivo indica o período de 01/04/2020 a 30/04/2020, \
+                 o Registro 0000 informa de 01/05/2020 a 31/05/2020"
+            ),
+        ]
+    );
+
+    // Abril está no período do arquivo bimestral, mas o mês do período de apuração é março.
+    assert_eq!(continuidades[0].meses_ausentes, [data(2020, 4, 1)]);
+    assert!(relatorio.contains("   Período irregular: parcial.txt: período parcial"));
+}
+
+#[test]
+/// cargo test -- --show-output ler_registros_0000_e_0120
+fn ler_registros_0000_e_0120() -> EFDResult<()> {
+    let linhas = [
+        "|0000|006|0|||01042020|30042020|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+        "|0001|0|",
+        "|0120|022020|02|",
+        "|0120|032020|02|",
+        "|0990|5|",
+        "|9999|5|",
+    ];
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+
+    let periodo = PeriodoEscriturado::ler(temp_file.path())?.expect("Registro 0000");
+
+    assert_eq!(
+        periodo,
+        PeriodoEscriturado {
+            arquivo: temp_file.path().to_path_buf(),
+            cnpj_base: Arc::from("12345678"),
+            dt_ini: data(2020, 4, 1),
+            dt_fin: data(2020, 4, 30),
+            situacao_especial: false,
+            periodos_dispensados: vec![data(2020, 2, 1), data(2020, 3, 1)],
+        }
+    );
+
+    // Arquivo de exemplo: um único mês regular, sem relatório.
+    let exemplo =
+        PeriodoEscriturado::ler(Path::new("examples/efd_data_random"))?.expect("Registro 0000");
+    assert_eq!(exemplo.periodo_de_apuracao(), data(2020, 11, 1));
+    assert_eq!(
+        relatorio_continuidade(&verificar_continuidade(&[exemplo])),
+        ""
+    );
+
+    Ok(())
+}
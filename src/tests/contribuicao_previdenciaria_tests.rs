@@ -0,0 +1,135 @@
+use super::*;
+use crate::{EFDResult, create_a_temp_file, read_and_parse_file};
+use indicatif::MultiProgress;
+use std::path::Path;
+
+/// Dois estabelecimentos na CPRB:
+/// - matriz com duas atividades (alíquotas de 4,5% e 2,5%);
+/// - filial com a contribuição calculada a maior;
+/// - Registro 0145 com a receita das atividades divergente dos registros P100 e
+///   receita total maior que a dos estabelecimentos (não conferida).
+const CPRB: &[&str] = &[
+    "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+    "|0001|0|",
+    "|0145|2|1500000,00|600000,00|900000,00||",
+    "|P001|0|",
+    "|P010|12345678000195|",
+    "|P100|01112020|30112020|800000,00|12345678|500000,00|50000,00|450000,00|4,5000|20250,00|||",
+    "|P100|01112020|30112020|800000,00|87654321|100000,00|0,00|100000,00|2,5000|2500,00|||",
+    "|P010|12345678000276|",
+    "|P100|01112020|30112020|200000,00|12345678|10000,00|0,00|10000,00|4,5000|500,00|||",
+    "|P200|112020|23250,00|100,00|0,00|23150,00|298501|",
+    "|P210|0|100,00|01||Redução da contribuição|01112020|",
+    "|P990|8|",
+    "|9999|13|",
+];
+
+fn apurar(linhas: &[&str]) -> EFDResult<ApuracaoDaCprb> {
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let mut sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+    let context = SpedContext::new(sped_file.take_bloco_0(), temp_file.path())?;
+    Ok(apurar_cprb(&sped_file, &context))
+}
+
+#[test]
+/// cargo test -- --show-output apurar_cprb_por_estabelecimento
+fn apurar_cprb_por_estabelecimento() -> EFDResult<()> {
+    let apuracao = apurar(CPRB)?;
+    let relatorio = relatorio_cprb(&apuracao);
+    println!("{relatorio}");
+
+    let linhas: Vec<(&str, &str, Decimal)> = apuracao
+        .contribuicoes
+        .iter()
+        .map(|c| {
+            (
+                c.estabelecimento_cnpj.as_ref(),
+                c.cod_ativ_econ.as_ref(),
+                c.vl_cont_calculado,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        linhas,
+        [
+            ("12345678000195", "12345678", Decimal::new(2025000, 2)),
+            ("12345678000195", "87654321", Decimal::new(250000, 2)),
+            ("12345678000276", "12345678", Decimal::new(45000, 2)),
+        ]
+    );
+    assert_eq!(apuracao.contribuicoes[2].num_linha_efd, Some(9));
+
+    assert_eq!(
+        relatorio,
+        "Contribuição Previdenciária sobre a Receita Bruta (Bloco P): 2 estabelecimento(s), \
+         CPRB apurada 23.250,00.\n\
+         \x20  CNPJ 12345678000195: receita das atividades 600.000,00, exclusões 50.000,00, \
+         base de cálculo 550.000,00, alíquota 4,5000%, 2,5000%, CPRB apurada 22.750,00\n\
+         \x20  CNPJ 12345678000276: receita das atividades 10.000,00, exclusões 0,00, \
+         base de cálculo 10.000,00, alíquota 4,5000%, CPRB apurada 500,00\n\
+         \x20  Divergências da apuração: 2\n\
+         \x20     0145 [linha nº 3] VL_REC_ATIV: declarado 600.000,00, esperado 610.000,00 \
+         (Σ VL_REC_ATIV_ESTAB dos registros P100)\n\
+         \x20     P100 [linha nº 9] VL_CONT_APU: declarado 500,00, esperado 450,00 \
+         (VL_BC_CONT × ALIQ_CONT / 100)\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output consolidacao_e_registro_0145_ausentes
+fn consolidacao_e_registro_0145_ausentes() -> EFDResult<()> {
+    // Sem 0145 e sem P200; base de cálculo sem as exclusões; ajuste P210 sem P200 é ignorado.
+    let linhas = [
+        CPRB[0],
+        CPRB[1],
+        CPRB[3],
+        CPRB[4],
+        "|P100|01112020|30112020|800000,00|12345678|500000,00|50000,00|500000,00|4,5000|22500,00|||",
+        CPRB[10],
+        "|P990|4|",
+        "|9999|8|",
+    ];
+    let apuracao = apurar(&linhas)?;
+
+    let resumo: Vec<(&str, Option<usize>, &str)> = apuracao
+        .divergencias
+        .iter()
+        .map(|d| (d.registro, d.linha, d.campo.as_str()))
+        .collect();
+
+    assert_eq!(
+        resumo,
+        [
+            ("P200", None, "VL_TOT_CONT_APU"),
+            ("0145", None, "VL_REC_ATIV"),
+            ("P100", Some(5), "VL_BC_CONT"),
+        ]
+    );
+    assert!(
+        relatorio_cprb(&apuracao)
+            .contains("      P200 não informado: VL_TOT_CONT_APU esperado 22.500,00")
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output arquivo_sem_cprb
+fn arquivo_sem_cprb() -> EFDResult<()> {
+    // Arquivo de exemplo: Bloco P sem movimento e sem Registro 0145.
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let mut sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    let context = SpedContext::new(sped_file.take_bloco_0(), path)?;
+
+    let apuracao = apurar_cprb(&sped_file, &context);
+
+    assert_eq!(apuracao, ApuracaoDaCprb::default());
+    assert_eq!(relatorio_cprb(&apuracao), "");
+
+    Ok(())
+}
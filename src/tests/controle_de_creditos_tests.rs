@@ -0,0 +1,166 @@
+use super::*;
+use crate::{
+    EFDResult, read_and_parse_file,
+    tests::escrituracoes_mensais::{CNPJ_BASE, data, escrituracao, trimestre},
+};
+use indicatif::MultiProgress;
+use std::path::Path;
+
+/// Crédito 101 de 10/2020: apurado no M100 e controlado no 1100 a partir de 11/2020.
+const OUTUBRO: &[&str] = &[
+    "|M100|101|0|1000,00|1,6500|||16,50|0,00|0,00|0,00|16,50|1|6,50|10,00|",
+    "|9999|3|",
+];
+
+/// Crédito 201 de 11/2020 com saldo a utilizar em períodos futuros.
+const NOVEMBRO: &[&str] = &[
+    "|M100|201|0|500,00|1,0000|||5,00|0,00|0,00|0,00|5,00|0||5,00|",
+    "|1100|102020|01||101|16,50|0,00|16,50|6,50|0,00|0,00|10,00|4,00|0,00|0,00|0,00|0,00|6,00|",
+    "|9999|4|",
+];
+
+/// Saldo final do crédito 101 divergente e crédito 201 sem controle.
+const DEZEMBRO: &[&str] = &[
+    "|1100|102020|01||101|16,50|0,00|16,50|10,50|0,00|0,00|6,00|2,00|0,00|0,00|0,00|0,00|3,00|",
+    "|9999|3|",
+];
+
+fn extrair(linhas: &[&str], periodo: NaiveDate) -> EFDResult<ControleDeCreditos> {
+    let sped_file = escrituracao(linhas, periodo)?;
+    Ok(ControleDeCreditos::extrair(&sped_file, CNPJ_BASE, periodo))
+}
+
+/// Controle dos créditos de outubro a dezembro de 2020.
+fn controle_trimestral(
+    dezembro: &[&str],
+) -> EFDResult<(ControleDeCreditos, Vec<(u32, NaiveDate)>)> {
+    let mut controle = ControleDeCreditos::default();
+    let mut escrituracoes = Vec::new();
+
+    for (periodo, sped_file) in trimestre([OUTUBRO, NOVEMBRO, dezembro])? {
+        controle.append(&mut ControleDeCreditos::extrair(
+            &sped_file, CNPJ_BASE, periodo,
+        ));
+        escrituracoes.push((CNPJ_BASE, periodo));
+    }
+
+    Ok((controle, escrituracoes))
+}
+
+#[test]
+/// cargo test -- --show-output razao_dos_creditos
+fn razao_dos_creditos() -> EFDResult<()> {
+    let (controle, escrituracoes) = controle_trimestral(DEZEMBRO)?;
+
+    let divergencias = verificar_controle_de_creditos(&controle, &escrituracoes, Decimal::ZERO);
+    let relatorio = relatorio_controle_de_creditos(&controle, &divergencias, true);
+    println!("{relatorio}");
+
+    assert_eq!(controle.saldos.len(), 2);
+    assert_eq!(controle.saldos[0].sd_cred_disp_efd, Decimal::new(1000, 2));
+    assert_eq!(controle.creditos_do_periodo.len(), 2);
+
+    assert_eq!(
+        relatorio,
+        "Controle dos créditos de períodos anteriores (Registros 1100/1500): 1 crédito(s).\n\
+         \x20  CNPJ base 12345678, PIS/PASEP, COD_CRED 101 de 10/2020:\n\
+         \x20     11/2020 [1100, linha nº 3]: saldo inicial 10,00, desconto 4,00, ressarcimento 0,00, \
+         compensação 0,00, transferência 0,00, outras 0,00, saldo final 6,00\n\
+         \x20     12/2020 [1100, linha nº 2]: saldo inicial 6,00, desconto 2,00, ressarcimento 0,00, \
+         compensação 0,00, transferência 0,00, outras 0,00, saldo final 3,00\n\n\
+         Controle dos créditos divergente dos saldos esperados: 2 divergência(s).\n\
+         \x20  CNPJ base 12345678, período 12/2020, PIS/PASEP, COD_CRED 101 de 10/2020:\n\
+         \x20     1100 [linha nº 2] SLD_CRED_FIM: declarado 3,00, esperado 4,00 \
+         (SD_CRED_DISP_EFD menos os valores utilizados no período)\n\
+         \x20  CNPJ base 12345678, período 12/2020, PIS/PASEP, COD_CRED 201 de 11/2020:\n\
+         \x20     1100 não informado: SD_CRED_DISP_EFD esperado 5,00 (SLD_CRED do M100 de 11/2020)\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output saldo_inicial_diferente_do_saldo_final_anterior
+fn saldo_inicial_diferente_do_saldo_final_anterior() -> EFDResult<()> {
+    // Dezembro inicia com 7,00, mas novembro terminou com 6,00.
+    let dezembro = [
+        "|1100|102020|01||101|16,50|0,00|16,50|10,50|0,00|0,00|7,00|2,00|0,00|0,00|0,00|0,00|5,00|",
+        "|1100|112020|01||201|5,00|0,00|5,00|0,00|0,00|0,00|5,00|0,00|0,00|0,00|0,00|0,00|5,00|",
+        DEZEMBRO[1],
+    ];
+    let (controle, escrituracoes) = controle_trimestral(&dezembro)?;
+
+    let divergencias = verificar_controle_de_creditos(&controle, &escrituracoes, Decimal::ZERO);
+
+    let resumo: Vec<(&str, &str, String)> = divergencias
+        .iter()
+        .map(|d| {
+            (
+                d.origem.rotulo.as_str(),
+                d.campo.as_str(),
+                d.referencia.clone(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        resumo,
+        [
+            (
+                "COD_CRED 101 de 10/2020",
+                "SD_CRED_DISP_EFD",
+                "VL_TOT_CRED_APU menos os valores utilizados em períodos anteriores".to_string()
+            ),
+            (
+                "COD_CRED 101 de 10/2020",
+                "SD_CRED_DISP_EFD",
+                "SLD_CRED_FIM de 11/2020".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        (divergencias[1].declarado, divergencias[1].esperado),
+        (Decimal::new(700, 2), Decimal::new(600, 2))
+    );
+
+    // Dentro da tolerância, não há divergência.
+    assert!(verificar_controle_de_creditos(&controle, &escrituracoes, Decimal::ONE).is_empty());
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output sem_escrituracao_do_mes_seguinte
+fn sem_escrituracao_do_mes_seguinte() -> EFDResult<()> {
+    // O saldo do M100 de outubro só pode ser conferido com a escrituração de novembro.
+    let controle = extrair(OUTUBRO, data(2020, 10))?;
+    let divergencias =
+        verificar_controle_de_creditos(&controle, &[(CNPJ_BASE, data(2020, 10))], Decimal::ZERO);
+
+    assert!(divergencias.is_empty());
+    assert_eq!(
+        relatorio_controle_de_creditos(&controle, &divergencias, true),
+        ""
+    );
+
+    // Arquivo de exemplo: sem o razão (um único arquivo), apenas as divergências do próprio mês.
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    let controle = ControleDeCreditos::extrair(&sped_file, CNPJ_BASE, data(2020, 11));
+    let divergencias =
+        verificar_controle_de_creditos(&controle, &[(CNPJ_BASE, data(2020, 11))], Decimal::ZERO);
+    let relatorio = relatorio_controle_de_creditos(&controle, &divergencias, false);
+    println!("{relatorio}");
+
+    assert_eq!(controle.saldos.len(), 23);
+    assert_eq!(divergencias.len(), 2);
+    assert!(
+        divergencias
+            .iter()
+            .all(|d| d.origem.periodo == data(2020, 11))
+    );
+    assert!(!relatorio.contains("Controle dos créditos de períodos anteriores"));
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use super::*;
+use crate::{AppConfig, EFDResult, analyze_one_file};
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+use std::{fs, path::Path};
+use tempfile::NamedTempFile;
+
+const CHAVE: &str = "35-2011-12.345.678/0001-95-55-001-000.001.234-100.001.234-5";
+
+/// Item de entrada escriturado no arquivo e estabelecimento informados.
+fn item(arquivo: &str, cnpj: &str, linha: usize, mes: u32) -> DocsFiscais {
+    DocsFiscais {
+        arquivo_efd: Arc::from(arquivo),
+        num_linha_efd: Some(linha),
+        estabelecimento_cnpj: Arc::from(cnpj),
+        periodo_de_apuracao: NaiveDate::from_ymd_opt(2020, mes, 1),
+        tipo_de_operacao: Some(TipoDeOperacao::Entrada),
+        registro: Arc::from("C170"),
+        participante_cnpj: Arc::from("12.345.678/0001-95"),
+        modelo_doc_fiscal: Arc::from("55 - Nota Fiscal Eletrônica"),
+        serie_doc: Arc::from("1"),
+        num_doc: Some(1234),
+        chave_doc: Arc::from(CHAVE),
+        // Emissão por terceiros: o emitente é o participante.
+        emitente: Arc::from("12.345.678/0001-95"),
+        valor_item: Some(dec!(100)),
+        ..Default::default()
+    }
+}
+
+fn sem_chave(doc: DocsFiscais) -> DocsFiscais {
+    DocsFiscais {
+        chave_doc: Arc::from(""),
+        ..doc
+    }
+}
+
+#[test]
+/// cargo test -- --show-output duplicidade_por_chave_e_por_identidade
+fn duplicidade_por_chave_e_por_identidade() {
+    let mut saida = item("efd_12.txt", "11.111.111/0001-11", 50, 12);
+    saida.tipo_de_operacao = Some(TipoDeOperacao::Saida);
+
+    let mut modelo_57 = sem_chave(item("efd_11.txt", "11.111.111/0001-11", 90, 11));
+    modelo_57.modelo_doc_fiscal = Arc::from("57 - Conhecimento de Transporte Eletrônico");
+
+    let docs = [
+        // Mesma chave em dois períodos: a ocorrência de novembro possui dois itens.
+        item("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        item("efd_11.txt", "11.111.111/0001-11", 21, 11),
+        item("efd_12.txt", "11.111.111/0001-11", 30, 12),
+        // Saída do mesmo documento: outro tipo de operação, sem duplicidade.
+        saida,
+        // Mesma identidade, sem chave, em dois estabelecimentos.
+        sem_chave(item("efd_11.txt", "22.222.222/0001-22", 40, 11)),
+        // Modelo distinto: outro documento.
+        modelo_57,
+    ];
+
+    let duplicados = verificar_documentos_duplicados(&docs);
+    let relatorio = relatorio_documentos_duplicados(&duplicados);
+    println!("{relatorio}");
+
+    let ocorrencias: Vec<(&str, &str, Option<usize>, usize, Decimal)> = duplicados
+        .iter()
+        .map(|d| {
+            (
+                d.criterio.as_ref(),
+                d.arquivo_efd.as_ref(),
+                d.num_linha_efd,
+                d.ocorrencias,
+                d.valor_item,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        ocorrencias,
+        [
+            (CRITERIO_CHAVE, "efd_11.txt", Some(20), 2, dec!(200)),
+            (CRITERIO_CHAVE, "efd_12.txt", Some(30), 2, dec!(100)),
+            (CRITERIO_IDENTIDADE, "efd_11.txt", Some(20), 3, dec!(200)),
+            (CRITERIO_IDENTIDADE, "efd_11.txt", Some(40), 3, dec!(100)),
+            (CRITERIO_IDENTIDADE, "efd_12.txt", Some(30), 3, dec!(100)),
+        ]
+    );
+
+    assert_eq!(
+        duplicados[2].identificacao.as_ref(),
+        "Emitente 12.345.678/0001-95, Participante 12.345.678/0001-95, Modelo 55, Série 1, Nº 1234"
+    );
+
+    assert!(relatorio.starts_with(
+        "Documentos duplicados entre arquivos ou estabelecimentos: 2 documento(s), 5 ocorrência(s).\n"
+    ));
+    assert!(relatorio.contains(
+        "      efd_12.txt [linha nº 30]: período 12/2020, estabelecimento 11.111.111/0001-11, Registro C170\n"
+    ));
+}
+
+#[test]
+/// cargo test -- --show-output identidade_com_a_mesma_chave
+fn identidade_com_a_mesma_chave() {
+    // Duplicidade já informada pela chave: a identidade do documento não é repetida.
+    let docs = [
+        item("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        item("efd_12.txt", "11.111.111/0001-11", 30, 12),
+    ];
+
+    let duplicados = verificar_documentos_duplicados(&docs);
+    assert_eq!(duplicados.len(), 2);
+    assert!(
+        duplicados
+            .iter()
+            .all(|d| d.criterio.as_ref() == CRITERIO_CHAVE)
+    );
+
+    // Sem duplicidade, não há relatório.
+    assert_eq!(verificar_documentos_duplicados(&docs[..1]), Vec::new());
+    assert_eq!(relatorio_documentos_duplicados(&[]), "");
+}
+
+#[test]
+/// cargo test -- --show-output emissao_propria_de_estabelecimentos_distintos
+fn emissao_propria_de_estabelecimentos_distintos() {
+    // Emissão própria, sem chave: o emitente é o estabelecimento.
+    let propria = |arquivo: &str, cnpj: &str, linha: usize, mes: u32| DocsFiscais {
+        emitente: Arc::from(cnpj),
+        ..sem_chave(item(arquivo, cnpj, linha, mes))
+    };
+
+    // Mesmo número em estabelecimentos distintos: documentos distintos.
+    let docs = [
+        propria("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        propria("efd_11.txt", "22.222.222/0001-22", 40, 11),
+    ];
+    assert_eq!(verificar_documentos_duplicados(&docs), Vec::new());
+
+    // Mesmo estabelecimento em dois períodos: duplicidade.
+    let docs = [
+        propria("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        propria("efd_12.txt", "11.111.111/0001-11", 30, 12),
+    ];
+    let duplicados = verificar_documentos_duplicados(&docs);
+    assert_eq!(duplicados.len(), 2);
+    assert_eq!(
+        duplicados[0].identificacao.as_ref(),
+        "Emitente 11.111.111/0001-11, Participante 12.345.678/0001-95, Modelo 55, Série 1, Nº 1234"
+    );
+}
+
+#[test]
+/// cargo test -- --show-output identidade_com_chaves_distintas
+fn identidade_com_chaves_distintas() {
+    // Mesma identidade e chaves de acesso distintas: documentos distintos.
+    let mut outra_chave = item("efd_12.txt", "11.111.111/0001-11", 30, 12);
+    outra_chave.chave_doc = Arc::from(CHAVE.replace("-5", "-9").as_str());
+
+    let docs = [
+        item("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        outra_chave,
+    ];
+    assert_eq!(verificar_documentos_duplicados(&docs), Vec::new());
+
+    // Ocorrência sem chave: a identidade do documento indica a duplicidade.
+    let docs = [
+        item("efd_11.txt", "11.111.111/0001-11", 20, 11),
+        sem_chave(item("efd_12.txt", "11.111.111/0001-11", 30, 12)),
+    ];
+    let duplicados = verificar_documentos_duplicados(&docs);
+    assert_eq!(duplicados.len(), 2);
+    assert!(
+        duplicados
+            .iter()
+            .all(|d| d.criterio.as_ref() == CRITERIO_IDENTIDADE)
+    );
+}
+
+#[test]
+/// cargo test -- --show-output arquivo_de_exemplo_em_duplicidade
+fn arquivo_de_exemplo_em_duplicidade() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let config = AppConfig::default();
+
+    let path = Path::new("examples/efd_data_random");
+    let copia = NamedTempFile::new()?;
+    fs::copy(path, copia.path())?;
+
+    let original = analyze_one_file(&multiprogressbar, path, 0, 2, &config)?;
+    assert_eq!(
+        verificar_documentos_duplicados(&original.all_docs),
+        Vec::new()
+    );
+
+    let copia = analyze_one_file(&multiprogressbar, copia.path(), 1, 2, &config)?;
+    let docs: Vec<DocsFiscais> = [original.all_docs, copia.all_docs].concat();
+
+    // Todos os documentos do arquivo de exemplo são escriturados nos dois arquivos.
+    let duplicados = verificar_documentos_duplicados(&docs);
+    assert!(!duplicados.is_empty());
+    assert!(duplicados.iter().all(|d| d.ocorrencias == 2));
+    assert!(
+        duplicados
+            .iter()
+            .any(|d| d.criterio.as_ref() == CRITERIO_CHAVE)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use super::*;
+use crate::{
+    AppConfig, RegistroC100, RegistroC170, SpedReader, analyze_one_file, read_and_parse_file,
+    read_and_parse_file_leniente,
+};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+use tempfile::TempDir;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+/// Cópia do arquivo de exemplo com um VL_ITEM inválido no C170 da linha 57
+/// e uma data inválida no C100 da linha 56.
+fn arquivo_com_erros(dir: &TempDir) -> EFDResult<PathBuf> {
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let texto = texto
+        .replacen("|6|70398|0,00|0|060|", "|6|70X98|0,00|0|060|", 1)
+        .replacen(
+            "|17112020|17112020|150,00|",
+            "|32112020|17112020|150,00|",
+            1,
+        );
+
+    let path = dir.path().join("efd_com_erros.txt");
+    std::fs::write(&path, texto)?;
+
+    Ok(path)
+}
+
+#[test]
+/// cargo test -- --show-output leitura_leniente
+fn leitura_leniente() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let path = arquivo_com_erros(&dir)?;
+    let multiprogressbar = MultiProgress::new();
+
+    // Modo padrão: o primeiro erro interrompe a leitura.
+    assert!(read_and_parse_file(&path, &multiprogressbar, 0, 1).is_err());
+
+    // Modo leniente: todos os erros são registrados e as demais linhas são lidas.
+    let (sped_file, erros) = read_and_parse_file_leniente(&path, &multiprogressbar, 0, 1)?;
+
+    for erro in &erros {
+        println!("{erro}");
+    }
+
+    assert_eq!(erros.len(), 2);
+    assert_eq!((erros[0].linha, erros[0].registro.as_str()), (56, "C100"));
+    assert_eq!((erros[1].linha, erros[1].registro.as_str()), (57, "C170"));
+    assert_eq!(erros[1].campo.as_deref(), Some("VL_ITEM"));
+    assert!(erros[1].descricao.contains("70X98"));
+    assert!(!erros[1].descricao.contains('\n'));
+
+    assert_eq!(
+        sped_file
+            .obter_lista_registros::<RegistroC170>("C170")
+            .len(),
+        22
+    );
+    assert!(
+        sped_file
+            .obter_lista_registros::<RegistroC100>("C100")
+            .iter()
+            .all(|c100| c100.line_number != 56)
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output descendentes_do_registro_descartado
+fn descendentes_do_registro_descartado() -> EFDResult<()> {
+    // C100 da linha 68 com DT_DOC inválida: os seus registros C110, C120 e C170
+    // (linhas 69 a 71) não podem ser vinculados ao C100 da linha 64.
+    let dir = TempDir::new()?;
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let texto = texto.replacen(
+        "|483634|39433305844046645829237696203178042295788167|0911",
+        "|483634|39433305844046645829237696203178042295788167|3211",
+        1,
+    );
+    let path = dir.path().join("efd_com_erros.txt");
+    std::fs::write(&path, texto)?;
+
+    let multiprogressbar = MultiProgress::new();
+    let (sped_file, erros) = read_and_parse_file_leniente(&path, &multiprogressbar, 0, 1)?;
+
+    for erro in &erros {
+        println!("{erro}");
+    }
+
+    let linhas: Vec<(usize, &str, Option<&str>)> = erros
+        .iter()
+        .map(|erro| (erro.linha, erro.registro.as_str(), erro.campo.as_deref()))
+        .collect();
+    assert_eq!(
+        linhas,
+        [
+            (68, "C100", Some("DT_DOC")),
+            (69, "C110", None),
+            (70, "C120", None),
+            (71, "C170", None),
+        ]
+    );
+    assert!(
+        erros[3]
+            .descricao
+            .contains("Registro C100 descartado (linha nº 68)")
+    );
+    assert!(
+        sped_file
+            .obter_lista_registros::<RegistroC170>("C170")
+            .iter()
+            .all(|c170| c170.line_number != 71)
+    );
+
+    // Mesmos registros descartados no SpedReader.
+    let mut reader = SpedReader::open(&path)?.leniente();
+    let registros = reader.by_ref().collect::<EFDResult<Vec<_>>>()?;
+    assert!(
+        registros
+            .iter()
+            .all(|r| !(68..=71).contains(&r.line_number()))
+    );
+    assert_eq!(reader.erros_de_leitura(), erros.as_slice());
+
+    // Nenhum item do C100 descartado é atribuído a outro documento.
+    let config = AppConfig {
+        leniente: true,
+        ..AppConfig::default()
+    };
+    let informacoes = analyze_one_file(&multiprogressbar, &path, 0, 1, &config)?;
+    assert!(!informacoes.all_docs.is_empty());
+    assert!(informacoes.all_docs.iter().all(|doc| {
+        !doc.num_linha_efd
+            .is_some_and(|linha| (68..=71).contains(&linha))
+    }));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output leitura_leniente_sem_erros
+fn leitura_leniente_sem_erros() -> EFDResult<()> {
+    let path = Path::new(EFD_EXEMPLO);
+    let multiprogressbar = MultiProgress::new();
+
+    let estrito = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    let (leniente, erros) = read_and_parse_file_leniente(path, &multiprogressbar, 0, 1)?;
+
+    assert_eq!(erros, Vec::new());
+    assert_eq!(
+        leniente.iter_registros().count(),
+        estrito.iter_registros().count()
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output todos_os_campos_com_erro
+fn todos_os_campos_com_erro() -> EFDResult<()> {
+    // C170 da linha 57 com VL_ITEM e VL_DESC inválidos.
+    let dir = TempDir::new()?;
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    let texto = texto.replacen("|6|70398|0,00|0|060|", "|6|70X98|0,0Y|0|060|", 1);
+    let path = dir.path().join("efd_com_erros.txt");
+    std::fs::write(&path, texto)?;
+
+    let multiprogressbar = MultiProgress::new();
+    let (_sped_file, erros) = read_and_parse_file_leniente(&path, &multiprogressbar, 0, 1)?;
+
+    for erro in &erros {
+        println!("{erro}");
+    }
+
+    let campos: Vec<(usize, Option<&str>)> = erros
+        .iter()
+        .map(|erro| (erro.linha, erro.campo.as_deref()))
+        .collect();
+    assert_eq!(campos, [(57, Some("VL_ITEM")), (57, Some("VL_DESC"))]);
+
+    // Mesmos erros no SpedReader.
+    let mut reader = SpedReader::open(&path)?.leniente();
+    let registros = reader.by_ref().collect::<EFDResult<Vec<_>>>()?;
+    assert!(!registros.is_empty());
+    assert_eq!(reader.erros_de_leitura(), erros.as_slice());
+
+    let relatorio = relatorio_erros_de_leitura(&erros);
+    println!("{relatorio}");
+    assert!(relatorio.starts_with(
+        "Modo leniente: 1 linha(s) descartada(s) (2 erro(s) de leitura).\n\
+         \x20  Registros descartados: C170 (linha nº 57).\n"
+    ));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output gravar_csv_de_erros
+fn gravar_csv_de_erros() -> EFDResult<()> {
+    let dir = TempDir::new()?;
+    let path = arquivo_com_erros(&dir)?;
+    let multiprogressbar = MultiProgress::new();
+
+    let (_sped_file, erros) = read_and_parse_file_leniente(&path, &multiprogressbar, 0, 1)?;
+
+    let path_csv = dir.path().join("erros.csv");
+    write_erros_csv(&path_csv, &erros)?;
+
+    let csv = std::fs::read_to_string(&path_csv)?;
+    let linhas: Vec<&str> = csv.lines().collect();
+    println!("{csv}");
+
+    assert_eq!(linhas.len(), 3);
+    assert_eq!(
+        linhas[0],
+        "\"Arquivo\"|\"Linha\"|\"Registro\"|\"Campo\"|\"Descrição do Erro\""
+    );
+    assert!(linhas[2].contains("|57|\"C170\"|\"VL_ITEM\"|"));
+
+    Ok(())
+}
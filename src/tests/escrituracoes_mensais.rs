@@ -0,0 +1,47 @@
+use chrono::NaiveDate;
+use indicatif::MultiProgress;
+
+use crate::{EFDResult, SpedFile, create_a_temp_file, mes_seguinte, read_and_parse_file};
+
+// ============================================================================
+// Escriturações mensais de teste
+// ============================================================================
+
+/*
+Escriturações de um mesmo CNPJ base em meses consecutivos, usadas pelos testes das
+conferências entre meses (ex: controle de créditos e retenções na fonte).
+*/
+
+/// CNPJ base das escriturações de teste.
+pub const CNPJ_BASE: u32 = 12345678;
+
+/// Primeiro dia do mês.
+pub fn data(ano: i32, mes: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(ano, mes, 1).unwrap()
+}
+
+/// Escrituração do período: Registro 0000 seguido das `linhas`.
+pub fn escrituracao(linhas: &[&str], periodo: NaiveDate) -> EFDResult<SpedFile> {
+    let dt_fin = mes_seguinte(periodo).and_then(|d| d.pred_opt()).unwrap();
+    let registro_0000 = format!(
+        "|0000|006|0|||{}|{}|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+        periodo.format("%d%m%Y"),
+        dt_fin.format("%d%m%Y"),
+    );
+
+    let mut conteudo = vec![registro_0000.as_str()];
+    conteudo.extend(linhas);
+
+    let temp_file = create_a_temp_file(&conteudo.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)
+}
+
+/// Escriturações de outubro a dezembro de 2020, com o período de cada uma.
+pub fn trimestre(meses: [&[&str]; 3]) -> EFDResult<Vec<(NaiveDate, SpedFile)>> {
+    meses
+        .into_iter()
+        .zip([data(2020, 10), data(2020, 11), data(2020, 12)])
+        .map(|(linhas, periodo)| Ok((periodo, escrituracao(linhas, periodo)?)))
+        .collect()
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::{EFDResult, create_a_temp_file, read_and_parse_file};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+fn validar(path: &Path) -> EFDResult<Vec<Inconsistencia>> {
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    Ok(validar_hierarquia(&sped_file, path))
+}
+
+fn linhas_originais() -> EFDResult<Vec<String>> {
+    let bytes = std::fs::read(EFD_EXEMPLO)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    Ok(texto.lines().map(String::from).collect())
+}
+
+fn posicao(linhas: &[String], prefixo: &str) -> usize {
+    linhas
+        .iter()
+        .position(|l| l.starts_with(prefixo))
+        .unwrap_or_else(|| panic!("registro {prefixo} não encontrado"))
+}
+
+#[test]
+/// cargo test -- --show-output tabela_de_hierarquia
+fn tabela_de_hierarquia() {
+    let c170 = regra_hierarquia("C170").unwrap();
+    assert_eq!(c170.pai, Some("C100"));
+    assert_eq!(c170.ocorrencia, Ocorrencia::Multipla);
+
+    let r0145 = regra_hierarquia("0145").unwrap();
+    assert_eq!(r0145.pai, Some("0140"));
+    assert_eq!(r0145.ocorrencia, Ocorrencia::Unica);
+
+    // Um registro 1010 por processo judicial.
+    let r1010 = regra_hierarquia("1010").unwrap();
+    assert_eq!(r1010.pai, Some("1001"));
+    assert_eq!(r1010.ocorrencia, Ocorrencia::Multipla);
+
+    assert_eq!(regra_hierarquia("0000").unwrap().pai, None);
+    assert_eq!(regra_hierarquia("XXXX"), None);
+}
+
+#[test]
+/// cargo test -- --show-output hierarquia_do_arquivo_de_exemplo
+fn hierarquia_do_arquivo_de_exemplo() -> EFDResult<()> {
+    let inconsistencias = validar(Path::new(EFD_EXEMPLO))?;
+
+    assert_eq!(inconsistencias, Vec::new());
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output hierarquia_alterada
+fn hierarquia_alterada() -> EFDResult<()> {
+    let mut linhas = linhas_originais()?;
+
+    // 1. Registro 0111 repetido sob o mesmo 0110.
+    let idx_0111 = posicao(&linhas, "|0111|");
+    linhas.insert(idx_0111 + 1, linhas[idx_0111].clone());
+
+    // 2. Registro C170 logo após o C010 (sem C100).
+    let idx_c170 = posicao(&linhas, "|C170|");
+    let c170 = linhas.remove(idx_c170);
+    let idx_c010 = posicao(&linhas, "|C010|");
+    linhas.insert(idx_c010 + 1, c170);
+
+    // 3. Registro M105 sob o registro M500.
+    let m105 = linhas[posicao(&linhas, "|M105|")].clone();
+    let idx_m500 = posicao(&linhas, "|M500|");
+    linhas.insert(idx_m500 + 1, m105);
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let inconsistencias = validar(temp_file.path())?;
+
+    for inconsistencia in &inconsistencias {
+        println!("{inconsistencia}");
+    }
+
+    assert_eq!(inconsistencias.len(), 3);
+
+    assert_eq!(inconsistencias[0].registro, "0111");
+    assert_eq!(inconsistencias[0].linha, idx_0111 + 2);
+    assert!(inconsistencias[0].descricao.contains(&format!(
+        "ocorrência repetida sob o registro 0110 (linha nº {idx_0111}): registro já informado na linha nº {}",
+        idx_0111 + 1
+    )));
+
+    assert_eq!(inconsistencias[1].registro, "C170");
+    assert_eq!(inconsistencias[1].linha, idx_c010 + 2);
+    assert!(inconsistencias[1].descricao.contains("órfão"));
+
+    assert_eq!(inconsistencias[2].registro, "M105");
+    assert_eq!(inconsistencias[2].linha, idx_m500 + 2);
+    assert!(
+        inconsistencias[2]
+            .descricao
+            .contains("registro pai inesperado M500")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use super::*;
+use crate::{
+    Bloco1, EFDError, RegistroGenerico, RegistroM210, SpedWriter, create_a_temp_file,
+    parse_sped_fields, read_and_parse_file,
+};
+use indicatif::MultiProgress;
+
+fn parse(line: &str, leiaute: &Leiaute) -> EFDResult<Option<SpedRecord>> {
+    let mut reg_buf = [0u8; 4];
+    parse_sped_fields(Path::new("teste.txt"), 10, line, &mut reg_buf, leiaute)
+}
+
+#[test]
+/// cargo test -- --show-output selecionar_leiaute
+fn selecionar_leiaute() {
+    let antigo = Leiaute::selecionar(Some(3), Some(data(2018, 1, 1)));
+    let atual = Leiaute::selecionar(Some(6), Some(data(2020, 11, 1)));
+
+    assert_eq!(antigo.descricao, "leiaute até 31/12/2018");
+    assert_eq!(atual.descricao, "leiaute a partir de 01/01/2019");
+    assert_eq!(
+        antigo.to_string(),
+        "leiaute até 31/12/2018 (COD_VER 001 a 005)"
+    );
+
+    // Sem COD_VER, prevalece o período de apuração.
+    assert!(std::ptr::eq(
+        Leiaute::selecionar(None, Some(data(2019, 5, 1))),
+        atual
+    ));
+    assert!(std::ptr::eq(
+        Leiaute::selecionar(None, Some(data(2015, 5, 1))),
+        antigo
+    ));
+
+    // Versão e período incompatíveis: prevalece o período.
+    assert!(std::ptr::eq(
+        Leiaute::selecionar(Some(5), Some(data(2019, 1, 1))),
+        atual
+    ));
+
+    // Apenas a versão.
+    assert!(std::ptr::eq(Leiaute::selecionar(Some(2), None), antigo));
+
+    // Sem informação: leiaute mais recente.
+    assert!(std::ptr::eq(
+        Leiaute::selecionar(None, None),
+        Leiaute::mais_recente()
+    ));
+}
+
+#[test]
+/// cargo test -- --show-output parser_conforme_leiaute
+fn parser_conforme_leiaute() -> EFDResult<()> {
+    let antigo = Leiaute::selecionar(Some(3), None);
+    let atual = Leiaute::selecionar(Some(6), None);
+
+    let m210_antigo = "|M210|01|12345678,47|12345678,47|1,65|0||470307,38|0|0|0|0|470307,38|";
+    let m210_atual =
+        "|M210|01|5130600,46|5130600,46|0|0|5130600,46|1,65|||68096,27|0|0|0|0|68096,27|";
+
+    let record = parse(m210_antigo, antigo)?.unwrap();
+    assert!(record.downcast_ref::<RegistroM210Antigo>().is_ok());
+
+    let record = parse(m210_atual, atual)?.unwrap();
+    let m210 = record.downcast_ref::<RegistroM210>()?;
+    assert_eq!(m210.line_number, 10);
+
+    // Registro no leiaute de outra versão: quantidade de campos inválida.
+    let error = parse(m210_atual, antigo).unwrap_err().flatten();
+    assert!(matches!(error, EFDError::InvalidFieldCount { .. }));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output registro_inexistente_no_leiaute
+fn registro_inexistente_no_leiaute() -> EFDResult<()> {
+    let antigo = Leiaute::selecionar(Some(3), None);
+    let atual = Leiaute::selecionar(Some(6), None);
+
+    let line = "|1050|30112020|01||0|0|0|0|0|0|0|0|0|0|0|0|01|||";
+
+    let record = parse(line, atual)?.unwrap();
+    assert!(matches!(
+        record,
+        SpedRecord::Bloco1(ref bloco) if matches!(**bloco, Bloco1::R1050(_))
+    ));
+
+    // Inexistente no leiaute anterior a 2019: preservado como registro genérico.
+    let record = parse(line, antigo)?.unwrap();
+    let generico = record.downcast_ref::<RegistroGenerico>()?;
+    assert_eq!(generico.registro, "1050");
+    assert_eq!(generico.line_number, 10);
+    assert_eq!(generico.to_sped_line(), line);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output validar_registro_inexistente_no_leiaute
+fn validar_registro_inexistente_no_leiaute() -> EFDResult<()> {
+    let linhas = |registro_0000: &str| {
+        [
+            registro_0000,
+            "|1001|0|",
+            "|1050|30112018|01||0|0|0|0|0|0|0|0|0|0|0|0|01|||",
+            "|1990|3|",
+        ]
+        .join("\n")
+    };
+
+    let validar = |conteudo: &str| -> EFDResult<Vec<Inconsistencia>> {
+        let temp_file = create_a_temp_file(conteudo, false)?;
+        let multiprogressbar = MultiProgress::new();
+        let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+        Ok(validar_leiaute(&sped_file, Path::new("teste.txt")))
+    };
+
+    // COD_VER 003 (novembro de 2018): o registro 1050 ainda não existia.
+    let inconsistencias = validar(&linhas(
+        "|0000|003|0|||01112018|30112018|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+    ))?;
+
+    assert_eq!(inconsistencias.len(), 1);
+    assert_eq!(
+        inconsistencias[0].to_string(),
+        "teste.txt:[linha nº 3] Registro 1050: registro inexistente no leiaute do COD_VER 003: \
+         leiaute até 31/12/2018 (COD_VER 001 a 005)"
+    );
+
+    // COD_VER 006: o registro 1050 é interpretado pelo parser do leiaute.
+    let inconsistencias = validar(&linhas(
+        "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+    ))?;
+
+    assert_eq!(inconsistencias, Vec::new());
+
+    Ok(())
+}
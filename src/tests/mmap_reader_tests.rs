@@ -0,0 +1,82 @@
+use super::*;
+use crate::{SpedFile, SpedReader, create_a_temp_file, read_and_parse_file};
+use indicatif::MultiProgress;
+use std::io::Write;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+fn reescrever(mut sped_file: SpedFile) -> EFDResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    sped_file.sort_records_by_line_number();
+    sped_file.write_to(&mut buffer)?;
+    Ok(buffer)
+}
+
+#[test]
+/// cargo test -- --show-output mmap_equivale_ao_sped_reader
+fn mmap_equivale_ao_sped_reader() -> EFDResult<()> {
+    let path = Path::new(EFD_EXEMPLO);
+    let multiprogressbar = MultiProgress::new();
+
+    // Arquivo texto comum: lido com mmap.
+    let mmap = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+
+    // Leitura sequencial via BufRead (uma linha por vez).
+    let mut padrao = SpedFile::new();
+    for record in SpedReader::open(path)? {
+        padrao.add_record(record?);
+    }
+
+    assert_eq!(SpedMmap::open(path)?.number_of_lines(), 544);
+
+    assert_eq!(
+        mmap.iter_registros().count(),
+        padrao.iter_registros().count()
+    );
+    assert_eq!(reescrever(mmap)?, reescrever(padrao)?);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output linhas_emprestadas_do_mmap
+fn linhas_emprestadas_do_mmap() -> EFDResult<()> {
+    let content = "|0000|006|0|||01112020|30112020|EMPRESA|12345678000195|SP|3550308||00|0|\n\
+                   \n\
+                   |0001|0|\n\
+                   |9999|3|\n\
+                   ASSINATURA DIGITAL";
+    let temp_file = create_a_temp_file(content, false)?;
+    let sped_mmap = SpedMmap::open(temp_file.path())?;
+
+    let linhas: Vec<LinhaSped> = sped_mmap.linhas().collect();
+    let numeros: Vec<usize> = linhas.iter().map(|l| l.line_number).collect();
+    let registros: Vec<&[u8]> = linhas.iter().map(LinhaSped::registro).collect();
+
+    assert_eq!(numeros, [1, 3, 4]);
+    assert_eq!(registros, [b"0000", b"0001", b"9999"]);
+    assert_eq!(sped_mmap.number_of_lines(), 5);
+
+    // UTF-8: o texto é uma fatia do próprio mapeamento (sem cópia).
+    let texto = linhas[1].texto(sped_mmap.path())?;
+    assert!(matches!(texto, Cow::Borrowed("|0001|0|")));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output linha_em_windows_1252
+fn linha_em_windows_1252() -> EFDResult<()> {
+    let mut temp_file = tempfile::NamedTempFile::new()?;
+    // "AÇÚCAR" em WINDOWS-1252: Ç = 0xC7, Ú = 0xDA
+    temp_file.write_all(b"|0001|0|\n|0500|01012020|01|A|3|123|A\xC7\xDACAR||\n")?;
+
+    let sped_mmap = SpedMmap::open(temp_file.path())?;
+    let linha = sped_mmap.linhas().nth(1).unwrap();
+
+    let texto = linha.texto(sped_mmap.path())?;
+    assert!(matches!(texto, Cow::Owned(_)));
+    assert_eq!(texto, "|0500|01012020|01|A|3|123|AÇÚCAR||");
+
+    Ok(())
+}
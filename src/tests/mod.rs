@@ -1 +1,4 @@
 mod pattern_adapter;
+
+#[cfg(test)]
+pub mod escrituracoes_mensais;
@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
-    EFDResult, SpedFile, SpedRecordTrait, create_a_temp_file, get_string_utf8, read_and_parse_file,
-    setup_logging,
+    EFDResult, Leiaute, RegistroGenerico, SpedFile, SpedRecordTrait, create_a_temp_file,
+    estimar_nivel, get_string_utf8, read_and_parse_file, setup_logging,
 };
 use glob::glob;
 use indicatif::MultiProgress;
@@ -30,10 +30,8 @@ const SPED_EFD: &str = "\
     |c170|1|192428||1|31|1234,74|0|0|020|1653|16|16,67|18|20197,9|112210,57|0|0|0|49||0|0|0|56|1234,74|1,65|||2221,77|56|1234,74|7,6|||10233,61|9110200000101133|
     |M001|0|
     |M200|71785,57|44600,74|0|26825,83|26825,83|0|0|0|0|0|0|0|
-    |M210|01|5130600,46|5130600,46|0|0|5130600,46|1,65|||68096,27|0|0|0|0|68096,27|
     |M210|01|12345678,47|12345678,47|1,65|0||470307,38|0|0|0|0|470307,38|
     |M600|336358,91|212547,48|0|123811,43|123811,43|0|0|0|0|0|0|0|
-    |M610|01|5130600,46|5130600,46|0|0|5130600,46|7,6|||313655,53|0|0|0|0|313655,53|
     |M610|01|12345678,47|12345678,47|7,6|0||2166264,29|0|0|0|0|2166264,29|
     |M990|90|
     |1500|042024|01||202|0,69|0|0,69|0|0|0|0,69|0||0|0|0|0,69|
@@ -84,6 +82,9 @@ fn parser_serial() -> EFDResult<()> {
 
     let mut line_number = 0;
 
+    // Versão do leiaute: a mais recente até a leitura do Registro 0000.
+    let mut leiaute = Leiaute::mais_recente();
+
     // Lemos o arquivo em blocos de bytes até encontrar o NEWLINE_BYTE (\n)
     while reader.read_until(crate::NEWLINE_BYTE, &mut bytes_buffer)? > 0 {
         line_number += 1;
@@ -101,11 +102,15 @@ fn parser_serial() -> EFDResult<()> {
 
         // 2. Faz o parse usando os buffers reutilizáveis
         // O vetor de campos (fields) será criado localmente dentro do parser (Safe & Fast)
-        match parse_sped_fields(path, line_number, &line_buf, &mut reg_buf)? {
+        match parse_sped_fields(path, line_number, &line_buf, &mut reg_buf, leiaute)? {
             Some(sped_record) => {
                 // Imprime apenas se um registro foi realmente processado (Debug)
                 sped_record.println();
 
+                if let Ok(reg_0000) = sped_record.downcast_ref::<Registro0000>() {
+                    leiaute = Leiaute::do_registro_0000(reg_0000);
+                }
+
                 // Move o registro para a estrutura de dados final
                 sped_file_data.add_record(sped_record);
             }
@@ -220,3 +225,32 @@ fn parser_parallel() -> EFDResult<()> {
 
     Ok(())
 }
+
+#[test]
+/// cargo test -- --show-output registro_nao_suportado
+fn registro_nao_suportado() -> EFDResult<()> {
+    let temp_file = create_a_temp_file(SPED_EFD, false)?;
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+
+    // |1507| não existe no leiaute: preservado no Bloco 1 como registro genérico.
+    // As linhas sem o padrão de registro ("|001|", "|POS_9999_A|") continuam ignoradas.
+    let contagem: Vec<(&str, usize)> = sped_file.contar_registros_genericos().into_iter().collect();
+    assert_eq!(contagem, [("1507", 1)]);
+
+    let r1507 = sped_file.obter_registro::<RegistroGenerico>("1507")?;
+    assert_eq!(r1507.bloco, '1');
+    assert_eq!(r1507.campos.len(), 17);
+    assert_eq!(r1507.campos[0], "042024");
+    assert_eq!(r1507.campos[16], "19,53");
+
+    assert_eq!(estimar_nivel("0000"), 0);
+    assert_eq!(estimar_nivel("C001"), 1);
+    assert_eq!(estimar_nivel("C010"), 2);
+    assert_eq!(estimar_nivel("C700"), 3);
+    assert_eq!(estimar_nivel("C701"), 4);
+    assert_eq!(estimar_nivel("M900"), 2);
+    assert_eq!(estimar_nivel("1507"), 3);
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use super::*;
+use crate::{AppConfig, MesesDoAno, NaturezaBaseCalculo, TipoDeCredito, analyze_one_file};
+use indicatif::MultiProgress;
+use std::{path::Path, sync::Arc};
+
+fn registro_0111(trib_mi: Decimal, nt_mi: Decimal, exp: Decimal, cum: Decimal) -> Registro0111 {
+    Registro0111 {
+        nivel: 3,
+        bloco: '0',
+        registro: "0111".into(),
+        line_number: 5,
+        rec_bru_ncum_trib_mi: Some(trib_mi),
+        rec_bru_ncum_nt_mi: Some(nt_mi),
+        rec_bru_ncum_exp: Some(exp),
+        rec_bru_cum: Some(cum),
+        rec_bru_total: Some(trib_mi + nt_mi + exp + cum),
+    }
+}
+
+/// Linha com os campos comuns aos itens de um mesmo estabelecimento e período.
+fn linha(tipo_de_operacao: TipoDeOperacao, cst: u16) -> DocsFiscais {
+    DocsFiscais {
+        estabelecimento_cnpj: Arc::from("12345678000195"),
+        ano: Some(2020),
+        trimestre: Some(4),
+        mes: Some(MesesDoAno::Novembro),
+        tipo_de_operacao: Some(tipo_de_operacao),
+        cst: CodigoSituacaoTributaria::from_u16(cst),
+        aliq_pis: Some(dec!(1.65)),
+        aliq_cofins: Some(dec!(7.6)),
+        ..Default::default()
+    }
+}
+
+fn receita(cst: u16, valor: Decimal) -> DocsFiscais {
+    DocsFiscais {
+        valor_item: Some(valor),
+        valor_bc: Some(valor),
+        ..linha(TipoDeOperacao::Saida, cst)
+    }
+}
+
+/// Aquisição de insumos (natureza 02) com crédito comum de CST 53.
+fn aquisicao(valor_bc: Decimal) -> DocsFiscais {
+    DocsFiscais {
+        valor_item: Some(valor_bc),
+        valor_bc: Some(valor_bc),
+        tipo_de_credito: Some(TipoDeCredito::AliquotaBasica),
+        natureza_bc: NaturezaBaseCalculo::from_u16(2),
+        ..linha(TipoDeOperacao::Entrada, 53)
+    }
+}
+
+/// Detalhamento do crédito (M505) com o código de crédito rateado.
+fn detalhamento(cod_credito: &str, valor_bc: Decimal) -> DocsFiscais {
+    DocsFiscais {
+        valor_bc: Some(valor_bc),
+        cod_credito: cod_credito.parse().ok(),
+        tipo_de_credito: Some(TipoDeCredito::AliquotaBasica),
+        natureza_bc: NaturezaBaseCalculo::from_u16(2),
+        ..linha(TipoDeOperacao::Detalhamento, 53)
+    }
+}
+
+#[test]
+/// cargo test -- --show-output percentuais_de_rateio
+fn percentuais_de_rateio() {
+    let rb = ReceitaBrutaDoRateio::escriturada(&registro_0111(
+        dec!(79586),
+        dec!(41043),
+        dec!(94613),
+        dec!(32047),
+    ));
+
+    assert_eq!(rb.total(), dec!(247289));
+    assert_eq!(rb.percentual(RecBrutaNCumTribMercInterno), dec!(32.1834));
+    assert_eq!(rb.percentual(RecBrutaNCumNTribMercInterno), dec!(16.5972));
+    assert_eq!(rb.percentual(RecBrutaNCumDeExportacao), dec!(38.2601));
+    assert_eq!(rb.percentual(RecBrutaCumulativa), dec!(12.9593));
+
+    // Sem receita bruta não há percentual de rateio.
+    assert_eq!(
+        ReceitaBrutaDoRateio::default().percentual(RecBrutaNCumTribMercInterno),
+        Decimal::ZERO
+    );
+}
+
+#[test]
+/// cargo test -- --show-output recalcular_creditos_com_percentuais_dos_documentos
+fn recalcular_creditos_com_percentuais_dos_documentos() -> EFDResult<()> {
+    // Registro 0111: 50% tributada e 50% não tributada no mercado interno.
+    let ctx = SpedContext {
+        periodo_de_apuracao: NaiveDate::from_ymd_opt(2020, 11, 1),
+        registro_0111: Some(registro_0111(
+            dec!(5000),
+            dec!(5000),
+            Decimal::ZERO,
+            Decimal::ZERO,
+        )),
+        ..Default::default()
+    };
+
+    // Documentos: 80% tributada (CST 01) e 20% não tributada (CST 06) no mercado interno.
+    let linhas = [
+        receita(1, dec!(8000)),
+        receita(6, dec!(2000)),
+        aquisicao(dec!(1000)),
+        detalhamento("101", dec!(500)),
+        detalhamento("201", dec!(500)),
+    ];
+
+    let conferencia =
+        conferir_rateio_dos_creditos(&ctx, &linhas, false)?.expect("Registro 0111 informado");
+
+    let relatorio = relatorio_rateio_dos_creditos(&conferencia);
+    println!("{relatorio}");
+
+    assert_eq!(
+        conferencia.apurada.percentual(RecBrutaNCumTribMercInterno),
+        dec!(80)
+    );
+    assert_eq!(
+        conferencia.apurada.percentual(RecBrutaNCumNTribMercInterno),
+        dec!(20)
+    );
+
+    let [trib, ntrib, exp] = conferencia.impactos.as_slice() else {
+        panic!("esperados três tipos de rateio");
+    };
+
+    assert_eq!(
+        (trib.bc_declarada, trib.bc_recalculada),
+        (dec!(500), dec!(800))
+    );
+    assert_eq!(
+        (trib.pis_declarado, trib.pis_recalculado),
+        (dec!(8.25), dec!(13.20))
+    );
+    assert_eq!(
+        (ntrib.bc_declarada, ntrib.bc_recalculada),
+        (dec!(500), dec!(200))
+    );
+    assert_eq!(
+        (ntrib.cofins_declarado, ntrib.cofins_recalculado),
+        (dec!(38.00), dec!(15.20))
+    );
+    assert_eq!(
+        (exp.bc_declarada, exp.bc_recalculada),
+        (Decimal::ZERO, Decimal::ZERO)
+    );
+
+    // A mesma base de cálculo é apenas redistribuída entre os tipos de rateio do CST 53.
+    assert_eq!(
+        conferencia.diferenca_de_credito(),
+        (Decimal::ZERO, Decimal::ZERO)
+    );
+
+    assert!(relatorio.contains("(período 11/2020)"));
+    assert!(relatorio.contains(
+        "Receita Bruta Não Cumulativa: Tributada no Mercado Interno: \
+         0111 = 5.000,00 (50,0000%), documentos = 8.000,00 (80,0000%)"
+    ));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output conferir_rateio_do_arquivo_de_exemplo
+fn conferir_rateio_do_arquivo_de_exemplo() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let info = analyze_one_file(&multiprogressbar, path, 0, 1, &AppConfig::default())?;
+
+    let inicio = info
+        .messages
+        .find("Rateio dos créditos comuns:")
+        .expect("relatório do rateio dos créditos comuns");
+    println!("{}", &info.messages[inicio..]);
+
+    // |0111|79586|41043|94613|32047|247289|
+    assert!(info.messages[inicio..].contains("0111 = 79.586,00 (32,1834%)"));
+
+    Ok(())
+}
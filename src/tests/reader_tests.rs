@@ -0,0 +1,99 @@
+use super::*;
+use crate::{RegistroC170, RegistroM100, RegistroM210Antigo, read_and_parse_file};
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+const SPED_EFD: &str = "\
+    |0000|003|1||| 01012018 |31012018|EMPRESA Teste ABC|12345678901234|SP|3555338||00|0|
+    |0001|0|
+
+    |M001|0|
+    |M210|01|12345678,47|12345678,47|1,65|0||470307,38|0|0|0|0|470307,38|
+    |M990|3|
+    |9999|7|
+    |POS_9999|Linha que deve ser ignorada|
+    ";
+
+#[test]
+/// cargo test -- --show-output ler_todos_os_registros
+fn ler_todos_os_registros() -> EFDResult<()> {
+    let records: Vec<SpedRecord> = SpedReader::open(EFD_EXEMPLO)?.collect::<EFDResult<_>>()?;
+
+    // Mesmos registros (e linhas) obtidos pela leitura completa do arquivo.
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+    let mut esperados: Vec<(usize, &str)> = sped_file
+        .iter_registros()
+        .map(|r| (r.line_number(), r.registro_name()))
+        .collect();
+    esperados.sort_unstable();
+
+    let lidos: Vec<(usize, &str)> = records
+        .iter()
+        .map(|r| (r.line_number(), r.registro_name()))
+        .collect();
+
+    assert_eq!(lidos, esperados);
+    assert_eq!(lidos.last(), Some(&(544, "9999")));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output filtrar_registros_e_blocos
+fn filtrar_registros_e_blocos() -> EFDResult<()> {
+    let reader = SpedReader::open(EFD_EXEMPLO)?.with_registros(&["c170", "M100"]);
+    let records: Vec<SpedRecord> = reader.collect::<EFDResult<_>>()?;
+
+    let c170: Vec<&RegistroC170> = records
+        .iter()
+        .filter_map(|r| r.downcast_ref::<RegistroC170>().ok())
+        .collect();
+    let m100: Vec<&RegistroM100> = records
+        .iter()
+        .filter_map(|r| r.downcast_ref::<RegistroM100>().ok())
+        .collect();
+
+    assert_eq!(c170.len(), 23);
+    assert_eq!(m100.len(), 50);
+    assert_eq!(records.len(), c170.len() + m100.len());
+
+    let mut reader = SpedReader::open(EFD_EXEMPLO)?.with_blocos(&['m']);
+    let first = reader.next().transpose()?;
+    assert_eq!(
+        first.map(|r| r.registro_name().to_string()),
+        Some("M001".into())
+    );
+    assert!(reader.all(|r| r.is_ok_and(|r| r.bloco() == 'M')));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output ler_de_buffer_em_memoria
+fn ler_de_buffer_em_memoria() -> EFDResult<()> {
+    let mut reader = SpedReader::new(SPED_EFD.as_bytes())
+        .with_path("memoria.txt")
+        .with_blocos(&['M']);
+
+    let m001 = reader.next().transpose()?.unwrap();
+    assert_eq!(m001.registro_name(), "M001");
+    assert_eq!(m001.line_number(), 4);
+
+    // COD_VER 003: o M210 segue o leiaute antigo.
+    let m210 = reader.next().transpose()?.unwrap();
+    let m210 = m210.downcast_ref::<RegistroM210Antigo>()?;
+    assert_eq!(m210.vl_cont_per, Some(dec!(470307.38)));
+    assert_eq!(reader.leiaute().descricao, "leiaute até 31/12/2018");
+
+    let m990 = reader.next().transpose()?.unwrap();
+    assert_eq!(m990.registro_name(), "M990");
+
+    // A leitura termina no 9999 (filtrado), sem alcançar as linhas seguintes.
+    assert!(reader.next().is_none());
+    assert_eq!(reader.line_number(), 7);
+
+    Ok(())
+}
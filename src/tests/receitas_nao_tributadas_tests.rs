@@ -0,0 +1,184 @@
+use super::*;
+use crate::{
+    CodigoSituacaoTributaria, EFDResult, TOLERANCIA_DO_CALCULO, create_a_temp_file,
+    read_and_parse_file,
+};
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+use std::{path::Path, sync::Arc};
+
+/// Receitas com alíquota zero (CST 06):
+/// - M400: VL_TOT_REC 1.400,00 e M410 somando 1.300,00;
+/// - M410 NAT_REC 101 (adubos) com 1.000,00, mas os itens com NCM do Capítulo 31 somam 1.200,00;
+/// - M800/M810 consistentes com os itens.
+const RECEITAS: &[&str] = &[
+    "|0000|006|0|||01112020|30112020|Empresa Ficticia SA|12345678000195|SP|2360575|786800285|00|9|",
+    "|M001|0|",
+    "|M200|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|",
+    "|M400|06|1400,00|||",
+    "|M410|101|1000,00|||",
+    "|M410|999|300,00|||",
+    "|M600|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|0,00|",
+    "|M800|06|1400,00|||",
+    "|M810|101|1200,00|||",
+    "|M810|999|200,00|||",
+    "|M990|10|",
+    "|9999|12|",
+];
+
+/// Item de saída com o CST (de PIS/PASEP e de COFINS) e a NCM informados.
+fn item_de_saida(cst: u16, cod_ncm: &str, valor_item: Decimal) -> DocsFiscais {
+    DocsFiscais {
+        tipo_de_operacao: Some(TipoDeOperacao::Saida),
+        cst: CodigoSituacaoTributaria::from_u16(cst),
+        cst_pis: CodigoSituacaoTributaria::from_u16(cst),
+        cod_ncm: Arc::from(cod_ncm),
+        valor_item: Some(valor_item),
+        ..Default::default()
+    }
+}
+
+fn conciliar(linhas: &[&str], docs: &[DocsFiscais]) -> EFDResult<ConciliacaoDasReceitas> {
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+    let periodo = NaiveDate::from_ymd_opt(2020, 11, 1);
+    Ok(conciliar_receitas_nao_tributadas(
+        &sped_file,
+        docs,
+        periodo,
+        TOLERANCIA_DO_CALCULO,
+    ))
+}
+
+#[test]
+/// cargo test -- --show-output receitas_por_cst_e_natureza
+fn receitas_por_cst_e_natureza() -> EFDResult<()> {
+    let docs = [
+        item_de_saida(6, "3105.20.00", dec!(1200.00)),
+        item_de_saida(6, "", dec!(200.00)),
+        // Revenda de gasolina (monofásica) sem M400/M800 com CST 04.
+        item_de_saida(4, "27101259", dec!(500.00)),
+        // Receita tributada: fora da conciliação.
+        item_de_saida(1, "31052000", dec!(9999.00)),
+    ];
+
+    let conciliacao = conciliar(RECEITAS, &docs)?;
+    let relatorio = relatorio_receitas_nao_tributadas(&conciliacao);
+    println!("{relatorio}");
+
+    assert_eq!(conciliacao.receitas.len(), 4);
+    assert_eq!(
+        relatorio,
+        "Receitas sem incidência da contribuição (M400/M410 e M800/M810) x itens dos documentos \
+         (período 11/2020).\n\
+         \x20  PIS/PASEP, CST 04: M400 0,00, M410 0,00, itens 500,00\n\
+         \x20  PIS/PASEP, CST 06: M400 1.400,00, M410 1.300,00, itens 1.400,00\n\
+         \x20  COFINS, CST 04: M800 0,00, M810 0,00, itens 500,00\n\
+         \x20  COFINS, CST 06: M800 1.400,00, M810 1.400,00, itens 1.400,00\n\
+         \x20  NAT_REC do CST 04 conferida pela NCM apenas para os códigos \
+         101, 102, 103, 104, 105, 201, 301, 401, 501 da Tabela 4.3.10\n\
+         \x20  NAT_REC do CST 06 conferida pela NCM apenas para os códigos \
+         101, 102, 103, 105, 114 da Tabela 4.3.13\n\
+         \x20  Divergências da conciliação: 6\n\
+         \x20     M400 não informado: VL_TOT_REC esperado 500,00 \
+         (Σ VL_ITEM dos itens de saída com CST 04)\n\
+         \x20     M410 não informado: VL_REC (NAT_REC 101) esperado 500,00 \
+         (Σ VL_ITEM dos itens com NCM da Tabela 4.3.10)\n\
+         \x20     M400 [linha nº 4] VL_TOT_REC: declarado 1.400,00, esperado 1.300,00 \
+         (Σ VL_REC dos registros M410)\n\
+         \x20     M410 [linha nº 5] VL_REC (NAT_REC 101): declarado 1.000,00, esperado 1.200,00 \
+         (Σ VL_ITEM dos itens com NCM da Tabela 4.3.13)\n\
+         \x20     M800 não informado: VL_TOT_REC esperado 500,00 \
+         (Σ VL_ITEM dos itens de saída com CST 04)\n\
+         \x20     M810 não informado: VL_REC (NAT_REC 101) esperado 500,00 \
+         (Σ VL_ITEM dos itens com NCM da Tabela 4.3.10)\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output cst_de_cada_contribuicao
+fn cst_de_cada_contribuicao() -> EFDResult<()> {
+    // Item com CST 06 de COFINS e CST 07 de PIS/PASEP: cada contribuição é conciliada
+    // pelo seu próprio CST.
+    let item = DocsFiscais {
+        cst_pis: CodigoSituacaoTributaria::from_u16(7),
+        ..item_de_saida(6, "", dec!(1400.00))
+    };
+    let linhas = [
+        RECEITAS[0],
+        RECEITAS[1],
+        RECEITAS[2],
+        "|M400|07|1400,00|||",
+        "|M410|999|1400,00|||",
+        RECEITAS[6],
+        "|M800|06|1400,00|||",
+        "|M810|999|1400,00|||",
+        RECEITAS[10],
+        RECEITAS[11],
+    ];
+
+    let conciliacao = conciliar(&linhas, &[item])?;
+    println!("{}", relatorio_receitas_nao_tributadas(&conciliacao));
+
+    let receitas: Vec<(Contribuicao, u16, Decimal)> = conciliacao
+        .receitas
+        .iter()
+        .map(|r| (r.contribuicao, r.cst, r.vl_rec_itens))
+        .collect();
+    assert_eq!(
+        receitas,
+        [
+            (Contribuicao::Pis, 7, dec!(1400.00)),
+            (Contribuicao::Cofins, 6, dec!(1400.00)),
+        ]
+    );
+    assert!(conciliacao.divergencias.is_empty());
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output natureza_da_receita_pela_ncm
+fn natureza_da_receita_pela_ncm() {
+    let codigo = |cst: u16, ncm: &str| natureza_da_receita_do_ncm(cst, ncm).map(|n| n.codigo);
+
+    assert_eq!(codigo(6, "3105.20.00"), Some("101"));
+    assert_eq!(codigo(6, "0407.21.00"), Some("114"));
+    assert_eq!(codigo(6, "1006.30.21"), Some("105"));
+    assert_eq!(codigo(4, "2710.12.59"), Some("101"));
+    // Gasolina de aviação não compõe a natureza 101.
+    assert_eq!(codigo(4, "2710.12.51"), None);
+    assert_eq!(codigo(4, "3004.90.99"), Some("201"));
+    // A NCM consta apenas da tabela de outro CST.
+    assert_eq!(codigo(6, "2710.12.59"), None);
+    assert_eq!(codigo(4, ""), None);
+
+    assert_eq!(
+        natureza_da_receita(6, "101").map(|n| n.tabela),
+        Some("4.3.13")
+    );
+}
+
+#[test]
+/// cargo test -- --show-output sem_receitas_nao_tributadas
+fn sem_receitas_nao_tributadas() -> EFDResult<()> {
+    // Sem apuração no Bloco M, os itens com CST 06 não são conciliados.
+    let linhas = [RECEITAS[0], "|9999|2|"];
+    let docs = [item_de_saida(6, "3105.20.00", dec!(1200.00))];
+    let conciliacao = conciliar(&linhas, &docs)?;
+    assert_eq!(relatorio_receitas_nao_tributadas(&conciliacao), "");
+
+    // Arquivo de exemplo: sem apuração no Bloco M e sem os registros M400/M800.
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    let conciliacao = conciliar_receitas_nao_tributadas(&sped_file, &[], None, Decimal::ZERO);
+
+    assert_eq!(conciliacao, ConciliacaoDasReceitas::default());
+    assert_eq!(relatorio_receitas_nao_tributadas(&conciliacao), "");
+
+    Ok(())
+}
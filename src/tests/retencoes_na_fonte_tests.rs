@@ -0,0 +1,202 @@
+use super::*;
+use crate::{
+    EFDResult, read_and_parse_file,
+    tests::escrituracoes_mensais::{CNPJ_BASE, data, escrituracao, trimestre},
+};
+use indicatif::MultiProgress;
+use std::path::Path;
+
+/// Duas retenções da mesma fonte pagadora em 10/2020 (a terceira F600 é de responsabilidade
+/// do declarante e não pode ser deduzida), parcialmente deduzidas no próprio mês.
+const OUTUBRO: &[&str] = &[
+    "|F600|03|15102020|1000,00|36,50|5952|0|11222333000181|6,50|30,00|0|",
+    "|F600|03|20102020|500,00|19,50|5952|0|11222333000181|3,50|16,00|0|",
+    "|F600|01|25102020|100,00|3,65|5952|0|99888777000166|0,65|3,00|1|",
+    "|M200|100,00|0,00|0,00|100,00|4,00|0,00|96,00|0,00|0,00|0,00|0,00|96,00|",
+    "|M600|460,00|0,00|0,00|460,00|20,00|0,00|440,00|0,00|0,00|0,00|0,00|440,00|",
+    "|1300|03|102020|10,00|4,00|0,00|0,00|6,00|",
+    "|1700|03|102020|46,00|20,00|0,00|0,00|26,00|",
+    "|9999|9|",
+];
+
+/// Saldos de 10/2020 deduzidos em 11/2020.
+const NOVEMBRO: &[&str] = &[
+    "|M200|100,00|0,00|0,00|100,00|3,00|0,00|97,00|0,00|0,00|0,00|0,00|97,00|",
+    "|M600|460,00|0,00|0,00|460,00|20,00|0,00|440,00|0,00|0,00|0,00|0,00|440,00|",
+    "|1300|03|102020|10,00|7,00|0,00|0,00|3,00|",
+    "|1700|03|102020|46,00|40,00|0,00|0,00|6,00|",
+    "|9999|6|",
+];
+
+/// Saldo de PIS/PASEP não transportado e valor retido de COFINS alterado.
+const DEZEMBRO: &[&str] = &["|1700|03|102020|50,00|40,00|0,00|0,00|10,00|", "|9999|3|"];
+
+fn extrair(linhas: &[&str], periodo: NaiveDate) -> EFDResult<RetencoesNaFonte> {
+    let sped_file = escrituracao(linhas, periodo)?;
+    Ok(RetencoesNaFonte::extrair(&sped_file, CNPJ_BASE, periodo))
+}
+
+/// Retenções na fonte de outubro a dezembro de 2020.
+fn retencoes_trimestrais() -> EFDResult<(RetencoesNaFonte, Vec<(u32, NaiveDate)>)> {
+    let mut retencoes = RetencoesNaFonte::default();
+    let mut escrituracoes = Vec::new();
+
+    for (periodo, sped_file) in trimestre([OUTUBRO, NOVEMBRO, DEZEMBRO])? {
+        retencoes.append(&mut RetencoesNaFonte::extrair(
+            &sped_file, CNPJ_BASE, periodo,
+        ));
+        escrituracoes.push((CNPJ_BASE, periodo));
+    }
+
+    Ok((retencoes, escrituracoes))
+}
+
+#[test]
+/// cargo test -- --show-output retencoes_do_trimestre
+fn retencoes_do_trimestre() -> EFDResult<()> {
+    let (retencoes, escrituracoes) = retencoes_trimestrais()?;
+
+    let divergencias = verificar_retencoes_na_fonte(&retencoes, &escrituracoes, Decimal::ZERO);
+    let relatorio = relatorio_retencoes_na_fonte(&retencoes, &divergencias);
+    println!("{relatorio}");
+
+    assert_eq!(retencoes.retencoes.len(), 1);
+    assert_eq!(retencoes.retencoes[0].quantidade, 2);
+    assert_eq!(retencoes.saldos[0].pr_rec_ret, Some(data(2020, 10)));
+
+    assert_eq!(
+        relatorio,
+        "Retenções na fonte (F600, M200/M600 e Registros 1300/1700): 3 escrituração(ões).\n\
+         \x20  CNPJ base 12345678, período 10/2020:\n\
+         \x20     F600 [linha nº 2] natureza 03, fonte pagadora 11222333000181: 2 retenção(ões), \
+         PIS/PASEP 10,00, COFINS 46,00\n\
+         \x20     M200 [linha nº 5] PIS/PASEP deduzida: 4,00 (não cumulativa 4,00, cumulativa 0,00)\n\
+         \x20     M600 [linha nº 6] COFINS deduzida: 20,00 (não cumulativa 20,00, cumulativa 0,00)\n\
+         \x20     1300 [linha nº 7] PIS/PASEP, natureza 03 de 10/2020: retido 10,00, deduzido 4,00, \
+         ressarcimento 0,00, compensação 0,00, saldo 6,00\n\
+         \x20     1700 [linha nº 8] COFINS, natureza 03 de 10/2020: retido 46,00, deduzido 20,00, \
+         ressarcimento 0,00, compensação 0,00, saldo 26,00\n\
+         \x20  CNPJ base 12345678, período 11/2020:\n\
+         \x20     M200 [linha nº 2] PIS/PASEP deduzida: 3,00 (não cumulativa 3,00, cumulativa 0,00)\n\
+         \x20     M600 [linha nº 3] COFINS deduzida: 20,00 (não cumulativa 20,00, cumulativa 0,00)\n\
+         \x20     1300 [linha nº 4] PIS/PASEP, natureza 03 de 10/2020: retido 10,00, deduzido 7,00, \
+         ressarcimento 0,00, compensação 0,00, saldo 3,00\n\
+         \x20     1700 [linha nº 5] COFINS, natureza 03 de 10/2020: retido 46,00, deduzido 40,00, \
+         ressarcimento 0,00, compensação 0,00, saldo 6,00\n\
+         \x20  CNPJ base 12345678, período 12/2020:\n\
+         \x20     1700 [linha nº 2] COFINS, natureza 03 de 10/2020: retido 50,00, deduzido 40,00, \
+         ressarcimento 0,00, compensação 0,00, saldo 10,00\n\n\
+         Retenções na fonte divergentes dos valores esperados: 2 divergência(s).\n\
+         \x20  CNPJ base 12345678, período 12/2020, PIS/PASEP, natureza 03 de 10/2020:\n\
+         \x20     1300 não informado: SLD_RET esperado 3,00 (SLD_RET de 11/2020)\n\
+         \x20  CNPJ base 12345678, período 12/2020, COFINS, natureza 03 de 10/2020:\n\
+         \x20     1700 [linha nº 2] VL_RET_APU: declarado 50,00, esperado 46,00 \
+         (VL_RET_APU de 11/2020)\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output retencao_sem_controle_e_deducao_divergente
+fn retencao_sem_controle_e_deducao_divergente() -> EFDResult<()> {
+    // Sem o 1300: a retenção de PIS/PASEP do F600 não é controlada e a dedução do M200
+    // não tem origem. No 1700, a dedução acumulada diminui em novembro.
+    let outubro = [
+        OUTUBRO[0], OUTUBRO[1], OUTUBRO[2], OUTUBRO[3], OUTUBRO[4], OUTUBRO[6], OUTUBRO[7],
+    ];
+    let novembro = [
+        NOVEMBRO[1],
+        "|1700|03|102020|46,00|15,00|0,00|0,00|31,00|",
+        NOVEMBRO[4],
+    ];
+
+    let mut retencoes = extrair(&outubro, data(2020, 10))?;
+    retencoes.append(&mut extrair(&novembro, data(2020, 11))?);
+    let escrituracoes = [(CNPJ_BASE, data(2020, 10)), (CNPJ_BASE, data(2020, 11))];
+
+    let divergencias = verificar_retencoes_na_fonte(&retencoes, &escrituracoes, Decimal::ZERO);
+    println!(
+        "{}",
+        relatorio_retencoes_na_fonte(&retencoes, &divergencias)
+    );
+
+    let resumo: Vec<(&str, Option<usize>, &str, Decimal, Decimal)> = divergencias
+        .iter()
+        .map(|d| {
+            (
+                d.registro,
+                d.linha,
+                d.campo.as_str(),
+                d.declarado,
+                d.esperado,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        resumo,
+        [
+            (
+                "1300",
+                None,
+                "VL_RET_APU",
+                Decimal::ZERO,
+                Decimal::new(1000, 2)
+            ),
+            (
+                "M200",
+                Some(5),
+                "VL_RET_NC + VL_RET_CUM",
+                Decimal::new(400, 2),
+                Decimal::ZERO
+            ),
+            (
+                "M600",
+                Some(2),
+                "VL_RET_NC + VL_RET_CUM",
+                Decimal::new(2000, 2),
+                Decimal::new(-500, 2)
+            ),
+            (
+                "1700",
+                Some(3),
+                "VL_RET_DED",
+                Decimal::new(1500, 2),
+                Decimal::new(2000, 2)
+            ),
+        ]
+    );
+
+    // Dentro da tolerância, resta apenas a dedução do M600.
+    let divergencias =
+        verificar_retencoes_na_fonte(&retencoes, &escrituracoes, Decimal::new(10, 0));
+    assert_eq!(divergencias.len(), 1);
+    assert_eq!(divergencias[0].registro, "M600");
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output arquivo_sem_retencoes
+fn arquivo_sem_retencoes() -> EFDResult<()> {
+    // Sem a escrituração do mês anterior, a dedução só é conferida para retenções do período.
+    let novembro = extrair(NOVEMBRO, data(2020, 11))?;
+    let divergencias =
+        verificar_retencoes_na_fonte(&novembro, &[(CNPJ_BASE, data(2020, 11))], Decimal::ZERO);
+    assert!(divergencias.is_empty());
+
+    // Arquivo de exemplo: sem F600, sem retenções deduzidas e sem os registros 1300/1700.
+    let multiprogressbar = MultiProgress::new();
+    let path = Path::new("examples/efd_data_random");
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    let retencoes = RetencoesNaFonte::extrair(&sped_file, CNPJ_BASE, data(2020, 11));
+    let divergencias =
+        verificar_retencoes_na_fonte(&retencoes, &[(CNPJ_BASE, data(2020, 11))], Decimal::ZERO);
+
+    assert!(retencoes.retencoes.is_empty() && retencoes.saldos.is_empty());
+    assert!(divergencias.is_empty());
+    assert_eq!(relatorio_retencoes_na_fonte(&retencoes, &divergencias), "");
+
+    Ok(())
+}
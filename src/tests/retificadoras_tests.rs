@@ -0,0 +1,229 @@
+use super::*;
+use std::collections::HashMap;
+
+const RECIBO_ORIGINAL: &str = "11.22.33.44.55.66.77.88.99.00-1";
+const RECIBO_RETIFICADORA: &str = "99.88.77.66.55.44.33.22.11.00-2";
+
+fn escrituracao(
+    arquivo: &str,
+    cnpj: &str,
+    mes: u32,
+    retificadora: bool,
+    num_rec_anterior: Option<&str>,
+) -> Escrituracao {
+    Escrituracao {
+        arquivo: PathBuf::from(arquivo),
+        cnpj: Arc::from(cnpj),
+        periodo_de_apuracao: NaiveDate::from_ymd_opt(2020, mes, 1).unwrap(),
+        retificadora,
+        num_rec_anterior: num_rec_anterior.map(Arc::from),
+    }
+}
+
+fn sem_assinatura(_arquivo: &Path) -> Option<NaiveDateTime> {
+    None
+}
+
+fn substituidas(selecao: &SelecaoDeEscrituracoes) -> Vec<(&str, Vec<&str>, MotivoDaSubstituicao)> {
+    selecao
+        .substituidas
+        .iter()
+        .map(|s| {
+            (
+                s.arquivo.to_str().unwrap(),
+                s.substitutas.iter().map(|p| p.to_str().unwrap()).collect(),
+                s.motivo,
+            )
+        })
+        .collect()
+}
+
+#[test]
+/// cargo test -- --show-output original_substituida_pela_retificadora
+fn original_substituida_pela_retificadora() {
+    let escrituracoes = [
+        escrituracao("efd_11_original.txt", "12345678000195", 11, false, None),
+        escrituracao(
+            "efd_11_retificadora.txt",
+            "12345678000195",
+            11,
+            true,
+            Some(RECIBO_ORIGINAL),
+        ),
+        // Outro período e outro CNPJ: sem retificadora.
+        escrituracao("efd_12_original.txt", "12345678000195", 12, false, None),
+        escrituracao("efd_11_filial.txt", "12345678000276", 11, false, None),
+    ];
+
+    let selecao = selecionar_escrituracoes(&escrituracoes, sem_assinatura);
+    let relatorio = relatorio_retificadoras(&selecao, &escrituracoes, false);
+    println!("{relatorio}");
+
+    assert_eq!(
+        substituidas(&selecao),
+        [(
+            "efd_11_original.txt",
+            vec!["efd_11_retificadora.txt"],
+            MotivoDaSubstituicao::OriginalRetificada
+        )]
+    );
+    assert!(selecao.indeterminadas.is_empty());
+    assert!(selecao.substituido(Path::new("efd_11_original.txt")));
+    assert!(!selecao.substituido(Path::new("efd_12_original.txt")));
+
+    assert_eq!(
+        relatorio,
+        "Escriturações substituídas por retificadoras: 1 arquivo(s) ignorado(s).\n\
+         \x20  efd_11_original.txt (CNPJ 12345678000195, período 11/2020): \
+         escrituração original com retificadora do mesmo período.\n\
+         \x20     Substituído por: efd_11_retificadora.txt\n\n"
+    );
+
+    // Com --manter-retificadas, os arquivos são apenas informados.
+    assert!(
+        relatorio_retificadoras(&selecao, &escrituracoes, true)
+            .contains("1 arquivo(s) mantido(s) na análise (--manter-retificadas).")
+    );
+}
+
+#[test]
+/// cargo test -- --show-output cadeia_de_recibos
+fn cadeia_de_recibos() {
+    // Os nomes das escriturações anteriores contêm o nº do recibo (nomes gerados pelo PVA).
+    let escrituracoes = [
+        escrituracao(
+            "PISCOFINS_retificadora_2_5555.txt",
+            "12345678000195",
+            11,
+            true,
+            Some(RECIBO_RETIFICADORA),
+        ),
+        escrituracao(
+            "PISCOFINS_original_112233445566778899001.txt",
+            "12345678000195",
+            11,
+            false,
+            None,
+        ),
+        escrituracao(
+            "PISCOFINS_retificadora_1_998877665544332211002.txt",
+            "12345678000195",
+            11,
+            true,
+            Some(RECIBO_ORIGINAL),
+        ),
+    ];
+
+    let selecao = selecionar_escrituracoes(&escrituracoes, |_| {
+        panic!("a cadeia de recibos dispensa a assinatura digital")
+    });
+
+    assert_eq!(
+        substituidas(&selecao),
+        [
+            (
+                "PISCOFINS_retificadora_1_998877665544332211002.txt",
+                vec!["PISCOFINS_retificadora_2_5555.txt"],
+                MotivoDaSubstituicao::ReciboAnterior
+            ),
+            (
+                "PISCOFINS_original_112233445566778899001.txt",
+                vec!["PISCOFINS_retificadora_1_998877665544332211002.txt"],
+                MotivoDaSubstituicao::ReciboAnterior
+            ),
+        ]
+    );
+    assert!(selecao.indeterminadas.is_empty());
+}
+
+#[test]
+/// cargo test -- --show-output retificadoras_pela_data_da_assinatura
+fn retificadoras_pela_data_da_assinatura() {
+    let escrituracoes = [
+        escrituracao("original.txt", "12345678000195", 11, false, None),
+        escrituracao("retificadora_a.txt", "12345678000195", 11, true, None),
+        escrituracao("retificadora_b.txt", "12345678000195", 11, true, None),
+    ];
+
+    let datas: HashMap<&str, NaiveDateTime> = HashMap::from([
+        (
+            "retificadora_a.txt",
+            NaiveDate::from_ymd_opt(2021, 3, 10)
+                .and_then(|d| d.and_hms_opt(10, 0, 0))
+                .unwrap(),
+        ),
+        (
+            "retificadora_b.txt",
+            NaiveDate::from_ymd_opt(2021, 2, 10)
+                .and_then(|d| d.and_hms_opt(10, 0, 0))
+                .unwrap(),
+        ),
+    ]);
+
+    let selecao = selecionar_escrituracoes(&escrituracoes, |arquivo| {
+        datas.get(arquivo.to_str()?).copied()
+    });
+
+    assert_eq!(
+        substituidas(&selecao),
+        [
+            (
+                "retificadora_b.txt",
+                vec!["retificadora_a.txt"],
+                MotivoDaSubstituicao::AssinaturaAnterior
+            ),
+            (
+                "original.txt",
+                vec!["retificadora_a.txt"],
+                MotivoDaSubstituicao::OriginalRetificada
+            ),
+        ]
+    );
+
+    // Sem assinatura digital, as retificadoras são mantidas e a original indica as candidatas.
+    let selecao = selecionar_escrituracoes(&escrituracoes, sem_assinatura);
+    let relatorio = relatorio_retificadoras(&selecao, &escrituracoes, false);
+    println!("{relatorio}");
+
+    assert_eq!(
+        substituidas(&selecao),
+        [(
+            "original.txt",
+            vec!["retificadora_a.txt", "retificadora_b.txt"],
+            MotivoDaSubstituicao::OriginalRetificada
+        )]
+    );
+    assert_eq!(
+        selecao.indeterminadas,
+        [vec![
+            PathBuf::from("retificadora_a.txt"),
+            PathBuf::from("retificadora_b.txt")
+        ]]
+    );
+    assert!(relatorio.contains("sem retificadora mais recente identificável"));
+    assert!(relatorio.contains(
+        "      Substituído por uma das retificadoras: retificadora_a.txt, retificadora_b.txt\n"
+    ));
+}
+
+#[test]
+/// cargo test -- --show-output ler_escrituracao_do_arquivo_de_exemplo
+fn ler_escrituracao_do_arquivo_de_exemplo() -> EFDResult<()> {
+    let path = Path::new("examples/efd_data_random");
+    let escrituracao = Escrituracao::ler(path)?.expect("Registro 0000");
+
+    assert_eq!(
+        escrituracao,
+        Escrituracao {
+            arquivo: path.to_path_buf(),
+            ..escrituracao_de_exemplo()
+        }
+    );
+
+    Ok(())
+}
+
+fn escrituracao_de_exemplo() -> Escrituracao {
+    // |0000|006|0|||01112020|30112020|Empresa Ficticia 89631 SA|12345678901230|...
+    escrituracao("", "12345678901230", 11, false, None)
+}
@@ -0,0 +1,154 @@
+use super::*;
+use crate::{create_a_temp_file, setup_logging};
+use encoding_rs::WINDOWS_1252;
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+fn validar(path: &Path) -> EFDResult<Vec<Inconsistencia>> {
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+    Ok(validar_bloco_9(&sped_file, path))
+}
+
+/// Gera uma cópia do arquivo de exemplo com os registros de encerramento recalculados.
+fn linhas_reescritas() -> EFDResult<Vec<String>> {
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+
+    let mut bytes: Vec<u8> = Vec::new();
+    sped_file.write_to(&mut bytes)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+
+    Ok(texto.lines().map(String::from).collect())
+}
+
+#[test]
+/// cargo test -- --show-output validar_arquivo_de_exemplo
+fn validar_arquivo_de_exemplo() -> EFDResult<()> {
+    setup_logging();
+
+    let inconsistencias = validar(Path::new(EFD_EXEMPLO))?;
+
+    for inconsistencia in &inconsistencias {
+        println!("{inconsistencia}");
+    }
+
+    // |9999|434| em um arquivo de 544 linhas
+    assert!(inconsistencias.iter().any(|i| {
+        i.registro == "9999"
+            && i.linha == 544
+            && i.descricao
+                .contains("declarada = 434, linhas apuradas no arquivo = 544")
+    }));
+
+    // |9900|C170|85| com 23 registros C170
+    assert!(inconsistencias.iter().any(|i| i.registro == "9900"
+        && i.linha == 502
+        && i.descricao.contains("C170 declarada = 85, apurada = 23")));
+
+    // As linhas de encerramento são informadas em ordem crescente.
+    assert!(inconsistencias.is_sorted_by_key(|i| i.linha));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output validar_arquivo_reescrito
+fn validar_arquivo_reescrito() -> EFDResult<()> {
+    let linhas = linhas_reescritas()?;
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+
+    let inconsistencias = validar(temp_file.path())?;
+
+    assert_eq!(inconsistencias, Vec::new());
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output validar_arquivo_alterado
+fn validar_arquivo_alterado() -> EFDResult<()> {
+    let linhas: Vec<String> = linhas_reescritas()?
+        .into_iter()
+        // Remove o totalizador dos registros C170 e o registro 9999 (arquivo truncado).
+        .filter(|linha| !linha.starts_with("|9900|C170|") && !linha.starts_with("|9999|"))
+        // Altera a quantidade de linhas do Bloco C.
+        .map(|linha| match linha.strip_prefix("|C990|") {
+            Some(_) => "|C990|1|".to_string(),
+            None => linha,
+        })
+        .collect();
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let inconsistencias = validar(temp_file.path())?;
+
+    for inconsistencia in &inconsistencias {
+        println!("{inconsistencia}");
+    }
+
+    let primeiro_c170 = linhas.iter().position(|l| l.starts_with("|C170|")).unwrap() + 1;
+    let linha_c990 = linhas.iter().position(|l| l.starts_with("|C990|")).unwrap() + 1;
+
+    assert!(inconsistencias.iter().any(|i| i.registro == "C170"
+        && i.linha == primeiro_c170
+        && i.descricao.contains("sem o registro 9900")));
+
+    assert!(inconsistencias.iter().any(|i| i.registro == "C990"
+        && i.linha == linha_c990
+        && i.descricao.starts_with("QTD_LIN_C declarada = 1")));
+
+    assert!(inconsistencias.iter().any(|i| i.registro == "9999"
+        && i.linha == linhas.len()
+        && i.descricao.contains("arquivo truncado")));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output validar_linha_em_branco_no_bloco
+fn validar_linha_em_branco_no_bloco() -> EFDResult<()> {
+    let mut linhas = linhas_reescritas()?;
+    let linha_c990 = linhas.iter().position(|l| l.starts_with("|C990|")).unwrap();
+    linhas.insert(linha_c990, String::new());
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let inconsistencias = validar(temp_file.path())?;
+
+    // QTD_LIN_C conta os registros do bloco, não o intervalo de linhas.
+    assert!(!inconsistencias.iter().any(|i| i.registro == "C990"));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output validar_registro_nao_suportado
+fn validar_registro_nao_suportado() -> EFDResult<()> {
+    let mut linhas = linhas_reescritas()?;
+    let linha_c990 = linhas.iter().position(|l| l.starts_with("|C990|")).unwrap();
+    linhas.insert(linha_c990, "|C700|01|Campo Livre|".to_string());
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+
+    // O registro genérico é contabilizado nos registros de encerramento.
+    let inconsistencias = validar_bloco_9(&sped_file, temp_file.path());
+
+    for inconsistencia in &inconsistencias {
+        println!("{inconsistencia}");
+    }
+
+    assert!(inconsistencias.iter().any(|i| i.registro == "C700"
+        && i.linha == linha_c990 + 1
+        && i.descricao.contains("1 ocorrência(s) sem o registro 9900")));
+    assert!(
+        inconsistencias
+            .iter()
+            .any(|i| i.registro == "C990" && i.descricao.contains("declarada = "))
+    );
+
+    // O nível estimado não participa da validação da hierarquia.
+    assert_eq!(validar_hierarquia(&sped_file, temp_file.path()), Vec::new());
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use super::*;
+use crate::{
+    EFDResult, Registro0000, RegistroC100, RegistroC170, RegistroGenerico, RegistroPai,
+    SpedContext, create_a_temp_file, read_and_parse_file, setup_logging,
+};
+use encoding_rs::WINDOWS_1252;
+use indicatif::MultiProgress;
+use rust_decimal_macros::dec;
+use std::{collections::BTreeMap, path::Path};
+
+const EFD_EXEMPLO: &str = "examples/efd_data_random";
+
+/// Lê o arquivo, reescreve via `SpedFile::write_to` e retorna as linhas geradas.
+fn reescrever(path: &Path) -> EFDResult<Vec<String>> {
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(path, &multiprogressbar, 0, 1)?;
+
+    let mut bytes: Vec<u8> = Vec::new();
+    sped_file.write_to(&mut bytes)?;
+
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    assert!(texto.ends_with("\r\n"), "Linhas devem terminar com CR LF");
+
+    Ok(texto.lines().map(String::from).collect())
+}
+
+fn linhas_originais(path: &Path) -> EFDResult<Vec<String>> {
+    let bytes = std::fs::read(path)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+    Ok(texto
+        .lines()
+        .map(str::trim)
+        .filter(|linha| !linha.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+#[test]
+/// cargo test -- --show-output formatar_campos
+fn formatar_campos() {
+    let mut buffer = String::new();
+
+    Some(dec!(1000.00000)).write_sped_field(&mut buffer);
+    buffer.push('|');
+    Some(dec!(-0.65)).write_sped_field(&mut buffer);
+    buffer.push('|');
+    Some(dec!(734)).write_sped_field(&mut buffer);
+    buffer.push('|');
+    None::<Decimal>.write_sped_field(&mut buffer);
+    buffer.push('|');
+    NaiveDate::from_ymd_opt(2020, 11, 1)
+        .unwrap()
+        .write_sped_field(&mut buffer);
+    buffer.push('|');
+    write_sped_periodo(&NaiveDate::from_ymd_opt(2024, 4, 1), &mut buffer);
+    buffer.push('|');
+    write_sped_com_zeros(&Some(60u16), 3, &mut buffer);
+    buffer.push('|');
+    Some(CodigoSituacaoTributaria::from_u16(1).unwrap()).write_sped_field(&mut buffer);
+
+    assert_eq!(buffer, "1000,00000|-0,65|734||01112020|042024|060|01");
+}
+
+#[test]
+/// cargo test -- --show-output reescrever_arquivo_de_exemplo
+fn reescrever_arquivo_de_exemplo() -> EFDResult<()> {
+    setup_logging();
+
+    let path = Path::new(EFD_EXEMPLO);
+    let originais = linhas_originais(path)?;
+    let geradas = reescrever(path)?;
+
+    // Os registros de dados devem ser reproduzidos exatamente como no arquivo original.
+    let eh_dado = |linha: &&String| {
+        let reg = linha.get(1..5).unwrap_or_default();
+        !reg.ends_with("990") && !reg.starts_with('9')
+    };
+    let originais_dados: Vec<&String> = originais.iter().filter(eh_dado).collect();
+    let geradas_dados: Vec<&String> = geradas.iter().filter(eh_dado).collect();
+
+    assert_eq!(originais_dados.len(), geradas_dados.len());
+    for (original, gerada) in originais_dados.iter().zip(&geradas_dados) {
+        assert_eq!(original, gerada);
+    }
+
+    // Os totalizadores são recalculados a partir das linhas efetivamente emitidas.
+    let total = geradas.len();
+    assert_eq!(geradas.last(), Some(&format!("|9999|{total}|")));
+    assert!(geradas.contains(&"|9900|C170|23|".to_string()));
+
+    let qtd_9900 = geradas.iter().filter(|l| l.starts_with("|9900|")).count();
+    assert!(geradas.contains(&format!("|9900|9900|{qtd_9900}|")));
+
+    let qtd_lin_c = geradas.iter().filter(|l| l.starts_with("|C")).count();
+    assert!(geradas.contains(&format!("|C990|{qtd_lin_c}|")));
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output reescrita_idempotente
+fn reescrita_idempotente() -> EFDResult<()> {
+    let primeira = reescrever(Path::new(EFD_EXEMPLO))?.join("\n");
+
+    let temp_file = create_a_temp_file(&primeira, false)?;
+    let segunda = reescrever(temp_file.path())?.join("\n");
+
+    assert_eq!(primeira, segunda);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output reescrever_fora_de_ordem
+fn reescrever_fora_de_ordem() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let mut sped_file = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+
+    let mut ordenado: Vec<u8> = Vec::new();
+    sped_file.write_to(&mut ordenado)?;
+
+    // Registros armazenados fora da ordem das linhas: a saída é a mesma.
+    sped_file.bloco_0.reverse();
+    sped_file.bloco_c.reverse();
+    sped_file.bloco_m.rotate_left(3);
+
+    let mut desordenado: Vec<u8> = Vec::new();
+    sped_file.write_to(&mut desordenado)?;
+
+    assert_eq!(ordenado, desordenado);
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output alterar_e_reescrever
+fn alterar_e_reescrever() -> EFDResult<()> {
+    let multiprogressbar = MultiProgress::new();
+    let mut sped_file = read_and_parse_file(Path::new(EFD_EXEMPLO), &multiprogressbar, 0, 1)?;
+
+    if let Some(reg) = sped_file.obter_registro_mut::<Registro0000>("0000") {
+        reg.nome = Some("EMPRESA CORRIGIDA".into());
+    }
+
+    let c170 = sped_file.obter_registro::<RegistroC170>("C170")?;
+    assert!(c170.to_sped_line().starts_with("|C170|1|12345678|"));
+
+    let mut bytes: Vec<u8> = Vec::new();
+    sped_file.write_to(&mut bytes)?;
+    let (texto, _encoding, _had_errors) = WINDOWS_1252.decode(&bytes);
+
+    assert!(
+        texto
+            .lines()
+            .next()
+            .is_some_and(|linha| linha.contains("|EMPRESA CORRIGIDA|"))
+    );
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output preservar_texto_dos_campos
+fn preservar_texto_dos_campos() -> EFDResult<()> {
+    // UF em minúsculas e NUM_DOC com zeros à esquerda.
+    let linhas: Vec<String> = linhas_originais(Path::new(EFD_EXEMPLO))?
+        .into_iter()
+        .map(|linha| match linha.get(1..5) {
+            Some("0000") => linha.replace("|SP|", "|sp|"),
+            Some("C100") => linha.replace("|193391|", "|000193391|"),
+            _ => linha,
+        })
+        .collect();
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let mut sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+
+    let c100 = sped_file.obter_registro::<RegistroC100>("C100")?;
+    assert_eq!(c100.num_doc.as_deref(), Some("000193391"));
+    assert_eq!(c100.get_num_doc(), Some(193391));
+
+    // Reescrita: os campos são reproduzidos como no arquivo original.
+    let geradas = reescrever(temp_file.path())?;
+    assert_eq!(geradas[0], linhas[0]);
+    assert!(geradas.iter().any(|l| l.contains("|000193391|")));
+
+    // Análise: a UF da matriz é normalizada para maiúsculas no contexto.
+    let context = SpedContext::new(sped_file.take_bloco_0(), temp_file.path())?;
+    assert_eq!(context.matriz_estabelecimento_uf.as_ref(), "SP");
+
+    Ok(())
+}
+
+#[test]
+/// cargo test -- --show-output preservar_registros_nao_suportados
+fn preservar_registros_nao_suportados() -> EFDResult<()> {
+    // Registro inexistente no leiaute, inserido duas vezes antes do C990.
+    let registro_novo = "|C700|01|Campo Livre||123,45|";
+
+    let mut linhas = linhas_originais(Path::new(EFD_EXEMPLO))?;
+    let linha_c990 = linhas.iter().position(|l| l.starts_with("|C990|")).unwrap();
+    linhas.insert(linha_c990, registro_novo.to_string());
+    linhas.insert(linha_c990, registro_novo.to_string());
+
+    let temp_file = create_a_temp_file(&linhas.join("\n"), false)?;
+    let multiprogressbar = MultiProgress::new();
+    let sped_file = read_and_parse_file(temp_file.path(), &multiprogressbar, 0, 1)?;
+
+    assert_eq!(
+        sped_file.contar_registros_genericos(),
+        BTreeMap::from([("C700", 2)])
+    );
+    assert!(
+        sped_file
+            .relatorio_registros_genericos()
+            .contains("Registro C700: 2 ocorrência(s)")
+    );
+
+    let generico = sped_file.obter_registro::<RegistroGenerico>("C700")?;
+    assert_eq!(generico.bloco, 'C');
+    assert_eq!(generico.nivel, 3);
+    assert_eq!(generico.line_number, linha_c990 + 1);
+    assert_eq!(generico.campos, ["01", "Campo Livre", "", "123,45"]);
+
+    // Reescrita: as linhas são preservadas na posição original e totalizadas no Bloco 9.
+    let geradas = reescrever(temp_file.path())?;
+    let posicao = geradas.iter().position(|l| l == registro_novo).unwrap();
+
+    assert_eq!(geradas[posicao + 1], registro_novo);
+    assert!(geradas[posicao + 2].starts_with("|C990|"));
+    assert!(geradas.contains(&"|9900|C700|2|".to_string()));
+
+    Ok(())
+}
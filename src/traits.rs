@@ -1155,6 +1155,9 @@ where
 }
 
 /// Trait para validação e conversão de CNPJ (Alfanumérico 14 dígitos).
+///
+/// Apenas o tamanho é verificado na leitura: os dígitos verificadores são
+/// conferidos em `cnpj_valido` e reportados por `SpedFile::relatorio_identificadores_invalidos`.
 pub trait ToCNPJ {
     /// Limpa e valida o CNPJ. Retorna Some(Arc<str>) se válido, None se vazio, ou Erro se inválido.
     fn to_optional_cnpj(
@@ -69,6 +69,18 @@ pub fn display_value<T: Display>(valor: &Option<T>) -> String {
     valor.as_ref().map(|v| v.to_string()).unwrap_or_default()
 }
 
+/// Valor numérico armazenado como texto no formato da EFD: "1234,567".
+///
+/// Ex: QUANT_BC_PIS, QUANT_BC_COFINS e SD_CRED_DISP_EFD.
+pub fn decimal_textual(valor: Option<&str>) -> Option<Decimal> {
+    valor.and_then(|v| v.replace(',', ".").parse().ok())
+}
+
+/// Soma de campos opcionais: campos não informados valem zero.
+pub fn soma(valores: impl IntoIterator<Item = Option<Decimal>>) -> Decimal {
+    valores.into_iter().flatten().sum()
+}
+
 // ==============================================================================
 // Agregações e Cálculos Genéricos
 // ==============================================================================
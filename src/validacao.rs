@@ -0,0 +1,380 @@
+use compact_str::CompactString;
+use indicatif::MultiProgress;
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    AppConfig, Bloco0, Bloco1, Bloco9, BlocoA, BlocoC, BlocoD, BlocoF, BlocoI, BlocoM, BlocoP,
+    EFDError, EFDResult, ResultExt, SpedFile, SpedRecordTrait, conciliar_totais,
+    read_and_parse_file, validar_hierarquia, validar_leiaute,
+};
+
+// ============================================================================
+// Inconsistência
+// ============================================================================
+
+/// Inconsistência estrutural encontrada em um arquivo SPED EFD.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistencia {
+    /// Arquivo SPED EFD analisado.
+    pub arquivo: PathBuf,
+
+    /// Número da linha do registro que originou a inconsistência.
+    pub linha: usize,
+
+    /// Código do registro (ex: "C990", "9900").
+    pub registro: CompactString,
+
+    /// Descrição da divergência encontrada.
+    pub descricao: String,
+}
+
+impl Inconsistencia {
+    pub(crate) fn new(
+        arquivo: &Path,
+        linha: usize,
+        registro: impl Into<CompactString>,
+        descricao: String,
+    ) -> Self {
+        Self {
+            arquivo: arquivo.to_path_buf(),
+            linha,
+            registro: registro.into(),
+            descricao,
+        }
+    }
+}
+
+impl fmt::Display for Inconsistencia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:[linha nº {}] Registro {}: {}",
+            self.arquivo.display(),
+            self.linha,
+            self.registro,
+            self.descricao
+        )
+    }
+}
+
+// ============================================================================
+// Validação dos registros de encerramento (X990, 9900 e 9999)
+// ============================================================================
+
+/// Confronta os registros de encerramento com os registros efetivamente lidos do arquivo:
+///
+/// - X990: QTD_LIN_X com o número de linhas de cada bloco;
+/// - 9900: QTD_REG_BLC com a quantidade de cada registro (incluindo registros sem 9900);
+/// - 9999: QTD_LIN com o número total de linhas (ausência do 9999 indica arquivo truncado).
+///
+/// Registros não suportados pelo parser (`RegistroGenerico`) também são contabilizados.
+///
+/// As inconsistências são retornadas na ordem das linhas do arquivo.
+pub fn validar_bloco_9(sped_file: &SpedFile, arquivo: &Path) -> Vec<Inconsistencia> {
+    let mut inconsistencias = Vec::new();
+
+    // Macro local para despachar cada bloco ao seu registro de encerramento (X990)
+    macro_rules! validar_blocos {
+        ($( $bloco:literal: $campo:ident => $enum:ident::$variant:ident.$qtd:ident ),* $(,)?) => {
+            $(
+                validar_qtd_lin_bloco(
+                    arquivo,
+                    $bloco,
+                    &sped_file.$campo,
+                    |r| match r {
+                        $enum::$variant(r) => Some(r.$qtd),
+                        _ => None,
+                    },
+                    &mut inconsistencias,
+                );
+            )*
+        };
+    }
+
+    validar_blocos!(
+        '0': bloco_0 => Bloco0::R0990.qtd_lin_0,
+        'A': bloco_a => BlocoA::RA990.qtd_lin_a,
+        'C': bloco_c => BlocoC::RC990.qtd_lin_c,
+        'D': bloco_d => BlocoD::RD990.qtd_lin_d,
+        'F': bloco_f => BlocoF::RF990.qtd_lin_f,
+        'I': bloco_i => BlocoI::RI990.qtd_lin_i,
+        'M': bloco_m => BlocoM::RM990.qtd_lin_m,
+        'P': bloco_p => BlocoP::RP990.qtd_lin_p,
+        '1': bloco_1 => Bloco1::R1990.qtd_lin_1,
+        '9': bloco_9 => Bloco9::R9990.qtd_lin_9,
+    );
+
+    validar_registros_9900(sped_file, arquivo, &mut inconsistencias);
+    validar_registro_9999(sped_file, arquivo, &mut inconsistencias);
+
+    inconsistencias.sort_by(|a, b| (a.linha, &a.registro).cmp(&(b.linha, &b.registro)));
+    inconsistencias
+}
+
+/// Compara QTD_LIN_X do registro X990 com o número de registros lidos do bloco,
+/// incluindo os registros de abertura (X001) e de encerramento (X990).
+///
+/// `qtd_lin_x` retorna `Some(QTD_LIN_X)` apenas para o registro X990.
+fn validar_qtd_lin_bloco<T: SpedRecordTrait>(
+    arquivo: &Path,
+    bloco: char,
+    registros: &[T],
+    qtd_lin_x: impl Fn(&T) -> Option<Option<u64>>,
+    inconsistencias: &mut Vec<Inconsistencia>,
+) {
+    let Some(ultima) = registros.iter().map(|r| r.line_number()).max() else {
+        return; // Bloco sem registros
+    };
+
+    let registro = format!("{bloco}990");
+
+    let Some((encerramento, declarado)) = registros
+        .iter()
+        .find_map(|r| qtd_lin_x(r).map(|qtd| (r, qtd)))
+    else {
+        inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            ultima,
+            registro.as_str(),
+            format!("registro de encerramento do Bloco {bloco} ausente."),
+        ));
+        return;
+    };
+
+    let apurado = registros.len() as u64;
+    let linha = encerramento.line_number();
+
+    match declarado {
+        Some(qtd) if qtd == apurado => {}
+        Some(qtd) => inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            linha,
+            registro.as_str(),
+            format!(
+                "QTD_LIN_{bloco} declarada = {qtd}, linhas apuradas no Bloco {bloco} = {apurado}."
+            ),
+        )),
+        None => inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            linha,
+            registro.as_str(),
+            format!(
+                "QTD_LIN_{bloco} não informada (linhas apuradas no Bloco {bloco} = {apurado})."
+            ),
+        )),
+    }
+}
+
+/// Compara QTD_REG_BLC de cada registro 9900 com a quantidade de registros lidos.
+fn validar_registros_9900(
+    sped_file: &SpedFile,
+    arquivo: &Path,
+    inconsistencias: &mut Vec<Inconsistencia>,
+) {
+    // registro -> (quantidade apurada, linha da primeira ocorrência)
+    let mut apurados: HashMap<&str, (u64, usize)> = HashMap::new();
+    for registro in sped_file.iter_registros() {
+        apurados
+            .entry(registro.registro_name())
+            .or_insert((0, registro.line_number()))
+            .0 += 1;
+    }
+
+    // registro -> linha do 9900 que o declarou
+    let mut declarados: HashMap<&str, usize> = HashMap::new();
+
+    for r9900 in sped_file.bloco_9.iter().filter_map(|r| match r {
+        Bloco9::R9900(r) => Some(r),
+        _ => None,
+    }) {
+        let linha = r9900.line_number;
+
+        let Some(reg_blc) = r9900.reg_blc.as_deref() else {
+            inconsistencias.push(Inconsistencia::new(
+                arquivo,
+                linha,
+                "9900",
+                "REG_BLC não informado.".to_string(),
+            ));
+            continue;
+        };
+
+        if let Some(linha_anterior) = declarados.insert(reg_blc, linha) {
+            inconsistencias.push(Inconsistencia::new(
+                arquivo,
+                linha,
+                "9900",
+                format!("registro {reg_blc} já totalizado na linha nº {linha_anterior}."),
+            ));
+            continue;
+        }
+
+        let apurado = apurados.get(reg_blc).map_or(0, |&(qtd, _)| qtd);
+
+        match r9900.qtd_reg_blc {
+            Some(qtd) if qtd == apurado => {}
+            Some(qtd) => inconsistencias.push(Inconsistencia::new(
+                arquivo,
+                linha,
+                "9900",
+                format!(
+                    "QTD_REG_BLC do registro {reg_blc} declarada = {qtd}, apurada = {apurado}."
+                ),
+            )),
+            None => inconsistencias.push(Inconsistencia::new(
+                arquivo,
+                linha,
+                "9900",
+                format!("QTD_REG_BLC do registro {reg_blc} não informada (apurada = {apurado})."),
+            )),
+        }
+    }
+
+    for (registro, (qtd, linha)) in apurados {
+        if !declarados.contains_key(registro) {
+            inconsistencias.push(Inconsistencia::new(
+                arquivo,
+                linha,
+                registro,
+                format!("{qtd} ocorrência(s) sem o registro 9900 correspondente."),
+            ));
+        }
+    }
+}
+
+/// Compara QTD_LIN do registro 9999 com o número total de linhas do arquivo.
+fn validar_registro_9999(
+    sped_file: &SpedFile,
+    arquivo: &Path,
+    inconsistencias: &mut Vec<Inconsistencia>,
+) {
+    // As linhas são contadas a partir do registro 0000.
+    let primeira = sped_file
+        .bloco_0
+        .iter()
+        .map(|r| r.line_number())
+        .min()
+        .unwrap_or(1);
+
+    let Some(r9999) = sped_file.bloco_9.iter().find_map(|r| match r {
+        Bloco9::R9999(r) => Some(r),
+        _ => None,
+    }) else {
+        let ultima = sped_file
+            .iter_registros()
+            .map(|r| r.line_number())
+            .max()
+            .unwrap_or(0);
+        inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            ultima,
+            "9999",
+            "registro de encerramento do arquivo ausente (arquivo truncado ou incompleto)."
+                .to_string(),
+        ));
+        return;
+    };
+
+    let apurado = (r9999.line_number + 1).saturating_sub(primeira) as u64;
+
+    match r9999.qtd_lin {
+        Some(qtd) if qtd == apurado => {}
+        Some(qtd) => inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            r9999.line_number,
+            "9999",
+            format!("QTD_LIN declarada = {qtd}, linhas apuradas no arquivo = {apurado}."),
+        )),
+        None => inconsistencias.push(Inconsistencia::new(
+            arquivo,
+            r9999.line_number,
+            "9999",
+            format!("QTD_LIN não informada (linhas apuradas no arquivo = {apurado})."),
+        )),
+    }
+}
+
+// ============================================================================
+// Modo --validar
+// ============================================================================
+
+/// Valida a estrutura dos arquivos selecionados e imprime o relatório de inconsistências.
+///
+/// Retorna o número total de inconsistências encontradas.
+pub fn validar_arquivos(config: &AppConfig, write: &mut dyn Write) -> EFDResult<usize> {
+    let arquivos_efd: &[PathBuf] = &config.all_files;
+    let total_files_count: usize = arquivos_efd.len();
+    let multiprogressbar: MultiProgress = MultiProgress::new();
+
+    // (inconsistências, relatório dos registros não suportados) de cada arquivo
+    let resultados: Vec<(Vec<Inconsistencia>, String)> = arquivos_efd
+        .par_iter()
+        .enumerate()
+        .with_max_len(1)
+        .map(|(index, arquivo)| {
+            let sped_file =
+                read_and_parse_file(arquivo, &multiprogressbar, index, total_files_count).map_loc(
+                    |error| EFDError::AnalyzeFileError {
+                        source: Box::new(error),
+                        arquivo: arquivo.clone(),
+                    },
+                )?;
+            let mut inconsistencias = validar_bloco_9(&sped_file, arquivo);
+            inconsistencias.extend(validar_hierarquia(&sped_file, arquivo));
+            inconsistencias.extend(validar_leiaute(&sped_file, arquivo));
+            inconsistencias.extend(conciliar_totais(&sped_file, arquivo, config.tolerancia));
+            inconsistencias.sort_by_key(|i| i.linha);
+            Ok((inconsistencias, sped_file.relatorio_registros_genericos()))
+        })
+        .collect::<EFDResult<_>>()?;
+
+    writeln!(
+        write,
+        "Validação estrutural (X990, 9900, 9999, hierarquia e leiaute dos registros e totais dos documentos):\n"
+    )?;
+
+    let mut total = 0;
+    for (index, (arquivo, (inconsistencias, nao_suportados))) in
+        arquivos_efd.iter().zip(&resultados).enumerate()
+    {
+        writeln!(write, "EFD {:02}: {}", index + 1, arquivo.display())?;
+        write.write_all(nao_suportados.as_bytes())?;
+
+        if inconsistencias.is_empty() {
+            writeln!(write, "   Nenhuma inconsistência encontrada.\n")?;
+            continue;
+        }
+
+        for inconsistencia in inconsistencias {
+            writeln!(
+                write,
+                "   [linha nº {}] Registro {}: {}",
+                inconsistencia.linha, inconsistencia.registro, inconsistencia.descricao
+            )?;
+        }
+        writeln!(write)?;
+
+        total += inconsistencias.len();
+    }
+
+    writeln!(
+        write,
+        "Total: {total} inconsistência(s) em {total_files_count} arquivo(s) analisado(s).\n"
+    )?;
+
+    Ok(total)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/validacao_tests.rs"]
+mod validacao_tests;
@@ -0,0 +1,306 @@
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use encoding_rs::WINDOWS_1252;
+use rust_decimal::Decimal;
+use std::{collections::HashMap, fmt::Write as _, io::Write, sync::Arc};
+
+use crate::{
+    CodigoDoCredito, CodigoSituacaoTributaria, DELIMITER_CHAR, EFDResult, GrupoDeContas, SpedFile,
+    SpedRecordTrait, TipoDoItem,
+};
+
+/// Terminador de linha exigido pelo leiaute (Guia Prático: caracteres CR + LF).
+const LINE_TERMINATOR: &[u8] = b"\r\n";
+
+// ============================================================================
+// SpedWriter
+// ============================================================================
+
+/// Operação inversa de `SpedParser`: converte um registro de volta em uma linha da EFD.
+pub trait SpedWriter: SpedRecordTrait {
+    /// Acrescenta ao buffer os campos de dados do registro (campo 02 em diante),
+    /// cada um precedido pelo delimitador '|'.
+    fn write_fields(&self, buffer: &mut String);
+
+    /// Nomes dos campos de dados do registro (campo 02 em diante), na ordem do leiaute.
+    ///
+    /// Vazio para registros sem leiaute conhecido (ver `RegistroGenerico`).
+    fn nomes_dos_campos(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Acrescenta ao buffer a linha completa `|REG|CAMPO_02|...|CAMPO_N|` (sem quebra de linha).
+    fn write_line(&self, buffer: &mut String) {
+        buffer.push(DELIMITER_CHAR);
+        buffer.push_str(self.registro_name());
+        self.write_fields(buffer);
+        buffer.push(DELIMITER_CHAR);
+    }
+
+    /// Retorna a linha completa do registro.
+    fn to_sped_line(&self) -> String {
+        let mut buffer = String::new();
+        self.write_line(&mut buffer);
+        buffer
+    }
+}
+
+// ============================================================================
+// ToSpedField
+// ============================================================================
+
+/// Formatação de um campo individual segundo as regras do leiaute da EFD.
+///
+/// Campos vazios (None) não escrevem nada entre os delimitadores.
+pub trait ToSpedField {
+    fn write_sped_field(&self, buffer: &mut String);
+}
+
+impl<T: ToSpedField> ToSpedField for Option<T> {
+    #[inline]
+    fn write_sped_field(&self, buffer: &mut String) {
+        if let Some(value) = self {
+            value.write_sped_field(buffer);
+        }
+    }
+}
+
+/// Valores decimais usam vírgula, sem separador de milhar.
+///
+/// O `Decimal` preserva a escala lida do arquivo, logo "1000,00000" é reescrito como "1000,00000".
+impl ToSpedField for Decimal {
+    fn write_sped_field(&self, buffer: &mut String) {
+        let start = buffer.len();
+        let _ = write!(buffer, "{self}");
+        if let Some(pos) = buffer[start..].find('.') {
+            buffer.replace_range(start + pos..start + pos + 1, ",");
+        }
+    }
+}
+
+/// Datas no formato `DDMMAAAA`.
+impl ToSpedField for NaiveDate {
+    fn write_sped_field(&self, buffer: &mut String) {
+        let _ = write!(buffer, "{}", self.format("%d%m%Y"));
+    }
+}
+
+impl ToSpedField for CompactString {
+    #[inline]
+    fn write_sped_field(&self, buffer: &mut String) {
+        buffer.push_str(self);
+    }
+}
+
+impl ToSpedField for Arc<str> {
+    #[inline]
+    fn write_sped_field(&self, buffer: &mut String) {
+        buffer.push_str(self);
+    }
+}
+
+impl ToSpedField for char {
+    #[inline]
+    fn write_sped_field(&self, buffer: &mut String) {
+        buffer.push(*self);
+    }
+}
+
+macro_rules! impl_to_sped_field_display {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl ToSpedField for $t {
+                #[inline]
+                fn write_sped_field(&self, buffer: &mut String) {
+                    let _ = write!(buffer, "{self}");
+                }
+            }
+        )*
+    };
+}
+
+// CodigoDoCredito já implementa Display com 3 dígitos (XYY).
+impl_to_sped_field_display!(u8, u16, u32, u64, usize, CodigoDoCredito);
+
+impl ToSpedField for CodigoSituacaoTributaria {
+    fn write_sped_field(&self, buffer: &mut String) {
+        let _ = write!(buffer, "{:02}", self.code());
+    }
+}
+
+impl ToSpedField for TipoDoItem {
+    fn write_sped_field(&self, buffer: &mut String) {
+        let _ = write!(buffer, "{:02}", *self as u8);
+    }
+}
+
+impl ToSpedField for GrupoDeContas {
+    fn write_sped_field(&self, buffer: &mut String) {
+        let _ = write!(buffer, "{:02}", self.code());
+    }
+}
+
+/// Inteiro completado com zeros à esquerda (ex: CST_ICMS "060", COD_VER "006").
+pub fn write_sped_com_zeros<T: std::fmt::Display>(
+    value: &Option<T>,
+    width: usize,
+    buffer: &mut String,
+) {
+    if let Some(v) = value {
+        let _ = write!(buffer, "{v:0>width$}");
+    }
+}
+
+/// Período de apuração no formato `MMAAAA` (ex: PER_APU_CRED).
+pub fn write_sped_periodo(value: &Option<NaiveDate>, buffer: &mut String) {
+    if let Some(date) = value {
+        let _ = write!(buffer, "{}", date.format("%m%Y"));
+    }
+}
+
+// ============================================================================
+// Escrita do SpedFile
+// ============================================================================
+
+/// Contabiliza as linhas emitidas para recompor os registros de encerramento
+/// (X990, 9900, 9990 e 9999).
+struct ContadorDeLinhas<'w, W: Write> {
+    writer: &'w mut W,
+    buffer: String,
+    total: u64,
+    /// Quantidade por registro, na ordem da primeira ocorrência.
+    registros: Vec<(CompactString, u64)>,
+    indices: HashMap<CompactString, usize>,
+}
+
+impl<'w, W: Write> ContadorDeLinhas<'w, W> {
+    fn new(writer: &'w mut W) -> Self {
+        Self {
+            writer,
+            buffer: String::with_capacity(1024),
+            total: 0,
+            registros: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Emite a linha contida em `self.buffer` em ISO-8859-1 (WINDOWS-1252), como exige o PVA.
+    fn emitir(&mut self, registro: &str) -> EFDResult<()> {
+        let (bytes, _encoding, _had_errors) = WINDOWS_1252.encode(&self.buffer);
+        self.writer.write_all(&bytes)?;
+        self.writer.write_all(LINE_TERMINATOR)?;
+
+        self.total += 1;
+        match self.indices.get(registro) {
+            Some(&idx) => self.registros[idx].1 += 1,
+            None => {
+                self.indices
+                    .insert(CompactString::new(registro), self.registros.len());
+                self.registros.push((CompactString::new(registro), 1));
+            }
+        }
+        Ok(())
+    }
+
+    fn emitir_registro<R: SpedWriter + ?Sized>(&mut self, record: &R) -> EFDResult<()> {
+        self.buffer.clear();
+        record.write_line(&mut self.buffer);
+        self.emitir(record.registro_name())
+    }
+
+    /// Emite uma linha gerada (registros de encerramento) a partir de seus campos.
+    fn emitir_campos(
+        &mut self,
+        registro: &str,
+        campos: &[&dyn std::fmt::Display],
+    ) -> EFDResult<()> {
+        self.buffer.clear();
+        self.buffer.push(DELIMITER_CHAR);
+        self.buffer.push_str(registro);
+        for campo in campos {
+            let _ = write!(self.buffer, "{DELIMITER_CHAR}{campo}");
+        }
+        self.buffer.push(DELIMITER_CHAR);
+        self.emitir(registro)
+    }
+
+    /// Emite os registros de um bloco, descartando o X990 original e
+    /// acrescentando um novo X990 com a quantidade de linhas recalculada.
+    fn emitir_bloco<R: SpedWriter>(&mut self, bloco: char, registros: &[R]) -> EFDResult<()> {
+        let inicio = self.total;
+
+        // Ordem das linhas do arquivo (hierarquia pai/filho), independente da ordem armazenada.
+        let mut ordenados: Vec<&R> = registros
+            .iter()
+            .filter(|r| !r.registro_name().ends_with("990"))
+            .collect();
+        ordenados.sort_by_key(|r| r.line_number());
+
+        for record in ordenados {
+            self.emitir_registro(record)?;
+        }
+
+        if self.total > inicio {
+            let registro = format!("{bloco}990");
+            let qtd_lin = self.total - inicio + 1;
+            self.emitir_campos(&registro, &[&qtd_lin])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SpedFile {
+    /// Serializa o SpedFile como arquivo texto da EFD Contribuições, pronto para o PVA.
+    ///
+    /// Os blocos são emitidos na sequência do leiaute (0, A, C, D, F, I, M, P, 1 e 9) e,
+    /// dentro de cada bloco, na ordem dos números de linha, qualquer que seja a ordem
+    /// de armazenamento (ex: após a leitura paralela ou alterações nos vetores dos blocos).
+    ///
+    /// Os registros de encerramento são sempre recalculados: cada X990, o Bloco 9
+    /// inteiro (9001, 9900 por registro, 9990) e o 9999 com o total de linhas.
+    pub fn write_to(&self, writer: &mut impl Write) -> EFDResult<()> {
+        let mut contador = ContadorDeLinhas::new(writer);
+
+        contador.emitir_bloco('0', &self.bloco_0)?;
+        contador.emitir_bloco('A', &self.bloco_a)?;
+        contador.emitir_bloco('C', &self.bloco_c)?;
+        contador.emitir_bloco('D', &self.bloco_d)?;
+        contador.emitir_bloco('F', &self.bloco_f)?;
+        contador.emitir_bloco('I', &self.bloco_i)?;
+        contador.emitir_bloco('M', &self.bloco_m)?;
+        contador.emitir_bloco('P', &self.bloco_p)?;
+        contador.emitir_bloco('1', &self.bloco_1)?;
+
+        // Bloco 9: 9001 + um 9900 por registro (incluindo 9001, 9900, 9990 e 9999) + 9990.
+        let inicio_bloco_9 = contador.total;
+        contador.emitir_campos("9001", &[&0])?;
+
+        let mut totais = contador.registros.clone();
+        let qtd_9900 = totais.len() as u64 + 3; // + 9900, 9990 e 9999
+        totais.push(("9900".into(), qtd_9900));
+        totais.push(("9990".into(), 1));
+        totais.push(("9999".into(), 1));
+
+        for (registro, quantidade) in &totais {
+            contador.emitir_campos("9900", &[registro, quantidade])?;
+        }
+
+        let qtd_lin_9 = contador.total - inicio_bloco_9 + 2; // + 9990 e 9999
+        contador.emitir_campos("9990", &[&qtd_lin_9])?;
+
+        let qtd_lin = contador.total + 1;
+        contador.emitir_campos("9999", &[&qtd_lin])?;
+
+        contador.writer.flush()?;
+        Ok(())
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+#[cfg(test)]
+#[path = "tests/writer_tests.rs"]
+mod writer_tests;